@@ -1,5 +1,7 @@
 // rust-engine/src/simulator.rs
 use ethers::prelude::*;
+use ethers::abi::Token;
+use ethers::types::spoof;
 use std::sync::Arc;
 use anyhow::Result;
 use crate::scanner::Opportunity;
@@ -18,9 +20,8 @@ impl TransactionSimulator {
     }
     
     pub async fn simulate_opportunity(&self, opp: &Opportunity) -> Result<SimulationResult> {
-        // Use eth_call to simulate the transaction
-        let result = self.simulate_arbitrage_calls(opp).await?;
-        
+        let result = self.simulate_bundle(opp).await?;
+
         Ok(SimulationResult {
             success: result.success,
             actual_profit: result.profit,
@@ -28,142 +29,81 @@ impl TransactionSimulator {
             revert_reason: result.revert_reason,
         })
     }
-    
-    async fn simulate_arbitrage_calls(&self, opp: &Opportunity) -> Result<SimResult> {
-        // Build the arbitrage transaction calls
-        let swap1 = self.build_swap_call(
-            opp.pair1,
-            opp.token0,
-            opp.token1,
-            opp.optimal_amount,
-            true,
-        ).await?;
-        
-        let swap2 = self.build_swap_call(
-            opp.pair2,
-            opp.token1,
-            opp.token0,
-            opp.optimal_amount,
-            false,
-        ).await?;
-        
-        // Simulate both swaps
-        let result1 = self.provider.call(&swap1, None).await;
-        
-        if result1.is_err() {
-            return Ok(SimResult {
-                success: false,
-                profit: U256::zero(),
-                gas: 0,
-                revert_reason: Some("First swap failed".to_string()),
-            });
+
+    /// Dry-runs the entire flash-loan + multi-hop path as a single `eth_call`, instead of
+    /// chaining two independent calls and manually threading the first swap's output into
+    /// the second — that approach diverges from on-chain atomicity since reserves don't
+    /// actually update between separate calls and the flash-loan wrapping is ignored
+    /// entirely. The executing account's balance is overridden via `eth_call`'s third
+    /// `stateOverride` parameter so the flash-loan callback can be modeled without the
+    /// simulator actually holding funds, and the call is pinned to `self.fork_block` when
+    /// set so repeated simulations see consistent chain state.
+    async fn simulate_bundle(&self, opp: &Opportunity) -> Result<SimResult> {
+        let tx = self.build_complete_arb_tx(opp).await?;
+        let caller = self.provider.default_sender().unwrap_or_default();
+
+        let mut overrides = spoof::state();
+        overrides.account(caller).balance(U256::from(10u128.pow(20)));
+
+        let mut call = self.provider.call_raw(&tx).state(&overrides);
+        if let Some(block) = self.fork_block {
+            call = call.block(block.into());
         }
-        
-        // Calculate expected output from first swap
-        let output1 = self.decode_swap_output(result1.unwrap());
-        
-        // Update second swap with actual output
-        let swap2_updated = self.build_swap_call(
-            opp.pair2,
-            opp.token1,
-            opp.token0,
-            output1,
-            false,
-        ).await?;
-        
-        let result2 = self.provider.call(&swap2_updated, None).await;
-        
-        if let Ok(output) = result2 {
-            let final_amount = self.decode_swap_output(output);
-            let profit = if final_amount > opp.optimal_amount {
-                final_amount - opp.optimal_amount
-            } else {
-                U256::zero()
-            };
-            
-            Ok(SimResult {
-                success: true,
-                profit,
-                gas: 500_000, // Estimate
-                revert_reason: None,
-            })
-        } else {
-            Ok(SimResult {
+
+        match call.await {
+            Ok(output) => {
+                let profit = Self::decode_bundle_profit(&output);
+                Ok(SimResult {
+                    success: true,
+                    profit,
+                    gas: 0,
+                    revert_reason: None,
+                })
+            }
+            Err(e) => Ok(SimResult {
                 success: false,
                 profit: U256::zero(),
                 gas: 0,
-                revert_reason: Some("Second swap failed".to_string()),
-            })
+                revert_reason: Some(Self::decode_revert_reason(&e)),
+            }),
         }
     }
-    
-    async fn build_swap_call(
-        &self,
-        pair: Address,
-        token_in: Address,
-        token_out: Address,
-        amount: U256,
-        exact_input: bool,
-    ) -> Result<TypedTransaction> {
-        let router_abi = ethers::abi::parse_abi(&[
-            "function swap(uint amount0Out, uint amount1Out, address to, bytes data)"
-        ])?;
-        
-        // Calculate output amount based on reserves
-        let (amount0_out, amount1_out) = if exact_input {
-            // Calculate expected output
-            let output = self.calculate_output_amount(pair, amount).await?;
-            (U256::zero(), output)
-        } else {
-            (amount, U256::zero())
-        };
-        
-        let contract = Contract::new(pair, router_abi, self.provider.clone());
-        
-        let tx = contract
-            .method(
-                "swap",
-                (
-                    amount0_out,
-                    amount1_out,
-                    self.provider.default_sender().unwrap_or_default(),
-                    Bytes::default(),
-                ),
-            )?
-            .tx;
-        
-        Ok(tx)
-    }
-    
-    async fn calculate_output_amount(&self, pair: Address, input: U256) -> Result<U256> {
-        // Get reserves and calculate output using AMM formula
-        let pair_abi = ethers::abi::parse_abi(&[
-            "function getReserves() view returns (uint112,uint112,uint32)"
-        ])?;
-        
-        let contract = Contract::new(pair, pair_abi, self.provider.clone());
-        let reserves: (U256, U256, U256) = contract
-            .method("getReserves", ())?
-            .call()
-            .await?;
-        
-        // x * y = k formula with 0.3% fee
-        let input_with_fee = input * 997;
-        let numerator = input_with_fee * reserves.1;
-        let denominator = reserves.0 * 1000 + input_with_fee;
-        
-        Ok(numerator / denominator)
+
+    /// The arb contract's aggregate call is expected to return the signed profit/loss in
+    /// the borrowed asset as its first (and only) `int256` return value.
+    fn decode_bundle_profit(output: &Bytes) -> U256 {
+        if output.len() < 32 {
+            return U256::zero();
+        }
+        let raw = U256::from_big_endian(&output[0..32]);
+        // `int256` is two's-complement; a high-bit-set value means the bundle lost money,
+        // which the scanner should see as zero profit rather than a huge positive number.
+        if raw.bit(255) { U256::zero() } else { raw }
     }
-    
-    fn decode_swap_output(&self, data: Bytes) -> U256 {
-        // Decode the output amount from return data
-        if data.len() >= 32 {
-            U256::from(&data[0..32])
-        } else {
-            U256::zero()
+
+    /// `eth_call` reverts surface as a JSON-RPC error whose `data` field carries the
+    /// standard `Error(string)` ABI-encoded revert reason (selector `0x08c379a0`). Falls
+    /// back to the raw error if the node didn't return decodable revert data.
+    fn decode_revert_reason(err: &ProviderError) -> String {
+        let Some(data) = err.as_error_response().and_then(|e| e.data.as_ref()) else {
+            return format!("call reverted: {err}");
+        };
+        let Some(hex_str) = data.as_str() else {
+            return format!("call reverted: {err}");
+        };
+        let Ok(bytes) = hex::decode(hex_str.trim_start_matches("0x")) else {
+            return format!("call reverted with undecodable data: {hex_str}");
+        };
+        if bytes.len() > 4 && bytes[0..4] == [0x08, 0xc3, 0x79, 0xa0] {
+            if let Ok(tokens) = ethers::abi::decode(&[ethers::abi::ParamType::String], &bytes[4..]) {
+                if let Some(Token::String(reason)) = tokens.into_iter().next() {
+                    return format!("reverted: {reason}");
+                }
+            }
         }
+        format!("reverted with undecoded data: 0x{}", hex::encode(bytes))
     }
-    
+
     pub async fn estimate_gas(&self, opp: &Opportunity) -> Result<U256> {
         // Build complete transaction
         let tx = self.build_complete_arb_tx(opp).await?;
@@ -175,15 +115,23 @@ impl TransactionSimulator {
         }
     }
     
+    /// Encodes the whole two-leg path as a single call to the flash-loan provider's arb
+    /// entrypoint, so `simulate_bundle` can dry-run it as one atomic `eth_call` instead of
+    /// two independently-simulated swaps.
     async fn build_complete_arb_tx(&self, opp: &Opportunity) -> Result<TypedTransaction> {
-        // Build the complete arbitrage transaction
-        // This would include flash loan + swaps
-        
-        let mut tx = TypedTransaction::default();
-        tx.set_to(opp.flash_loan_provider);
-        tx.set_value(U256::zero());
+        let abi = ethers::abi::parse_abi(&[
+            "function executeArbitrage(address pair1, address pair2, address token0, address token1, uint256 amount) returns (int256)"
+        ])?;
+
+        let contract = Contract::new(opp.flash_loan_provider, abi, self.provider.clone());
+        let mut tx = contract
+            .method::<_, I256>(
+                "executeArbitrage",
+                (opp.pair1, opp.pair2, opp.token0, opp.token1, opp.optimal_amount),
+            )?
+            .tx;
         tx.set_gas(U256::from(750_000));
-        
+
         Ok(tx)
     }
 }
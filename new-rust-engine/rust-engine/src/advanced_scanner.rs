@@ -8,6 +8,221 @@ use dashmap::DashMap;
 use futures::StreamExt;
 
 use crate::config::{ChainConfig, DexConfig};
+use crate::flash_loan_optimizer::FlashLoanOptimizer;
+
+/// Curve/StableSwap invariant math for an `n`-coin pool, used to price stable and
+/// liquid-staking pools correctly instead of forcing the constant-product formula on
+/// them. Mirrors the reference Curve StableSwap Newton iteration; kept in `f64` rather
+/// than fixed-point since every other price computation in this scanner already is.
+mod stable_math {
+    /// Curve's own reference implementation bounds Newton iteration at 255 rounds; in
+    /// practice both solves below converge in under 10.
+    const MAX_ITERATIONS: u32 = 255;
+    /// Stop once two successive estimates differ by less than this.
+    const EPSILON: f64 = 1e-8;
+
+    /// Solves the StableSwap invariant `D` for `balances` and amplification `amp`:
+    /// `A·n^n·Σx_i + D = A·D·n^n + D^(n+1)/(n^n·Πx_i)`, via Newton iteration starting
+    /// from `D₀ = Σx_i`.
+    pub fn compute_d(balances: &[f64], amp: f64) -> f64 {
+        let n = balances.len() as f64;
+        let s: f64 = balances.iter().sum();
+        if s <= 0.0 {
+            return 0.0;
+        }
+        let ann = amp * n.powf(n);
+        let mut d = s;
+
+        for _ in 0..MAX_ITERATIONS {
+            let mut d_p = d;
+            for &balance in balances {
+                if balance <= 0.0 {
+                    return 0.0;
+                }
+                d_p = d_p * d / (balance * n);
+            }
+            let d_prev = d;
+            let denominator = (ann - 1.0) * d + (n + 1.0) * d_p;
+            if denominator == 0.0 {
+                return d;
+            }
+            d = (ann * s + d_p * n) * d / denominator;
+            if (d - d_prev).abs() <= EPSILON {
+                break;
+            }
+        }
+        d
+    }
+
+    /// Holding `d` fixed, solves for the new balance of the output coin given every other
+    /// balance (the input coin's balance already includes the trade), via Newton
+    /// iteration on `y² + (b−D)y − c = 0`.
+    fn compute_y(balances_without_out: &[f64], d: f64, amp: f64, n_coins: usize) -> f64 {
+        let n = n_coins as f64;
+        let ann = amp * n.powf(n);
+
+        let sum_prime: f64 = balances_without_out.iter().sum();
+        let mut c = d;
+        for &balance in balances_without_out {
+            c = c * d / (balance * n);
+        }
+        c = c * d / (ann * n);
+        let b = sum_prime + d / ann;
+
+        let mut y = d;
+        for _ in 0..MAX_ITERATIONS {
+            let y_prev = y;
+            let denominator = 2.0 * y + b - d;
+            if denominator == 0.0 {
+                return y;
+            }
+            y = (y * y + c) / denominator;
+            if (y - y_prev).abs() <= EPSILON {
+                break;
+            }
+        }
+        y
+    }
+
+    /// Output amount for swapping `amount_in` of `in_index` into `out_index`, solved from
+    /// the invariant instead of assumed constant-product.
+    pub fn swap_output(balances: &[f64], in_index: usize, out_index: usize, amount_in: f64, amp: f64) -> f64 {
+        if in_index == out_index || amount_in <= 0.0 {
+            return 0.0;
+        }
+        let d = compute_d(balances, amp);
+        let balances_without_out: Vec<f64> = balances
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != out_index)
+            .map(|(i, &balance)| if i == in_index { balance + amount_in } else { balance })
+            .collect();
+
+        let y = compute_y(&balances_without_out, d, amp, balances.len());
+        (balances[out_index] - y).max(0.0)
+    }
+
+    /// Marginal spot price of `in_index` in terms of `out_index` — output per unit input
+    /// for a vanishingly small trade — approximated by a finite difference against a
+    /// trade of one-millionth of the input coin's balance.
+    pub fn spot_price(balances: &[f64], in_index: usize, out_index: usize, amp: f64) -> f64 {
+        let dx = (balances[in_index] * 1e-6).max(1e-9);
+        swap_output(balances, in_index, out_index, dx, amp) / dx
+    }
+}
+
+/// Which curve a pool prices trades with. Constant-product is the Uniswap V2 default;
+/// stable/LSD-oriented DEXes run an amplified invariant that's far flatter near the peg,
+/// so pricing them as constant-product both overstates their real depth and reports
+/// phantom spreads against genuinely constant-product venues.
+#[derive(Clone, Copy, Debug)]
+pub enum PoolKind {
+    ConstantProduct,
+    StableSwap { amp: f64 },
+}
+
+impl PoolKind {
+    /// Guesses a pool's curve from its DEX name, the same heuristic `swap_curve`'s
+    /// `CurveType::from_dex_name` uses in the main bot: Curve/Solidly-style forks run an
+    /// amplified invariant, everything else defaults to constant-product.
+    fn from_dex_name(name: &str) -> Self {
+        let lower = name.to_lowercase();
+        if lower.contains("curve")
+            || lower.contains("stable")
+            || lower.contains("velodrome")
+            || lower.contains("aerodrome")
+            || lower.contains("solidly")
+        {
+            PoolKind::StableSwap { amp: 100.0 }
+        } else {
+            PoolKind::ConstantProduct
+        }
+    }
+
+    /// Spot price of token1 in terms of token0 (same orientation as the naive
+    /// `reserve0/reserve1` ratio), priced per-curve instead of always assuming
+    /// constant-product.
+    fn spot_price(&self, reserve0: f64, reserve1: f64) -> f64 {
+        match self {
+            PoolKind::ConstantProduct => reserve0 / reserve1,
+            PoolKind::StableSwap { amp } => stable_math::spot_price(&[reserve0, reserve1], 1, 0, *amp),
+        }
+    }
+}
+
+/// Profit-maximizing trade size across two constant-product pools quoting the same
+/// token pair, found by golden-section search rather than just reporting the
+/// reserve-ratio spread as if it were realizable profit.
+mod optimal_size {
+    const INV_GOLDEN_RATIO: f64 = 0.6180339887498949;
+    const TOLERANCE: f64 = 1e-6;
+    const MAX_ITERATIONS: u32 = 100;
+
+    /// Net profit (in token B) of routing `x` of token B through pool1 (buying token A)
+    /// then pool2 (selling token A back for token B):
+    /// `g·(g·x·a1/(b1+g·x))·b2 / (a2 + g·x·a1/(b1+g·x)) − x`.
+    fn profit(x: f64, a1: f64, b1: f64, a2: f64, b2: f64, g: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        let a_bought = g * x * a1 / (b1 + g * x);
+        let b_received = g * a_bought * b2 / (a2 + a_bought);
+        b_received - x
+    }
+
+    /// Golden-section search for the maximizer of `profit` over `[0, b1]` — `profit` is
+    /// unimodal/concave in `x` there, so this converges without needing a derivative.
+    /// Returns `(optimal_input, expected_profit)`, both denominated in token B.
+    pub fn solve(a1: f64, b1: f64, a2: f64, b2: f64, g: f64) -> (f64, f64) {
+        if b1 <= 0.0 || a1 <= 0.0 || a2 <= 0.0 || b2 <= 0.0 {
+            return (0.0, 0.0);
+        }
+
+        let mut lo = 0.0;
+        let mut hi = b1;
+        let mut c = hi - (hi - lo) * INV_GOLDEN_RATIO;
+        let mut d = lo + (hi - lo) * INV_GOLDEN_RATIO;
+        let mut profit_c = profit(c, a1, b1, a2, b2, g);
+        let mut profit_d = profit(d, a1, b1, a2, b2, g);
+
+        for _ in 0..MAX_ITERATIONS {
+            if hi - lo < TOLERANCE {
+                break;
+            }
+            if profit_c < profit_d {
+                lo = c;
+                c = d;
+                profit_c = profit_d;
+                d = lo + (hi - lo) * INV_GOLDEN_RATIO;
+                profit_d = profit(d, a1, b1, a2, b2, g);
+            } else {
+                hi = d;
+                d = c;
+                profit_d = profit_c;
+                c = hi - (hi - lo) * INV_GOLDEN_RATIO;
+                profit_c = profit(c, a1, b1, a2, b2, g);
+            }
+        }
+
+        let x = (lo + hi) / 2.0;
+        let best_profit = profit(x, a1, b1, a2, b2, g);
+        if best_profit > 0.0 {
+            (x, best_profit)
+        } else {
+            (0.0, 0.0)
+        }
+    }
+}
+
+/// Swap fee assumed for pools this scanner doesn't otherwise track a fee for (a
+/// `PairData` only carries reserves, not its DEX's `fee_bps`) — the same 0.3% default
+/// Uniswap V2 forks overwhelmingly charge, used as a fallback gamma elsewhere in this
+/// tree (`scanner::combined_gamma`).
+const DEFAULT_FEE_MULTIPLIER: f64 = 0.997;
+
+/// Gas units assumed for a flash-loan arbitrage transaction, used only to turn the
+/// current gas price into a wei-scale cost for `FlashLoanOptimizer::gate_opportunity`.
+const FLASH_LOAN_TX_GAS_UNITS: u64 = 400_000;
 
 pub struct AdvancedScanner {
     provider: Arc<Provider<Http>>,
@@ -16,6 +231,9 @@ pub struct AdvancedScanner {
     all_pairs: Arc<RwLock<HashMap<String, Vec<PairData>>>>,
     pair_update_times: Arc<DashMap<String, u64>>,
     all_tokens: Arc<RwLock<HashSet<Address>>>,
+    /// Gates opportunities against flash-loan funding cost before they're surfaced —
+    /// see `find_all_opportunities`.
+    flash_optimizer: FlashLoanOptimizer,
 }
 
 #[derive(Clone, Debug)]
@@ -27,6 +245,7 @@ struct PairData {
     token1: Address,
     reserves: (U256, U256),
     last_update: u64,
+    pool_kind: PoolKind,
 }
 
 impl AdvancedScanner {
@@ -51,6 +270,7 @@ impl AdvancedScanner {
             all_pairs: Arc::new(RwLock::new(HashMap::new())),
             pair_update_times: Arc::new(DashMap::new()),
             all_tokens: Arc::new(RwLock::new(HashSet::new())),
+            flash_optimizer: FlashLoanOptimizer::new(),
         })
     }
     
@@ -130,6 +350,29 @@ impl AdvancedScanner {
         Ok(())
     }
     
+    /// Tags `pair_address` with a `PoolKind` the same way `PoolKind::from_dex_name` does,
+    /// except a `StableSwap` guess is followed by a live `A()` call so real stable pools
+    /// are priced with their actual amplification coefficient instead of the heuristic
+    /// default of 100 — `A` ranges from single digits (volatile pegs) to the thousands
+    /// (tight stablecoin-only pegs), and `stable_math` misprices both ends of that range
+    /// under a one-size-fits-all default.
+    async fn live_pool_kind(&self, pair_address: Address, dex_name: &str) -> PoolKind {
+        let default = PoolKind::from_dex_name(dex_name);
+        let PoolKind::StableSwap { amp: default_amp } = default else { return default };
+
+        let Ok(amp_abi) = ethers::abi::parse_abi(&["function A() view returns (uint256)"]) else {
+            return default;
+        };
+        let curve_pool = Contract::new(pair_address, amp_abi, self.provider.clone());
+        let Ok(call) = curve_pool.method::<_, U256>("A", ()) else {
+            return default;
+        };
+        match call.call().await {
+            Ok(amp) => PoolKind::StableSwap { amp: amp.as_u128() as f64 },
+            Err(_) => PoolKind::StableSwap { amp: default_amp },
+        }
+    }
+
     async fn fetch_pair_details(&self, pair_address: Address, dex_name: String) -> Result<Option<PairData>> {
         let pair_abi = ethers::abi::parse_abi(&[
             "function token0() view returns (address)",
@@ -155,6 +398,8 @@ impl AdvancedScanner {
             Err(_) => return Ok(None),
         };
         
+        let pool_kind = self.live_pool_kind(pair_address, &dex_name).await;
+
         Ok(Some(PairData {
             dex_name,
             factory: Address::zero(),
@@ -163,6 +408,7 @@ impl AdvancedScanner {
             token1,
             reserves: (reserves.0, reserves.1),
             last_update: self.current_timestamp(),
+            pool_kind,
         }))
     }
     
@@ -210,69 +456,198 @@ impl AdvancedScanner {
     }
     
     pub async fn find_all_opportunities(&self) -> Vec<ArbitrageOpportunity> {
-        let mut opportunities = Vec::new();
         let pairs = self.all_pairs.read().await;
-        
-        for (_token_pair, dex_pairs) in pairs.iter() {
-            if dex_pairs.len() < 2 {
-                continue;
+        let gas_cost_wei = self.current_gas_cost_wei().await;
+
+        let mut opportunities: Vec<ArbitrageOpportunity> = pairs
+            .values()
+            .flat_map(|dex_pairs| self.opportunities_for_dex_pairs(dex_pairs, gas_cost_wei))
+            .collect();
+
+        opportunities.sort_by(|a, b| b.expected_profit.partial_cmp(&a.expected_profit).unwrap());
+        opportunities.truncate(20); // Return top 20 opportunities
+        opportunities
+    }
+
+    /// Same detection as `find_all_opportunities`, restricted to the single token-pair
+    /// `key` (`"{token0:?}-{token1:?}"`) — used by `subscribe_to_updates` to re-run
+    /// detection only for the pair a `Sync` event just touched, instead of rescanning
+    /// every pair on every reserve update.
+    pub async fn find_opportunities_for_key(&self, key: &str) -> Vec<ArbitrageOpportunity> {
+        let pairs = self.all_pairs.read().await;
+        let Some(dex_pairs) = pairs.get(key) else {
+            return Vec::new();
+        };
+
+        let gas_cost_wei = self.current_gas_cost_wei().await;
+        self.opportunities_for_dex_pairs(dex_pairs, gas_cost_wei)
+    }
+
+    async fn current_gas_cost_wei(&self) -> U256 {
+        let gas_price = self.provider.get_gas_price().await.unwrap_or_default();
+        gas_price * U256::from(FLASH_LOAN_TX_GAS_UNITS)
+    }
+
+    /// Core pairwise-spread detection over one token pair's quotes across DEXes, shared
+    /// by `find_all_opportunities` (every pair) and `find_opportunities_for_key` (one
+    /// pair, off a `Sync` event).
+    fn opportunities_for_dex_pairs(&self, dex_pairs: &[PairData], gas_cost_wei: U256) -> Vec<ArbitrageOpportunity> {
+        let mut opportunities = Vec::new();
+
+        if dex_pairs.len() < 2 {
+            return opportunities;
+        }
+
+        let mut prices: Vec<(String, f64, &PairData)> = Vec::new();
+
+        for pair_data in dex_pairs {
+            if pair_data.reserves.0 > U256::zero() && pair_data.reserves.1 > U256::zero() {
+                let reserve0 = pair_data.reserves.0.as_u128() as f64;
+                let reserve1 = pair_data.reserves.1.as_u128() as f64;
+                let price = pair_data.pool_kind.spot_price(reserve0, reserve1);
+                prices.push((pair_data.dex_name.clone(), price, pair_data));
             }
-            
-            let mut prices: Vec<(String, f64, &PairData)> = Vec::new();
-            
-            for pair_data in dex_pairs {
-                if pair_data.reserves.0 > U256::zero() && pair_data.reserves.1 > U256::zero() {
-                    let price = pair_data.reserves.0.as_u128() as f64 / 
-                               pair_data.reserves.1.as_u128() as f64;
-                    prices.push((pair_data.dex_name.clone(), price, pair_data));
+        }
+
+        for i in 0..prices.len() {
+            for j in i+1..prices.len() {
+                let (dex1, price1, data1) = &prices[i];
+                let (dex2, price2, data2) = &prices[j];
+
+                let spread_pct = ((price1 - price2).abs() / price1.min(*price2)) * 100.0;
+
+                if spread_pct > 0.3 {
+                    // Buy token0 where it's cheaper (higher price1/price2, since price
+                    // is token0-per-token1: more token0 per token1 paid), sell it where
+                    // it's pricier.
+                    let (buy_data, sell_data) = if price1 >= price2 { (*data1, *data2) } else { (*data2, *data1) };
+                    let a1 = buy_data.reserves.0.as_u128() as f64;
+                    let b1 = buy_data.reserves.1.as_u128() as f64;
+                    let a2 = sell_data.reserves.0.as_u128() as f64;
+                    let b2 = sell_data.reserves.1.as_u128() as f64;
+
+                    let (optimal_input, expected_profit) =
+                        optimal_size::solve(a1, b1, a2, b2, DEFAULT_FEE_MULTIPLIER);
+                    let realized_spread_after_impact = if optimal_input > 0.0 {
+                        (expected_profit / optimal_input) * 100.0
+                    } else {
+                        0.0
+                    };
+
+                    // Reject/drop whatever doesn't clear break-even once the cost of
+                    // actually borrowing `optimal_input` is accounted for, instead of
+                    // surfacing a spread that's only real before funding cost.
+                    let borrow_amount = U256::from(optimal_input.max(0.0) as u128);
+                    let Some(funded) = self.flash_optimizer.gate_opportunity(
+                        borrow_amount,
+                        expected_profit,
+                        gas_cost_wei,
+                    ) else {
+                        continue;
+                    };
+
+                    opportunities.push(ArbitrageOpportunity {
+                        token0: data1.token0,
+                        token1: data1.token1,
+                        dex1: dex1.clone(),
+                        dex2: dex2.clone(),
+                        pair1: data1.pair_address,
+                        pair2: data2.pair_address,
+                        spread_pct,
+                        reserves1: data1.reserves,
+                        reserves2: data2.reserves,
+                        optimal_input,
+                        expected_profit,
+                        realized_spread_after_impact,
+                        flash_loan_providers: funded.providers,
+                        borrowed_amount: funded.borrowed_amount,
+                        loan_fee: funded.loan_fee,
+                        net_profit_after_loan: funded.net_profit,
+                    });
                 }
             }
-            
-            for i in 0..prices.len() {
-                for j in i+1..prices.len() {
-                    let (dex1, price1, data1) = &prices[i];
-                    let (dex2, price2, data2) = &prices[j];
-                    
-                    let spread_pct = ((price1 - price2).abs() / price1.min(*price2)) * 100.0;
-                    
-                    if spread_pct > 0.3 {
-                        opportunities.push(ArbitrageOpportunity {
-                            token0: data1.token0,
-                            token1: data1.token1,
-                            dex1: dex1.clone(),
-                            dex2: dex2.clone(),
-                            pair1: data1.pair_address,
-                            pair2: data2.pair_address,
-                            spread_pct,
-                            reserves1: data1.reserves,
-                            reserves2: data2.reserves,
-                        });
-                    }
+        }
+
+        opportunities
+    }
+
+    /// Replaces block-only polling with an event-driven feed: subscribes to `Sync` logs
+    /// for every currently-known `pair_address` over `ws_provider`, decodes the two
+    /// reserves straight out of the log data, and updates `all_pairs` in place as soon
+    /// as each event lands — instead of waiting on `smart_update_reserves`'s 5-second
+    /// staleness timer. Each update triggers `find_opportunities_for_key` for just that
+    /// pair, so a fresh arbitrage surfaces within one block.
+    pub async fn subscribe_to_updates(self: Arc<Self>) -> Result<()> {
+        let Some(ws_provider) = self.ws_provider.clone() else {
+            return Ok(());
+        };
+
+        let pair_addresses: Vec<Address> = {
+            let pairs = self.all_pairs.read().await;
+            pairs.values().flat_map(|dex_pairs| dex_pairs.iter().map(|p| p.pair_address)).collect()
+        };
+
+        if pair_addresses.is_empty() {
+            return Ok(());
+        }
+
+        let sync_topic = H256::from(ethers::utils::keccak256("Sync(uint112,uint112)"));
+        let filter = Filter::new().address(pair_addresses).topic0(sync_topic);
+
+        tokio::spawn(async move {
+            let Ok(mut stream) = ws_provider.subscribe_logs(&filter).await else {
+                return;
+            };
+
+            while let Some(log) = stream.next().await {
+                let Some((reserve0, reserve1)) = Self::decode_sync_log(&log) else {
+                    continue;
+                };
+                let Some(key) = self.apply_reserve_update(log.address, reserve0, reserve1).await else {
+                    continue;
+                };
+
+                for opp in self.find_opportunities_for_key(&key).await {
+                    println!(
+                        "⚡ Sync-triggered opportunity: {} ↔️ {} | {:.3}% spread",
+                        opp.dex1, opp.dex2, opp.spread_pct
+                    );
                 }
             }
+        });
+
+        Ok(())
+    }
+
+    /// Decodes a `Sync(uint112 reserve0, uint112 reserve1)` log's two non-indexed,
+    /// word-padded reserves straight out of `log.data`, without needing the full ABI.
+    fn decode_sync_log(log: &Log) -> Option<(U256, U256)> {
+        if log.data.0.len() < 64 {
+            return None;
         }
-        
-        opportunities.sort_by(|a, b| b.spread_pct.partial_cmp(&a.spread_pct).unwrap());
-        opportunities.truncate(20); // Return top 20 opportunities
-        opportunities
+        let reserve0 = U256::from_big_endian(&log.data.0[0..32]);
+        let reserve1 = U256::from_big_endian(&log.data.0[32..64]);
+        Some((reserve0, reserve1))
     }
-    
-    pub async fn subscribe_to_updates(&self) -> Result<()> {
-        if let Some(ws_provider) = &self.ws_provider {
-            let ws_clone = ws_provider.clone();
-            
-            tokio::spawn(async move {
-                if let Ok(mut stream) = ws_clone.subscribe_blocks().await {
-                    while let Some(block) = stream.next().await {
-                        println!("New block: {}", block.number.unwrap_or_default());
-                    }
+
+    /// Writes a `Sync`-reported reserve pair into the matching `PairData` wherever it
+    /// lives in `all_pairs`, returning the token-pair key it was found under so the
+    /// caller can re-run detection for just that pair.
+    async fn apply_reserve_update(&self, pair_address: Address, reserve0: U256, reserve1: U256) -> Option<String> {
+        let now = self.current_timestamp();
+        let mut pairs = self.all_pairs.write().await;
+        for (key, dex_pairs) in pairs.iter_mut() {
+            for pair_data in dex_pairs.iter_mut() {
+                if pair_data.pair_address == pair_address {
+                    pair_data.reserves = (reserve0, reserve1);
+                    pair_data.last_update = now;
+                    return Some(key.clone());
                 }
-            });
+            }
         }
-        
-        Ok(())
+        None
     }
-    
+
     fn current_timestamp(&self) -> u64 {
         std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -281,7 +656,41 @@ impl AdvancedScanner {
     }
 }
 
-#[derive(Debug, Clone)]
+/// `serde(with = "hex_or_decimal_pair")`: external aggregator APIs (0x, CoW-style quote
+/// endpoints) encode big integers inconsistently as `0x`-prefixed hex or plain decimal
+/// strings, so reserve tuples need to accept either on the way in. Always serializes back
+/// to decimal strings, which every consumer of this wire format can parse unambiguously.
+mod hex_or_decimal_pair {
+    use ethers::types::U256;
+    use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+    fn parse(raw: &str) -> Result<U256, String> {
+        if let Some(hex) = raw.strip_prefix("0x").or_else(|| raw.strip_prefix("0X")) {
+            U256::from_str_radix(hex, 16).map_err(|e| e.to_string())
+        } else {
+            U256::from_dec_str(raw).map_err(|e| e.to_string())
+        }
+    }
+
+    pub fn serialize<S>(value: &(U256, U256), serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (value.0.to_string(), value.1.to_string()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<(U256, U256), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (a, b): (String, String) = Deserialize::deserialize(deserializer)?;
+        let a = parse(&a).map_err(D::Error::custom)?;
+        let b = parse(&b).map_err(D::Error::custom)?;
+        Ok((a, b))
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ArbitrageOpportunity {
     pub token0: Address,
     pub token1: Address,
@@ -290,6 +699,24 @@ pub struct ArbitrageOpportunity {
     pub pair1: Address,
     pub pair2: Address,
     pub spread_pct: f64,
+    #[serde(with = "hex_or_decimal_pair")]
     pub reserves1: (U256, U256),
+    #[serde(with = "hex_or_decimal_pair")]
     pub reserves2: (U256, U256),
+    /// Profit-maximizing trade size, in token1 units, from `optimal_size::solve`.
+    pub optimal_input: f64,
+    /// Net profit at `optimal_input`, in token1 units, after both pools' price impact.
+    pub expected_profit: f64,
+    /// `expected_profit / optimal_input` as a percentage — the spread actually realizable
+    /// after price impact, as opposed to `spread_pct`'s zero-size reserve-ratio spread.
+    pub realized_spread_after_impact: f64,
+    /// Flash-loan provider(s) `FlashLoanOptimizer::gate_opportunity` chose to fund this
+    /// trade with, and how much each was borrowed for. More than one entry means no
+    /// single provider's `max_loan_amount` covered the full size.
+    pub flash_loan_providers: Vec<(String, U256)>,
+    pub borrowed_amount: U256,
+    pub loan_fee: U256,
+    /// `expected_profit` minus the loan fee and gas — what this opportunity nets after
+    /// the cost of actually borrowing the capital to capture it.
+    pub net_profit_after_loan: f64,
 }
\ No newline at end of file
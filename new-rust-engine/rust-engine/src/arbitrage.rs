@@ -4,6 +4,11 @@ use anyhow::Result;
 use std::sync::Arc;
 use crate::wallet::WalletManager;
 use crate::config::Config;
+use crate::monitor::get_eip1559_fees;
+use crate::scanner::CyclicOpportunity;
+
+/// Signer chain used by this bot; `WalletManager` is keyed by this name.
+const EXECUTION_CHAIN: &str = "arbitrum";
 
 #[derive(Debug, Clone)]
 pub struct ArbitrageOpportunity {
@@ -15,6 +20,47 @@ pub struct ArbitrageOpportunity {
     pub estimated_profit: U256,
     pub profit_after_gas: U256,
     pub gas_estimate: U256,
+    /// Ordered hop list for a multi-hop ring (`WETH -> USDC -> ARB -> WETH`, say), each
+    /// entry the token held *after* that hop and the DEX it traded on. Empty for a plain
+    /// two-leg `token_a`/`token_b` opportunity, which `build_arbitrage_calldata` still
+    /// encodes as a single swap; non-empty for a cycle from `scan_triangular_arbitrage`,
+    /// encoded as the full ordered route instead.
+    pub path: Vec<(Address, String)>,
+}
+
+impl ArbitrageOpportunity {
+    /// Builds a ring opportunity from a detected `CyclicOpportunity`, pricing gas the same
+    /// way a plain two-leg opportunity does. `token_a`/`token_b` are kept as the cycle's
+    /// first token and its first hop's destination so callers that only look at those two
+    /// fields (logging, dashboards) still see a sensible pair.
+    pub fn from_cycle(cycle: &CyclicOpportunity, gas_estimate: U256) -> Option<Self> {
+        let &first = cycle.tokens.first()?;
+        let &second = cycle.tokens.get(1)?;
+        if cycle.estimated_profit <= 0.0 {
+            return None;
+        }
+
+        let path = cycle.tokens[1..]
+            .iter()
+            .zip(cycle.dexes.iter())
+            .map(|(&token, dex)| (token, dex.clone()))
+            .collect();
+
+        let optimal_amount = U256::from(cycle.optimal_amount.max(0.0) as u128);
+        let estimated_profit = U256::from(cycle.estimated_profit.max(0.0) as u128);
+
+        Some(Self {
+            token_a: first,
+            token_b: second,
+            buy_from_dex: cycle.dexes.first().cloned().unwrap_or_default(),
+            sell_to_dex: cycle.dexes.last().cloned().unwrap_or_default(),
+            optimal_amount,
+            estimated_profit,
+            profit_after_gas: estimated_profit.saturating_sub(gas_estimate),
+            gas_estimate,
+            path,
+        })
+    }
 }
 
 pub struct ArbitrageExecutor {
@@ -40,39 +86,37 @@ impl ArbitrageExecutor {
     }
     
     pub async fn execute_with_flashloan(&self, opp: ArbitrageOpportunity) -> Result<H256> {
-        // Build flashloan execution data
-        let abi = ethers::abi::parse_abi(&[
-            "function executeFlashLoan(address asset, uint256 amount, bytes calldata params)"
-        ])?;
-        
-        let contract = Contract::new(
-            self.flashloan_contract,
-            abi,
-            self.wallet.client()
-        );
-        
-        // Encode swap parameters
-        let swap_params = ethers::abi::encode(&[
-            ethers::abi::Token::Address(opp.token_a),
-            ethers::abi::Token::Address(opp.token_b),
-            ethers::abi::Token::Uint(opp.optimal_amount),
-            ethers::abi::Token::String(opp.buy_from_dex),
-            ethers::abi::Token::String(opp.sell_to_dex),
-        ]);
-        
-        // Execute with higher gas price for priority
-        let gas_price = self.provider.get_gas_price().await?;
-        let priority_gas = gas_price * 120 / 100; // 20% higher
-        
-        // Fix: Create the call and store it in a variable
-        let call = contract
-            .method::<_, ()>("executeFlashLoan", (opp.token_a, opp.optimal_amount, swap_params))?
-            .gas(500000)
-            .gas_price(priority_gas);
-        
-        let pending_tx = call.send().await?;
+        let client = self.wallet.client_for(EXECUTION_CHAIN).ok_or_else(|| {
+            anyhow::anyhow!("no {EXECUTION_CHAIN} signer configured on WalletManager")
+        })?;
+
+        let calldata = self.build_arbitrage_calldata(&opp)?;
+
+        // Build an EIP-1559 transaction priced off the projected next base fee, falling
+        // back to a 20%-bumped legacy gas price on chains/RPCs that don't support 1559.
+        let tx: TypedTransaction = if self.config.legacy_fallback {
+            let gas_price = self.provider.get_gas_price().await?;
+            TransactionRequest::new()
+                .to(self.flashloan_contract)
+                .data(calldata)
+                .gas(500000)
+                .gas_price(gas_price * 120 / 100)
+                .into()
+        } else {
+            let (max_fee_per_gas, max_priority_fee_per_gas) =
+                get_eip1559_fees(&self.provider, &self.config).await?;
+            Eip1559TransactionRequest::new()
+                .to(self.flashloan_contract)
+                .data(calldata)
+                .gas(500000)
+                .max_fee_per_gas(max_fee_per_gas)
+                .max_priority_fee_per_gas(max_priority_fee_per_gas)
+                .into()
+        };
+
+        let pending_tx = client.send_transaction(tx, None).await?;
         let tx_hash = pending_tx.tx_hash();
-        
+
         Ok(tx_hash)
     }
     
@@ -81,33 +125,59 @@ impl ArbitrageExecutor {
         // This prevents wasting gas on failed transactions
         
         let call_data = self.build_arbitrage_calldata(opp)?;
-        
-        let tx = TypedTransaction::Legacy(TransactionRequest {
-            to: Some(NameOrAddress::Address(self.flashloan_contract)),
-            data: Some(call_data),
-            gas: Some(U256::from(500000)),
-            ..Default::default()
-        });
-        
+
+        let tx: TypedTransaction = if self.config.legacy_fallback {
+            TransactionRequest::new()
+                .to(self.flashloan_contract)
+                .data(call_data)
+                .gas(U256::from(500000))
+                .into()
+        } else {
+            let (max_fee_per_gas, max_priority_fee_per_gas) =
+                get_eip1559_fees(&self.provider, &self.config).await?;
+            Eip1559TransactionRequest::new()
+                .to(self.flashloan_contract)
+                .data(call_data)
+                .gas(U256::from(500000))
+                .max_fee_per_gas(max_fee_per_gas)
+                .max_priority_fee_per_gas(max_priority_fee_per_gas)
+                .into()
+        };
+
         match self.provider.call(&tx, None).await {
             Ok(_) => Ok(true),
             Err(_) => Ok(false),
         }
     }
     
+    /// Builds the `executeFlashLoan` calldata for `opp`. The `params` bytes carry the
+    /// route: for a plain two-leg opportunity (`opp.path` empty) that's just
+    /// `(token_b, buy_from_dex, sell_to_dex)`; for a multi-hop ring (`opp.path` from
+    /// `ArbitrageOpportunity::from_cycle`) it's the full ordered hop list of
+    /// `(token, dex)` pairs, so the flashloan contract can walk every leg of the cycle in
+    /// one call instead of only ever supporting a single buy/sell pair.
     fn build_arbitrage_calldata(&self, opp: &ArbitrageOpportunity) -> Result<Bytes> {
-        // Build the calldata for the arbitrage execution
         let abi = ethers::abi::parse_abi(&[
             "function executeFlashLoan(address,uint256,bytes)"
         ])?;
-        
         let func = abi.function("executeFlashLoan")?;
-        let params = ethers::abi::encode(&[
-            ethers::abi::Token::Address(opp.token_a),
-            ethers::abi::Token::Uint(opp.optimal_amount),
-            ethers::abi::Token::Bytes(vec![]),
-        ]);
-        
+
+        let params = if opp.path.is_empty() {
+            ethers::abi::encode(&[
+                ethers::abi::Token::Address(opp.token_b),
+                ethers::abi::Token::String(opp.buy_from_dex.clone()),
+                ethers::abi::Token::String(opp.sell_to_dex.clone()),
+            ])
+        } else {
+            let hop_tokens = ethers::abi::Token::Array(
+                opp.path.iter().map(|(token, _)| ethers::abi::Token::Address(*token)).collect(),
+            );
+            let hop_dexes = ethers::abi::Token::Array(
+                opp.path.iter().map(|(_, dex)| ethers::abi::Token::String(dex.clone())).collect(),
+            );
+            ethers::abi::encode(&[hop_tokens, hop_dexes])
+        };
+
         Ok(func.encode_input(&[
             ethers::abi::Token::Address(opp.token_a),
             ethers::abi::Token::Uint(opp.optimal_amount),
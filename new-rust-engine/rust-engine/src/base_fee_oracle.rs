@@ -0,0 +1,129 @@
+// rust-engine/src/base_fee_oracle.rs
+//
+// Forecasts base fee several blocks ahead so the scanner can decide whether an
+// opportunity is still worth executing by the time a transaction actually lands,
+// instead of pricing gas off the current block alone.
+
+use ethers::prelude::*;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use parking_lot::RwLock;
+use anyhow::Result;
+
+/// How many recent block samples to keep in the rolling window. The fill-ratio EWMA is
+/// updated from every sample and does not depend on this size.
+const WINDOW_SIZE: usize = 20;
+
+/// Weight given to the newest sample when updating the EWMA fill ratio. Lower than
+/// 0.5 since L2 block fullness is noisy block-to-block.
+const EWMA_ALPHA: f64 = 0.2;
+
+/// EIP-1559 allows the base fee to move by at most 1/8th per block.
+const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
+#[derive(Debug, Clone, Copy)]
+struct BlockSample {
+    gas_used: U256,
+    gas_limit: U256,
+}
+
+/// Tracks a rolling window of recent block headers for one chain and forecasts the
+/// base fee N blocks out by iterating the EIP-1559 recurrence, projecting future
+/// `gas_used` from an EWMA of observed fill ratios since real usage isn't known yet.
+pub struct BaseFeeOracle {
+    provider: Arc<Provider<Http>>,
+    window: RwLock<VecDeque<BlockSample>>,
+    ewma_fill_ratio: RwLock<Option<f64>>,
+}
+
+impl BaseFeeOracle {
+    pub fn new(provider: Arc<Provider<Http>>) -> Self {
+        Self {
+            provider,
+            window: RwLock::new(VecDeque::with_capacity(WINDOW_SIZE)),
+            ewma_fill_ratio: RwLock::new(None),
+        }
+    }
+
+    /// Pulls the latest block and folds it into the rolling window/EWMA. Call this
+    /// once per tick from the scanner's update loop.
+    pub async fn sample_latest(&self) -> Result<()> {
+        let block = self
+            .provider
+            .get_block(BlockNumber::Latest)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("latest block unavailable"))?;
+
+        if block.gas_limit.is_zero() {
+            return Ok(());
+        }
+
+        let fill_ratio = block.gas_used.as_u128() as f64 / block.gas_limit.as_u128() as f64;
+        {
+            let mut ewma = self.ewma_fill_ratio.write();
+            *ewma = Some(match *ewma {
+                Some(prev) => EWMA_ALPHA * fill_ratio + (1.0 - EWMA_ALPHA) * prev,
+                None => fill_ratio,
+            });
+        }
+
+        let mut window = self.window.write();
+        if window.len() == WINDOW_SIZE {
+            window.pop_front();
+        }
+        window.push_back(BlockSample { gas_used: block.gas_used, gas_limit: block.gas_limit });
+
+        Ok(())
+    }
+
+    /// Forecasts `baseFeePerGas` `blocks_ahead` blocks from now. Block 0 is priced from
+    /// the real latest `base_fee_per_gas`/`gas_used`; every subsequent block's gas usage
+    /// is projected from the EWMA fill ratio since its real usage can't be known yet.
+    pub async fn forecast_base_fee(&self, blocks_ahead: u64) -> Result<U256> {
+        let block = self
+            .provider
+            .get_block(BlockNumber::Latest)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("latest block unavailable"))?;
+
+        let mut base_fee = block.base_fee_per_gas.unwrap_or_default();
+        let gas_limit = block.gas_limit;
+        let gas_target = gas_limit / 2;
+        if gas_target.is_zero() {
+            return Ok(base_fee);
+        }
+
+        let fill_ratio = match *self.ewma_fill_ratio.read() {
+            Some(ratio) => ratio,
+            None => block.gas_used.as_u128() as f64 / gas_limit.as_u128() as f64,
+        };
+        let projected_gas_used = U256::from((gas_limit.as_u128() as f64 * fill_ratio) as u128);
+
+        // First step uses the real `gas_used` for this latest block; the rest use the
+        // EWMA-projected usage since actual future usage is unknown.
+        let mut gas_used = block.gas_used;
+        for _ in 0..blocks_ahead {
+            base_fee = Self::next_base_fee(base_fee, gas_used, gas_target);
+            gas_used = projected_gas_used;
+        }
+
+        Ok(base_fee)
+    }
+
+    /// `base_{k+1}` from `base_k`/`gas_used_k`/`gas_target`, following the deterministic
+    /// EIP-1559 recurrence clamped to at most a 1/8th move per block.
+    fn next_base_fee(base_fee: U256, gas_used: U256, gas_target: U256) -> U256 {
+        if gas_used == gas_target || base_fee.is_zero() {
+            return base_fee;
+        }
+
+        if gas_used > gas_target {
+            let delta = (base_fee * (gas_used - gas_target) / gas_target / BASE_FEE_MAX_CHANGE_DENOMINATOR)
+                .max(U256::one());
+            base_fee + delta
+        } else {
+            let delta = base_fee * (gas_target - gas_used) / gas_target / BASE_FEE_MAX_CHANGE_DENOMINATOR;
+            base_fee.saturating_sub(delta)
+        }
+    }
+}
@@ -1,5 +1,13 @@
 use ethers::prelude::*;
 use anyhow::Result;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct ChainConfig {
+    pub enabled: bool,
+    pub rpc_url: String,
+    pub chain_id: u64,
+}
 
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -8,18 +16,81 @@ pub struct Config {
     pub min_profit_wei: U256,
     pub max_gas_price: U256,
     pub monitoring_pairs: Vec<(Address, Address)>,
+    pub chains: HashMap<String, ChainConfig>,
+    /// Ceiling on `max_fee_per_gas` an EIP-1559 transaction will offer, regardless of how
+    /// high the projected base fee climbs.
+    pub max_base_fee: U256,
+    /// Flat `max_priority_fee_per_gas` tip in wei. Zero means "derive it from the median
+    /// reward reported by `eth_feeHistory` instead".
+    pub priority_fee_wei: U256,
+    /// Build legacy (type-0) transactions priced off `eth_gasPrice` instead of EIP-1559.
+    /// Needed for chains/RPCs that don't support `eth_feeHistory` or type-2 transactions.
+    pub legacy_fallback: bool,
 }
 
 impl Config {
     pub fn from_env() -> Result<Self> {
+        let mut chains = HashMap::new();
+
+        chains.insert(
+            "arbitrum".to_string(),
+            ChainConfig {
+                enabled: true,
+                rpc_url: std::env::var("RPC_URL")
+                    .unwrap_or_else(|_| "https://arb1.arbitrum.io/rpc".to_string()),
+                chain_id: 42161,
+            },
+        );
+        chains.insert(
+            "ethereum".to_string(),
+            ChainConfig {
+                enabled: std::env::var("ETH_RPC_URL").is_ok(),
+                rpc_url: std::env::var("ETH_RPC_URL")
+                    .unwrap_or_else(|_| "https://eth.llamarpc.com".to_string()),
+                chain_id: 1,
+            },
+        );
+        chains.insert(
+            "polygon".to_string(),
+            ChainConfig {
+                enabled: std::env::var("POLYGON_RPC_URL").is_ok(),
+                rpc_url: std::env::var("POLYGON_RPC_URL")
+                    .unwrap_or_else(|_| "https://polygon-rpc.com".to_string()),
+                chain_id: 137,
+            },
+        );
+        chains.insert(
+            "bsc".to_string(),
+            ChainConfig {
+                enabled: std::env::var("BSC_RPC_URL").is_ok(),
+                rpc_url: std::env::var("BSC_RPC_URL")
+                    .unwrap_or_else(|_| "https://bsc-dataseed.binance.org".to_string()),
+                chain_id: 56,
+            },
+        );
+
         Ok(Self {
-            ws_url: std::env::var("WS_URL").unwrap_or_else(|_| 
+            ws_url: std::env::var("WS_URL").unwrap_or_else(|_|
                 "wss://arb-mainnet.g.alchemy.com/v2/YOUR_KEY".to_string()),
             http_url: std::env::var("RPC_URL").unwrap_or_else(|_|
                 "https://arb1.arbitrum.io/rpc".to_string()),
             min_profit_wei: U256::from(10u64.pow(16)), // 0.01 ETH minimum profit
             max_gas_price: U256::from(10u64.pow(9) * 100), // 100 Gwei max
             monitoring_pairs: vec![],
+            chains,
+            max_base_fee: std::env::var("MAX_BASE_FEE_GWEI")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(|gwei| U256::from(gwei) * U256::from(10u64.pow(9)))
+                .unwrap_or_else(|| U256::from(10u64.pow(9) * 200)), // 200 Gwei max
+            priority_fee_wei: std::env::var("PRIORITY_FEE_GWEI")
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(|gwei| U256::from(gwei) * U256::from(10u64.pow(9)))
+                .unwrap_or_default(), // 0 => derive from eth_feeHistory
+            legacy_fallback: std::env::var("LEGACY_GAS_FALLBACK")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
         })
     }
 }
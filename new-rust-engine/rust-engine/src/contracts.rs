@@ -2,6 +2,116 @@
 use ethers::prelude::*;
 use ethers::abi::Abi;
 use anyhow::Result;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use std::path::Path;
+
+/// Deserializes an `Address`, rejecting malformed input instead of panicking via
+/// `.unwrap()`, and accepting either a checksummed or lowercase `0x`-prefixed string.
+fn deserialize_address<'de, D>(deserializer: D) -> Result<Address, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    raw.parse::<Address>()
+        .map_err(|e| D::Error::custom(format!("invalid address '{raw}': {e}")))
+}
+
+
+// Type-safe compile-time bindings, generated from the same interfaces as the
+// `parse_abi` helpers below. Prefer these at new call sites (`UniswapV2Pair::new(...)`,
+// `UniswapV2Factory::new(...)`, ...) so argument/return types and event decoding are
+// checked at build time instead of failing at runtime on a typo or signature drift.
+abigen!(
+    UniswapV2Pair,
+    r#"[
+        function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast)
+        function token0() external view returns (address)
+        function token1() external view returns (address)
+        function swap(uint amount0Out, uint amount1Out, address to, bytes data) external
+        function sync() external
+        event Sync(uint112 reserve0, uint112 reserve1)
+        event Swap(address indexed sender, uint amount0In, uint amount1In, uint amount0Out, uint amount1Out, address indexed to)
+    ]"#
+);
+
+abigen!(
+    UniswapV2Factory,
+    r#"[
+        function getPair(address tokenA, address tokenB) external view returns (address pair)
+        function allPairs(uint) external view returns (address)
+        function allPairsLength() external view returns (uint)
+    ]"#
+);
+
+abigen!(
+    UniswapV2Router,
+    r#"[
+        function swapExactTokensForTokens(uint amountIn, uint amountOutMin, address[] calldata path, address to, uint deadline) external returns (uint[] memory amounts)
+        function getAmountsOut(uint amountIn, address[] calldata path) external view returns (uint[] memory amounts)
+    ]"#
+);
+
+abigen!(
+    Erc20,
+    r#"[
+        function balanceOf(address) external view returns (uint256)
+        function transfer(address to, uint256 amount) external returns (bool)
+        function approve(address spender, uint256 amount) external returns (bool)
+        function decimals() external view returns (uint8)
+        function symbol() external view returns (string)
+    ]"#
+);
+
+abigen!(
+    UniswapV3Factory,
+    r#"[
+        function getPool(address tokenA, address tokenB, uint24 fee) external view returns (address pool)
+    ]"#
+);
+
+abigen!(
+    UniswapV3Pool,
+    r#"[
+        function slot0() external view returns (uint160 sqrtPriceX96, int24 tick, uint16 observationIndex, uint16 observationCardinality, uint16 observationCardinalityNext, uint8 feeProtocol, bool unlocked)
+        function liquidity() external view returns (uint128)
+        function fee() external view returns (uint24)
+    ]"#
+);
+
+// The canonical Multicall3 deployment, at the same address on every chain this bot
+// targets. Only the legacy `aggregate` entry point is bound (Multicall3 still implements
+// it for Multicall1 compatibility) since none of our batched reads need per-call failure
+// tolerance — if one `getReserves()` call reverts, the whole scan round should surface
+// that rather than silently treating the pool as missing.
+abigen!(
+    Multicall3,
+    r#"[
+        function aggregate((address target, bytes callData)[] calls) external returns (uint256 blockNumber, bytes[] returnData)
+    ]"#
+);
+
+/// Address of the Multicall3 contract, deployed at this address on essentially every
+/// EVM chain (Arbitrum, Optimism, Base included).
+pub const MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+
+abigen!(
+    AaveFlashLoan,
+    r#"[
+        function flashLoan(address receiver, address[] calldata assets, uint256[] calldata amounts, uint256[] calldata modes, address onBehalfOf, bytes calldata params, uint16 referralCode) external
+        function FLASHLOAN_PREMIUM_TOTAL() external view returns (uint128)
+    ]"#
+);
+
+/// Chainlink's price-feed interface. `latestRoundData` and `decimals` are the only reads
+/// the scanner needs — `decimals` to scale `answer` and `updatedAt` to reject a round
+/// that's gone stale — so nothing else of the real `AggregatorV3Interface` is bound.
+abigen!(
+    AggregatorV3Interface,
+    r#"[
+        function latestRoundData() external view returns (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound)
+        function decimals() external view returns (uint8)
+    ]"#
+);
 
 // Contract ABIs
 pub fn get_flash_loan_abi() -> Result<Abi> {
@@ -62,27 +172,138 @@ pub fn get_arbitrage_bytecode() -> Bytes {
 }
 
 // Contract addresses for different chains
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ChainContracts {
     pub chain_id: u64,
     pub flash_loan_providers: Vec<FlashProvider>,
     pub dex_factories: Vec<DexFactory>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FlashProvider {
     pub name: String,
+    #[serde(
+        serialize_with = "serialize_address",
+        deserialize_with = "deserialize_address"
+    )]
     pub address: Address,
     pub fee_bps: u16, // basis points
+    #[serde(
+        default,
+        serialize_with = "serialize_opt_hex_or_decimal",
+        deserialize_with = "deserialize_opt_hex_or_decimal"
+    )]
+    pub max_loan_amount: Option<U256>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DexFactory {
     pub name: String,
+    #[serde(
+        serialize_with = "serialize_address",
+        deserialize_with = "deserialize_address"
+    )]
     pub factory: Address,
+    #[serde(
+        serialize_with = "serialize_address",
+        deserialize_with = "deserialize_address"
+    )]
     pub router: Address,
     pub fee_bps: u16,
 }
 
+/// Registry of per-chain flash-loan providers and DEX factories, loaded from the
+/// built-in defaults and optionally overridden/extended from a config file so adding a
+/// chain or DEX no longer requires a recompile.
+pub struct ContractRegistry {
+    chains: std::collections::HashMap<u64, ChainContracts>,
+}
+
+impl ContractRegistry {
+    pub fn with_defaults() -> Self {
+        let mut chains = std::collections::HashMap::new();
+        for chain_id in [42161u64, 10, 8453] {
+            chains.insert(chain_id, get_chain_contracts(chain_id));
+        }
+        Self { chains }
+    }
+
+    /// Load a registry from a TOML or JSON file (by extension) and merge it over the
+    /// built-in defaults: entries for a chain id already present extend that chain's
+    /// provider/factory lists, new chain ids are added outright. A JSON file is a
+    /// top-level array of chain entries; a TOML file lists them as `[[chains]]` tables.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct TomlRegistry {
+            chains: Vec<ChainContracts>,
+        }
+
+        let mut registry = Self::with_defaults();
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+
+        let overrides: Vec<ChainContracts> = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => toml::from_str::<TomlRegistry>(&contents)?.chains,
+            _ => serde_json::from_str(&contents)?,
+        };
+
+        for entry in overrides {
+            registry.merge(entry);
+        }
+
+        Ok(registry)
+    }
+
+    fn merge(&mut self, overrides: ChainContracts) {
+        match self.chains.get_mut(&overrides.chain_id) {
+            Some(existing) => {
+                existing.flash_loan_providers.extend(overrides.flash_loan_providers);
+                existing.dex_factories.extend(overrides.dex_factories);
+            }
+            None => {
+                self.chains.insert(overrides.chain_id, overrides);
+            }
+        }
+    }
+
+    pub fn get(&self, chain_id: u64) -> ChainContracts {
+        self.chains.get(&chain_id).cloned().unwrap_or(ChainContracts {
+            chain_id,
+            flash_loan_providers: vec![],
+            dex_factories: vec![],
+        })
+    }
+}
+
+fn serialize_address<S>(value: &Address, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    format!("{value:?}").serialize(serializer)
+}
+
+fn serialize_opt_hex_or_decimal<S>(value: &Option<U256>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    value.map(|v| v.to_string()).serialize(serializer)
+}
+
+fn deserialize_opt_hex_or_decimal<'de, D>(deserializer: D) -> Result<Option<U256>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw: Option<String> = Option::deserialize(deserializer)?;
+    raw.map(|raw| {
+        if let Some(hex) = raw.strip_prefix("0x").or_else(|| raw.strip_prefix("0X")) {
+            U256::from_str_radix(hex, 16).map_err(D::Error::custom)
+        } else {
+            U256::from_dec_str(&raw).map_err(D::Error::custom)
+        }
+    })
+    .transpose()
+}
+
 pub fn get_chain_contracts(chain_id: u64) -> ChainContracts {
     match chain_id {
         42161 => ChainContracts { // Arbitrum
@@ -92,11 +313,13 @@ pub fn get_chain_contracts(chain_id: u64) -> ChainContracts {
                     name: "Aave V3".to_string(),
                     address: "0x794a61358D6845594F94dc1DB02A252b5b4814aD".parse().unwrap(),
                     fee_bps: 9, // 0.09%
+                    max_loan_amount: None,
                 },
                 FlashProvider {
                     name: "Balancer".to_string(),
                     address: "0xBA12222222228d8Ba445958a75a0704d566BF2C8".parse().unwrap(),
                     fee_bps: 5, // 0.05%
+                    max_loan_amount: None,
                 },
             ],
             dex_factories: vec![
@@ -133,6 +356,7 @@ pub fn get_chain_contracts(chain_id: u64) -> ChainContracts {
                     name: "Aave V3".to_string(),
                     address: "0x794a61358D6845594F94dc1DB02A252b5b4814aD".parse().unwrap(),
                     fee_bps: 9,
+                    max_loan_amount: None,
                 },
             ],
             dex_factories: vec![
@@ -157,6 +381,7 @@ pub fn get_chain_contracts(chain_id: u64) -> ChainContracts {
                     name: "Aave V3".to_string(),
                     address: "0xA238Dd80C259a72e81d7e4664a9801593F98d1c5".parse().unwrap(),
                     fee_bps: 9,
+                    max_loan_amount: None,
                 },
             ],
             dex_factories: vec![
@@ -2,18 +2,47 @@
 use ethers::prelude::*;
 use ethers::types::transaction::eip2718::TypedTransaction;
 use ethers::abi::Token;
+use ethers::middleware::gas_oracle::GasOracleMiddleware;
+use ethers::middleware::nonce_manager::NonceManagerMiddleware;
 use std::sync::Arc;
 use std::env;
 use anyhow::{Result, anyhow};
 
 // Import from config module
 use crate::config::ChainConfig;
+use crate::gas_oracle::{FeeHistoryGasOracle, GasBumpPolicy};
 use crate::scanner::Opportunity;
 
+/// `NonceManagerMiddleware` tracks nonces locally across rapid successive submissions
+/// instead of re-reading `eth_getTransactionCount` (which races under concurrent sends);
+/// `GasOracleMiddleware` sources fees from `FeeHistoryGasOracle` instead of the provider's
+/// flat `eth_gasPrice`.
+type Client = NonceManagerMiddleware<
+    GasOracleMiddleware<
+        SignerMiddleware<Arc<Provider<Http>>, LocalWallet>,
+        FeeHistoryGasOracle<Provider<Http>>,
+    >,
+>;
+
 pub struct FlashLoanExecutor {
     provider: Arc<Provider<Http>>,
     config: ChainConfig,
     wallet: LocalWallet,
+    client: Client,
+    gas_oracle: FeeHistoryGasOracle<Provider<Http>>,
+    gas_policy: GasBumpPolicy,
+    /// Gates `execute_opportunity` actually broadcasting a transaction. Defaults to
+    /// `false` (dry-run: preflight-simulate only) unless `EXECUTE_LIVE` is set, so running
+    /// this executor can never submit a real transaction by accident.
+    execute_live: bool,
+}
+
+/// What `execute_opportunity` actually did: either it stopped after the preflight
+/// simulation (the default, safe mode) or it went on to broadcast a signed transaction.
+#[derive(Debug)]
+pub enum ExecutionOutcome {
+    DryRun { net_profit: U256, gas_used: u64 },
+    Submitted { tx_hash: H256 },
 }
 
 impl FlashLoanExecutor {
@@ -21,69 +50,147 @@ impl FlashLoanExecutor {
         // Get private key from environment variable
         let private_key = env::var("PRIVATE_KEY")
             .expect("PRIVATE_KEY must be set in .env file");
-        
+
         // Parse the private key (add 0x if not present)
         let private_key = if private_key.starts_with("0x") {
             private_key
         } else {
             format!("0x{}", private_key)
         };
-        
+
         let wallet = private_key
             .parse::<LocalWallet>()
             .expect("Invalid private key format")
             .with_chain_id(config.chain_id);
-        
+
+        let gas_policy = GasBumpPolicy::from_env();
+        let gas_oracle = FeeHistoryGasOracle::new(provider.clone(), gas_policy);
+
+        let signer = SignerMiddleware::new(provider.clone(), wallet.clone());
+        let gas_oracle_middleware = GasOracleMiddleware::new(
+            signer,
+            FeeHistoryGasOracle::new(provider.clone(), gas_policy),
+        );
+        let client = NonceManagerMiddleware::new(gas_oracle_middleware, wallet.address());
+
+        let execute_live = env::var("EXECUTE_LIVE")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+
         Self {
             provider,
             config,
             wallet,
+            client,
+            gas_oracle,
+            gas_policy,
+            execute_live,
         }
     }
-    
-    pub async fn execute_opportunity(&self, opp: &Opportunity) -> Result<H256> {
+
+    /// Prices `tx` using the shared fee logic and rewrites it as either an EIP-1559 or
+    /// legacy request per `self.gas_policy`, so `max_fee_per_gas`/`max_priority_fee_per_gas`
+    /// (or a plain `gas_price` on chains without 1559 support) are filled in right before
+    /// the nonce-managed client sends it.
+    async fn apply_gas_pricing(&self, tx: TypedTransaction) -> Result<TypedTransaction> {
+        let to = tx.to().cloned();
+        let data = tx.data().cloned().unwrap_or_default();
+        let value = tx.value().copied().unwrap_or_default();
+
+        if self.gas_policy.legacy_fallback {
+            let gas_price = self.gas_oracle.fetch().await?;
+            let mut legacy = TransactionRequest::new()
+                .data(data)
+                .value(value)
+                .gas_price(gas_price)
+                .chain_id(self.config.chain_id);
+            if let Some(to) = to {
+                legacy = legacy.to(to);
+            }
+            return Ok(TypedTransaction::Legacy(legacy));
+        }
+
+        let (max_fee_per_gas, max_priority_fee_per_gas) = self.gas_oracle.estimate_eip1559_fees().await?;
+        let mut eip1559 = Eip1559TransactionRequest::new()
+            .data(data)
+            .value(value)
+            .max_fee_per_gas(max_fee_per_gas)
+            .max_priority_fee_per_gas(max_priority_fee_per_gas)
+            .chain_id(self.config.chain_id);
+        if let Some(to) = to {
+            eip1559 = eip1559.to(to);
+        }
+        Ok(TypedTransaction::Eip1559(eip1559))
+    }
+
+    /// Dry-runs `opp`'s flash-loan bundle against forked chain state via `revm` instead of
+    /// broadcasting blind, so a stale price or a pool that moved since the scan surfaces as
+    /// a local revert/non-positive profit rather than real gas burned on a failed send.
+    pub async fn simulate_opportunity(&self, opp: &Opportunity) -> Result<crate::simulator::SimOutcome> {
+        let tx = self.build_flash_loan_tx(opp).await?;
+        let block = self.provider.get_block_number().await?.as_u64();
+
+        crate::simulator::simulate_flash_loan_tx(
+            self.provider.clone(),
+            block,
+            self.wallet.address(),
+            &tx,
+            opp.token0,
+        )
+        .await
+    }
+
+    async fn build_flash_loan_tx(&self, opp: &Opportunity) -> Result<TypedTransaction> {
+        let flash_provider = self.select_best_provider(opp).await?;
+
+        match flash_provider {
+            FlashLoanProvider::AaveV3(addr) => self.build_aave_flash_loan(addr, opp).await,
+            FlashLoanProvider::Balancer(addr) => self.build_balancer_flash_loan(addr, opp).await,
+            FlashLoanProvider::UniswapV3(addr) => self.build_uniswap_flash_loan(addr, opp).await,
+        }
+    }
+
+    pub async fn execute_opportunity(&self, opp: &Opportunity) -> Result<ExecutionOutcome> {
         // Check if we have enough ETH for gas
         let balance = self.provider.get_balance(self.wallet.address(), None).await?;
         let gas_price = self.provider.get_gas_price().await?;
         let estimated_gas_cost = gas_price * U256::from(750_000u64); // Estimated gas units
-        
+
         if balance < estimated_gas_cost {
-            return Err(anyhow!("Insufficient ETH for gas. Need at least {} ETH", 
+            return Err(anyhow!("Insufficient ETH for gas. Need at least {} ETH",
                 ethers::utils::format_ether(estimated_gas_cost)));
         }
-        
-        let flash_provider = self.select_best_provider(opp).await?;
-        
-        let tx = match flash_provider {
-            FlashLoanProvider::AaveV3(addr) => {
-                self.build_aave_flash_loan(addr, opp).await?
-            },
-            FlashLoanProvider::Balancer(addr) => {
-                self.build_balancer_flash_loan(addr, opp).await?
-            },
-            FlashLoanProvider::UniswapV3(addr) => {
-                self.build_uniswap_flash_loan(addr, opp).await?
-            },
-        };
-        
-        // Apply gas multiplier from environment
-        let gas_multiplier: f64 = env::var("GAS_MULTIPLIER")
-            .unwrap_or_else(|_| "1.0".to_string())
-            .parse()
-            .unwrap_or(1.0);
-        
-        let adjusted_gas_price = U256::from((gas_price.as_u64() as f64 * gas_multiplier) as u64);
-        
-        // Create a mutable transaction to set gas price
-        let mut tx_with_gas = tx;
-        tx_with_gas.set_gas_price(adjusted_gas_price);
-        
-        let client = SignerMiddleware::new(self.provider.clone(), self.wallet.clone());
-        let pending = client
-            .send_transaction(tx_with_gas, None)
-            .await?;
-        
-        Ok(pending.tx_hash())
+
+        // Verify then send: reject anything that would revert or clear less than the
+        // configured minimum profit before it ever reaches the mempool.
+        let min_profit_wei: U256 = env::var("MIN_PROFIT_WEI")
+            .ok()
+            .and_then(|v| v.parse::<u128>().ok())
+            .map(U256::from)
+            .unwrap_or_else(|| U256::from(10u64.pow(16))); // 0.01 ETH default
+
+        let sim = self.simulate_opportunity(opp).await?;
+        if let Some(reason) = &sim.reverted {
+            return Err(anyhow!("preflight simulation reverted: {reason}"));
+        }
+        if !sim.is_profitable(min_profit_wei) {
+            return Err(anyhow!(
+                "preflight simulation profit {} wei below minimum {} wei",
+                sim.net_profit,
+                min_profit_wei
+            ));
+        }
+
+        if !self.execute_live {
+            return Ok(ExecutionOutcome::DryRun { net_profit: sim.net_profit, gas_used: sim.gas_used });
+        }
+
+        let tx = self.build_flash_loan_tx(opp).await?;
+        let tx = self.apply_gas_pricing(tx).await?;
+
+        let pending = self.client.send_transaction(tx, None).await?;
+
+        Ok(ExecutionOutcome::Submitted { tx_hash: pending.tx_hash() })
     }
     
     async fn build_aave_flash_loan(&self, pool: Address, opp: &Opportunity) -> Result<TypedTransaction> {
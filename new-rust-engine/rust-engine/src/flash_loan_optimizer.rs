@@ -0,0 +1,155 @@
+// rust-engine/src/flash_loan_optimizer.rs
+//
+// Links flash-loan funding cost to `AdvancedScanner`'s opportunities so each one carries
+// what it actually costs to borrow the capital needed to capture it
+// (`net_profit_after_loan`), instead of only a spread with no regard for the loan fee
+// or gas to realize it. `spread_pct` itself stays the raw reserve-ratio spread.
+
+use ethers::types::U256;
+
+/// One flash-loan venue this bot is willing to borrow from, with its fee and the
+/// largest single loan it's assumed to support. Fees mirror the real on-chain rates
+/// (Aave V3 charges 9 bps, Balancer is fee-free, Uniswap V3 flash swaps pass through
+/// the pool's own 30 bps swap fee) instead of guessing a flat rate for every provider.
+#[derive(Clone, Debug)]
+pub struct FlashLoanProviderQuote {
+    pub name: String,
+    pub fee_bps: u32,
+    pub max_loan_amount: U256,
+}
+
+/// Result of gating a candidate opportunity against flash-loan funding cost: the
+/// provider(s) it would actually borrow from, how much, and what's left of
+/// `expected_profit` after the loan fee and gas.
+#[derive(Clone, Debug)]
+pub struct FundedOpportunity {
+    pub providers: Vec<(String, U256)>,
+    pub borrowed_amount: U256,
+    pub loan_fee: U256,
+    pub net_profit: f64,
+}
+
+pub struct FlashLoanOptimizer {
+    providers: Vec<FlashLoanProviderQuote>,
+}
+
+impl FlashLoanOptimizer {
+    pub fn new() -> Self {
+        Self {
+            providers: vec![
+                FlashLoanProviderQuote {
+                    name: "Aave V3".to_string(),
+                    fee_bps: 9,
+                    max_loan_amount: U256::from(10_000_000u64) * U256::exp10(18),
+                },
+                FlashLoanProviderQuote {
+                    name: "Balancer".to_string(),
+                    fee_bps: 0,
+                    max_loan_amount: U256::from(5_000_000u64) * U256::exp10(18),
+                },
+                FlashLoanProviderQuote {
+                    name: "dYdX".to_string(),
+                    fee_bps: 2,
+                    max_loan_amount: U256::from(2_000_000u64) * U256::exp10(18),
+                },
+                FlashLoanProviderQuote {
+                    name: "Uniswap V3".to_string(),
+                    fee_bps: 30,
+                    max_loan_amount: U256::from(1_000_000u64) * U256::exp10(18),
+                },
+            ],
+        }
+    }
+
+    /// Cheapest provider (lowest `fee_bps`) that can cover `amount` in a single loan.
+    pub fn get_best_provider(&self, amount: U256) -> Option<&FlashLoanProviderQuote> {
+        self.providers
+            .iter()
+            .filter(|p| p.max_loan_amount >= amount)
+            .min_by_key(|p| p.fee_bps)
+    }
+
+    /// Loan fee plus `gas_cost` for borrowing `amount` from `provider`.
+    pub fn estimate_total_cost(&self, provider: &FlashLoanProviderQuote, amount: U256, gas_cost: U256) -> U256 {
+        let loan_fee = amount * U256::from(provider.fee_bps) / U256::from(10_000u64);
+        loan_fee + gas_cost
+    }
+
+    /// Minimum gross profit needed for `provider` to clear `gas_cost` after its loan fee.
+    pub fn calculate_break_even_profit(&self, provider: &FlashLoanProviderQuote, gas_cost: U256) -> U256 {
+        let fee_bps = U256::from(provider.fee_bps);
+        gas_cost * U256::from(10_000u64) / (U256::from(10_000u64) - fee_bps)
+    }
+
+    /// Splits `total_amount` across providers cheapest-first when no single provider's
+    /// `max_loan_amount` covers it alone.
+    pub fn find_multi_provider_strategy(&self, total_amount: U256) -> Vec<(FlashLoanProviderQuote, U256)> {
+        let mut strategy = Vec::new();
+        let mut remaining = total_amount;
+
+        let mut by_fee: Vec<&FlashLoanProviderQuote> = self.providers.iter().collect();
+        by_fee.sort_by_key(|p| p.fee_bps);
+
+        for provider in by_fee {
+            if remaining.is_zero() {
+                break;
+            }
+            let borrow = remaining.min(provider.max_loan_amount);
+            if !borrow.is_zero() {
+                strategy.push((provider.clone(), borrow));
+                remaining -= borrow;
+            }
+        }
+
+        strategy
+    }
+
+    /// Gates a candidate opportunity (`amount` to borrow, `expected_profit` before
+    /// funding cost, both in the same wei-scale units as `gas_cost`) against flash-loan
+    /// funding cost. Returns `None` when even the cheapest viable funding doesn't clear
+    /// break-even, so callers drop rather than down-rank opportunities that can't
+    /// actually turn a profit once borrowed.
+    pub fn gate_opportunity(&self, amount: U256, expected_profit: f64, gas_cost: U256) -> Option<FundedOpportunity> {
+        if let Some(provider) = self.get_best_provider(amount) {
+            let total_cost = self.estimate_total_cost(provider, amount, gas_cost);
+            let break_even = self.calculate_break_even_profit(provider, gas_cost);
+            let net_profit = expected_profit - total_cost.as_u128() as f64;
+
+            if expected_profit <= break_even.as_u128() as f64 || net_profit <= 0.0 {
+                return None;
+            }
+
+            return Some(FundedOpportunity {
+                providers: vec![(provider.name.clone(), amount)],
+                borrowed_amount: amount,
+                loan_fee: total_cost.saturating_sub(gas_cost),
+                net_profit,
+            });
+        }
+
+        // No single provider can cover the full size — split across the cheapest
+        // providers that can, then gate against their combined cost.
+        let strategy = self.find_multi_provider_strategy(amount);
+        let borrowed_amount: U256 = strategy.iter().fold(U256::zero(), |acc, (_, amt)| acc + amt);
+        if borrowed_amount < amount {
+            return None;
+        }
+
+        let loan_fee: U256 = strategy
+            .iter()
+            .fold(U256::zero(), |acc, (provider, amt)| acc + (*amt * U256::from(provider.fee_bps) / U256::from(10_000u64)));
+        let total_cost = loan_fee + gas_cost;
+        let net_profit = expected_profit - total_cost.as_u128() as f64;
+
+        if net_profit <= 0.0 {
+            return None;
+        }
+
+        Some(FundedOpportunity {
+            providers: strategy.iter().map(|(p, amt)| (p.name.clone(), *amt)).collect(),
+            borrowed_amount,
+            loan_fee,
+            net_profit,
+        })
+    }
+}
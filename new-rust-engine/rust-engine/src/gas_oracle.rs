@@ -0,0 +1,98 @@
+// rust-engine/src/gas_oracle.rs
+//
+// EIP-1559 fee sourcing shared by the live `FlashLoanExecutor` and the mock-mode main
+// loops, so both price gas the same way instead of the executor reading a flat
+// `eth_gasPrice` while mock mode estimates costs some other way.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use ethers::prelude::*;
+use ethers::middleware::gas_oracle::{GasOracle, GasOracleError};
+use ethers::types::U256;
+use std::env;
+use std::sync::Arc;
+
+/// How aggressively to bump the priority fee above what `eth_feeHistory` reports, and
+/// whether to fall back to legacy (type-0) pricing for chains/RPCs without 1559 support.
+#[derive(Debug, Clone, Copy)]
+pub struct GasBumpPolicy {
+    /// Multiplier applied to the percentile-derived priority fee, e.g. `1.2` for a 20% bump.
+    /// This is the typed replacement for the old bare `GAS_MULTIPLIER` float.
+    pub priority_fee_multiplier: f64,
+    /// Percentile of `eth_feeHistory`'s per-block reward distribution used as the base tip.
+    pub tip_percentile: f64,
+    /// Build legacy transactions instead of EIP-1559 ones.
+    pub legacy_fallback: bool,
+}
+
+impl GasBumpPolicy {
+    pub fn from_env() -> Self {
+        let priority_fee_multiplier = env::var("GAS_MULTIPLIER")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1.0);
+        let tip_percentile = env::var("GAS_TIP_PERCENTILE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(50.0);
+        let legacy_fallback = env::var("GAS_LEGACY_FALLBACK")
+            .ok()
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+
+        Self { priority_fee_multiplier, tip_percentile, legacy_fallback }
+    }
+}
+
+/// A `GasOracle` that sources EIP-1559 fees from `eth_feeHistory` instead of a single
+/// `eth_gasPrice` read, applying `policy`'s percentile and bump to the priority fee.
+/// Generic over `M` so the live executor (HTTP) and the mock-mode main loops (WebSocket)
+/// can share this exact fee logic instead of each reading gas their own way.
+#[derive(Debug)]
+pub struct FeeHistoryGasOracle<M> {
+    provider: Arc<M>,
+    policy: GasBumpPolicy,
+}
+
+impl<M> FeeHistoryGasOracle<M> {
+    pub fn new(provider: Arc<M>, policy: GasBumpPolicy) -> Self {
+        Self { provider, policy }
+    }
+}
+
+#[async_trait]
+impl<M: Middleware<Error = ProviderError>> GasOracle for FeeHistoryGasOracle<M> {
+    async fn fetch(&self) -> Result<U256, GasOracleError> {
+        Ok(self.provider.get_gas_price().await?)
+    }
+
+    async fn estimate_eip1559_fees(&self) -> Result<(U256, U256), GasOracleError> {
+        let history = self
+            .provider
+            .fee_history(10u64, BlockNumber::Latest, &[self.policy.tip_percentile])
+            .await?;
+
+        let base_fee = history.base_fee_per_gas.last().copied().unwrap_or_default();
+
+        let mut rewards: Vec<U256> = history
+            .reward
+            .iter()
+            .filter_map(|block_rewards| block_rewards.first().copied())
+            .collect();
+        let median_tip = if rewards.is_empty() {
+            U256::from(1_500_000_000u64) // 1.5 gwei, a reasonable tip floor with no history
+        } else {
+            rewards.sort();
+            rewards[rewards.len() / 2]
+        };
+
+        let bumped_tip = U256::from(
+            (median_tip.as_u128() as f64 * self.policy.priority_fee_multiplier) as u128,
+        );
+        // Headroom over the current base fee so the tx stays valid if it climbs before
+        // inclusion, matching the `max_fee_per_gas >= base_fee + tip` requirement.
+        let max_fee = base_fee.saturating_mul(U256::from(2)) + bumped_tip;
+
+        Ok((max_fee, bumped_tip))
+    }
+}
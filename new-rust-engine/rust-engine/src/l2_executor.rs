@@ -1,15 +1,21 @@
 // File: src/l2_executor.rs
 
 use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
 use ethers::utils::parse_ether;
 use std::sync::Arc;
 use tokio::time::{timeout, Duration};
 
+/// Gas units a flash-loan arbitrage transaction is assumed to burn, used both to cap
+/// the transaction's own `.gas()` and to size the profit-guard's gas cost estimate.
+const ARB_GAS_LIMIT: u64 = 500_000;
+
 pub struct L2ExecutionEngine {
     providers: std::collections::HashMap<String, Arc<SignerMiddleware<Provider<Http>, Wallet<k256::ecdsa::SigningKey>>>>,
     flashloan_contracts: std::collections::HashMap<String, Address>,
     max_slippage_bps: u32,
-    max_gas_price_gwei: u64,
+    /// Flat `max_priority_fee_per_gas` tip offered on top of the current base fee.
+    priority_fee_gwei: u64,
 }
 
 impl L2ExecutionEngine {
@@ -56,10 +62,23 @@ impl L2ExecutionEngine {
             providers,
             flashloan_contracts,
             max_slippage_bps: 100, // 1% max slippage
-            max_gas_price_gwei: 50, // Max 50 gwei gas price
+            priority_fee_gwei: 1, // 1 gwei tip is plenty on low-contention L2s
         })
     }
-    
+
+    /// Current `base_fee_per_gas` for `network`, falling back to zero on pre-London
+    /// chains (the caller then just offers `priority_fee` as the whole fee).
+    async fn base_fee(
+        &self,
+        provider: &Arc<SignerMiddleware<Provider<Http>, Wallet<k256::ecdsa::SigningKey>>>,
+    ) -> Result<U256, Box<dyn std::error::Error>> {
+        let block = provider
+            .get_block(BlockNumber::Latest)
+            .await?
+            .ok_or("Latest block unavailable")?;
+        Ok(block.base_fee_per_gas.unwrap_or_default())
+    }
+
     pub async fn execute_arbitrage(
         &self,
         network: &str,
@@ -72,57 +91,73 @@ impl L2ExecutionEngine {
     ) -> Result<TransactionReceipt, Box<dyn std::error::Error>> {
         let provider = self.providers.get(network)
             .ok_or("Network not supported")?;
-        
-        // Check current gas price
-        let gas_price = provider.get_gas_price().await?;
-        if gas_price > U256::from(self.max_gas_price_gwei) * U256::from(1_000_000_000u64) {
-            return Err("Gas price too high".into());
-        }
-        
-        // Simulate transaction first
+
+        // Price the transaction off the current base fee plus a configurable tip,
+        // instead of rejecting above a flat gwei cap.
+        let base_fee = self.base_fee(provider).await?;
+        let priority_fee = U256::from(self.priority_fee_gwei) * U256::from(1_000_000_000u64);
+        let max_fee_per_gas = base_fee * U256::from(2) + priority_fee;
+
+        // Simulate transaction first, accounting for the base fee that will be burned
+        // (not recoverable) regardless of how the arbitrage itself plays out.
         let simulated_profit = self.simulate_arbitrage(
             network,
             buy_dex,
             sell_dex,
             token_in,
             token_out,
-            amount_in
+            amount_in,
+            base_fee,
+            priority_fee,
+            max_fee_per_gas,
         ).await?;
-        
+
         if simulated_profit < min_profit {
             return Err("Simulated profit too low".into());
         }
-        
+
         // Build arbitrage contract call
         let arb_contract = self.deploy_arbitrage_contract(network).await?;
-        
+
         let arb_abi = ethers::abi::parse_abi(&[
             "function executeArbitrage(address,address,address,address,uint256,uint256)",
         ])?;
-        
+
         let contract = Contract::new(arb_contract, arb_abi, provider.clone());
-        
-        // Execute with flash loan
-        let tx = contract
+
+        // Build the call through the ABI helper to get correctly-encoded calldata, then
+        // rewrite it as an EIP-1559 request so it carries `max_fee_per_gas`/
+        // `max_priority_fee_per_gas` instead of a legacy `gas_price`.
+        let legacy_call = contract
             .method::<_, H256>(
                 "executeArbitrage",
                 (buy_dex, sell_dex, token_in, token_out, amount_in, min_profit)
             )?
-            .gas(500000)
-            .gas_price(gas_price)
-            .send()
+            .gas(ARB_GAS_LIMIT);
+
+        let mut eip1559_tx = Eip1559TransactionRequest::new()
+            .data(legacy_call.tx.data().cloned().unwrap_or_default())
+            .gas(ARB_GAS_LIMIT)
+            .max_fee_per_gas(max_fee_per_gas)
+            .max_priority_fee_per_gas(priority_fee);
+        if let Some(to) = legacy_call.tx.to() {
+            eip1559_tx = eip1559_tx.to(to.clone());
+        }
+
+        let tx = provider
+            .send_transaction(TypedTransaction::Eip1559(eip1559_tx), None)
             .await?;
-        
+
         // Wait for confirmation with timeout
         let receipt = timeout(
             Duration::from_secs(30),
             tx
         ).await??
             .ok_or("Transaction failed")?;
-        
+
         Ok(receipt)
     }
-    
+
     async fn simulate_arbitrage(
         &self,
         network: &str,
@@ -131,10 +166,13 @@ impl L2ExecutionEngine {
         token_in: Address,
         token_out: Address,
         amount_in: U256,
+        base_fee: U256,
+        priority_fee: U256,
+        max_fee_per_gas: U256,
     ) -> Result<U256, Box<dyn std::error::Error>> {
         let provider = self.providers.get(network)
             .ok_or("Network not supported")?;
-        
+
         // Get output amount from buy DEX
         let buy_output = self.get_amount_out(
             provider.clone(),
@@ -143,7 +181,7 @@ impl L2ExecutionEngine {
             token_out,
             amount_in
         ).await?;
-        
+
         // Get output amount from selling on sell DEX
         let sell_output = self.get_amount_out(
             provider.clone(),
@@ -152,13 +190,21 @@ impl L2ExecutionEngine {
             token_in,
             buy_output
         ).await?;
-        
-        // Calculate profit
-        if sell_output > amount_in {
-            Ok(sell_output - amount_in)
+
+        // Calculate gross profit, then subtract the gas actually expected to be
+        // charged (the base fee is burned, not just paid as a tip), matching how the
+        // London fork charges `effective_gas_price = min(max_fee_per_gas, base_fee +
+        // priority_fee)`.
+        let gross_profit = if sell_output > amount_in {
+            sell_output - amount_in
         } else {
-            Ok(U256::zero())
-        }
+            return Ok(U256::zero());
+        };
+
+        let effective_gas_price = max_fee_per_gas.min(base_fee + priority_fee);
+        let gas_cost = U256::from(ARB_GAS_LIMIT) * effective_gas_price;
+
+        Ok(gross_profit.saturating_sub(gas_cost))
     }
     
     async fn get_amount_out(
@@ -7,6 +7,48 @@ use tokio::time::{Duration, interval};
 use dashmap::DashMap;
 use futures::future::join_all;
 
+// Emitted once per pool by a Uniswap V2-style factory (and its Solidly-style stable-pool
+// forks); every fork reuses this exact event signature, so one binding covers all of them.
+abigen!(
+    UniswapV2Factory,
+    r#"[
+        event PairCreated(address indexed token0, address indexed token1, address pair, uint256)
+    ]"#
+);
+
+abigen!(
+    UniswapV3Factory,
+    r#"[
+        event PoolCreated(address indexed token0, address indexed token1, uint24 indexed fee, int24 tickSpacing, address pool)
+    ]"#
+);
+
+// Used to keep cached reserves/sqrt-price current incrementally instead of re-querying
+// `getReserves()`/`slot0()` on every scan tick.
+abigen!(
+    UniswapV2PairEvents,
+    r#"[
+        event Sync(uint112 reserve0, uint112 reserve1)
+    ]"#
+);
+
+abigen!(
+    UniswapV3PoolEvents,
+    r#"[
+        event Swap(address indexed sender, address indexed recipient, int256 amount0, int256 amount1, uint160 sqrtPriceX96, uint128 liquidity, int24 tick)
+    ]"#
+);
+
+/// How many blocks to request per `eth_getLogs` call during pair discovery. Public RPC
+/// endpoints commonly cap a single `eth_getLogs` response (both in block range and result
+/// count), so a full history scan is chunked rather than requested in one shot.
+const LOG_SCAN_CHUNK_BLOCKS: u64 = 2_000;
+
+/// How far back to look for `Sync`/`Swap` events when a pair has never been synced before
+/// (`block_number == 0`). Scanning from genesis on a public RPC is impractical, and a
+/// freshly-discovered pair only needs its *current* reserves, not its whole history.
+const RESERVE_SYNC_LOOKBACK_BLOCKS: u64 = 2_000;
+
 // L2 Network Configuration
 #[derive(Debug, Clone)]
 pub struct L2Network {
@@ -15,6 +57,13 @@ pub struct L2Network {
     rpc_url: String,
     weth_address: Address,
     block_time_ms: u64,
+    /// Chainlink `AggregatorV3Interface` ETH/USD feed for this network; `Address::zero()`
+    /// where no feed is modeled, in which case `eth_price_usd` falls straight to the pool
+    /// fallback.
+    chainlink_eth_usd_feed: Address,
+    /// USDC address on this network, used only to find the deepest WETH/USDC pool as a
+    /// fallback price source when the Chainlink feed is stale or unavailable.
+    usdc_address: Address,
 }
 
 // DEX Configuration for each L2
@@ -25,12 +74,19 @@ pub struct L2Dex {
     router: Address,
     fee_bps: Vec<u32>, // Multiple fee tiers for V3
     version: DexVersion,
+    /// Uniswap QuoterV2-compatible contract for depth-aware sizing; `None` for V2/stable
+    /// pools and for V3 forks whose quoter isn't modeled here, in which case sizing falls
+    /// back to the constant-product spot price from `get_pair_price`.
+    quoter: Option<Address>,
 }
 
 #[derive(Debug, Clone)]
 enum DexVersion {
     V2,
     V3,
+    /// Solidly-style stable pools (Velodrome, Aerodrome, Camelot's stable pairs), priced
+    /// via the `x³y + y³x = k` invariant instead of constant-product.
+    Stable,
 }
 
 pub struct L2ArbitrageScanner {
@@ -39,6 +95,52 @@ pub struct L2ArbitrageScanner {
     dexes: HashMap<String, Vec<L2Dex>>,
     pair_cache: Arc<DashMap<String, PairInfo>>,
     min_profit_usd: f64,
+    /// Per-network execution gas units and priority-fee tip assumptions, now fields
+    /// instead of match-arm constants baked into `calculate_gas_cost`.
+    gas_profiles: HashMap<String, GasProfile>,
+    /// Short-TTL cache for `eth_price_usd`, keyed by network, so the 500ms scan loop
+    /// doesn't hit the Chainlink feed (or the pool fallback) every tick.
+    eth_price_cache: Arc<DashMap<String, (f64, std::time::Instant)>>,
+}
+
+/// How long a cached `eth_price_usd` result is trusted before being refreshed.
+const ETH_PRICE_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How old a Chainlink round is allowed to be before it's treated as stale and the pool
+/// fallback is used instead.
+const CHAINLINK_STALENESS_THRESHOLD_SECS: i64 = 3600;
+
+/// Cost model inputs for one network: how much execution gas the arb transaction is
+/// assumed to burn, and the priority-fee tip on top of the base fee.
+#[derive(Debug, Clone, Copy)]
+struct GasProfile {
+    gas_units: u64,
+    priority_fee_gwei: u64,
+}
+
+/// The OP-stack `GasPriceOracle` predeploy, present on Optimism/Base-style L2s, which
+/// prices the L1 calldata-posting cost of a transaction. Networks without this precompile
+/// (Arbitrum's fee model folds L1 cost into the gas price itself; zkEVMs have their own
+/// mechanism) are simply skipped when pricing the L1 data-fee term.
+const OP_STACK_GAS_PRICE_ORACLE: &str = "0x420000000000000000000000000000000000000F";
+
+/// Breakdown of what an arbitrage transaction on a given network is expected to cost,
+/// so `display_opportunity` can show where the money goes instead of one opaque total.
+#[derive(Debug, Clone, Copy)]
+struct GasCostBreakdown {
+    /// `(base_fee + tip) * gas_units`, converted to USD.
+    l2_execution_fee_usd: f64,
+    /// OP-stack L1 calldata-posting fee, in USD; `0.0` on chains without that precompile.
+    l1_data_fee_usd: f64,
+    /// The priority-fee tip actually assumed, in USD, for display purposes only (it's
+    /// already included in `l2_execution_fee_usd`).
+    tip_usd: f64,
+}
+
+impl GasCostBreakdown {
+    fn total(&self) -> f64 {
+        self.l2_execution_fee_usd + self.l1_data_fee_usd
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -47,6 +149,11 @@ struct PairInfo {
     token1: Address,
     pair_address: Address,
     reserves: (U256, U256),
+    /// Latest `sqrtPriceX96` synced from a V3 pool's `Swap` events; zero until the first
+    /// sync completes, in which case `get_pair_price` falls back to a live `slot0()` call.
+    sqrt_price_x96: U256,
+    /// Block height reserves/sqrt-price were last synced through, so `sync_reserves` only
+    /// re-scans the blocks produced since.
     block_number: u64,
 }
 
@@ -63,6 +170,8 @@ impl L2ArbitrageScanner {
             rpc_url: "https://arb1.arbitrum.io/rpc".to_string(),
             weth_address: "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1".parse()?,
             block_time_ms: 250,
+            chainlink_eth_usd_feed: "0x639Fe6ab55C921f74e7fac1ee960C0B6293ba612".parse()?,
+            usdc_address: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831".parse()?,
         };
         
         let arb_provider = Arc::new(Provider::<Http>::try_from(&arbitrum.rpc_url)?);
@@ -76,6 +185,7 @@ impl L2ArbitrageScanner {
                 router: "0xE592427A0AEce92De3Edee1F18E0157C05861564".parse()?,
                 fee_bps: vec![500, 3000, 10000], // 0.05%, 0.3%, 1%
                 version: DexVersion::V3,
+                quoter: Some("0x61fFE014bA17989E743c5F6cB21bF9697530B21e".parse()?),
             },
             L2Dex {
                 name: "SushiswapV3".to_string(),
@@ -83,6 +193,7 @@ impl L2ArbitrageScanner {
                 router: "0x8A21F6768C1f8075791D08546Dadf6daA0bE820c".parse()?,
                 fee_bps: vec![100, 500, 2500, 10000],
                 version: DexVersion::V3,
+                quoter: None,
             },
             L2Dex {
                 name: "Camelot".to_string(),
@@ -90,6 +201,7 @@ impl L2ArbitrageScanner {
                 router: "0xc873fEcbd354f5A56E00E710B90EF4201db2448d".parse()?,
                 fee_bps: vec![300],
                 version: DexVersion::V2,
+                quoter: None,
             },
             L2Dex {
                 name: "TraderJoe".to_string(),
@@ -97,6 +209,7 @@ impl L2ArbitrageScanner {
                 router: "0xb4315e873dBcf96Ffd0acd8EA43f689D8c20fB30".parse()?,
                 fee_bps: vec![300],
                 version: DexVersion::V2,
+                quoter: None,
             },
             L2Dex {
                 name: "Zyberswap".to_string(),
@@ -104,6 +217,7 @@ impl L2ArbitrageScanner {
                 router: "0x16e71B13fE6079B4312063F7E81F76d165Ad32Ad".parse()?,
                 fee_bps: vec![100, 500, 2500],
                 version: DexVersion::V3,
+                quoter: None,
             },
             L2Dex {
                 name: "RamsesV2".to_string(),
@@ -111,6 +225,7 @@ impl L2ArbitrageScanner {
                 router: "0xAA23611badAFB62D37E7295A682D21960ac85A90".parse()?,
                 fee_bps: vec![100, 500, 3000],
                 version: DexVersion::V3,
+                quoter: None,
             },
         ];
         
@@ -124,6 +239,8 @@ impl L2ArbitrageScanner {
             rpc_url: "https://mainnet.optimism.io".to_string(),
             weth_address: "0x4200000000000000000000000000000000000006".parse()?,
             block_time_ms: 2000,
+            chainlink_eth_usd_feed: "0x13e3Ee699D1909E989722E753853AE30b17e08c5".parse()?,
+            usdc_address: "0x0b2C639c533813f4Aa9D7837CAf62653d097Ff85".parse()?,
         };
         
         let opt_provider = Arc::new(Provider::<Http>::try_from(&optimism.rpc_url)?);
@@ -136,6 +253,7 @@ impl L2ArbitrageScanner {
                 router: "0xE592427A0AEce92De3Edee1F18E0157C05861564".parse()?,
                 fee_bps: vec![500, 3000, 10000],
                 version: DexVersion::V3,
+                quoter: Some("0x61fFE014bA17989E743c5F6cB21bF9697530B21e".parse()?),
             },
             L2Dex {
                 name: "Velodrome".to_string(),
@@ -143,6 +261,7 @@ impl L2ArbitrageScanner {
                 router: "0xa732398118DF09b50c87dE4392b77bd2e80BC862".parse()?,
                 fee_bps: vec![100, 300],
                 version: DexVersion::V2,
+                quoter: None,
             },
         ];
         
@@ -156,6 +275,8 @@ impl L2ArbitrageScanner {
             rpc_url: "https://mainnet.base.org".to_string(),
             weth_address: "0x4200000000000000000000000000000000000006".parse()?,
             block_time_ms: 2000,
+            chainlink_eth_usd_feed: "0x71041dddad3595F9CEd3DcCFBe3D1F4b0a16Bb70".parse()?,
+            usdc_address: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".parse()?,
         };
         
         let base_provider = Arc::new(Provider::<Http>::try_from(&base.rpc_url)?);
@@ -168,6 +289,7 @@ impl L2ArbitrageScanner {
                 router: "0x2626664c2603336E57B271c5C0b26F421741e481".parse()?,
                 fee_bps: vec![500, 3000, 10000],
                 version: DexVersion::V3,
+                quoter: Some("0x61fFE014bA17989E743c5F6cB21bF9697530B21e".parse()?),
             },
             L2Dex {
                 name: "BaseSwap".to_string(),
@@ -175,6 +297,7 @@ impl L2ArbitrageScanner {
                 router: "0x327Df1E6de05895d2ab08513aaDD9313Fe505d86".parse()?,
                 fee_bps: vec![250],
                 version: DexVersion::V2,
+                quoter: None,
             },
             L2Dex {
                 name: "Aerodrome".to_string(),
@@ -182,6 +305,7 @@ impl L2ArbitrageScanner {
                 router: "0xcF77a3Ba9A5CA399B7c97c74d54e5b1Beb874E43".parse()?,
                 fee_bps: vec![100, 300],
                 version: DexVersion::V2,
+                quoter: None,
             },
         ];
         
@@ -195,6 +319,10 @@ impl L2ArbitrageScanner {
             rpc_url: "https://zkevm-rpc.com".to_string(),
             weth_address: "0x4F9A0e7FD2Bf6067db6994CF12E4495Df938E6e9".parse()?,
             block_time_ms: 2000,
+            // No Chainlink deployment on Polygon zkEVM at time of writing; price falls
+            // straight through to the WETH/USDC pool fallback.
+            chainlink_eth_usd_feed: Address::zero(),
+            usdc_address: "0xA8CE8aee21bC2A48a5EF670afCc9274C7bbbC035".parse()?,
         };
         
         let zkevm_provider = Arc::new(Provider::<Http>::try_from(&polygon_zkevm.rpc_url)?);
@@ -207,18 +335,27 @@ impl L2ArbitrageScanner {
                 router: "0xf6Ad3CcF71Abb3E12beCf6b3D2a74C963859ADCd".parse()?,
                 fee_bps: vec![500, 3000],
                 version: DexVersion::V3,
+                quoter: None,
             },
         ];
         
         dexes.insert("polygon_zkevm".to_string(), zkevm_dexes);
         networks.insert("polygon_zkevm".to_string(), polygon_zkevm);
 
+        let mut gas_profiles = HashMap::new();
+        gas_profiles.insert("arbitrum".to_string(), GasProfile { gas_units: 250_000, priority_fee_gwei: 1 });
+        gas_profiles.insert("optimism".to_string(), GasProfile { gas_units: 200_000, priority_fee_gwei: 1 });
+        gas_profiles.insert("base".to_string(), GasProfile { gas_units: 180_000, priority_fee_gwei: 1 });
+        gas_profiles.insert("polygon_zkevm".to_string(), GasProfile { gas_units: 300_000, priority_fee_gwei: 1 });
+
         Ok(Self {
             networks,
             providers,
             dexes,
             pair_cache: Arc::new(DashMap::new()),
             min_profit_usd: 5.0, // Minimum $5 profit after gas
+            gas_profiles,
+            eth_price_cache: Arc::new(DashMap::new()),
         })
     }
 
@@ -232,7 +369,7 @@ impl L2ArbitrageScanner {
                 println!("  Scanning {} on {}", dex.name, network_name);
                 
                 match dex.version {
-                    DexVersion::V2 => {
+                    DexVersion::V2 | DexVersion::Stable => {
                         self.discover_v2_pairs(provider.clone(), &dex).await?;
                     },
                     DexVersion::V3 => {
@@ -246,105 +383,192 @@ impl L2ArbitrageScanner {
         Ok(())
     }
 
+    /// Discovers V2/stable-pool pairs from the factory's `PairCreated` log history instead
+    /// of brute-force iterating `allPairs(0..1000)` — which caps discovery at the first
+    /// 1000 pairs ever created and can't see anything newer, and burns one `eth_call` per
+    /// pair along the way. Scanning genesis-to-tip on a public RPC is impractical, so this
+    /// starts from a recent height and walks forward in `LOG_SCAN_CHUNK_BLOCKS` windows.
     async fn discover_v2_pairs(
         &self,
         provider: Arc<Provider<Http>>,
         dex: &L2Dex
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let factory_abi = ethers::abi::parse_abi(&[
-            "function allPairs(uint256) view returns (address)",
-            "function allPairsLength() view returns (uint256)",
-        ])?;
-        
-        let factory = Contract::new(dex.factory, factory_abi, provider);
-        
-        let length: U256 = factory
-            .method("allPairsLength", ())?
-            .call()
-            .await?;
-        
-        let pairs_to_check = length.as_u64().min(1000); // Check first 1000 pairs
-        
-        for i in 0..pairs_to_check {
-            let pair_address: Address = factory
-                .method("allPairs", U256::from(i))?
-                .call()
-                .await?;
-                
-            // Store pair info
-            let key = format!("{}_{}_pair_{}", dex.name, dex.factory, i);
-            self.pair_cache.insert(key, PairInfo {
-                token0: Address::zero(),
-                token1: Address::zero(),
-                pair_address,
-                reserves: (U256::zero(), U256::zero()),
-                block_number: 0,
-            });
+        let latest_block = provider.get_block_number().await?.as_u64();
+        let mut start = latest_block.saturating_sub(RESERVE_SYNC_LOOKBACK_BLOCKS * 10);
+
+        while start <= latest_block {
+            let end = (start + LOG_SCAN_CHUNK_BLOCKS - 1).min(latest_block);
+
+            let filter = Filter::new()
+                .address(dex.factory)
+                .event(&PairCreatedFilter::abi_signature())
+                .from_block(start)
+                .to_block(end);
+
+            let logs = provider.get_logs(&filter).await?;
+            for log in logs {
+                let raw = RawLog { topics: log.topics.clone(), data: log.data.to_vec() };
+                let Ok(event) = PairCreatedFilter::decode_log(&raw) else {
+                    continue;
+                };
+
+                let key = format!("{}_{}_{}", dex.name, event.token_0, event.token_1);
+                self.pair_cache.insert(key, PairInfo {
+                    token0: event.token_0,
+                    token1: event.token_1,
+                    pair_address: event.pair,
+                    reserves: (U256::zero(), U256::zero()),
+                    sqrt_price_x96: U256::zero(),
+                    block_number: 0,
+                });
+            }
+
+            start = end + 1;
         }
-        
+
         Ok(())
     }
 
+    /// Discovers V3 pools from the factory's `PoolCreated` log history instead of guessing
+    /// at a handful of common token pairs and calling `getPool` for each fee tier — which
+    /// misses every pool between tokens not on that short hardcoded list.
     async fn discover_v3_pairs(
         &self,
         provider: Arc<Provider<Http>>,
         dex: &L2Dex
     ) -> Result<(), Box<dyn std::error::Error>> {
-        // V3 pools are created on demand, so we'll check common pairs
-        let common_tokens = vec![
-            "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1", // WETH
-            "0xaf88d065e77c8cC2239327C5EDb3A432268e5831", // USDC
-            "0xFd086bC7CD5C481DCC9C85ebE478A1C0b69FCbb9", // USDT
-            "0x2f2a2543B76A4166549F7aaB2e75Bef0aefC5B0f", // WBTC
-            "0xDA10009cBd5D07dd0CeCc66161FC93D7c9000da1", // DAI
-        ];
-        
-        for i in 0..common_tokens.len() {
-            for j in i+1..common_tokens.len() {
-                let token0: Address = common_tokens[i].parse()?;
-                let token1: Address = common_tokens[j].parse()?;
-                
-                for fee in &dex.fee_bps {
-                    let pool = self.get_v3_pool_address(provider.clone(), dex.factory, token0, token1, *fee).await;
-                    if let Ok(pool_addr) = pool {
-                        if pool_addr != Address::zero() {
-                            let key = format!("{}_{}_{}_{}_{}", dex.name, token0, token1, fee, dex.factory);
-                            self.pair_cache.insert(key, PairInfo {
-                                token0,
-                                token1,
-                                pair_address: pool_addr,
-                                reserves: (U256::zero(), U256::zero()),
-                                block_number: 0,
-                            });
+        let latest_block = provider.get_block_number().await?.as_u64();
+        let mut start = latest_block.saturating_sub(RESERVE_SYNC_LOOKBACK_BLOCKS * 10);
+
+        while start <= latest_block {
+            let end = (start + LOG_SCAN_CHUNK_BLOCKS - 1).min(latest_block);
+
+            let filter = Filter::new()
+                .address(dex.factory)
+                .event(&PoolCreatedFilter::abi_signature())
+                .from_block(start)
+                .to_block(end);
+
+            let logs = provider.get_logs(&filter).await?;
+            for log in logs {
+                let raw = RawLog { topics: log.topics.clone(), data: log.data.to_vec() };
+                let Ok(event) = PoolCreatedFilter::decode_log(&raw) else {
+                    continue;
+                };
+
+                let key = format!("{}_{}_{}_{}_{}", dex.name, event.token_0, event.token_1, event.fee, dex.factory);
+                self.pair_cache.insert(key, PairInfo {
+                    token0: event.token_0,
+                    token1: event.token_1,
+                    pair_address: event.pool,
+                    reserves: (U256::zero(), U256::zero()),
+                    sqrt_price_x96: U256::zero(),
+                    block_number: 0,
+                });
+            }
+
+            start = end + 1;
+        }
+
+        Ok(())
+    }
+
+    /// Keeps cached reserves (V2/stable pools, via `Sync`) and sqrt-price (V3 pools, via
+    /// `Swap`) current incrementally, instead of `get_pair_price` re-querying
+    /// `getReserves()`/`slot0()` on every 500ms scan tick. Intended to be called
+    /// periodically (e.g. once per new block) from a background task; each call only scans
+    /// the blocks produced since that pair's last sync.
+    pub async fn sync_reserves(&self) -> Result<(), Box<dyn std::error::Error>> {
+        for (network_name, provider) in &self.providers {
+            let latest_block = provider.get_block_number().await?.as_u64();
+            let dexes = match self.dexes.get(network_name) {
+                Some(dexes) => dexes,
+                None => continue,
+            };
+
+            let mut v2_keys = Vec::new();
+            let mut v3_keys = Vec::new();
+            for entry in self.pair_cache.iter() {
+                let dex = match dexes.iter().find(|d| entry.key().starts_with(&d.name)) {
+                    Some(d) => d,
+                    None => continue,
+                };
+                match dex.version {
+                    DexVersion::V2 | DexVersion::Stable => v2_keys.push(entry.key().clone()),
+                    DexVersion::V3 => v3_keys.push(entry.key().clone()),
+                }
+            }
+
+            for key in v2_keys {
+                let Some(pair_address) = self.pair_cache.get(&key).map(|e| e.pair_address) else { continue };
+                let last_synced = self.pair_cache.get(&key).map(|e| e.block_number).unwrap_or(0);
+                let from_block = if last_synced == 0 {
+                    latest_block.saturating_sub(RESERVE_SYNC_LOOKBACK_BLOCKS)
+                } else {
+                    last_synced + 1
+                };
+                if from_block > latest_block {
+                    continue;
+                }
+
+                let filter = Filter::new()
+                    .address(pair_address)
+                    .event(&SyncFilter::abi_signature())
+                    .from_block(from_block)
+                    .to_block(latest_block);
+
+                let logs = provider.get_logs(&filter).await?;
+                if let Some(log) = logs.last() {
+                    let raw = RawLog { topics: log.topics.clone(), data: log.data.to_vec() };
+                    if let Ok(event) = SyncFilter::decode_log(&raw) {
+                        if let Some(mut entry) = self.pair_cache.get_mut(&key) {
+                            entry.reserves = (U256::from(event.reserve_0), U256::from(event.reserve_1));
+                            entry.block_number = latest_block;
                         }
                     }
+                } else if last_synced == 0 {
+                    if let Some(mut entry) = self.pair_cache.get_mut(&key) {
+                        entry.block_number = latest_block;
+                    }
+                }
+            }
+
+            for key in v3_keys {
+                let Some(pair_address) = self.pair_cache.get(&key).map(|e| e.pair_address) else { continue };
+                let last_synced = self.pair_cache.get(&key).map(|e| e.block_number).unwrap_or(0);
+                let from_block = if last_synced == 0 {
+                    latest_block.saturating_sub(RESERVE_SYNC_LOOKBACK_BLOCKS)
+                } else {
+                    last_synced + 1
+                };
+                if from_block > latest_block {
+                    continue;
+                }
+
+                let filter = Filter::new()
+                    .address(pair_address)
+                    .event(&SwapFilter::abi_signature())
+                    .from_block(from_block)
+                    .to_block(latest_block);
+
+                let logs = provider.get_logs(&filter).await?;
+                if let Some(log) = logs.last() {
+                    let raw = RawLog { topics: log.topics.clone(), data: log.data.to_vec() };
+                    if let Ok(event) = SwapFilter::decode_log(&raw) {
+                        if let Some(mut entry) = self.pair_cache.get_mut(&key) {
+                            entry.sqrt_price_x96 = event.sqrt_price_x96;
+                            entry.block_number = latest_block;
+                        }
+                    }
+                } else if last_synced == 0 {
+                    if let Some(mut entry) = self.pair_cache.get_mut(&key) {
+                        entry.block_number = latest_block;
+                    }
                 }
             }
         }
-        
-        Ok(())
-    }
 
-    async fn get_v3_pool_address(
-        &self,
-        provider: Arc<Provider<Http>>,
-        factory: Address,
-        token0: Address,
-        token1: Address,
-        fee: u32
-    ) -> Result<Address, Box<dyn std::error::Error>> {
-        let factory_abi = ethers::abi::parse_abi(&[
-            "function getPool(address,address,uint24) view returns (address)",
-        ])?;
-        
-        let factory_contract = Contract::new(factory, factory_abi, provider);
-        
-        let pool: Address = factory_contract
-            .method("getPool", (token0, token1, fee))?
-            .call()
-            .await?;
-            
-        Ok(pool)
+        Ok(())
     }
 
     pub async fn scan_opportunities(&self) -> Result<(), Box<dyn std::error::Error>> {
@@ -363,17 +587,21 @@ impl L2ArbitrageScanner {
                 let network_dexes = self.dexes[&network_name].clone();
                 let cache = self.pair_cache.clone();
                 let min_profit = self.min_profit_usd;
-                
+                let gas_profile = self.gas_profiles[&network_name];
+                let eth_price = self.eth_price_usd(&network_name).await;
+
                 let handle = tokio::spawn(async move {
                     Self::scan_network(
                         network_name,
                         provider,
                         network_dexes,
                         cache,
-                        min_profit
+                        min_profit,
+                        gas_profile,
+                        eth_price
                     ).await
                 });
-                
+
                 handles.push(handle);
             }
             
@@ -394,14 +622,14 @@ impl L2ArbitrageScanner {
         provider: Arc<Provider<Http>>,
         dexes: Vec<L2Dex>,
         cache: Arc<DashMap<String, PairInfo>>,
-        min_profit_usd: f64
+        min_profit_usd: f64,
+        gas_profile: GasProfile,
+        eth_price: f64
     ) -> Result<Vec<ArbitrageOpportunity>, anyhow::Error> {
         let mut opportunities = Vec::new();
         let block = provider.get_block_number().await?;
-        
-        // Get gas price for this network
-        let gas_price = provider.get_gas_price().await?;
-        let gas_cost = Self::calculate_gas_cost(&network_name, gas_price);
+
+        let gas_cost = Self::calculate_gas_cost(&provider, &network_name, &gas_profile, eth_price).await;
         
         // Check pairs between different DEXes
         for i in 0..dexes.len() {
@@ -416,19 +644,20 @@ impl L2ArbitrageScanner {
                         // Check if same pair exists on dex2
                         let pair_info = entry.value();
                         
-                        // Get prices from both DEXes
+                        // Get prices from both DEXes, preferring already-synced reserves
+                        // over a fresh getReserves()/slot0() call.
                         let price1 = Self::get_pair_price(
                             provider.clone(),
-                            pair_info.pair_address,
+                            pair_info,
                             &dex1.version
                         ).await;
-                        
+
                         // Find corresponding pair on dex2
                         let dex2_key = key.replace(&dex1.name, &dex2.name);
                         if let Some(dex2_pair) = cache.get(&dex2_key) {
                             let price2 = Self::get_pair_price(
                                 provider.clone(),
-                                dex2_pair.pair_address,
+                                &dex2_pair,
                                 &dex2.version
                             ).await;
                             
@@ -450,13 +679,24 @@ impl L2ArbitrageScanner {
                                 let net_spread = spread_pct - total_fees;
                                 
                                 if net_spread > 0.01 {
-                                    // Calculate profit on $10k trade
-                                    let trade_amount_usd = 10000.0;
+                                    // Size against the buy leg's real depth via its quoter when
+                                    // one is configured, instead of assuming a flat $10k notional
+                                    // clears at the undisturbed spot price.
+                                    let flat_trade_amount_usd = 10000.0;
+                                    let buy_dex = if p1 < p2 { dex1 } else { dex2 };
+                                    let trade_amount_usd = Self::size_trade_usd(
+                                        provider.clone(),
+                                        buy_dex,
+                                        pair_info,
+                                        p1.min(p2),
+                                        flat_trade_amount_usd,
+                                    ).await;
+
                                     let gross_profit = trade_amount_usd * (net_spread / 100.0);
-                                    let net_profit = gross_profit - gas_cost;
-                                    
+                                    let net_profit = gross_profit - gas_cost.total();
+
                                     if net_profit > min_profit_usd {
-                                        opportunities.push(ArbitrageOpportunity {
+                                        opportunities.push(ArbitrageOpportunity::TwoLeg {
                                             network: network_name.clone(),
                                             dex_buy: if p1 < p2 { dex1.name.clone() } else { dex2.name.clone() },
                                             dex_sell: if p1 < p2 { dex2.name.clone() } else { dex1.name.clone() },
@@ -464,7 +704,10 @@ impl L2ArbitrageScanner {
                                             token1: pair_info.token1,
                                             spread_pct,
                                             net_spread,
-                                            gas_cost,
+                                            l2_fee: gas_cost.l2_execution_fee_usd,
+                                            l1_data_fee: gas_cost.l1_data_fee_usd,
+                                            tip: gas_cost.tip_usd,
+                                            gas_cost: gas_cost.total(),
                                             net_profit,
                                             block_number: block.as_u64(),
                                         });
@@ -476,81 +719,609 @@ impl L2ArbitrageScanner {
                 }
             }
         }
-        
+
+        opportunities.extend(Self::find_multihop_opportunities(
+            &network_name,
+            &dexes,
+            &cache,
+            block.as_u64(),
+        ));
+
         Ok(opportunities)
     }
 
+    /// Builds a directed token graph from the pair cache — edge weight
+    /// `-ln(effective_rate_after_fees)` — and runs Bellman-Ford from every node to find
+    /// negative-weight cycles: routes where compounding swaps around the cycle return more
+    /// of the starting token than you put in. Single buy→sell pairs only capture two-pool
+    /// spreads; this catches triangular and longer mispricings across the whole pair graph.
+    /// Cycles are capped at `MAX_HOPS` legs to keep routes executable and the search cheap.
+    fn find_multihop_opportunities(
+        network_name: &str,
+        dexes: &[L2Dex],
+        cache: &DashMap<String, PairInfo>,
+        block_number: u64,
+    ) -> Vec<ArbitrageOpportunity> {
+        const MAX_HOPS: usize = 4;
+
+        struct GraphEdge {
+            from: usize,
+            to: usize,
+            weight: f64,
+            dex: String,
+        }
+
+        let mut node_index: HashMap<Address, usize> = HashMap::new();
+        let mut nodes: Vec<Address> = Vec::new();
+        let mut edges: Vec<GraphEdge> = Vec::new();
+
+        for entry in cache.iter() {
+            let pair_info = entry.value();
+            if pair_info.token0.is_zero() || pair_info.token1.is_zero() {
+                continue;
+            }
+            if pair_info.reserves.0.is_zero() || pair_info.reserves.1.is_zero() {
+                continue;
+            }
+
+            let dex = match dexes.iter().find(|d| entry.key().starts_with(&d.name)) {
+                Some(d) => d,
+                None => continue,
+            };
+            let fee = dex.fee_bps.first().copied().unwrap_or(30) as f64 / 10000.0;
+
+            let r0 = pair_info.reserves.0.as_u128() as f64;
+            let r1 = pair_info.reserves.1.as_u128() as f64;
+            if r0 <= 0.0 || r1 <= 0.0 {
+                continue;
+            }
+
+            let rate_0_to_1 = (r1 / r0) * (1.0 - fee);
+            let rate_1_to_0 = (r0 / r1) * (1.0 - fee);
+            if rate_0_to_1 <= 0.0 || rate_1_to_0 <= 0.0 {
+                continue;
+            }
+
+            let idx0 = *node_index.entry(pair_info.token0).or_insert_with(|| {
+                nodes.push(pair_info.token0);
+                nodes.len() - 1
+            });
+            let idx1 = *node_index.entry(pair_info.token1).or_insert_with(|| {
+                nodes.push(pair_info.token1);
+                nodes.len() - 1
+            });
+
+            edges.push(GraphEdge { from: idx0, to: idx1, weight: -rate_0_to_1.ln(), dex: dex.name.clone() });
+            edges.push(GraphEdge { from: idx1, to: idx0, weight: -rate_1_to_0.ln(), dex: dex.name.clone() });
+        }
+
+        let n = nodes.len();
+        if n < 2 {
+            return Vec::new();
+        }
+
+        let mut opportunities = Vec::new();
+        let mut seen_cycles: std::collections::HashSet<Vec<usize>> = std::collections::HashSet::new();
+
+        for source in 0..n {
+            let mut dist = vec![f64::INFINITY; n];
+            let mut pred: Vec<Option<usize>> = vec![None; n];
+            let mut pred_edge: Vec<Option<usize>> = vec![None; n];
+            dist[source] = 0.0;
+
+            let mut relaxed_edge_on_nth_pass = None;
+            for iter in 0..n {
+                let mut relaxed_this_pass = None;
+                for (ei, edge) in edges.iter().enumerate() {
+                    if dist[edge.from].is_finite() && dist[edge.from] + edge.weight < dist[edge.to] - 1e-12 {
+                        dist[edge.to] = dist[edge.from] + edge.weight;
+                        pred[edge.to] = Some(edge.from);
+                        pred_edge[edge.to] = Some(ei);
+                        relaxed_this_pass = Some(ei);
+                    }
+                }
+                if relaxed_this_pass.is_none() {
+                    break;
+                }
+                if iter == n - 1 {
+                    relaxed_edge_on_nth_pass = relaxed_this_pass;
+                }
+            }
+
+            let Some(ei) = relaxed_edge_on_nth_pass else { continue };
+
+            // A relaxation survived the (n-1)th pass: a negative cycle is reachable.
+            // Walk back n steps first to guarantee landing on a node that's actually
+            // inside the cycle rather than just upstream of it.
+            let mut node = edges[ei].to;
+            for _ in 0..n {
+                node = match pred[node] {
+                    Some(p) => p,
+                    None => break,
+                };
+            }
+
+            let mut cycle_edges = Vec::new();
+            let mut cur = node;
+            loop {
+                let Some(e) = pred_edge[cur] else { break };
+                cycle_edges.push(e);
+                cur = edges[e].from;
+                if cur == node || cycle_edges.len() > MAX_HOPS {
+                    break;
+                }
+            }
+
+            if cur != node || cycle_edges.len() < 2 || cycle_edges.len() > MAX_HOPS {
+                continue;
+            }
+
+            let mut node_set: Vec<usize> = cycle_edges.iter().map(|&e| edges[e].from).collect();
+            node_set.sort_unstable();
+            if !seen_cycles.insert(node_set) {
+                continue;
+            }
+
+            let total_weight: f64 = cycle_edges.iter().map(|&e| edges[e].weight).sum();
+            let implied_profit_pct = ((-total_weight).exp() - 1.0) * 100.0;
+            if implied_profit_pct <= 0.0 {
+                continue;
+            }
+
+            cycle_edges.reverse();
+            let route: Vec<Hop> = cycle_edges
+                .iter()
+                .map(|&e| Hop { dex: edges[e].dex.clone(), token: edges[e].to })
+                .collect();
+
+            opportunities.push(ArbitrageOpportunity::MultiHop {
+                network: network_name.to_string(),
+                route,
+                implied_profit_pct,
+                block_number,
+            });
+        }
+
+        opportunities
+    }
+
+    /// Prices a pair from its cached, event-synced reserves/sqrt-price (kept current by
+    /// `sync_reserves`) when available, falling back to a live `getReserves()`/`slot0()`
+    /// call only for a pair that hasn't been synced yet — rather than hitting the chain on
+    /// every 500ms scan tick for every pair regardless of whether anything changed.
     async fn get_pair_price(
         provider: Arc<Provider<Http>>,
-        pair_address: Address,
+        pair_info: &PairInfo,
         version: &DexVersion
     ) -> Result<f64, Box<dyn std::error::Error>> {
         match version {
             DexVersion::V2 => {
-                let pair_abi = ethers::abi::parse_abi(&[
-                    "function getReserves() view returns (uint112,uint112,uint32)",
-                ])?;
-                
-                let pair = Contract::new(pair_address, pair_abi, provider);
-                let reserves: (U256, U256, U256) = pair.method("getReserves", ())?.call().await?;
-                
-                if reserves.0 > U256::zero() && reserves.1 > U256::zero() {
-                    Ok(reserves.0.as_u128() as f64 / reserves.1.as_u128() as f64)
+                let (r0, r1) = if !pair_info.reserves.0.is_zero() && !pair_info.reserves.1.is_zero() {
+                    pair_info.reserves
+                } else {
+                    Self::fetch_v2_reserves(provider, pair_info.pair_address).await?
+                };
+
+                if r0 > U256::zero() && r1 > U256::zero() {
+                    Ok(r0.as_u128() as f64 / r1.as_u128() as f64)
                 } else {
                     Err("Zero liquidity".into())
                 }
             },
             DexVersion::V3 => {
-                let pool_abi = ethers::abi::parse_abi(&[
-                    "function slot0() view returns (uint160,int24,uint16,uint16,uint16,uint8,bool)",
-                ])?;
-                
-                let pool = Contract::new(pair_address, pool_abi, provider);
-                let slot0: (U256, i32, u16, u16, u16, u8, bool) = pool.method("slot0", ())?.call().await?;
-                
-                let sqrt_price = slot0.0;
+                let sqrt_price = if !pair_info.sqrt_price_x96.is_zero() {
+                    pair_info.sqrt_price_x96
+                } else {
+                    Self::fetch_v3_sqrt_price(provider, pair_info.pair_address).await?
+                };
+
                 let price = (sqrt_price.as_u128() as f64 / (1u128 << 96) as f64).powi(2);
                 Ok(price)
+            },
+            DexVersion::Stable => {
+                let (r0, r1) = if !pair_info.reserves.0.is_zero() && !pair_info.reserves.1.is_zero() {
+                    pair_info.reserves
+                } else {
+                    Self::fetch_v2_reserves(provider.clone(), pair_info.pair_address).await?
+                };
+
+                let decimals0 = Self::token_decimals(provider.clone(), pair_info.token0).await.unwrap_or(18);
+                let decimals1 = Self::token_decimals(provider.clone(), pair_info.token1).await.unwrap_or(18);
+
+                let x = Self::normalize_to_18(r0, decimals0);
+                let y = Self::normalize_to_18(r1, decimals1);
+
+                if x > 0.0 && y > 0.0 {
+                    Ok(Self::stable_marginal_price(x, y))
+                } else {
+                    Err("Zero liquidity".into())
+                }
             }
         }
     }
 
-    fn calculate_gas_cost(network: &str, gas_price: U256) -> f64 {
-        let gas_units = match network {
-            "arbitrum" => 250_000,
-            "optimism" => 200_000,
-            "base" => 180_000,
-            "polygon_zkevm" => 300_000,
-            _ => 250_000,
+    async fn fetch_v2_reserves(
+        provider: Arc<Provider<Http>>,
+        pair_address: Address,
+    ) -> Result<(U256, U256), Box<dyn std::error::Error>> {
+        let pair_abi = ethers::abi::parse_abi(&[
+            "function getReserves() view returns (uint112,uint112,uint32)",
+        ])?;
+        let pair = Contract::new(pair_address, pair_abi, provider);
+        let reserves: (U256, U256, U256) = pair.method("getReserves", ())?.call().await?;
+        Ok((reserves.0, reserves.1))
+    }
+
+    async fn fetch_v3_sqrt_price(
+        provider: Arc<Provider<Http>>,
+        pair_address: Address,
+    ) -> Result<U256, Box<dyn std::error::Error>> {
+        let pool_abi = ethers::abi::parse_abi(&[
+            "function slot0() view returns (uint160,int24,uint16,uint16,uint16,uint8,bool)",
+        ])?;
+        let pool = Contract::new(pair_address, pool_abi, provider);
+        let slot0: (U256, i32, u16, u16, u16, u8, bool) = pool.method("slot0", ())?.call().await?;
+        Ok(slot0.0)
+    }
+
+    /// Uses a Uniswap QuoterV2-style contract to get a depth-aware output quote instead of
+    /// assuming the constant-product spot price holds at size — `quoteExactInputSingle`
+    /// runs the real swap math, including within-tick price impact, via a static call.
+    async fn quote_exact_input_single(
+        provider: Arc<Provider<Http>>,
+        quoter: Address,
+        token_in: Address,
+        token_out: Address,
+        fee: u32,
+        amount_in: U256,
+    ) -> Result<U256, Box<dyn std::error::Error>> {
+        let quoter_abi = ethers::abi::parse_abi(&[
+            "function quoteExactInputSingle((address tokenIn,address tokenOut,uint256 amountIn,uint24 fee,uint160 sqrtPriceLimitX96)) returns (uint256 amountOut,uint160 sqrtPriceX96After,uint32 initializedTicksCrossed,uint256 gasEstimate)",
+        ])?;
+        let contract = Contract::new(quoter, quoter_abi, provider);
+        let params = (token_in, token_out, amount_in, fee, U256::zero());
+        let (amount_out, _, _, _): (U256, U256, u32, U256) =
+            contract.method("quoteExactInputSingle", params)?.call().await?;
+        Ok(amount_out)
+    }
+
+    /// Sizes the buy leg of a cross-DEX trade against the buy-side pool's real depth when
+    /// it exposes a quoter, rather than assuming a flat notional clears at the undisturbed
+    /// spot price (which ignores slippage on thinner pools and overstates profit). Falls
+    /// back to the flat notional when no quoter is configured for that DEX.
+    async fn size_trade_usd(
+        provider: Arc<Provider<Http>>,
+        buy_dex: &L2Dex,
+        pair_info: &PairInfo,
+        buy_price: f64,
+        flat_trade_amount_usd: f64,
+    ) -> f64 {
+        let quoter = match buy_dex.quoter {
+            Some(quoter) => quoter,
+            None => return flat_trade_amount_usd,
         };
-        
-        let eth_price = 2000.0; // Hardcoded ETH price, should fetch from oracle
-        (gas_price.as_u128() as f64 * gas_units as f64 * eth_price) / 1e18
+        if buy_price <= 0.0 {
+            return flat_trade_amount_usd;
+        }
+
+        let amount_in = U256::from((flat_trade_amount_usd / buy_price * 1e18) as u128);
+        let fee = buy_dex.fee_bps.first().copied().unwrap_or(3000);
+
+        match Self::quote_exact_input_single(
+            provider,
+            quoter,
+            pair_info.token0,
+            pair_info.token1,
+            fee,
+            amount_in,
+        ).await {
+            Ok(amount_out) if !amount_out.is_zero() => {
+                let realized_price = amount_in.as_u128() as f64 / amount_out.as_u128() as f64;
+                flat_trade_amount_usd * (buy_price / realized_price).min(1.0)
+            }
+            _ => flat_trade_amount_usd,
+        }
+    }
+
+    async fn token_decimals(
+        provider: Arc<Provider<Http>>,
+        token: Address
+    ) -> Result<u8, Box<dyn std::error::Error>> {
+        let abi = ethers::abi::parse_abi(&["function decimals() view returns (uint8)"])?;
+        let contract = Contract::new(token, abi, provider);
+        let decimals: u8 = contract.method("decimals", ())?.call().await?;
+        Ok(decimals)
+    }
+
+    /// Scales a raw reserve to an 18-decimal fixed-point float so pools between tokens of
+    /// different decimals (e.g. 6-decimal USDC vs 18-decimal WETH) compare on equal footing.
+    fn normalize_to_18(reserve: U256, decimals: u8) -> f64 {
+        let raw = reserve.as_u128() as f64;
+        if decimals as i32 >= 18 {
+            raw / 10f64.powi(decimals as i32 - 18)
+        } else {
+            raw * 10f64.powi(18 - decimals as i32)
+        }
+    }
+
+    /// Solidly-style stable-pool invariant: `x³y + y³x = k`. Stays much flatter near the 1:1
+    /// peg than constant-product, which is the whole point for pegged-asset pairs.
+    fn stable_invariant_k(x: f64, y: f64) -> f64 {
+        x.powi(3) * y + y.powi(3) * x
+    }
+
+    /// Marginal price of token0 in terms of token1 at the current reserve point, i.e. the
+    /// instantaneous `dy/dx` derived from `x³y + y³x = k`.
+    fn stable_marginal_price(x: f64, y: f64) -> f64 {
+        let numerator = 3.0 * x.powi(2) * y + y.powi(3);
+        let denominator = x.powi(3) + 3.0 * x * y.powi(2);
+        if denominator <= 0.0 {
+            return 0.0;
+        }
+        numerator / denominator
+    }
+
+    /// Solves for the output amount `dy` that preserves the stable invariant after adding
+    /// `dx` to the `x` side, via Newton's method on
+    /// `f(dy) = (x+dx)³(y-dy) + (y-dy)³(x+dx) - k`.
+    #[allow(dead_code)]
+    fn stable_amount_out(x: f64, y: f64, dx: f64) -> f64 {
+        let k = Self::stable_invariant_k(x, y);
+        let new_x = x + dx;
+        let mut dy = dx * (y / x).min(y);
+
+        for _ in 0..255 {
+            let new_y = y - dy;
+            if new_y <= 0.0 {
+                dy *= 0.5;
+                continue;
+            }
+
+            let f = new_x.powi(3) * new_y + new_y.powi(3) * new_x - k;
+            let f_prime = -new_x.powi(3) - 3.0 * new_y.powi(2) * new_x;
+            if f_prime == 0.0 {
+                break;
+            }
+
+            let next_dy = dy - f / f_prime;
+            if (next_dy - dy).abs() < 1e-12 {
+                dy = next_dy;
+                break;
+            }
+            dy = next_dy;
+        }
+
+        dy.max(0.0)
+    }
+
+    /// Live ETH/USD price for gas-cost and profit math, replacing the flat `$2000`
+    /// assumption the old model hardcoded. Tries Chainlink's `latestRoundData()` first;
+    /// if that feed is missing or its round is stale, falls back to deriving a price from
+    /// the deepest WETH/USDC pool in `pair_cache` with cached reserves (V2/stable pairs;
+    /// V3 entries carry none), and to $2000 if neither source yields a price. Cached for
+    /// `ETH_PRICE_CACHE_TTL` so the 500ms scan loop doesn't hit either source every tick.
+    async fn eth_price_usd(&self, network: &str) -> f64 {
+        if let Some(cached) = self.eth_price_cache.get(network) {
+            if cached.1.elapsed() < ETH_PRICE_CACHE_TTL {
+                return cached.0;
+            }
+        }
+
+        let price = match self.fetch_chainlink_eth_price(network).await {
+            Some(price) => price,
+            None => self.fallback_pool_eth_price(network).await.unwrap_or(2000.0),
+        };
+
+        self.eth_price_cache.insert(network.to_string(), (price, std::time::Instant::now()));
+        price
+    }
+
+    async fn fetch_chainlink_eth_price(&self, network: &str) -> Option<f64> {
+        let net = self.networks.get(network)?;
+        let provider = self.providers.get(network)?;
+        if net.chainlink_eth_usd_feed.is_zero() {
+            return None;
+        }
+
+        let abi = ethers::abi::parse_abi(&[
+            "function latestRoundData() view returns (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound)",
+            "function decimals() view returns (uint8)",
+        ]).ok()?;
+        let feed = Contract::new(net.chainlink_eth_usd_feed, abi, provider.clone());
+
+        let (_, answer, _, updated_at, _): (u128, U256, U256, U256, u128) =
+            feed.method("latestRoundData", ()).ok()?.call().await.ok()?;
+        let decimals: u8 = feed.method("decimals", ()).ok()?.call().await.ok()?;
+
+        // Chainlink answers are always non-negative in practice for a price feed; treat
+        // the high bit being set (negative under two's-complement) as a bad reading.
+        if answer.bit(255) || answer.is_zero() {
+            return None;
+        }
+
+        let now_secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        let age_secs = now_secs - updated_at.as_u64() as i64;
+        if age_secs > CHAINLINK_STALENESS_THRESHOLD_SECS {
+            return None;
+        }
+
+        Some(answer.as_u128() as f64 / 10f64.powi(decimals as i32))
+    }
+
+    /// Derives an ETH/USD price from the deepest WETH/USDC pool already sitting in
+    /// `pair_cache`, used only when the Chainlink feed is unavailable or stale.
+    async fn fallback_pool_eth_price(&self, network: &str) -> Option<f64> {
+        let net = self.networks.get(network)?;
+        if net.usdc_address.is_zero() {
+            return None;
+        }
+
+        let mut best: Option<(f64, U256)> = None;
+        for entry in self.pair_cache.iter() {
+            let info = entry.value();
+            let (weth_reserve, usdc_reserve) = if info.token0 == net.weth_address && info.token1 == net.usdc_address {
+                (info.reserves.0, info.reserves.1)
+            } else if info.token1 == net.weth_address && info.token0 == net.usdc_address {
+                (info.reserves.1, info.reserves.0)
+            } else {
+                continue;
+            };
+
+            if weth_reserve.is_zero() || usdc_reserve.is_zero() {
+                continue;
+            }
+
+            let weth_norm = Self::normalize_to_18(weth_reserve, 18);
+            let usdc_norm = Self::normalize_to_18(usdc_reserve, 6);
+            if weth_norm <= 0.0 {
+                continue;
+            }
+
+            let price = usdc_norm / weth_norm;
+            let is_deeper = best.map(|(_, depth)| weth_reserve > depth).unwrap_or(true);
+            if is_deeper {
+                best = Some((price, weth_reserve));
+            }
+        }
+
+        best.map(|(price, _)| price)
+    }
+
+    /// Prices the arb transaction under a real EIP-1559 model instead of multiplying a
+    /// legacy `get_gas_price()` by a flat gas-unit guess: the L2 execution leg is
+    /// `(base_fee + tip) * gas_units`, read from the latest block header rather than
+    /// assumed, and OP-stack networks additionally pay an L1 calldata-posting fee priced
+    /// via the `GasPriceOracle` precompile — which otherwise dominates the true cost on
+    /// those chains and was invisible to the old model entirely.
+    async fn calculate_gas_cost(
+        provider: &Arc<Provider<Http>>,
+        network: &str,
+        profile: &GasProfile,
+        eth_price: f64,
+    ) -> GasCostBreakdown {
+        let base_fee_wei = match provider.get_block(BlockNumber::Latest).await {
+            Ok(Some(block)) => block.base_fee_per_gas.unwrap_or_default(),
+            _ => U256::zero(),
+        };
+
+        let tip_wei = U256::from(profile.priority_fee_gwei) * U256::exp10(9);
+        let effective_gas_price = base_fee_wei + tip_wei;
+
+        let l2_execution_fee_usd =
+            (effective_gas_price.as_u128() as f64 * profile.gas_units as f64 * eth_price) / 1e18;
+        let tip_usd = (tip_wei.as_u128() as f64 * profile.gas_units as f64 * eth_price) / 1e18;
+
+        let l1_data_fee_usd = Self::op_stack_l1_data_fee(provider, network, eth_price)
+            .await
+            .unwrap_or(0.0);
+
+        GasCostBreakdown {
+            l2_execution_fee_usd,
+            l1_data_fee_usd,
+            tip_usd,
+        }
+    }
+
+    /// OP-stack chains (Optimism, Base) bill the cost of posting a transaction's calldata
+    /// to L1 separately from L2 execution gas, via the `GasPriceOracle` predeploy's
+    /// `getL1Fee(bytes)`. We don't have the final calldata at quoting time, so a
+    /// representative ~200-byte blob — roughly the size of an `executeArbitrage` call —
+    /// stands in. Networks without this precompile (Arbitrum folds L1 cost into its gas
+    /// price; zkEVMs have their own fee mechanism) just contribute zero here.
+    async fn op_stack_l1_data_fee(
+        provider: &Arc<Provider<Http>>,
+        network: &str,
+        eth_price: f64,
+    ) -> Result<f64, Box<dyn std::error::Error>> {
+        if !matches!(network, "optimism" | "base") {
+            return Ok(0.0);
+        }
+
+        let oracle_abi = ethers::abi::parse_abi(&[
+            "function getL1Fee(bytes memory data) view returns (uint256)",
+        ])?;
+        let oracle_address: Address = OP_STACK_GAS_PRICE_ORACLE.parse()?;
+        let oracle = Contract::new(oracle_address, oracle_abi, provider.clone());
+
+        let representative_calldata = Bytes::from(vec![0u8; 200]);
+        let l1_fee_wei: U256 = oracle
+            .method("getL1Fee", representative_calldata)?
+            .call()
+            .await?;
+
+        Ok((l1_fee_wei.as_u128() as f64 * eth_price) / 1e18)
     }
 
     fn display_opportunity(&self, opp: ArbitrageOpportunity) {
-        println!("\n💰 ARBITRAGE OPPORTUNITY DETECTED!");
-        println!("  Network: {}", opp.network);
-        println!("  Route: {} → {}", opp.dex_buy, opp.dex_sell);
-        println!("  Tokens: {:?} ↔ {:?}", opp.token0, opp.token1);
-        println!("  Spread: {:.4}%", opp.spread_pct);
-        println!("  Net Spread (after fees): {:.4}%", opp.net_spread);
-        println!("  Gas Cost: ${:.2}", opp.gas_cost);
-        println!("  Net Profit (on $10k): ${:.2}", opp.net_profit);
-        println!("  Block: {}", opp.block_number);
+        match opp {
+            ArbitrageOpportunity::TwoLeg {
+                network, dex_buy, dex_sell, token0, token1, spread_pct, net_spread,
+                l2_fee, l1_data_fee, tip, gas_cost, net_profit, block_number,
+            } => {
+                println!("\n💰 ARBITRAGE OPPORTUNITY DETECTED!");
+                println!("  Network: {}", network);
+                println!("  Route: {} → {}", dex_buy, dex_sell);
+                println!("  Tokens: {:?} ↔ {:?}", token0, token1);
+                println!("  Spread: {:.4}%", spread_pct);
+                println!("  Net Spread (after fees): {:.4}%", net_spread);
+                println!("  Gas Cost: ${:.2} (L2 execution ${:.2} incl. tip ${:.2}, L1 data ${:.2})",
+                    gas_cost, l2_fee, tip, l1_data_fee);
+                println!("  Net Profit (on up to $10k, depth-sized): ${:.2}", net_profit);
+                println!("  Block: {}", block_number);
+            }
+            ArbitrageOpportunity::MultiHop { network, route, implied_profit_pct, block_number } => {
+                println!("\n💰 MULTI-HOP ARBITRAGE CYCLE DETECTED!");
+                println!("  Network: {}", network);
+                print!("  Route:");
+                for hop in &route {
+                    print!(" --[{}]--> {:?}", hop.dex, hop.token);
+                }
+                println!();
+                println!("  Implied Profit (pre-gas, pre-slippage): {:.4}%", implied_profit_pct);
+                println!("  Block: {}", block_number);
+            }
+        }
     }
 }
 
+/// One leg of a multi-hop cycle: the DEX traded on for that leg and the token arrived at.
+#[derive(Debug, Clone)]
+struct Hop {
+    dex: String,
+    token: Address,
+}
+
 #[derive(Debug)]
-struct ArbitrageOpportunity {
-    network: String,
-    dex_buy: String,
-    dex_sell: String,
-    token0: Address,
-    token1: Address,
-    spread_pct: f64,
-    net_spread: f64,
-    gas_cost: f64,
-    net_profit: f64,
-    block_number: u64,
+enum ArbitrageOpportunity {
+    /// A two-pool cross-DEX spread, the original opportunity shape.
+    TwoLeg {
+        network: String,
+        dex_buy: String,
+        dex_sell: String,
+        token0: Address,
+        token1: Address,
+        spread_pct: f64,
+        net_spread: f64,
+        /// L2 execution fee: `(base_fee + tip) * gas_units`, in USD.
+        l2_fee: f64,
+        /// OP-stack L1 calldata-posting fee, in USD; `0.0` on chains without that precompile.
+        l1_data_fee: f64,
+        /// Priority-fee tip portion of `l2_fee`, broken out for display.
+        tip: f64,
+        gas_cost: f64,
+        net_profit: f64,
+        block_number: u64,
+    },
+    /// A negative-weight cycle across the token graph: compounding the effective rate
+    /// (after fees) around `route` returns more of the starting token than you put in,
+    /// ignoring gas and price impact.
+    MultiHop {
+        network: String,
+        route: Vec<Hop>,
+        implied_profit_pct: f64,
+        block_number: u64,
+    },
 }
\ No newline at end of file
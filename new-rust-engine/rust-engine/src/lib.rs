@@ -5,4 +5,7 @@ pub mod config;
 pub mod scanner;
 pub mod flash_loan;
 pub mod mempool;
-pub mod contracts;
\ No newline at end of file
+pub mod contracts;
+pub mod simulator;
+pub mod sizing;
+pub mod gas_oracle;
\ No newline at end of file
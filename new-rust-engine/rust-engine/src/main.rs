@@ -16,11 +16,27 @@ pub mod mempool;
 pub mod contracts;
 pub mod advanced_scanner;
 pub mod multi_rpc;
+pub mod simulator;
+pub mod sizing;
+pub mod gas_oracle;
+pub mod base_fee_oracle;
+pub mod flash_loan_optimizer;
+pub mod token_pricing;
+pub mod bench_harness;
 
 use config::{ChainConfig, DexConfig};
 use advanced_scanner::AdvancedScanner;
 use multi_rpc::MultiRpcProvider;
 use flash_loan::FlashLoanExecutor;
+use base_fee_oracle::BaseFeeOracle;
+
+/// Gas units a flash-loan arbitrage transaction is assumed to burn, used to turn the
+/// forecasted base fee into a USD gas cost.
+const ARB_TX_GAS_UNITS: u128 = 400_000;
+
+/// How many blocks ahead to forecast the base fee by — roughly the time between
+/// spotting an opportunity and the transaction landing.
+const FORECAST_BLOCKS_AHEAD: u64 = 2;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -189,38 +205,57 @@ async fn run_advanced_scanner(chain_info: ChainInfo) -> Result<()> {
     
     // Create flash loan executor
     let executor = FlashLoanExecutor::new(primary_provider.clone(), chain_info.config.clone());
-    
+
+    // Base fee forecaster: lets the opportunity loop price gas at what it's expected
+    // to cost when the tx actually lands instead of a hardcoded flat estimate.
+    let base_fee_oracle = Arc::new(BaseFeeOracle::new(primary_provider.clone()));
+    let eth_price_usd: f64 = env::var("ETH_PRICE_USD")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(2500.0);
+
     // Phase 1: Discover all pairs (do this once at startup)
     scanner.discover_all_pairs().await?;
-    
+
     // Phase 2: Subscribe to WebSocket updates if available
-    scanner.subscribe_to_updates().await.ok();
-    
+    scanner.clone().subscribe_to_updates().await.ok();
+
     // Phase 3: Main scanning loop with intelligent updates
     let scanner_clone = scanner.clone();
+    let base_fee_oracle_clone = base_fee_oracle.clone();
     tokio::spawn(async move {
         let mut update_interval = interval(Duration::from_millis(200)); // Fast updates
         loop {
             update_interval.tick().await;
             scanner_clone.smart_update_reserves().await.ok();
+            base_fee_oracle_clone.sample_latest().await.ok();
         }
     });
-    
+
     // Phase 4: Opportunity detection loop
     let mut opportunity_interval = interval(Duration::from_millis(100)); // Ultra-fast opportunity detection
     let min_profit_usd: f64 = env::var("MIN_PROFIT_USD")
         .unwrap_or_else(|_| "10".to_string()) // Lower threshold for L2s
         .parse()
         .unwrap_or(10.0);
-    
+
     loop {
         opportunity_interval.tick().await;
-        
+
         let opportunities = scanner.find_all_opportunities().await;
-        
+
+        let forecast_base_fee = base_fee_oracle.forecast_base_fee(FORECAST_BLOCKS_AHEAD).await.ok();
+        let gas_cost_usd = forecast_base_fee
+            .map(|base_fee| {
+                let cost_wei = base_fee.as_u128() as f64 * ARB_TX_GAS_UNITS as f64;
+                (cost_wei / 1e18) * eth_price_usd
+            })
+            .unwrap_or(0.5); // Fall back to the old flat L2 estimate if the forecast fails.
+
         for opp in opportunities.iter().take(10) { // Process top 10 opportunities
-            // Calculate estimated profit
-            let estimated_profit = calculate_profit(&opp, 0.001); // 0.1% slippage
+            // Calculate estimated profit, pricing gas at the forecasted base fee rather
+            // than a hardcoded $0.5.
+            let estimated_profit = calculate_profit(&opp, 0.001, gas_cost_usd); // 0.1% slippage
             
             if estimated_profit > min_profit_usd {
                 info!(
@@ -239,16 +274,19 @@ async fn run_advanced_scanner(chain_info: ChainInfo) -> Result<()> {
                     spread_pct: opp.spread_pct,
                     optimal_amount: calculate_optimal_trade_size(&opp),
                     profit_usd: estimated_profit,
-                    gas_cost_usd: 0.5, // Approximate for L2
+                    gas_cost_usd,
                     flash_loan_provider: chain_info.config.flash_loan_providers[0],
                 };
                 
                 // Execute if profitable enough
                 if estimated_profit > min_profit_usd * 2.0 { // Only execute if profit is 2x threshold
                     match executor.execute_opportunity(&exec_opp).await {
-                        Ok(tx_hash) => {
+                        Ok(crate::flash_loan::ExecutionOutcome::Submitted { tx_hash }) => {
                             info!("✅ Executed: {} | Profit: ${:.2}", tx_hash, estimated_profit);
                         },
+                        Ok(crate::flash_loan::ExecutionOutcome::DryRun { net_profit, .. }) => {
+                            info!("🧪 Dry run only (set EXECUTE_LIVE=true to submit) — preflight profit: {} wei", net_profit);
+                        },
                         Err(e) => {
                             warn!("❌ Execution failed: {}", e);
                         }
@@ -285,15 +323,14 @@ fn get_rpc_list(chain_prefix: &str) -> Vec<String> {
     rpcs
 }
 
-fn calculate_profit(opp: &advanced_scanner::ArbitrageOpportunity, slippage: f64) -> f64 {
+fn calculate_profit(opp: &advanced_scanner::ArbitrageOpportunity, slippage: f64, gas_cost_usd: f64) -> f64 {
     // Simplified profit calculation
     let trade_size = 1000.0; // $1000 trade
     let profit_pct = opp.spread_pct * (1.0 - slippage);
     let gross_profit = trade_size * (profit_pct / 100.0);
     let flash_fee = trade_size * 0.0009; // 0.09% flash loan fee
-    let gas_cost = 0.5; // L2 gas cost estimate
-    
-    gross_profit - flash_fee - gas_cost
+
+    gross_profit - flash_fee - gas_cost_usd
 }
 
 fn calculate_optimal_trade_size(opp: &advanced_scanner::ArbitrageOpportunity) -> U256 {
@@ -1,67 +1,86 @@
 use ethers::prelude::*;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use log::info;
+use rust_engine::config::ChainConfig;
+use rust_engine::scanner::{OpportunityScanner, ScanTrigger};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::init();
-    
+
     info!("⚡ HYPERSPEED ARBITRAGE BOT");
-    info!("Running at maximum speed with zero delays");
-    
+    info!("Block- and mempool-driven scanning (no busy-loop)");
+
     let mut handles = vec![];
     handles.push(tokio::spawn(scan_polygon()));
     handles.push(tokio::spawn(scan_bsc()));
-    
+
     for handle in handles {
         let _ = handle.await;
     }
-    
+
     Ok(())
 }
 
 async fn scan_polygon() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    info!("[POLYGON] Starting ultra-fast scanner");
-    
-    let provider = Arc::new(
-        Provider::<Http>::try_from("https://polygon-rpc.com")?
-    );
-    
-    run_scanner(provider, "Polygon", 
-        "0x5757371414417b8C6CAad45bAeF941aBc7d3Ab32",
-        "0xc35DADB65012eC5796536bD9864eD8773aBc74C4",
-    ).await
+    info!("[POLYGON] Starting event-driven scanner");
+
+    let provider = Arc::new(Provider::<Http>::try_from("https://polygon-rpc.com")?);
+    run_scanner(provider, "wss://polygon-bor-rpc.publicnode.com", "Polygon", 137).await
 }
 
 async fn scan_bsc() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    info!("[BSC] Starting ultra-fast scanner");
-    
-    let provider = Arc::new(
-        Provider::<Http>::try_from("https://bsc-dataseed.binance.org")?
-    );
-    
-    run_scanner(provider, "BSC",
-        "0xcA143Ce32Fe78f1f7019d7d551a6402fC5350c73",
-        "0xc35DADB65012eC5796536bD9864eD8773aBc74C4",
-    ).await
+    info!("[BSC] Starting event-driven scanner");
+
+    let provider = Arc::new(Provider::<Http>::try_from("https://bsc-dataseed.binance.org")?);
+    run_scanner(provider, "wss://bsc-rpc.publicnode.com", "BSC", 56).await
 }
 
+/// Builds an event-driven `OpportunityScanner` for `chain` and runs it until the
+/// WebSocket connection drops (or forever, under the HTTP-only polling fallback).
 async fn run_scanner(
     provider: Arc<Provider<Http>>,
+    ws_url: &str,
     chain: &str,
-    _dex1: &str,
-    _dex2: &str,
+    chain_id: u64,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    
-    let mut scans = 0u64;
-    
-    loop {
-        scans += 1;
-        
-        if scans % 100 == 0 {
-            info!("[{}] Scans: {} | Speed: ~{}/sec", chain, scans, scans/5);
+    let config = ChainConfig { enabled: true, rpc_url: provider.url().to_string(), chain_id };
+
+    let scanner = match Ws::connect(ws_url).await {
+        Ok(ws) => OpportunityScanner::new_with_ws(provider, Arc::new(Provider::new(ws)), config),
+        Err(e) => {
+            log::warn!("[{}] WebSocket unavailable ({}), falling back to polling", chain, e);
+            OpportunityScanner::new(provider, config)
         }
-        
-        tokio::task::yield_now().await;
-    }
+    };
+
+    let triggers = AtomicU64::new(0);
+    scanner
+        .run_event_driven(|trigger| {
+            let chain = chain.to_string();
+            let triggers = &triggers;
+            async move {
+                let count = triggers.fetch_add(1, Ordering::Relaxed) + 1;
+                match trigger {
+                    ScanTrigger::NewBlock(number) => {
+                        info!("[{}] Rescanning on block {} (trigger #{})", chain, number, count);
+                    }
+                    ScanTrigger::PendingSwap { tx_hash, amount_in, .. } => {
+                        info!(
+                            "[{}] Pre-empting pending swap {:?} (amount_in {}) (trigger #{})",
+                            chain, tx_hash, amount_in, count
+                        );
+                    }
+                    ScanTrigger::Poll => {
+                        if count % 100 == 0 {
+                            info!("[{}] Polling rescan #{}", chain, count);
+                        }
+                    }
+                }
+            }
+        })
+        .await?;
+
+    Ok(())
 }
\ No newline at end of file
@@ -1,54 +1,222 @@
 use ethers::prelude::*;
+use ethers::abi::{decode, ParamType, Token};
 use std::sync::Arc;
 use tokio_stream::StreamExt;
 use log::{info, warn};
 
+/// Chain this binary watches. `known_routers` is keyed by chain id so the registry can
+/// grow to cover other deployments without touching the decode logic.
+const CHAIN_ID: u64 = 42161;
+
+/// Nominal pool depth (in the input token's own units) used to size
+/// `estimate_price_impact_pct` when we have no live reserve data to check against —
+/// this binary doesn't maintain a pool cache the way `scanner.rs` does.
+const ASSUMED_POOL_DEPTH_TOKENS: f64 = 10_000.0;
+
+/// `swapExactTokensForTokens(uint256,uint256,address[],address,uint256)`
+const SELECTOR_SWAP_EXACT_TOKENS_FOR_TOKENS: [u8; 4] = [0x38, 0xed, 0x17, 0x39];
+/// `swapExactETHForTokens(uint256,address[],address,uint256)`
+const SELECTOR_SWAP_EXACT_ETH_FOR_TOKENS: [u8; 4] = [0x7f, 0xf3, 0x6a, 0xb5];
+/// `exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))`
+const SELECTOR_EXACT_INPUT_SINGLE: [u8; 4] = [0x41, 0x4b, 0xf3, 0x89];
+/// `exactInput((bytes,address,uint256,uint256,uint256))`
+const SELECTOR_EXACT_INPUT: [u8; 4] = [0xc0, 0x4b, 0x8d, 0x59];
+
+/// A decoded DEX swap sitting in the mempool, with enough detail for the arbitrage
+/// engine to decide whether a back-run clears its gas/flash-loan thresholds.
+#[derive(Debug, Clone)]
+struct PendingSwap {
+    tx_hash: H256,
+    router: Address,
+    dex: &'static str,
+    token_in: Address,
+    token_out: Address,
+    amount_in: U256,
+    amount_out_min: U256,
+    /// Rough constant-product price impact of `amount_in` against
+    /// `ASSUMED_POOL_DEPTH_TOKENS`, as a percentage. Not a substitute for pricing
+    /// against the pool's real reserves, just enough signal to rank targets.
+    estimated_price_impact_pct: f64,
+}
+
+/// Router addresses this binary treats as DEX entry points, keyed by chain id. Replaces
+/// the old `is_dex_trade` check, whose `sushiswap_router` constant was a copy-paste of
+/// the Uniswap V3 router address and so never actually matched SushiSwap traffic.
+fn known_routers(chain_id: u64) -> Vec<(Address, &'static str)> {
+    match chain_id {
+        42161 => vec![
+            ("0xE592427A0AEce92De3Edee1F18E0157C05861564".parse().unwrap(), "Uniswap V3"),
+            ("0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D".parse().unwrap(), "Uniswap V2"),
+            ("0x1b02dA8Cb0d097eB8D57A175b88c7D8b47997506".parse().unwrap(), "SushiSwap"),
+        ],
+        _ => vec![],
+    }
+}
+
+fn estimate_price_impact_pct(amount_in: U256) -> f64 {
+    let amount_in = amount_in.as_u128() as f64 / 1e18;
+    (amount_in / (amount_in + ASSUMED_POOL_DEPTH_TOKENS)) * 100.0
+}
+
+/// Decodes the swap calldata of a transaction already known to target a recognized
+/// router, returning `None` for selectors we don't recognize (limit orders, LP
+/// management calls, etc. routed through the same contract).
+fn decode_pending_swap(tx: &Transaction, router: Address, dex: &'static str) -> Option<PendingSwap> {
+    if tx.input.len() < 4 {
+        return None;
+    }
+    let selector: [u8; 4] = tx.input[0..4].try_into().ok()?;
+    let params = &tx.input[4..];
+
+    let (token_in, token_out, amount_in, amount_out_min) = match selector {
+        SELECTOR_SWAP_EXACT_TOKENS_FOR_TOKENS => {
+            let tokens = decode(
+                &[
+                    ParamType::Uint(256),
+                    ParamType::Uint(256),
+                    ParamType::Array(Box::new(ParamType::Address)),
+                    ParamType::Address,
+                    ParamType::Uint(256),
+                ],
+                params,
+            )
+            .ok()?;
+            let amount_in = tokens[0].clone().into_uint()?;
+            let amount_out_min = tokens[1].clone().into_uint()?;
+            let path = tokens[2].clone().into_array()?;
+            let token_in = path.first()?.clone().into_address()?;
+            let token_out = path.last()?.clone().into_address()?;
+            (token_in, token_out, amount_in, amount_out_min)
+        }
+        SELECTOR_SWAP_EXACT_ETH_FOR_TOKENS => {
+            let tokens = decode(
+                &[
+                    ParamType::Uint(256),
+                    ParamType::Array(Box::new(ParamType::Address)),
+                    ParamType::Address,
+                    ParamType::Uint(256),
+                ],
+                params,
+            )
+            .ok()?;
+            let amount_out_min = tokens[0].clone().into_uint()?;
+            let path = tokens[1].clone().into_array()?;
+            let token_in = path.first()?.clone().into_address()?;
+            let token_out = path.last()?.clone().into_address()?;
+            (token_in, token_out, tx.value, amount_out_min)
+        }
+        SELECTOR_EXACT_INPUT_SINGLE => {
+            let tokens = decode(
+                &[ParamType::Tuple(vec![
+                    ParamType::Address,
+                    ParamType::Address,
+                    ParamType::Uint(24),
+                    ParamType::Address,
+                    ParamType::Uint(256),
+                    ParamType::Uint(256),
+                    ParamType::Uint(256),
+                    ParamType::Uint(160),
+                ])],
+                params,
+            )
+            .ok()?;
+            let Token::Tuple(fields) = &tokens[0] else { return None };
+            let token_in = fields[0].clone().into_address()?;
+            let token_out = fields[1].clone().into_address()?;
+            // fields[4] is the deadline.
+            let amount_in = fields[5].clone().into_uint()?;
+            let amount_out_min = fields[6].clone().into_uint()?;
+            (token_in, token_out, amount_in, amount_out_min)
+        }
+        SELECTOR_EXACT_INPUT => {
+            let tokens = decode(
+                &[ParamType::Tuple(vec![
+                    ParamType::Bytes,
+                    ParamType::Address,
+                    ParamType::Uint(256),
+                    ParamType::Uint(256),
+                    ParamType::Uint(256),
+                ])],
+                params,
+            )
+            .ok()?;
+            let Token::Tuple(fields) = &tokens[0] else { return None };
+            // The V3 path is packed `token(20) | fee(3) | token(20) | fee(3) | ...`;
+            // we only care about the first and last hop's tokens.
+            let path = fields[0].clone().into_bytes()?;
+            if path.len() < 43 {
+                return None;
+            }
+            let token_in = Address::from_slice(&path[0..20]);
+            let token_out = Address::from_slice(&path[path.len() - 20..]);
+            let amount_in = fields[3].clone().into_uint()?;
+            let amount_out_min = fields[4].clone().into_uint()?;
+            (token_in, token_out, amount_in, amount_out_min)
+        }
+        _ => return None,
+    };
+
+    Some(PendingSwap {
+        tx_hash: tx.hash,
+        router,
+        dex,
+        token_in,
+        token_out,
+        amount_in,
+        amount_out_min,
+        estimated_price_impact_pct: estimate_price_impact_pct(amount_in),
+    })
+}
+
+fn decode_dex_trade(tx: &Transaction) -> Option<PendingSwap> {
+    let to = tx.to?;
+    let (router, dex) = known_routers(CHAIN_ID).into_iter().find(|(addr, _)| *addr == to)?;
+    decode_pending_swap(tx, router, dex)
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::init();
-    
+
     info!("🔥 MEMPOOL MONITORING MODE");
     info!("Watching for transactions in real-time...");
-    
+
     // Connect via WebSocket for real-time updates
     let ws = Ws::connect("wss://arbitrum-one.publicnode.com").await?;
     let provider = Arc::new(Provider::new(ws));
-    
+
     info!("✅ WebSocket connected");
     info!("👀 Monitoring pending transactions...");
-    
+
     // Subscribe to pending transactions
     let mut stream = provider.subscribe_pending_txs().await?;
-    
+
     let mut tx_count = 0;
-    
+
     while let Some(tx_hash) = stream.next().await {
         tx_count += 1;
-        
+
         // Get transaction details fast
         if let Ok(Some(tx)) = provider.get_transaction(tx_hash).await {
-            // Check if it's a DEX trade
-            if is_dex_trade(&tx) {
-                info!("🎯 DEX Trade detected!");
-                info!("   Hash: {:?}", tx_hash);
+            if let Some(swap) = decode_dex_trade(&tx) {
+                info!("🎯 DEX Trade detected! ({})", swap.dex);
+                info!("   Hash: {:?}", swap.tx_hash);
+                info!("   Router: {:?}", swap.router);
+                info!("   {:?} -> {:?}", swap.token_in, swap.token_out);
+                info!("   Amount in: {}", swap.amount_in);
+                info!("   Min amount out: {}", swap.amount_out_min);
+                info!("   Estimated price impact: {:.3}%", swap.estimated_price_impact_pct);
                 info!("   Gas: {} Gwei", tx.gas_price.unwrap_or_default() / 1_000_000_000);
-                info!("   Value: {} ETH", ethers::utils::format_ether(tx.value));
-                
-                // Here you would analyze and potentially frontrun/backrun
+
+                // Here the arbitrage engine would check `estimated_price_impact_pct`
+                // against its gas/flash-loan thresholds before sizing a back-run.
             }
         }
-        
+
         if tx_count % 100 == 0 {
             info!("📊 Processed {} transactions", tx_count);
         }
     }
-    
+
     Ok(())
 }
-
-fn is_dex_trade(tx: &Transaction) -> bool {
-    let uniswap_router: Address = "0x1b02dA8Cb0d097eB8D57A175b88c7D8b47997506".parse().unwrap();
-    let sushiswap_router: Address = "0x1b02dA8Cb0d097eB8D57A175b88c7D8b47997506".parse().unwrap();
-    
-    tx.to == Some(uniswap_router) || tx.to == Some(sushiswap_router)
-}
\ No newline at end of file
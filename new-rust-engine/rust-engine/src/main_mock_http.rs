@@ -8,8 +8,11 @@ mod monitor;
 mod config;
 mod arbitrage;
 mod wallet;
+mod gas_oracle;
 
 use monitor::PriceMonitor;
+use ethers::middleware::gas_oracle::GasOracle as _;
+use gas_oracle::{FeeHistoryGasOracle, GasBumpPolicy};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -52,14 +55,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     let mut interval = interval(Duration::from_secs(2)); // Check every 2 seconds
     let mut scan_count = 0;
-    
+
+    // Same EIP-1559 fee-history oracle the live `FlashLoanExecutor` prices against, so the
+    // simulated gas cost here tracks reality instead of a flat `eth_gasPrice` read.
+    let gas_oracle = FeeHistoryGasOracle::new(provider.clone(), GasBumpPolicy::from_env());
+
     loop {
         interval.tick().await;
         scan_count += 1;
-        
-        // Get real gas price
-        let gas_price = provider.get_gas_price().await?;
-        let gas_cost = gas_price * U256::from(400000);
+
+        let (max_fee_per_gas, _) = gas_oracle.estimate_eip1559_fees().await?;
+        let gas_cost = max_fee_per_gas * U256::from(400000);
         
         // Check each pair
         for (token_a, token_b) in &pairs {
@@ -181,8 +187,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         
         // Show we're still scanning every 20 iterations
         if scan_count % 20 == 0 {
-            info!("👀 Scan #{}: Still monitoring... (Gas: {} Gwei)", 
-                scan_count, gas_price / 1_000_000_000);
+            info!("👀 Scan #{}: Still monitoring... (Gas: {} Gwei)",
+                scan_count, max_fee_per_gas / 1_000_000_000);
         }
     }
 }
\ No newline at end of file
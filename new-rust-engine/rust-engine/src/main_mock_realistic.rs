@@ -8,9 +8,12 @@ mod wallet;
 mod arbitrage;
 mod monitor;
 mod config;
+mod gas_oracle;
 
 use monitor::PriceMonitor;
 use config::Config;
+use ethers::middleware::gas_oracle::GasOracle as _;
+use gas_oracle::{FeeHistoryGasOracle, GasBumpPolicy};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -34,17 +37,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
     
     let monitor = PriceMonitor::new(provider.clone(), config.clone());
-    
+
+    // Same EIP-1559 fee-history oracle the live `FlashLoanExecutor` prices against, so the
+    // simulated gas cost here tracks reality instead of a flat `eth_gasPrice` read.
+    let gas_oracle = FeeHistoryGasOracle::new(provider.clone(), GasBumpPolicy::from_env());
+
     info!("👀 Monitoring real Arbitrum mainnet prices...\n");
-    
+
     let mut interval = interval(Duration::from_millis(500));
-    
+
     loop {
         interval.tick().await;
-        
+
         // Get real gas price
-        let gas_price = provider.get_gas_price().await?;
-        let gas_cost = gas_price * U256::from(400000); // ~400k gas for arb
+        let (max_fee_per_gas, _) = gas_oracle.estimate_eip1559_fees().await?;
+        let gas_cost = max_fee_per_gas * U256::from(400000); // ~400k gas for arb
         
         // Find real arbitrage opportunities
         match monitor.find_arbitrage_opportunity().await {
@@ -59,7 +66,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 info!("   Pair: {:?} <-> {:?}", opp.token_a, opp.token_b);
                 info!("   Route: {} → {}", opp.buy_from_dex, opp.sell_to_dex);
                 info!("   Amount: {} ETH", ethers::utils::format_ether(opp.optimal_amount));
-                info!("   Gas price: {} Gwei", gas_price / 1_000_000_000);
+                info!("   Gas price: {} Gwei", max_fee_per_gas / 1_000_000_000);
                 info!("   Gas cost: {} ETH (${})", gas_cost_eth, 
                     gas_cost_eth.parse::<f64>().unwrap_or(0.0) * 2000.0);
                 
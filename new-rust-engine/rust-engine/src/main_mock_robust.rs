@@ -68,6 +68,63 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Uniswap-v2 swap output for a pool charging `fee_bps` (e.g. 30 for the standard 0.3%):
+/// `out = (in*(10000-fee_bps)*R_out) / (R_in*10000 + in*(10000-fee_bps))`, the fee-bps
+/// generalization of the canonical γ=997 constant-product formula.
+fn v2_swap_out(amount_in: U256, reserve_in: U256, reserve_out: U256, fee_bps: u32) -> U256 {
+    if amount_in.is_zero() || reserve_in.is_zero() || reserve_out.is_zero() {
+        return U256::zero();
+    }
+    let gamma = U256::from(10_000u64.saturating_sub(fee_bps as u64));
+    let amount_in_with_fee = amount_in * gamma;
+    let numerator = amount_in_with_fee * reserve_out;
+    let denominator = reserve_in * U256::from(10_000u64) + amount_in_with_fee;
+    numerator / denominator
+}
+
+/// Sizes a two-hop arbitrage (buy on `pool_buy`, sell on `pool_sell`, both `(reserve_in,
+/// reserve_out)` for that leg's direction) by ternary search over
+/// `profit(a) = v2_swap_out(v2_swap_out(a, pool_buy), pool_sell) - a`. Profit is strictly
+/// concave in `a` (single peak: zero at `a=0`, negative once the trade is big enough to
+/// blow through both pools' depth), so at each step we can discard whichever third of the
+/// search interval is worse and keep narrowing. Stops once the interval is within 1 wei or
+/// after 60 iterations (each iteration keeps 2/3 of the interval, so 60 iterations shrink
+/// it by a factor of about 4e10; for a starting reserve above that many wei the search
+/// stops before the interval reaches 1 wei). Returns the sizing
+/// amount and its net profit in the output token; the caller still has to subtract gas and
+/// flash-loan fees to get the true bottom line.
+fn optimal_arb_size(pool_buy: (U256, U256), pool_sell: (U256, U256), fee_bps: u32) -> (U256, U256) {
+    let (reserve_in_buy, reserve_out_buy) = pool_buy;
+    let (reserve_in_sell, reserve_out_sell) = pool_sell;
+
+    let profit = |amount_in: U256| -> U256 {
+        let bought = v2_swap_out(amount_in, reserve_in_buy, reserve_out_buy, fee_bps);
+        let sold = v2_swap_out(bought, reserve_in_sell, reserve_out_sell, fee_bps);
+        sold.saturating_sub(amount_in)
+    };
+
+    let mut lo = U256::zero();
+    let mut hi = reserve_in_buy;
+
+    for _ in 0..60 {
+        if hi <= lo + U256::one() {
+            break;
+        }
+        let third = (hi - lo) / U256::from(3u64);
+        let m1 = lo + third;
+        let m2 = hi - third;
+
+        if profit(m1) < profit(m2) {
+            lo = m1;
+        } else {
+            hi = m2;
+        }
+    }
+
+    let best_amount = lo + (hi - lo) / U256::from(2u64);
+    (best_amount, profit(best_amount))
+}
+
 async fn run_scanner(
     pairs: &[(& str, &str, &str, &str)],
     total_scans: &mut u32,
@@ -158,33 +215,42 @@ async fn run_scanner(
                     price_diff.as_u128() as f64 / 100.0);
             }
             
-            // If spread > 0.3% (30 basis points)
+            // Spread > 0.3% (30 basis points) is just a cheap pre-filter before running
+            // the ternary search below — it's computed off the spot reserve ratio, which
+            // ignores slippage entirely and isn't what decides profitability anymore.
             if price_diff > U256::from(30) {
                 *opportunities_found += 1;
-                
-                let amount = U256::from(10u64.pow(17)); // 0.1 ETH
-                let fees = amount * U256::from(60) / U256::from(10000); // 0.6%
-                let potential = (amount * price_diff) / U256::from(10000);
-                
-                if potential > fees + gas_cost {
+
+                let (pool_buy, pool_sell) = if uni_price < sushi_price {
+                    ((uni_reserves.0, uni_reserves.1), (sushi_reserves.1, sushi_reserves.0))
+                } else {
+                    ((sushi_reserves.0, sushi_reserves.1), (uni_reserves.1, uni_reserves.0))
+                };
+
+                // Size the trade against the real v2 swap curve instead of assuming a
+                // flat 0.1 ETH fills at the quoted spot price with no slippage.
+                let (amount, net_out) = optimal_arb_size(pool_buy, pool_sell, 30);
+
+                if amount > U256::zero() && net_out > gas_cost {
                     *profitable_trades += 1;
-                    let profit = potential - fees - gas_cost;
+                    let profit = net_out - gas_cost;
                     *total_profit += profit;
                     *mock_balance += profit;
-                    
+
                     info!("\n🎯 PROFITABLE OPPORTUNITY!");
                     info!("   Pair: {}/{}", name_a, name_b);
                     info!("   Spread: {:.2}%", price_diff.as_u128() as f64 / 100.0);
-                    info!("   Route: {} → {}", 
+                    info!("   Route: {} → {}",
                         if uni_price < sushi_price { "Uniswap" } else { "Sushiswap" },
                         if uni_price < sushi_price { "Sushiswap" } else { "Uniswap" });
+                    info!("   Optimal size: {} WETH", ethers::utils::format_ether(amount));
                     info!("   Profit: {} ETH (${:.2})",
                         ethers::utils::format_ether(profit),
                         ethers::utils::format_ether(profit).parse::<f64>()? * 2000.0);
                     info!("   Status: ✅ MOCK EXECUTED\n");
                 } else {
                     warn!("❌ Opportunity found but not profitable after gas!");
-                    warn!("   Spread: {:.2}% | Gas cost too high", 
+                    warn!("   Spread: {:.2}% | Pool can't absorb enough size, or gas cost too high",
                         price_diff.as_u128() as f64 / 100.0);
                 }
             }
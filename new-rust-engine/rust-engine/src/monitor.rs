@@ -3,12 +3,160 @@ use std::sync::Arc;
 use anyhow::Result;
 use crate::arbitrage::ArbitrageOpportunity;
 use crate::config::Config;
+use crate::contracts::{UniswapV2Factory, UniswapV2Pair, UniswapV3Factory, UniswapV3Pool};
 
 pub struct PriceMonitor {
     provider: Arc<Provider<Ws>>,
     config: Config,
     uniswap_factory: Address,
     sushiswap_factory: Address,
+    uniswap_v3_factory: Address,
+}
+
+/// A price quoted from a pool, tagged with which curve produced it so the caller can
+/// compare a V2 pool on one venue against a V3 pool on another for the same pair.
+#[derive(Debug, Clone, Copy)]
+pub enum PoolPrice {
+    V2 { reserve_in: U256, reserve_out: U256 },
+    V3 { sqrt_price_x96: U256, liquidity: u128, fee_bps: u32 },
+}
+
+impl PoolPrice {
+    /// Price of token1 in terms of token0 (token0 per token1, i.e. `reserve0 / reserve1`),
+    /// scaled by 1e18, in the same orientation for V2 and V3.
+    pub fn price_1e18(&self) -> U256 {
+        match *self {
+            PoolPrice::V2 { reserve_in, reserve_out } => {
+                if reserve_out.is_zero() {
+                    return U256::zero();
+                }
+                (reserve_in * U256::from(10u64.pow(18))) / reserve_out
+            }
+            PoolPrice::V3 { sqrt_price_x96, .. } => {
+                // (sqrtPriceX96 / 2^96)^2 is token1 per token0, so invert it to
+                // (2^96 / sqrtPriceX96)^2 to match the V2 branch, scaled by 1e18 to stay
+                // in integer space.
+                let q96 = U256::from(2u8).pow(U256::from(96u8));
+                let numerator = q96 * q96 * U256::from(10u64.pow(18));
+                let denominator = match sqrt_price_x96.checked_mul(sqrt_price_x96) {
+                    Some(d) if !d.is_zero() => d,
+                    _ => return U256::zero(),
+                };
+                numerator / denominator
+            }
+        }
+    }
+
+    /// Constant-product `(reserve0, reserve1)` this pool would need to produce its
+    /// current price, so the existing two-pool optimal-size formula can treat a V3 pool
+    /// the same as a V2 one. For V2 these are the pool's real reserves; for V3 they're
+    /// the standard "virtual reserves" derived from liquidity and `sqrtPriceX96`
+    /// (`reserve0 = L * Q96 / sqrtP`, `reserve1 = L * sqrtP / Q96`).
+    pub fn virtual_reserves(&self) -> (U256, U256) {
+        match *self {
+            PoolPrice::V2 { reserve_in, reserve_out } => (reserve_in, reserve_out),
+            PoolPrice::V3 { sqrt_price_x96, liquidity, .. } => {
+                if sqrt_price_x96.is_zero() {
+                    return (U256::zero(), U256::zero());
+                }
+                let l = U256::from(liquidity);
+                let q96 = U256::from(2u8).pow(U256::from(96u8));
+                (l * q96 / sqrt_price_x96, l * sqrt_price_x96 / q96)
+            }
+        }
+    }
+}
+
+/// Both Uniswap V2 and Sushiswap charge a 0.3% swap fee on Arbitrum.
+const POOL_FEE_BPS: u64 = 30;
+
+/// EIP-1559 allows the base fee to move by at most 1/8th per block.
+const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
+/// `max_fee_per_gas` headroom over the projected next base fee, so a transaction still
+/// lands even if two or three consecutive blocks push the base fee up the full 12.5%.
+const BASE_FEE_BUFFER_MULTIPLIER: u64 = 2;
+
+/// Fallback tip (1.5 Gwei) used when `eth_feeHistory` returns no data and `Config`
+/// doesn't pin a flat `priority_fee_wei`.
+const DEFAULT_PRIORITY_FEE_WEI: u64 = 1_500_000_000;
+
+/// Projects next block's `base_fee_per_gas` from the latest block's base fee and gas
+/// usage: `base_fee + base_fee * (gas_used - gas_target) / gas_target / 8`, clamped to
+/// at most a ±12.5% move per block per EIP-1559.
+async fn project_next_base_fee(provider: &Provider<Ws>) -> Result<U256> {
+    let block = provider
+        .get_block(BlockNumber::Latest)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("latest block unavailable"))?;
+    let base_fee = block.base_fee_per_gas.unwrap_or_default();
+    let gas_target = block.gas_limit / 2;
+
+    if base_fee.is_zero() || gas_target.is_zero() {
+        return Ok(base_fee);
+    }
+
+    let max_change = base_fee / U256::from(BASE_FEE_MAX_CHANGE_DENOMINATOR);
+    let gas_used = block.gas_used;
+
+    if gas_used > gas_target {
+        let delta = base_fee * (gas_used - gas_target) / gas_target / U256::from(BASE_FEE_MAX_CHANGE_DENOMINATOR);
+        Ok(base_fee + delta.min(max_change))
+    } else if gas_used < gas_target {
+        let delta = base_fee * (gas_target - gas_used) / gas_target / U256::from(BASE_FEE_MAX_CHANGE_DENOMINATOR);
+        Ok(base_fee.saturating_sub(delta.min(max_change)))
+    } else {
+        Ok(base_fee)
+    }
+}
+
+/// `Config.priority_fee_wei` if set, otherwise the median 50th-percentile reward over
+/// the last 10 blocks from `eth_feeHistory`.
+async fn suggest_priority_fee(provider: &Provider<Ws>, config: &Config) -> Result<U256> {
+    if !config.priority_fee_wei.is_zero() {
+        return Ok(config.priority_fee_wei);
+    }
+
+    let history = provider
+        .fee_history(10u64, BlockNumber::Latest, &[50.0])
+        .await?;
+    let mut tips: Vec<U256> = history
+        .reward
+        .into_iter()
+        .filter_map(|percentiles| percentiles.first().copied())
+        .collect();
+
+    if tips.is_empty() {
+        return Ok(U256::from(DEFAULT_PRIORITY_FEE_WEI));
+    }
+
+    tips.sort();
+    Ok(tips[tips.len() / 2])
+}
+
+/// Computes `(max_fee_per_gas, max_priority_fee_per_gas)` for an EIP-1559 transaction,
+/// capped by `Config.max_base_fee`.
+pub async fn get_eip1559_fees(provider: &Provider<Ws>, config: &Config) -> Result<(U256, U256)> {
+    let base_fee_next = project_next_base_fee(provider).await?;
+    let priority_fee = suggest_priority_fee(provider, config).await?;
+    let max_fee = (base_fee_next * U256::from(BASE_FEE_BUFFER_MULTIPLIER) + priority_fee)
+        .min(config.max_base_fee);
+    Ok((max_fee, priority_fee))
+}
+
+/// Integer square root via Newton's method, used to evaluate the closed-form optimal
+/// trade size without losing precision to a `f64` round-trip.
+fn isqrt(value: U256) -> U256 {
+    if value.is_zero() {
+        return U256::zero();
+    }
+    let mut x = value;
+    let mut y = (x + U256::one()) / U256::from(2u8);
+    while y < x {
+        x = y;
+        y = (x + value / x) / U256::from(2u8);
+    }
+    x
 }
 
 impl PriceMonitor {
@@ -20,8 +168,36 @@ impl PriceMonitor {
                 .parse::<Address>().unwrap(),
             sushiswap_factory: "0xc35DADB65012eC5796536bD9864eD8773aBc74C4"
                 .parse::<Address>().unwrap(),
+            uniswap_v3_factory: "0x1F98431c8aD98523631AE4a59f267346ea31F984"
+                .parse::<Address>().unwrap(),
         }
     }
+
+    /// Try every common V3 fee tier for `token_a`/`token_b` and return the first pool
+    /// found, priced from `slot0`. Falls back to `None` so the caller can compare against
+    /// the V2 quote instead.
+    async fn get_v3_price(&self, token_a: Address, token_b: Address) -> Result<Option<PoolPrice>> {
+        let factory = UniswapV3Factory::new(self.uniswap_v3_factory, self.provider.clone());
+
+        for fee in [500u32, 3000, 10000] {
+            let pool_address = factory.get_pool(token_a, token_b, fee).call().await?;
+            if pool_address == Address::zero() {
+                continue;
+            }
+
+            let pool = UniswapV3Pool::new(pool_address, self.provider.clone());
+            let slot0 = pool.slot_0().call().await?;
+            let liquidity = pool.liquidity().call().await?;
+
+            return Ok(Some(PoolPrice::V3 {
+                sqrt_price_x96: U256::from(slot0.0),
+                liquidity,
+                fee_bps: fee / 100,
+            }));
+        }
+
+        Ok(None)
+    }
     
     pub async fn find_arbitrage_opportunity(&self) -> Result<Option<ArbitrageOpportunity>> {
         let pairs = vec![
@@ -38,119 +214,181 @@ impl PriceMonitor {
         for (token_a, token_b) in pairs {
             let token_a = token_a.parse::<Address>()?;
             let token_b = token_b.parse::<Address>()?;
-            
-            // Get prices from both DEXs
-            let (uni_price, sushi_price) = self.get_prices(token_a, token_b).await?;
-            
-            // Calculate price difference
-            let price_diff = if uni_price > sushi_price {
-                ((uni_price - sushi_price) * U256::from(10000)) / sushi_price
-            } else {
-                ((sushi_price - uni_price) * U256::from(10000)) / uni_price
+
+            // Get prices from every venue we know how to quote: V2 Uniswap, V2 Sushiswap,
+            // and (if a pool exists for this pair) V3 Uniswap.
+            let venues = self.get_prices(token_a, token_b).await?;
+
+            // Find the pair of venues with the widest spread, buying on the cheaper one
+            // and selling on the more expensive one.
+            let mut best: Option<(&(&'static str, PoolPrice), &(&'static str, PoolPrice))> = None;
+            let mut best_diff = U256::zero();
+            for buy in &venues {
+                for sell in &venues {
+                    if std::ptr::eq(buy, sell) {
+                        continue;
+                    }
+                    let buy_price = buy.1.price_1e18();
+                    let sell_price = sell.1.price_1e18();
+                    if buy_price.is_zero() || sell_price <= buy_price {
+                        continue;
+                    }
+                    let diff = ((sell_price - buy_price) * U256::from(10000)) / buy_price;
+                    if diff > best_diff {
+                        best_diff = diff;
+                        best = Some((buy, sell));
+                    }
+                }
+            }
+
+            let Some((buy, sell)) = best else {
+                continue;
             };
-            
+
             // If price difference > 0.5% (50 basis points)
-            if price_diff > U256::from(50) {
+            if best_diff > U256::from(50) {
                 let optimal_amount = self.calculate_optimal_amount(
-                    token_a, 
-                    token_b, 
-                    uni_price, 
-                    sushi_price
+                    buy.1.virtual_reserves(),
+                    sell.1.virtual_reserves(),
+                    buy.0 == "uniswap",
                 ).await?;
-                
+
                 let estimated_profit = self.estimate_profit(
                     optimal_amount,
-                    uni_price,
-                    sushi_price
+                    buy.1.price_1e18(),
+                    sell.1.price_1e18(),
                 ).await?;
-                
-                let gas_estimate = U256::from(400000) * self.provider.get_gas_price().await?;
-                
+
+                let max_fee_per_gas = if self.config.legacy_fallback {
+                    self.provider.get_gas_price().await?
+                } else {
+                    let (max_fee_per_gas, _priority_fee) =
+                        get_eip1559_fees(&self.provider, &self.config).await?;
+                    max_fee_per_gas
+                };
+                let gas_estimate = U256::from(400000) * max_fee_per_gas;
+
                 if estimated_profit > gas_estimate {
                     return Ok(Some(ArbitrageOpportunity {
                         token_a,
                         token_b,
-                        buy_from_dex: if uni_price < sushi_price { 
-                            "uniswap".to_string() 
-                        } else { 
-                            "sushiswap".to_string() 
-                        },
-                        sell_to_dex: if uni_price < sushi_price { 
-                            "sushiswap".to_string() 
-                        } else { 
-                            "uniswap".to_string() 
-                        },
+                        buy_from_dex: buy.0.to_string(),
+                        sell_to_dex: sell.0.to_string(),
                         optimal_amount,
                         estimated_profit,
                         profit_after_gas: estimated_profit - gas_estimate,
                         gas_estimate,
+                        path: Vec::new(),
                     }));
                 }
             }
         }
-        
+
         Ok(None)
     }
-    
-    async fn get_prices(&self, token_a: Address, token_b: Address) -> Result<(U256, U256)> {
-        // Get pair addresses
-        let factory_abi = ethers::abi::parse_abi(&[
-            "function getPair(address,address) view returns (address)"
-        ])?;
-        
-        let pair_abi = ethers::abi::parse_abi(&[
-            "function getReserves() view returns (uint112,uint112,uint32)"
-        ])?;
-        
-        let uni_factory = Contract::new(
-            self.uniswap_factory,
-            factory_abi.clone(),
-            self.provider.clone()
-        );
-        
-        let sushi_factory = Contract::new(
-            self.sushiswap_factory,
-            factory_abi,
-            self.provider.clone()
-        );
-        
-        let uni_pair: Address = uni_factory
-            .method("getPair", (token_a, token_b))?
-            .call().await?;
-            
-        let sushi_pair: Address = sushi_factory
-            .method("getPair", (token_a, token_b))?
-            .call().await?;
-        
-        // Get reserves
-        let uni_contract = Contract::new(uni_pair, pair_abi.clone(), self.provider.clone());
-        let sushi_contract = Contract::new(sushi_pair, pair_abi, self.provider.clone());
-        
-        let uni_reserves: (U256, U256, U256) = uni_contract
-            .method("getReserves", ())?
-            .call().await?;
-            
-        let sushi_reserves: (U256, U256, U256) = sushi_contract
-            .method("getReserves", ())?
-            .call().await?;
-        
-        // Calculate prices (reserve0/reserve1)
-        let uni_price = (uni_reserves.0 * U256::from(10u64.pow(18))) / uni_reserves.1;
-        let sushi_price = (sushi_reserves.0 * U256::from(10u64.pow(18))) / sushi_reserves.1;
-        
-        Ok((uni_price, sushi_price))
+
+    /// Quotes `token_a`/`token_b` on every venue with a pool for the pair: V2 Uniswap,
+    /// V2 Sushiswap, and (if found) V3 Uniswap across the common fee tiers.
+    async fn get_prices(
+        &self,
+        token_a: Address,
+        token_b: Address,
+    ) -> Result<Vec<(&'static str, PoolPrice)>> {
+        let uni_factory = UniswapV2Factory::new(self.uniswap_factory, self.provider.clone());
+        let sushi_factory = UniswapV2Factory::new(self.sushiswap_factory, self.provider.clone());
+
+        let uni_pair_address = uni_factory.get_pair(token_a, token_b).call().await?;
+        let sushi_pair_address = sushi_factory.get_pair(token_a, token_b).call().await?;
+
+        let uni_pair = UniswapV2Pair::new(uni_pair_address, self.provider.clone());
+        let sushi_pair = UniswapV2Pair::new(sushi_pair_address, self.provider.clone());
+
+        let uni_reserves = uni_pair.get_reserves().call().await?;
+        let sushi_reserves = sushi_pair.get_reserves().call().await?;
+
+        let mut venues = vec![
+            (
+                "uniswap",
+                PoolPrice::V2 {
+                    reserve_in: U256::from(uni_reserves.0),
+                    reserve_out: U256::from(uni_reserves.1),
+                },
+            ),
+            (
+                "sushiswap",
+                PoolPrice::V2 {
+                    reserve_in: U256::from(sushi_reserves.0),
+                    reserve_out: U256::from(sushi_reserves.1),
+                },
+            ),
+        ];
+
+        if let Some(v3_price) = self.get_v3_price(token_a, token_b).await? {
+            venues.push(("uniswap_v3", v3_price));
+        }
+
+        Ok(venues)
     }
-    
+
+    /// Exact profit-maximizing input for two constant-product pools, given pool 1
+    /// reserves `(x1, y1)` (pay token X, receive token Y) and pool 2 reserves `(y2, x2)`
+    /// (pay token Y, receive token X back):
+    ///
+    /// `dx* = (sqrt(g1*g2*x1*y1*x2*y2) - x1*y2) / (g1*y2 + g1*g2*y1)`
+    ///
+    /// where `g1`/`g2` are the per-pool fee multipliers (both 0.997 here). Computed in
+    /// integer `U256` space, clamped to zero when no profitable direction exists.
     async fn calculate_optimal_amount(
         &self,
-        _token_a: Address,
-        _token_b: Address,
-        _uni_price: U256,
-        _sushi_price: U256,
+        buy_reserves: (U256, U256),
+        sell_reserves: (U256, U256),
+        buy_from_uni: bool,
     ) -> Result<U256> {
-        // Simplified optimal amount calculation
-        // In production, use proper mathematical optimization
-        Ok(U256::from(10u64.pow(17))) // 0.1 ETH for testing
+        // Pool 1 is whichever DEX is cheaper (where we buy token B with token A);
+        // pool 2 is the other (where we sell token B back for token A).
+        let (x1, y1) = buy_reserves;
+        // `sell_reserves` is `(reserve0, reserve1)` like `buy_reserves`, i.e. (X, Y).
+        let (x2, y2) = sell_reserves;
+        let _ = buy_from_uni;
+
+        let fee_mult_num = U256::from(10_000 - POOL_FEE_BPS);
+        let fee_mult_den = U256::from(10_000u64);
+
+        // Scale everything by fee_mult_den^2 before the sqrt so the fee multipliers stay
+        // in integer space: g1*g2*x1*y1*x2*y2 -> (fee_num^2 * x1*y1*x2*y2) / fee_den^2.
+        let product = x1
+            .checked_mul(y1)
+            .and_then(|v| v.checked_mul(x2))
+            .and_then(|v| v.checked_mul(y2))
+            .and_then(|v| v.checked_mul(fee_mult_num * fee_mult_num));
+        let Some(product) = product else {
+            return Ok(U256::zero());
+        };
+        let scaled = product / (fee_mult_den * fee_mult_den);
+        let sqrt_term = isqrt(scaled);
+
+        let x1_y2 = match x1.checked_mul(y2) {
+            Some(v) => v,
+            None => return Ok(U256::zero()),
+        };
+
+        if sqrt_term <= x1_y2 {
+            // No profitable arbitrage in this direction.
+            return Ok(U256::zero());
+        }
+
+        let numerator = sqrt_term - x1_y2;
+        let denominator = (fee_mult_num * y2 + fee_mult_num * fee_mult_num * y1 / fee_mult_den) / fee_mult_den;
+        if denominator.is_zero() {
+            return Ok(U256::zero());
+        }
+
+        let optimal = numerator / denominator;
+
+        // Cap by a conservative fraction of pool 1's reserve so slippage on the real
+        // trade stays close to the linearized closed form.
+        let cap = x1 / U256::from(10u8);
+        Ok(optimal.min(cap))
     }
     
     async fn estimate_profit(
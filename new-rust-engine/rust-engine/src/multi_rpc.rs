@@ -1,56 +1,310 @@
 // rust-engine/src/multi_rpc.rs
 use std::sync::Arc;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 use ethers::prelude::*;
 use anyhow::Result;
+use parking_lot::RwLock;
 
-/// Rotates between multiple RPC endpoints to avoid rate limits
+/// Weight given to the newest latency sample in the EWMA; lower reacts slower but is less
+/// noisy than a plain moving average.
+const LATENCY_EWMA_ALPHA: f64 = 0.2;
+
+/// Base circuit-breaker cooldown; it doubles per consecutive failure up to `MAX_COOLDOWN`,
+/// so a one-off timeout barely registers but a node that keeps failing backs off hard.
+const BASE_COOLDOWN: Duration = Duration::from_millis(500);
+const MAX_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// After this long without a failure, `consecutive_failures` is treated as decayed back to
+/// zero even if it was never explicitly reset, letting a recovered endpoint re-enter
+/// scoring on equal footing instead of carrying a stale penalty forever.
+const FAILURE_DECAY_WINDOW: Duration = Duration::from_secs(120);
+
+/// Default cap on concurrent in-flight requests per endpoint, so one slow call can't pile
+/// up unbounded retries against the same rate-limited node.
+const DEFAULT_MAX_IN_FLIGHT: usize = 8;
+
+/// Point-in-time health snapshot for a single RPC endpoint, returned by `stats()`.
+#[derive(Debug, Clone)]
+pub struct RpcHealth {
+    pub url: String,
+    pub successes: u64,
+    pub failures: u64,
+    pub consecutive_failures: u64,
+    pub ewma_latency_ms: f64,
+    pub circuit_open: bool,
+    pub in_flight: usize,
+}
+
+/// Rolling success/failure counts, EWMA latency, and circuit-breaker state for one
+/// endpoint. Counts are kept as plain atomics; latency and failure timestamps need a lock
+/// since they're read-modify-write floats/instants rather than single increments.
+struct EndpointHealth {
+    url: String,
+    successes: AtomicU64,
+    failures: AtomicU64,
+    consecutive_failures: AtomicU64,
+    ewma_latency_ms: RwLock<f64>,
+    last_failure: RwLock<Option<Instant>>,
+    in_flight: AtomicUsize,
+}
+
+impl EndpointHealth {
+    fn new(url: String) -> Self {
+        Self {
+            url,
+            successes: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+            consecutive_failures: AtomicU64::new(0),
+            ewma_latency_ms: RwLock::new(0.0),
+            last_failure: RwLock::new(None),
+            in_flight: AtomicUsize::new(0),
+        }
+    }
+
+    fn record_success(&self, latency: Duration) {
+        self.successes.fetch_add(1, Ordering::Relaxed);
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        self.record_latency(latency);
+    }
+
+    fn record_failure(&self, latency: Duration) {
+        self.failures.fetch_add(1, Ordering::Relaxed);
+        self.consecutive_failures.fetch_add(1, Ordering::Relaxed);
+        *self.last_failure.write() = Some(Instant::now());
+        self.record_latency(latency);
+    }
+
+    fn record_latency(&self, latency: Duration) {
+        let sample_ms = latency.as_secs_f64() * 1000.0;
+        let mut ewma = self.ewma_latency_ms.write();
+        *ewma = if *ewma == 0.0 {
+            sample_ms
+        } else {
+            LATENCY_EWMA_ALPHA * sample_ms + (1.0 - LATENCY_EWMA_ALPHA) * *ewma
+        };
+    }
+
+    /// Consecutive failures decayed to zero once `FAILURE_DECAY_WINDOW` has passed since
+    /// the last one, so an endpoint that's been quiet isn't penalized forever.
+    fn decayed_consecutive_failures(&self) -> u64 {
+        let raw = self.consecutive_failures.load(Ordering::Relaxed);
+        if raw == 0 {
+            return 0;
+        }
+        match *self.last_failure.read() {
+            Some(last) if last.elapsed() > FAILURE_DECAY_WINDOW => 0,
+            _ => raw,
+        }
+    }
+
+    fn cooldown_for(consecutive_failures: u64) -> Duration {
+        let shift = consecutive_failures.saturating_sub(1).min(8) as u32;
+        (BASE_COOLDOWN * 2u32.pow(shift)).min(MAX_COOLDOWN)
+    }
+
+    /// The circuit is open (endpoint skipped) while fewer than `cooldown_for(n)` has
+    /// elapsed since the nth-in-a-row failure.
+    fn circuit_open(&self) -> bool {
+        let consecutive_failures = self.decayed_consecutive_failures();
+        if consecutive_failures == 0 {
+            return false;
+        }
+        match *self.last_failure.read() {
+            Some(last) => last.elapsed() < Self::cooldown_for(consecutive_failures),
+            None => false,
+        }
+    }
+
+    /// Weighted selection score: smoothed success rate divided by latency, so a fast
+    /// reliable endpoint always outscores a slow or flaky one. Laplace-smoothed so a
+    /// brand-new endpoint with zero history scores as a coin flip rather than zero.
+    fn score(&self) -> f64 {
+        let successes = self.successes.load(Ordering::Relaxed) as f64;
+        let failures = self.failures.load(Ordering::Relaxed) as f64;
+        let success_rate = (successes + 1.0) / (successes + failures + 2.0);
+        let latency_ms = (*self.ewma_latency_ms.read()).max(1.0);
+        success_rate / latency_ms
+    }
+
+    fn snapshot(&self) -> RpcHealth {
+        RpcHealth {
+            url: self.url.clone(),
+            successes: self.successes.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+            consecutive_failures: self.decayed_consecutive_failures(),
+            ewma_latency_ms: *self.ewma_latency_ms.read(),
+            circuit_open: self.circuit_open(),
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Routes between multiple RPC endpoints by weighted health score instead of blind
+/// round-robin, skipping endpoints whose circuit breaker is open and capping concurrent
+/// in-flight requests per endpoint so a rate-limited node doesn't get hammered further.
 pub struct MultiRpcProvider {
     providers: Vec<Arc<Provider<Http>>>,
-    current: AtomicUsize,
+    health: Vec<EndpointHealth>,
+    max_in_flight: usize,
 }
 
 impl MultiRpcProvider {
     pub fn new(rpc_urls: Vec<String>) -> Result<Self> {
+        Self::with_max_in_flight(rpc_urls, DEFAULT_MAX_IN_FLIGHT)
+    }
+
+    pub fn with_max_in_flight(rpc_urls: Vec<String>, max_in_flight: usize) -> Result<Self> {
         let mut providers = Vec::new();
-        
+        let mut health = Vec::new();
+
         for url in rpc_urls {
-            let provider = Provider::<Http>::try_from(url)?;
+            let provider = Provider::<Http>::try_from(url.clone())?;
             providers.push(Arc::new(provider));
+            health.push(EndpointHealth::new(url));
         }
-        
+
         Ok(Self {
             providers,
-            current: AtomicUsize::new(0),
+            health,
+            max_in_flight,
         })
     }
-    
-    /// Get next provider in rotation
+
+    /// Picks the best-scoring endpoint that isn't circuit-broken and isn't already at its
+    /// in-flight cap. Falls back to the endpoint with the fewest consecutive failures if no
+    /// candidate is eligible, so a total-outage situation still returns something to try.
+    fn select_index(&self) -> usize {
+        let eligible = self.health.iter().enumerate().filter(|(_, h)| {
+            !h.circuit_open() && h.in_flight.load(Ordering::Relaxed) < self.max_in_flight
+        });
+
+        if let Some((idx, _)) = eligible.max_by(|(_, a), (_, b)| {
+            a.score().partial_cmp(&b.score()).unwrap_or(std::cmp::Ordering::Equal)
+        }) {
+            return idx;
+        }
+
+        self.health
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, h)| h.consecutive_failures.load(Ordering::Relaxed))
+            .map(|(idx, _)| idx)
+            .unwrap_or(0)
+    }
+
+    /// Returns the current best-scored provider.
     pub fn get_provider(&self) -> Arc<Provider<Http>> {
-        let idx = self.current.fetch_add(1, Ordering::Relaxed) % self.providers.len();
-        self.providers[idx].clone()
+        self.providers[self.select_index()].clone()
     }
-    
-    /// Execute with automatic retry on different RPCs
+
+    /// Snapshot of every endpoint's current health, for dashboards/logging.
+    pub fn stats(&self) -> Vec<RpcHealth> {
+        self.health.iter().map(EndpointHealth::snapshot).collect()
+    }
+
+    /// Execute with automatic retry, routing each attempt to the best-scored non-broken
+    /// endpoint and recording its duration/outcome back into that endpoint's health state.
     pub async fn execute_with_retry<F, T>(&self, operation: F) -> Result<T>
     where
         F: Fn(Arc<Provider<Http>>) -> futures::future::BoxFuture<'static, Result<T>>,
     {
         let mut last_error = None;
-        
-        // Try each provider
+
         for _ in 0..self.providers.len() {
-            let provider = self.get_provider();
-            
-            match operation(provider).await {
-                Ok(result) => return Ok(result),
+            let idx = self.select_index();
+            let health = &self.health[idx];
+            let provider = self.providers[idx].clone();
+
+            health.in_flight.fetch_add(1, Ordering::Relaxed);
+            let started = Instant::now();
+            let result = operation(provider).await;
+            let elapsed = started.elapsed();
+            health.in_flight.fetch_sub(1, Ordering::Relaxed);
+
+            match result {
+                Ok(value) => {
+                    health.record_success(elapsed);
+                    return Ok(value);
+                }
                 Err(e) => {
+                    health.record_failure(elapsed);
                     last_error = Some(e);
-                    // Continue to next provider
                 }
             }
         }
-        
+
         Err(last_error.unwrap_or_else(|| anyhow::anyhow!("All RPCs failed")))
     }
-}
\ No newline at end of file
+
+    /// The `n` best-scored endpoints that aren't currently circuit-broken, highest score
+    /// first. May return fewer than `n` if not enough healthy endpoints are available.
+    fn top_n_indices(&self, n: usize) -> Vec<usize> {
+        let mut eligible: Vec<usize> = self
+            .health
+            .iter()
+            .enumerate()
+            .filter(|(_, h)| !h.circuit_open())
+            .map(|(idx, _)| idx)
+            .collect();
+        eligible.sort_by(|&a, &b| {
+            self.health[b].score().partial_cmp(&self.health[a].score()).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        eligible.truncate(n);
+        eligible
+    }
+
+    /// Dispatches `operation` to the `n` best-scored non-broken endpoints in parallel and
+    /// returns whichever result a majority of them agree on, recording each endpoint's
+    /// latency/outcome into its health state along the way. Unlike `execute_with_retry`
+    /// (which trusts the first endpoint that answers at all), this tolerates an endpoint
+    /// that returns a *wrong* answer rather than an outright error — e.g. a lagging node
+    /// serving stale reserves — as long as it's outvoted by the others.
+    pub async fn quorum_read<F, T>(&self, n: usize, operation: F) -> Result<T>
+    where
+        F: Fn(Arc<Provider<Http>>) -> futures::future::BoxFuture<'static, Result<T>>,
+        T: Eq + std::hash::Hash + Clone,
+    {
+        let indices = self.top_n_indices(n.max(1));
+        if indices.is_empty() {
+            return Err(anyhow::anyhow!("no healthy RPC endpoints available"));
+        }
+
+        let attempts = indices.into_iter().map(|idx| {
+            let health = &self.health[idx];
+            let provider = self.providers[idx].clone();
+            let operation = &operation;
+            async move {
+                health.in_flight.fetch_add(1, Ordering::Relaxed);
+                let started = Instant::now();
+                let result = operation(provider).await;
+                let elapsed = started.elapsed();
+                health.in_flight.fetch_sub(1, Ordering::Relaxed);
+                match &result {
+                    Ok(_) => health.record_success(elapsed),
+                    Err(_) => health.record_failure(elapsed),
+                }
+                result
+            }
+        });
+
+        let results: Vec<T> = futures::future::join_all(attempts).await.into_iter().flatten().collect();
+        if results.is_empty() {
+            return Err(anyhow::anyhow!("all raced RPC endpoints failed"));
+        }
+
+        let mut votes: std::collections::HashMap<T, usize> = std::collections::HashMap::new();
+        for result in &results {
+            *votes.entry(result.clone()).or_insert(0) += 1;
+        }
+
+        let (value, count) = votes
+            .into_iter()
+            .max_by_key(|(_, count)| *count)
+            .ok_or_else(|| anyhow::anyhow!("quorum_read produced no votes"))?;
+        if count * 2 <= results.len() {
+            return Err(anyhow::anyhow!("no majority among {} RPC responses", results.len()));
+        }
+        Ok(value)
+    }
+}
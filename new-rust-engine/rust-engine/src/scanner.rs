@@ -1,15 +1,210 @@
 // rust-engine/src/scanner.rs
 use ethers::prelude::*;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use anyhow::Result;
+use tokio_stream::StreamExt;
 
 // Import from config module
 use crate::config::{ChainConfig, DexConfig};
+use crate::contracts::{GetReservesReturn, Multicall3, UniswapV2Factory, UniswapV2Pair, MULTICALL3_ADDRESS};
+use crate::multi_rpc::{MultiRpcProvider, RpcHealth};
+use crate::token_pricing::TokenPricing;
+
+/// Reserves for one pool, as returned by a batched `fetch_all_reserves` call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Reserves {
+    pub reserve0: U256,
+    pub reserve1: U256,
+}
+
+/// One directed edge of the token graph used for cyclic-arbitrage detection: swapping
+/// through `pair_address` on `dex` from `from` to `to`, weighted by `-ln(effective_rate)`
+/// (fee-adjusted) so a negative-weight cycle is a profitable trading loop.
+#[derive(Clone, Debug)]
+struct TokenGraphEdge {
+    from: usize,
+    to: usize,
+    weight: f64,
+    dex: String,
+    pair_address: Address,
+    /// Reserve of the `from` token and the `to` token in this pool, kept alongside
+    /// `weight` so a detected cycle's optimal input can be solved from the same CPMM
+    /// curves the weight was derived from, instead of re-fetching reserves a second time.
+    reserve_in: f64,
+    reserve_out: f64,
+    fee_bps: u32,
+}
+
+/// A detected profitable loop: `tokens[0] -> tokens[1] -> ... -> tokens[0]`, trading
+/// through `dexes[i]`/`pairs[i]` for each hop.
+#[derive(Clone, Debug)]
+pub struct CyclicOpportunity {
+    pub tokens: Vec<Address>,
+    pub dexes: Vec<String>,
+    pub pairs: Vec<Address>,
+    /// Estimated round-trip return, `exp(-total_weight) - 1`, as a fraction (0.01 = 1%).
+    pub estimated_return: f64,
+    /// Profit-maximizing input amount (in `tokens[0]`'s raw on-chain units) through the
+    /// whole loop, and its projected net profit in the same units, solved by
+    /// `solve_cycle_input`.
+    pub optimal_amount: f64,
+    pub estimated_profit: f64,
+}
+
+/// Bellman-Ford negative-cycle detection, seeded with every node at distance 0
+/// (equivalent to a virtual source connected to all nodes), so one pass over `|V|`
+/// relaxations finds a negative cycle reachable from anywhere in the graph. Returns the
+/// cycle as node indices (first == last) by walking predecessor pointers back from a node
+/// still relaxing on the `|V|`-th pass.
+fn bellman_ford_negative_cycle(num_nodes: usize, edges: &[TokenGraphEdge]) -> Option<Vec<usize>> {
+    if num_nodes == 0 {
+        return None;
+    }
+
+    let mut dist = vec![0.0f64; num_nodes];
+    let mut pred: Vec<Option<usize>> = vec![None; num_nodes];
+    let mut last_relaxed = None;
+
+    for _ in 0..num_nodes {
+        last_relaxed = None;
+        for edge in edges {
+            let candidate = dist[edge.from] + edge.weight;
+            if candidate < dist[edge.to] - 1e-12 {
+                dist[edge.to] = candidate;
+                pred[edge.to] = Some(edge.from);
+                last_relaxed = Some(edge.to);
+            }
+        }
+    }
+
+    let mut x = last_relaxed?;
+    for _ in 0..num_nodes {
+        x = pred[x]?;
+    }
+
+    let mut cycle = vec![x];
+    let mut cur = pred[x]?;
+    while cur != x {
+        cycle.push(cur);
+        cur = pred[cur]?;
+    }
+    cycle.push(x);
+    cycle.reverse();
+    Some(cycle)
+}
+
+/// Rotates a cycle (first == last node dropped) to start at its smallest node index, so
+/// the same loop found starting from a different node compares equal.
+fn canonical_rotation(cycle: &[usize]) -> Vec<usize> {
+    let hops = &cycle[..cycle.len() - 1];
+    let min_pos = hops.iter().enumerate().min_by_key(|(_, &n)| n).map(|(i, _)| i).unwrap_or(0);
+    hops.iter().cycle().skip(min_pos).take(hops.len()).copied().collect()
+}
+
+/// Runs `amount_in` through every hop of a detected cycle's edges in order, applying each
+/// pool's constant-product formula with its own fee, and returns the final output in
+/// `path[0].from`'s token. Mirrors `sizing::optimal_two_pool_size`'s per-hop formula but
+/// composed over an arbitrary-length loop instead of just two legs.
+fn cycle_output(amount_in: f64, path: &[TokenGraphEdge]) -> f64 {
+    let mut amount = amount_in.max(0.0);
+    for edge in path {
+        let gamma = 1.0 - (edge.fee_bps as f64 / 10_000.0);
+        let denom = edge.reserve_in + gamma * amount;
+        if denom <= 0.0 {
+            return 0.0;
+        }
+        amount = edge.reserve_out * gamma * amount / denom;
+    }
+    amount
+}
+
+/// Golden-section search for `max_{x in [0, upper]} cycle_output(x, path) - x`. Used instead
+/// of a closed form since an arbitrary-length cycle's composed output has no simple inverse
+/// once more than two constant-product legs are chained together.
+fn solve_cycle_input(path: &[TokenGraphEdge]) -> (f64, f64) {
+    let upper = path
+        .iter()
+        .map(|edge| edge.reserve_in)
+        .fold(f64::INFINITY, f64::min);
+    if !upper.is_finite() || upper <= 0.0 {
+        return (0.0, 0.0);
+    }
+
+    let profit = |x: f64| cycle_output(x, path) - x;
+    const GOLDEN: f64 = 0.6180339887498949;
+
+    let mut lo = 0.0;
+    let mut hi = upper * 0.5; // never worth borrowing past half the shallowest pool's reserve
+    let mut c = hi - GOLDEN * (hi - lo);
+    let mut d = lo + GOLDEN * (hi - lo);
+    let mut fc = profit(c);
+    let mut fd = profit(d);
+
+    for _ in 0..100 {
+        if (hi - lo).abs() < 1e-6 {
+            break;
+        }
+        if fc > fd {
+            hi = d;
+            d = c;
+            fd = fc;
+            c = hi - GOLDEN * (hi - lo);
+            fc = profit(c);
+        } else {
+            lo = c;
+            c = d;
+            fc = fd;
+            d = lo + GOLDEN * (hi - lo);
+            fd = profit(d);
+        }
+    }
+
+    let best_x = if fc > fd { c } else { d };
+    let best_profit = profit(best_x);
+    if best_profit <= 0.0 {
+        (0.0, 0.0)
+    } else {
+        (best_x, best_profit)
+    }
+}
 
 pub struct OpportunityScanner {
     provider: Arc<Provider<Http>>,
     config: ChainConfig,
     pair_cache: Arc<dashmap::DashMap<String, PairInfo>>,
+    /// Optional subscription-capable provider used only to drive `run_event_driven`'s
+    /// block/mempool streams. `eth_call`s still go through `provider` (plain HTTP), since
+    /// there's no reason to pay for a WS round-trip on reads.
+    ws_provider: Option<Arc<Provider<Ws>>>,
+    /// Optional quorum/failover layer over the configured RPC endpoints. When set,
+    /// `fetch_reserves` races `QUORUM_SIZE` of the best-scored endpoints and takes their
+    /// majority-agreeing answer instead of trusting whichever single endpoint `provider`
+    /// happens to be backed by.
+    multi_rpc: Option<Arc<MultiRpcProvider>>,
+    /// Per-token decimals cache and Chainlink USD feeds, so spreads and profit/gas
+    /// costs are priced off real on-chain values instead of the `1.0`/`2000.0`
+    /// constants this scanner used to assume.
+    pricing: Arc<TokenPricing>,
+}
+
+/// Number of endpoints raced per quorum read. 3 tolerates one lagging/wrong endpoint
+/// while still requiring real agreement rather than just picking a single answer.
+const QUORUM_SIZE: usize = 3;
+
+/// What woke `run_event_driven` up for this rescan: a new block landed, or a pending
+/// transaction decoded as a swap against one of the configured DEX routers.
+#[derive(Clone, Debug)]
+pub enum ScanTrigger {
+    NewBlock(U64),
+    PendingSwap {
+        tx_hash: H256,
+        router: Address,
+        path: Vec<Address>,
+        amount_in: U256,
+    },
+    /// Emitted on the fixed-interval fallback when no `Ws` provider is configured.
+    Poll,
 }
 
 #[derive(Clone, Debug)]
@@ -37,13 +232,107 @@ pub struct Opportunity {
 
 impl OpportunityScanner {
     pub fn new(provider: Arc<Provider<Http>>, config: ChainConfig) -> Self {
+        let pricing = Arc::new(TokenPricing::for_chain(provider.clone(), config.chain_id));
         Self {
             provider,
             config,
             pair_cache: Arc::new(dashmap::DashMap::new()),
+            ws_provider: None,
+            multi_rpc: None,
+            pricing,
         }
     }
-    
+
+    /// Like `new`, but also wires up a WebSocket provider so `run_event_driven` can react
+    /// to new blocks and pending mempool transactions instead of polling.
+    pub fn new_with_ws(provider: Arc<Provider<Http>>, ws_provider: Arc<Provider<Ws>>, config: ChainConfig) -> Self {
+        let pricing = Arc::new(TokenPricing::for_chain(provider.clone(), config.chain_id));
+        Self {
+            provider,
+            config,
+            pair_cache: Arc::new(dashmap::DashMap::new()),
+            ws_provider: Some(ws_provider),
+            multi_rpc: None,
+            pricing,
+        }
+    }
+
+    /// Like `new`, but every reserve read is raced across `multi_rpc`'s endpoints and
+    /// resolved by majority agreement, so a single dead or lagging RPC can no longer stall
+    /// or skew a scan. `provider` is kept as the single-endpoint fallback for calls that
+    /// don't go through `fetch_reserves` (e.g. `get_gas_price`).
+    pub fn new_with_multi_rpc(provider: Arc<Provider<Http>>, multi_rpc: Arc<MultiRpcProvider>, config: ChainConfig) -> Self {
+        let pricing = Arc::new(TokenPricing::for_chain(provider.clone(), config.chain_id));
+        Self {
+            provider,
+            config,
+            pair_cache: Arc::new(dashmap::DashMap::new()),
+            ws_provider: None,
+            multi_rpc: Some(multi_rpc),
+            pricing,
+        }
+    }
+
+    /// Per-endpoint health snapshot of the quorum/failover RPC layer, for logging/metrics.
+    /// `None` when this scanner wasn't constructed with `new_with_multi_rpc`.
+    pub fn rpc_health(&self) -> Option<Vec<RpcHealth>> {
+        self.multi_rpc.as_ref().map(|m| m.stats())
+    }
+
+    /// Drives continuous scanning off live chain events rather than a busy loop: exactly
+    /// one rescan per new block, plus an early rescan whenever a pending transaction
+    /// decodes as a `swapExactTokensForTokens` call against one of this chain's configured
+    /// routers (so a trade big enough to move the price is caught before it lands, not up
+    /// to a block late). Falls back to fixed-interval polling when no `Ws` provider was
+    /// configured, since plain HTTP has no subscription support.
+    pub async fn run_event_driven<F, Fut>(&self, mut on_trigger: F) -> Result<()>
+    where
+        F: FnMut(ScanTrigger) -> Fut,
+        Fut: std::future::Future<Output = ()>,
+    {
+        let Some(ws) = self.ws_provider.clone() else {
+            let mut interval = tokio::time::interval(std::time::Duration::from_millis(500));
+            loop {
+                interval.tick().await;
+                on_trigger(ScanTrigger::Poll).await;
+            }
+        };
+
+        let routers: HashSet<Address> = self.config.dexes.iter().map(|dex| dex.router).collect();
+
+        let mut blocks = ws.subscribe_blocks().await?;
+        let mut pending = ws.subscribe_pending_txs().await?;
+
+        loop {
+            tokio::select! {
+                Some(block) = blocks.next() => {
+                    on_trigger(ScanTrigger::NewBlock(block.number.unwrap_or_default())).await;
+                }
+                Some(tx_hash) = pending.next() => {
+                    let Ok(Some(tx)) = ws.get_transaction(tx_hash).await else {
+                        continue;
+                    };
+                    let Some(router) = tx.to.filter(|to| routers.contains(to)) else {
+                        continue;
+                    };
+                    if let Ok(crate::contracts::UniswapV2RouterCalls::SwapExactTokensForTokens(call)) =
+                        crate::contracts::UniswapV2RouterCalls::decode(&tx.input)
+                    {
+                        on_trigger(ScanTrigger::PendingSwap {
+                            tx_hash,
+                            router,
+                            path: call.path,
+                            amount_in: call.amount_in,
+                        }).await;
+                    }
+                }
+                else => break,
+            }
+        }
+
+        Ok(())
+    }
+
     pub async fn scan_all_pairs(&self) -> Result<Vec<Opportunity>> {
         let mut opportunities = Vec::new();
         let tokens = self.get_top_tokens();
@@ -67,29 +356,35 @@ impl OpportunityScanner {
                         let (dex1, price1) = &prices[i];
                         let (dex2, price2) = &prices[j];
                         let spread = self.calculate_spread(price1.price, price2.price);
-                        
+
                         if spread > 0.5 {
-                            let optimal_amount = self.calculate_optimal_amount(
-                                price1.reserves.0,
-                                price1.reserves.1,
-                                price2.reserves.0,
-                                price2.reserves.1,
-                            );
-                            
+                            let gamma = self.combined_gamma(dex1, dex2);
+                            // Buy token1 where it's cheaper (lower token0-per-token1 price),
+                            // sell it where it's pricier — `calculate_optimal_trade` assumes that ordering and
+                            // returns a zero-sized trade otherwise, so picking the wrong
+                            // side silently hides a real arbitrage in the other direction.
+                            let (buy_dex, buy_price, sell_dex, sell_price) = if price1.price <= price2.price {
+                                (dex1, price1, dex2, price2)
+                            } else {
+                                (dex2, price2, dex1, price1)
+                            };
+                            let trade = self.calculate_optimal_trade(buy_price, sell_price, gamma);
+                            let optimal_amount = U256::from(trade.input_amount.max(0.0) as u128);
+
                             let profit = self.calculate_profit(
-                                optimal_amount,
-                                spread,
+                                &trade,
+                                token0,
                                 gas_cost_usd,
                             ).await?;
-                            
+
                             if profit > 0.0 {
                                 opportunities.push(Opportunity {
                                     token0,
                                     token1,
-                                    dex1: dex1.clone(),
-                                    dex2: dex2.clone(),
-                                    pair1: price1.pair_address,
-                                    pair2: price2.pair_address,
+                                    dex1: buy_dex.clone(),
+                                    dex2: sell_dex.clone(),
+                                    pair1: buy_price.pair_address,
+                                    pair2: sell_price.pair_address,
                                     spread_pct: spread,
                                     optimal_amount,
                                     profit_usd: profit,
@@ -108,78 +403,269 @@ impl OpportunityScanner {
     
     async fn get_pair_price(&self, dex: &DexConfig, token0: Address, token1: Address) -> Result<Option<PriceData>> {
         let cache_key = format!("{:?}-{:?}-{:?}", dex.factory, token0, token1);
-        
+
         if let Some(pair_info) = self.pair_cache.get(&cache_key) {
-            return self.fetch_reserves(pair_info.pair_address).await;
+            return self.fetch_reserves(pair_info.pair_address, token0, token1).await;
         }
-        
-        let factory_abi = ethers::abi::parse_abi(&[
-            "function getPair(address,address) view returns (address)"
-        ])?;
-        
-        let factory = Contract::new(dex.factory, factory_abi, self.provider.clone());
-        let pair_address: Address = factory
-            .method("getPair", (token0, token1))?
-            .call()
-            .await?;
-        
+
+        let factory = UniswapV2Factory::new(dex.factory, self.provider.clone());
+        let pair_address = factory.get_pair(token0, token1).call().await?;
+
         if pair_address == Address::zero() {
             return Ok(None);
         }
-        
+
         self.pair_cache.insert(cache_key, PairInfo {
             token0,
             token1,
             pair_address,
             dex: dex.name.clone(),
         });
-        
-        self.fetch_reserves(pair_address).await
+
+        self.fetch_reserves(pair_address, token0, token1).await
     }
-    
-    async fn fetch_reserves(&self, pair_address: Address) -> Result<Option<PriceData>> {
-        let pair_abi = ethers::abi::parse_abi(&[
-            "function getReserves() view returns (uint112,uint112,uint32)"
-        ])?;
-        
-        let pair = Contract::new(pair_address, pair_abi, self.provider.clone());
-        let reserves: (U256, U256, U256) = pair
-            .method("getReserves", ())?
-            .call()
-            .await?;
-        
-        let price = reserves.0.as_u128() as f64 / reserves.1.as_u128().max(1) as f64;
-        
+
+    /// `token0`/`token1` are only used to normalize `price` by each side's real
+    /// decimals (via `pricing`) — `reserves` itself stays in raw on-chain units.
+    async fn fetch_reserves(&self, pair_address: Address, token0: Address, token1: Address) -> Result<Option<PriceData>> {
+        let (reserve0, reserve1) = if let Some(multi_rpc) = &self.multi_rpc {
+            let reserves = multi_rpc
+                .quorum_read(QUORUM_SIZE, move |provider| {
+                    Box::pin(async move {
+                        let pair = UniswapV2Pair::new(pair_address, provider);
+                        let (r0, r1, _) = pair.get_reserves().call().await?;
+                        Ok(Reserves { reserve0: U256::from(r0), reserve1: U256::from(r1) })
+                    })
+                })
+                .await?;
+            (reserves.reserve0, reserves.reserve1)
+        } else {
+            let pair = UniswapV2Pair::new(pair_address, self.provider.clone());
+            let (r0, r1, _) = pair.get_reserves().call().await?;
+            (U256::from(r0), U256::from(r1))
+        };
+
+        // Fall back to the raw (decimals-unaware) ratio if a token's `decimals()` call
+        // fails, rather than dropping the pair entirely.
+        let price = self.pricing.normalized_price(token0, token1, reserve0, reserve1).await
+            .unwrap_or_else(|_| reserve0.as_u128() as f64 / reserve1.as_u128().max(1) as f64);
+
         Ok(Some(PriceData {
             pair_address,
-            reserves: (reserves.0, reserves.1),
+            reserves: (reserve0, reserve1),
             price,
         }))
     }
+
+    /// Batches `getReserves()` across every pair in `pairs` into a single `eth_call` via
+    /// the well-known Multicall3 deployment, instead of one round-trip per pool. A `None`
+    /// entry means that pool's return data didn't decode as `getReserves()` output. The
+    /// batch goes through `aggregate`, so a single reverting call fails the whole batch.
+    pub async fn fetch_all_reserves(&self, pairs: &[Address]) -> Result<Vec<Option<Reserves>>> {
+        if pairs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let multicall_address: Address = MULTICALL3_ADDRESS.parse()?;
+        let multicall = Multicall3::new(multicall_address, self.provider.clone());
+
+        let calls: Vec<(Address, Bytes)> = pairs
+            .iter()
+            .map(|&pair| {
+                let pair_contract = UniswapV2Pair::new(pair, self.provider.clone());
+                (pair, pair_contract.get_reserves().calldata().unwrap_or_default())
+            })
+            .collect();
+
+        let (_block_number, return_data) = multicall.aggregate(calls).call().await?;
+
+        Ok(return_data
+            .into_iter()
+            .map(|bytes| {
+                GetReservesReturn::decode(&bytes)
+                    .ok()
+                    .map(|decoded| Reserves {
+                        reserve0: U256::from(decoded.reserve0),
+                        reserve1: U256::from(decoded.reserve1),
+                    })
+            })
+            .collect())
+    }
     
     fn calculate_spread(&self, price1: f64, price2: f64) -> f64 {
         ((price1 - price2).abs() / price1.min(price2)) * 100.0
     }
     
-    fn calculate_optimal_amount(&self, r1_0: U256, _r1_1: U256, r2_0: U256, _r2_1: U256) -> U256 {
-        let avg_reserve = (r1_0 + r2_0) / 2;
-        avg_reserve / 100
+    /// Effective fee factor for a two-leg loop through `dex1` then `dex2`. The closed-form
+    /// solver in `crate::sizing` assumes a single `gamma` shared by both legs, so when the
+    /// two DEXes charge different fees this averages them rather than picking one side.
+    fn combined_gamma(&self, dex1: &str, dex2: &str) -> f64 {
+        let gamma_for = |name: &str| {
+            self.config.dexes.iter()
+                .find(|dex| dex.name == name)
+                .map(|dex| 1.0 - (dex.fee_bps as f64 / 10_000.0))
+                .unwrap_or(0.997)
+        };
+        (gamma_for(dex1) + gamma_for(dex2)) / 2.0
     }
-    
-    async fn calculate_profit(&self, amount: U256, spread_pct: f64, gas_cost: f64) -> Result<f64> {
-        let token_price_usd = 1.0;
-        let trade_value = (amount.as_u128() as f64 / 1e18) * token_price_usd;
-        let gross_profit = trade_value * (spread_pct / 100.0) * 0.9;
+
+    /// Profit-maximizing trade size for a buy-on-`price1`/sell-on-`price2` loop, solved in
+    /// closed form from both pools' reserves rather than guessed as a fixed fraction of
+    /// reserve. `max_input` caps the solved size at 10% of pool1's input-side reserve, the
+    /// same conservative liquidity bound the rest of the scanner uses.
+    fn calculate_optimal_trade(&self, price1: &PriceData, price2: &PriceData, gamma: f64) -> crate::sizing::OptimalTrade {
+        let ra = price1.reserves.0.as_u128() as f64;
+        let rb = price1.reserves.1.as_u128() as f64;
+        let ra_prime = price2.reserves.0.as_u128() as f64;
+        let rb_prime = price2.reserves.1.as_u128() as f64;
+
+        crate::sizing::optimal_two_pool_size(ra, rb, ra_prime, rb_prime, gamma, ra * 0.1)
+    }
+
+    /// `input_token` is the side of the trade `trade.input_amount`/`trade.gross_profit`
+    /// are denominated in (see `sizing::optimal_two_pool_size`'s doc comment) — priced
+    /// off its live Chainlink rate instead of assuming every token is worth $1.
+    async fn calculate_profit(&self, trade: &crate::sizing::OptimalTrade, input_token: Address, gas_cost: f64) -> Result<f64> {
+        let token_price_usd = self.pricing.token_price_usd(input_token).await.unwrap_or(1.0);
+        let trade_value = (trade.input_amount / 1e18) * token_price_usd;
+        let gross_profit_usd = (trade.gross_profit / 1e18) * token_price_usd;
         let flash_loan_fee = trade_value * 0.0009;
-        Ok(gross_profit - gas_cost - flash_loan_fee)
+        Ok(gross_profit_usd - gas_cost - flash_loan_fee)
     }
-    
+
     async fn calculate_gas_cost(&self, gas_price: U256) -> Result<f64> {
         let gas_units = 500_000u64;
-        let eth_price = 2000.0;
+        let eth_price = self.pricing.eth_price_usd().await.unwrap_or(2000.0);
         Ok((gas_price.as_u64() as f64 * gas_units as f64 * eth_price) / 1e18)
     }
     
+    /// Builds the directed token graph used for cyclic-arbitrage detection: one edge per
+    /// (token pair, DEX, direction) with weight `-ln(effective_rate)`, where
+    /// `effective_rate` already accounts for that DEX's fee. When multiple DEXes quote the
+    /// same direction, only the best (lowest-weight) edge is kept, since a worse-priced
+    /// duplicate edge can never appear in the most negative cycle.
+    async fn build_token_graph(&self, tokens: &[Address]) -> Result<(HashMap<(usize, usize), TokenGraphEdge>, Vec<Address>)> {
+        let mut best: HashMap<(usize, usize), TokenGraphEdge> = HashMap::new();
+
+        for i in 0..tokens.len() {
+            for j in i + 1..tokens.len() {
+                let token0 = tokens[i];
+                let token1 = tokens[j];
+
+                for dex in &self.config.dexes {
+                    let Ok(Some(price_data)) = self.get_pair_price(dex, token0, token1).await else {
+                        continue;
+                    };
+                    let gamma = 1.0 - (dex.fee_bps as f64 / 10_000.0);
+                    let reserve0 = price_data.reserves.0.as_u128() as f64;
+                    let reserve1 = price_data.reserves.1.as_u128() as f64;
+                    if reserve0 <= 0.0 || reserve1 <= 0.0 {
+                        continue;
+                    }
+
+                    let rate_0_to_1 = gamma * reserve1 / reserve0;
+                    let rate_1_to_0 = gamma * reserve0 / reserve1;
+
+                    for (from, to, rate, reserve_in, reserve_out) in [
+                        (i, j, rate_0_to_1, reserve0, reserve1),
+                        (j, i, rate_1_to_0, reserve1, reserve0),
+                    ] {
+                        if rate <= 0.0 {
+                            continue;
+                        }
+                        let weight = -rate.ln();
+                        let better = best.get(&(from, to)).map(|e| weight < e.weight).unwrap_or(true);
+                        if better {
+                            best.insert((from, to), TokenGraphEdge {
+                                from,
+                                to,
+                                weight,
+                                dex: dex.name.clone(),
+                                pair_address: price_data.pair_address,
+                                reserve_in,
+                                reserve_out,
+                                fee_bps: dex.fee_bps,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok((best, tokens.to_vec()))
+    }
+
+    /// Finds profitable multi-hop loops (2-4 hops) across all configured DEXes by repeated
+    /// negative-cycle detection: each time a cycle is found, its weakest edge is neutralized
+    /// (weight set to 0) so the next Bellman-Ford pass is forced to find a different one.
+    /// Loop iterations are capped since the graph is small and finite, but an adversarial
+    /// arrangement of edges could otherwise cycle between the same two loops forever.
+    pub async fn scan_triangular_arbitrage(&self, max_hops: usize) -> Result<Vec<CyclicOpportunity>> {
+        let max_hops = max_hops.clamp(2, 4);
+        let tokens = self.get_top_tokens();
+        let (edge_map, nodes) = self.build_token_graph(&tokens).await?;
+
+        let mut edges: Vec<TokenGraphEdge> = edge_map.into_values().collect();
+        let mut seen_rotations: HashSet<Vec<usize>> = HashSet::new();
+        let mut opportunities = Vec::new();
+
+        const MAX_ITERATIONS: usize = 8;
+        for _ in 0..MAX_ITERATIONS {
+            let Some(cycle) = bellman_ford_negative_cycle(nodes.len(), &edges) else {
+                break;
+            };
+
+            let hops = cycle.len() - 1;
+            let canonical = canonical_rotation(&cycle);
+            let is_new = hops >= 2 && hops <= max_hops && seen_rotations.insert(canonical);
+
+            if is_new {
+                let mut path_edges = Vec::with_capacity(hops);
+                let mut total_weight = 0.0;
+                let mut ok = true;
+                for w in cycle.windows(2) {
+                    match edges.iter().find(|e| e.from == w[0] && e.to == w[1]) {
+                        Some(edge) => {
+                            total_weight += edge.weight;
+                            path_edges.push(edge.clone());
+                        }
+                        None => {
+                            ok = false;
+                            break;
+                        }
+                    }
+                }
+
+                if ok && total_weight < 0.0 {
+                    let (optimal_amount, estimated_profit) = solve_cycle_input(&path_edges);
+                    if estimated_profit > 0.0 {
+                        opportunities.push(CyclicOpportunity {
+                            tokens: cycle.iter().map(|&idx| nodes[idx]).collect(),
+                            dexes: path_edges.iter().map(|e| e.dex.clone()).collect(),
+                            pairs: path_edges.iter().map(|e| e.pair_address).collect(),
+                            estimated_return: (-total_weight).exp() - 1.0,
+                            optimal_amount,
+                            estimated_profit,
+                        });
+                    }
+                }
+            }
+
+            // Neutralize this cycle's weakest (most negative) edge so the next pass is
+            // forced toward a different cycle instead of rediscovering the same one.
+            if let Some(weakest) = cycle.windows(2)
+                .filter_map(|w| edges.iter().position(|e| e.from == w[0] && e.to == w[1]))
+                .min_by(|&a, &b| edges[a].weight.partial_cmp(&edges[b].weight).unwrap())
+            {
+                edges[weakest].weight = 0.0;
+            } else {
+                break;
+            }
+        }
+
+        Ok(opportunities)
+    }
+
     fn get_top_tokens(&self) -> Vec<Address> {
         match self.config.chain_id {
             42161 => vec![
@@ -0,0 +1,210 @@
+// rust-engine/src/simulator.rs
+//
+// revm-based preflight simulation: forks current chain state via a live `Provider` and
+// dry-runs a built flash-loan transaction locally before it's ever broadcast, so a stale
+// price or changed pool state is caught as a local revert instead of costing real gas.
+
+use anyhow::{anyhow, Result};
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use revm::db::{CacheDB, Database};
+use revm::primitives::{AccountInfo, Bytecode, ExecutionResult, Output, TransactTo, B160, B256, U256 as RU256};
+use revm::EVM;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Outcome of dry-running a flash-loan bundle: gas it would actually consume, the revert
+/// reason if it failed, and the net change in the borrowed asset's balance held by
+/// `receiver` (the wallet that would receive the flash-loaned funds and repay the loan).
+#[derive(Debug)]
+pub struct SimOutcome {
+    pub net_profit: U256,
+    pub gas_used: u64,
+    pub reverted: Option<String>,
+}
+
+impl SimOutcome {
+    pub fn is_profitable(&self, min_profit: U256) -> bool {
+        self.reverted.is_none() && self.net_profit >= min_profit
+    }
+}
+
+/// A `revm::Database` that lazily fetches account info, storage, code, and block hashes
+/// from a live `Provider` over JSON-RPC and memoizes everything in-memory, so a single
+/// simulation only ever fetches each slot/account once.
+struct ProviderDb {
+    provider: Arc<Provider<Http>>,
+    block: u64,
+    handle: tokio::runtime::Handle,
+    accounts: Mutex<HashMap<B160, AccountInfo>>,
+    storage: Mutex<HashMap<(B160, RU256), RU256>>,
+    block_hashes: Mutex<HashMap<u64, B256>>,
+}
+
+impl ProviderDb {
+    fn new(provider: Arc<Provider<Http>>, block: u64) -> Self {
+        Self {
+            provider,
+            block,
+            handle: tokio::runtime::Handle::current(),
+            accounts: Mutex::new(HashMap::new()),
+            storage: Mutex::new(HashMap::new()),
+            block_hashes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn block_on<F: std::future::Future>(&self, fut: F) -> F::Output {
+        tokio::task::block_in_place(|| self.handle.clone().block_on(fut))
+    }
+}
+
+impl Database for ProviderDb {
+    type Error = anyhow::Error;
+
+    fn basic(&mut self, address: B160) -> Result<Option<AccountInfo>, Self::Error> {
+        if let Some(info) = self.accounts.lock().unwrap().get(&address) {
+            return Ok(Some(info.clone()));
+        }
+
+        let addr = Address::from(address.0);
+        let block = Some(BlockId::from(self.block));
+        let (balance, nonce, code) = self.block_on(async {
+            let balance = self.provider.get_balance(addr, block).await?;
+            let nonce = self.provider.get_transaction_count(addr, block).await?;
+            let code = self.provider.get_code(addr, block).await?;
+            Ok::<_, ProviderError>((balance, nonce, code))
+        })
+        .map_err(|e| anyhow!("fetching account {addr:?}: {e}"))?;
+
+        let bytecode = Bytecode::new_raw(code.0.into());
+        let info = AccountInfo {
+            balance: RU256::from_limbs(balance.0),
+            nonce: nonce.as_u64(),
+            code_hash: bytecode.hash_slow(),
+            code: Some(bytecode),
+        };
+        self.accounts.lock().unwrap().insert(address, info.clone());
+        Ok(Some(info))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        Err(anyhow!("code_by_hash({code_hash}) not resolvable without an address"))
+    }
+
+    fn storage(&mut self, address: B160, index: RU256) -> Result<RU256, Self::Error> {
+        if let Some(value) = self.storage.lock().unwrap().get(&(address, index)).copied() {
+            return Ok(value);
+        }
+
+        let addr = Address::from(address.0);
+        let slot = H256::from_slice(&index.to_be_bytes::<32>());
+        let block = Some(BlockId::from(self.block));
+        let value = self
+            .block_on(self.provider.get_storage_at(addr, slot, block))
+            .map_err(|e| anyhow!("fetching storage {addr:?}[{slot:?}]: {e}"))?;
+
+        let value = RU256::from_be_bytes(value.0);
+        self.storage.lock().unwrap().insert((address, index), value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: RU256) -> Result<B256, Self::Error> {
+        let number: u64 = number.try_into().unwrap_or(0);
+        if let Some(hash) = self.block_hashes.lock().unwrap().get(&number).copied() {
+            return Ok(hash);
+        }
+
+        let block = self
+            .block_on(self.provider.get_block(number))
+            .map_err(|e| anyhow!("fetching block {number}: {e}"))?
+            .ok_or_else(|| anyhow!("block {number} not found"))?;
+        let hash = B256::from(block.hash.unwrap_or_default().0);
+        self.block_hashes.lock().unwrap().insert(number, hash);
+        Ok(hash)
+    }
+}
+
+/// Runs `IERC20(token).balanceOf(holder)` through `evm` without committing any state
+/// (a plain `transact()`, not `transact_commit()`), so probing the balance never disturbs
+/// whatever the main bundle call already wrote.
+fn read_erc20_balance(evm: &mut EVM<CacheDB<ProviderDb>>, token: Address, holder: Address) -> Result<U256> {
+    let calldata = ethers::abi::parse_abi(&["function balanceOf(address) view returns (uint256)"])?
+        .function("balanceOf")?
+        .encode_input(&[ethers::abi::Token::Address(holder)])?;
+
+    evm.env.tx.caller = B160::from(holder.0);
+    evm.env.tx.transact_to = TransactTo::Call(B160::from(token.0));
+    evm.env.tx.data = calldata.into();
+    evm.env.tx.gas_limit = 200_000;
+
+    let result = evm.transact().map_err(|e| anyhow!("balanceOf call failed: {e:?}"))?;
+    match result.result {
+        ExecutionResult::Success { output: Output::Call(bytes), .. } if bytes.len() >= 32 => {
+            Ok(U256::from_big_endian(&bytes[bytes.len() - 32..]))
+        }
+        _ => Ok(U256::zero()),
+    }
+}
+
+/// Forks chain state at `block` and dry-runs `to`/`data` as `caller` would send it,
+/// returning the gas it would burn, any revert reason, and the net change in `token`'s
+/// balance held by `caller` — the borrowed asset, so a flash loan that can't repay itself
+/// shows up as a non-positive `net_profit` rather than an opaque on-chain revert.
+pub async fn simulate_flash_loan_tx(
+    provider: Arc<Provider<Http>>,
+    block: u64,
+    caller: Address,
+    tx: &TypedTransaction,
+    token: Address,
+) -> Result<SimOutcome> {
+    let to = match tx.to() {
+        Some(NameOrAddress::Address(addr)) => *addr,
+        _ => return Err(anyhow!("simulated transaction has no concrete `to` address")),
+    };
+    let data = tx.data().cloned().unwrap_or_default();
+
+    let db = ProviderDb::new(provider, block);
+    let mut cache_db = CacheDB::new(db);
+
+    let caller_b160 = B160::from(caller.0);
+    if let Some(mut info) = cache_db.basic(caller_b160)? {
+        info.balance = RU256::from(10u128.pow(20));
+        cache_db.insert_account_info(caller_b160, info);
+    }
+
+    let mut evm = EVM::new();
+    evm.database(cache_db);
+    evm.env.block.number = RU256::from(block);
+
+    let balance_before = read_erc20_balance(&mut evm, token, caller)?;
+
+    evm.env.tx.caller = caller_b160;
+    evm.env.tx.transact_to = TransactTo::Call(B160::from(to.0));
+    evm.env.tx.data = data.0;
+    evm.env.tx.gas_limit = 5_000_000;
+
+    let result = evm
+        .transact_commit()
+        .map_err(|e| anyhow!("EVM execution error: {e:?}"))?;
+
+    match result {
+        ExecutionResult::Success { gas_used, .. } => {
+            let balance_after = read_erc20_balance(&mut evm, token, caller)?;
+            Ok(SimOutcome {
+                net_profit: balance_after.saturating_sub(balance_before),
+                gas_used,
+                reverted: None,
+            })
+        }
+        ExecutionResult::Revert { gas_used, output } => Ok(SimOutcome {
+            net_profit: U256::zero(),
+            gas_used,
+            reverted: Some(format!("reverted: 0x{}", hex::encode(output))),
+        }),
+        ExecutionResult::Halt { reason, gas_used } => Ok(SimOutcome {
+            net_profit: U256::zero(),
+            gas_used,
+            reverted: Some(format!("halted: {reason:?}")),
+        }),
+    }
+}
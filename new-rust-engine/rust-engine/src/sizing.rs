@@ -0,0 +1,66 @@
+// rust-engine/src/sizing.rs
+//
+// Closed-form optimal trade size for a two-pool constant-product arbitrage loop: buy on
+// pool1 (reserves Ra, Rb), sell back on pool2 (reserves Ra', Rb'), both charging fee
+// factor `gamma = 1 - fee`. Replaces guessing `optimal_amount` with the exact input that
+// maximizes net output after both legs' fees.
+
+/// Optimal input size, expected output, and gross profit (output minus input, before gas
+/// and flash-loan fees) for a two-pool loop, all in the same raw reserve units as `ra`/`rb`.
+#[derive(Debug, Clone, Copy)]
+pub struct OptimalTrade {
+    pub input_amount: f64,
+    pub expected_output: f64,
+    pub gross_profit: f64,
+}
+
+impl OptimalTrade {
+    fn zero() -> Self {
+        Self { input_amount: 0.0, expected_output: 0.0, gross_profit: 0.0 }
+    }
+}
+
+/// Solves `Δ* = (√(γ²·Ra·Rb·Ra'·Rb') − Ra·Rb') / (γ·(Rb' + γ·Rb))` for the constant-product
+/// swap formula `amountOut = γ·Δ·Rout / (Rin + γ·Δ)` applied on both legs, where `(ra, rb)`
+/// are pool1's reserves of the input/output token and `(ra_prime, rb_prime)` are pool2's
+/// reserves of the same two tokens in the same order. Returns a zero-sized trade when the
+/// radicand doesn't clear `Ra·Rb'`, i.e. there's no arbitrage once both pools' fees are
+/// accounted for.
+///
+/// `max_input` caps `Δ*` at a fraction of available liquidity (the caller's "available
+/// balance") rather than the unclamped theoretical optimum, which can otherwise imply
+/// draining a pool.
+pub fn optimal_two_pool_size(
+    ra: f64,
+    rb: f64,
+    ra_prime: f64,
+    rb_prime: f64,
+    gamma: f64,
+    max_input: f64,
+) -> OptimalTrade {
+    if ra <= 0.0 || rb <= 0.0 || ra_prime <= 0.0 || rb_prime <= 0.0 || gamma <= 0.0 || max_input <= 0.0 {
+        return OptimalTrade::zero();
+    }
+
+    let radicand = gamma * gamma * ra * rb * ra_prime * rb_prime;
+    let threshold = ra * rb_prime;
+    if radicand <= threshold {
+        return OptimalTrade::zero();
+    }
+
+    let delta = ((radicand.sqrt() - threshold) / (gamma * (rb_prime + gamma * rb))).min(max_input);
+    if delta <= 0.0 {
+        return OptimalTrade::zero();
+    }
+
+    // Leg 1: swap `delta` of the Ra-side asset into the Rb-side asset on pool1.
+    let leg1_out = gamma * delta * rb / (ra + gamma * delta);
+    // Leg 2: swap that output back through pool2 (Ra', Rb') into the Ra-side asset.
+    let leg2_out = gamma * leg1_out * ra_prime / (rb_prime + gamma * leg1_out);
+
+    OptimalTrade {
+        input_amount: delta,
+        expected_output: leg2_out,
+        gross_profit: leg2_out - delta,
+    }
+}
@@ -0,0 +1,134 @@
+// rust-engine/src/token_pricing.rs
+//
+// Decimal-aware, oracle-backed USD pricing for the scanner. `fetch_reserves` used to
+// compute `price = reserve0 / reserve1` with no regard for token decimals (meaningless
+// for an 18-decimal/6-decimal pair like WETH/USDC), and `calculate_profit`/
+// `calculate_gas_cost` hardcoded `token_price_usd = 1.0` / `eth_price = 2000.0`. This
+// module reads each token's real decimals (cached) and live Chainlink USD rates instead.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use anyhow::{anyhow, Result};
+use dashmap::DashMap;
+use ethers::prelude::*;
+
+use crate::contracts::{AggregatorV3Interface, Erc20};
+
+/// How old a Chainlink round is allowed to be before its price is rejected as stale.
+/// Most mainnet/L2 feeds heartbeat at least once an hour; this gives headroom while
+/// still catching a feed that's stopped updating entirely.
+const MAX_ROUND_AGE_SECS: u64 = 3600;
+
+/// Per-token decimals cache plus Chainlink `AggregatorV3Interface` feeds, used to
+/// normalize reserve-ratio pricing and to price profit/gas in real USD instead of
+/// assuming every token is a dollar and ETH is $2000.
+pub struct TokenPricing {
+    provider: Arc<Provider<Http>>,
+    decimals_cache: DashMap<Address, u8>,
+    /// Chainlink feed address per token this chain prices.
+    price_feeds: HashMap<Address, Address>,
+    eth_usd_feed: Address,
+}
+
+impl TokenPricing {
+    pub fn new(provider: Arc<Provider<Http>>, price_feeds: HashMap<Address, Address>, eth_usd_feed: Address) -> Self {
+        Self { provider, decimals_cache: DashMap::new(), price_feeds, eth_usd_feed }
+    }
+
+    /// `TokenPricing` pre-wired with the well-known mainnet/L2 Chainlink feeds for the
+    /// tokens `get_top_tokens` already scans — the same hardcoded-by-`chain_id` pattern
+    /// used there and for DEX addresses in `main.rs`.
+    pub fn for_chain(provider: Arc<Provider<Http>>, chain_id: u64) -> Self {
+        let (price_feeds, eth_usd_feed) = default_feeds(chain_id);
+        Self::new(provider, price_feeds, eth_usd_feed)
+    }
+
+    /// Token decimals, read once via `Erc20::decimals` and cached from then on — a
+    /// token's decimals never change after deployment, so there's no reason to pay for
+    /// a call on every quote.
+    pub async fn decimals(&self, token: Address) -> Result<u8> {
+        if let Some(cached) = self.decimals_cache.get(&token) {
+            return Ok(*cached);
+        }
+
+        let erc20 = Erc20::new(token, self.provider.clone());
+        let decimals = erc20.decimals().call().await?;
+        self.decimals_cache.insert(token, decimals);
+        Ok(decimals)
+    }
+
+    /// Spot price of `token1` in terms of `token0` (`reserve0 / reserve1`, i.e. token0 per
+    /// token1), normalized by each token's decimals
+    /// so a WETH(18)/USDC(6) pool doesn't report a price 10^12 off from the true one.
+    pub async fn normalized_price(&self, token0: Address, token1: Address, reserve0: U256, reserve1: U256) -> Result<f64> {
+        let decimals0 = self.decimals(token0).await?;
+        let decimals1 = self.decimals(token1).await?;
+        let r0 = reserve0.as_u128() as f64 / 10f64.powi(decimals0 as i32);
+        let r1 = reserve1.as_u128() as f64 / 10f64.powi(decimals1 as i32);
+        Ok(r0 / r1)
+    }
+
+    /// Live USD price for `token` off its configured Chainlink feed.
+    pub async fn token_price_usd(&self, token: Address) -> Result<f64> {
+        let feed = *self
+            .price_feeds
+            .get(&token)
+            .ok_or_else(|| anyhow!("no Chainlink feed configured for token {token:?}"))?;
+        self.feed_price_usd(feed).await
+    }
+
+    /// Live ETH/USD price off this chain's configured feed.
+    pub async fn eth_price_usd(&self) -> Result<f64> {
+        self.feed_price_usd(self.eth_usd_feed).await
+    }
+
+    async fn feed_price_usd(&self, feed: Address) -> Result<f64> {
+        let aggregator = AggregatorV3Interface::new(feed, self.provider.clone());
+        let (_, answer, _, updated_at, _) = aggregator.latest_round_data().call().await?;
+        let feed_decimals = aggregator.decimals().call().await?;
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        if now.saturating_sub(updated_at.as_u64()) > MAX_ROUND_AGE_SECS {
+            return Err(anyhow!("price feed {feed:?} is stale (last updated {updated_at})"));
+        }
+        if answer.is_negative() || answer.is_zero() {
+            return Err(anyhow!("price feed {feed:?} returned a non-positive answer"));
+        }
+
+        Ok(answer.into_raw().as_u128() as f64 / 10f64.powi(feed_decimals as i32))
+    }
+}
+
+/// Chainlink feed addresses for the tokens `get_top_tokens` scans on each supported
+/// chain, plus that chain's ETH/USD feed. Unrecognized chains get an empty table —
+/// `token_price_usd`/`eth_price_usd` then return an error and callers fall back to
+/// their old flat-constant default.
+fn default_feeds(chain_id: u64) -> (HashMap<Address, Address>, Address) {
+    match chain_id {
+        42161 => (
+            HashMap::from([
+                // USDC
+                ("0xaf88d065e77c8cC2239327C5EDb3A432268e5831".parse().unwrap(), "0x50834F3163758fcC1Df9973b6e91f0F0F0434aD3".parse().unwrap()),
+                // WETH, priced off the ETH/USD feed
+                ("0x82aF49447D8a07e3bd95BD0d56f35241523fBab1".parse().unwrap(), "0x639Fe6ab55C921f74e7fac1ee960C0B6293ba612".parse().unwrap()),
+            ]),
+            "0x639Fe6ab55C921f74e7fac1ee960C0B6293ba612".parse().unwrap(), // ETH/USD
+        ),
+        10 => (
+            HashMap::from([
+                ("0x7F5c764cBc14f9669B88837ca1490cCa17c31607".parse().unwrap(), "0x16a9FA2FDa030272Ce99B29CF780dFA30361E0f3".parse().unwrap()),
+                ("0x4200000000000000000000000000000000000006".parse().unwrap(), "0x13e3Ee699D1909E989722E753853AE30b17e08c5".parse().unwrap()),
+            ]),
+            "0x13e3Ee699D1909E989722E753853AE30b17e08c5".parse().unwrap(),
+        ),
+        8453 => (
+            HashMap::from([
+                ("0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".parse().unwrap(), "0x7e860098F58bBFC8648a4311b374B1D669a2bc6B".parse().unwrap()),
+                ("0x4200000000000000000000000000000000000006".parse().unwrap(), "0x71041dddad3595F9CEd3DcCFBe3D1F4b0a16Bb70".parse().unwrap()),
+            ]),
+            "0x71041dddad3595F9CEd3DcCFBe3D1F4b0a16Bb70".parse().unwrap(),
+        ),
+        _ => (HashMap::new(), Address::zero()),
+    }
+}
@@ -1,49 +1,62 @@
 use ethers::prelude::*;
 use ethers::signers::{LocalWallet, Signer};
+use std::collections::HashMap;
 use std::sync::Arc;
 use anyhow::Result;
 
+use crate::config::Config;
+
+type ChainClient = Arc<SignerMiddleware<Arc<Provider<Http>>, LocalWallet>>;
+
 pub struct WalletManager {
-    wallet: LocalWallet,
-    provider: Arc<Provider<Http>>,
-    client: Arc<SignerMiddleware<Arc<Provider<Http>>, LocalWallet>>,
+    address: Address,
+    clients: HashMap<String, ChainClient>,
 }
 
 impl WalletManager {
-    pub fn new() -> Result<Self> {
+    pub fn new(config: &Config) -> Result<Self> {
         let private_key = std::env::var("PRIVATE_KEY")
             .expect("PRIVATE_KEY must be set in .env file");
-        
-        let wallet = private_key.parse::<LocalWallet>()?
-            .with_chain_id(42161u64); // Arbitrum One
-        
-        let provider = Provider::<Http>::try_from(
-            std::env::var("RPC_URL").unwrap_or_else(|_| 
-                "https://arb1.arbitrum.io/rpc".to_string())
-        )?;
-        
-        let provider = Arc::new(provider);
-        let client = Arc::new(SignerMiddleware::new(
-            provider.clone(), 
-            wallet.clone()
-        ));
-        
-        Ok(Self {
-            wallet,
-            provider,
-            client,
-        })
+
+        let mut address = None;
+        let mut clients = HashMap::new();
+
+        for (chain_name, chain_config) in &config.chains {
+            if !chain_config.enabled {
+                continue;
+            }
+
+            let wallet = private_key
+                .parse::<LocalWallet>()?
+                .with_chain_id(chain_config.chain_id);
+            address.get_or_insert(wallet.address());
+
+            let provider = Arc::new(Provider::<Http>::try_from(chain_config.rpc_url.as_str())?);
+            let client = Arc::new(SignerMiddleware::new(provider, wallet));
+            clients.insert(chain_name.clone(), client);
+        }
+
+        let address = address.ok_or_else(|| {
+            anyhow::anyhow!("no enabled chains configured for WalletManager")
+        })?;
+
+        Ok(Self { address, clients })
     }
-    
+
     pub fn address(&self) -> Address {
-        self.wallet.address()
+        self.address
     }
-    
-    pub async fn get_balance(&self) -> Result<U256> {
-        Ok(self.provider.get_balance(self.wallet.address(), None).await?)
+
+    /// Returns the signer client for the given chain name (e.g. `"polygon"`, `"arbitrum"`),
+    /// so a `FlashLoanArbitrage` opportunity can be routed to the correct signer.
+    pub fn client_for(&self, chain: &str) -> Option<ChainClient> {
+        self.clients.get(chain).cloned()
     }
-    
-    pub fn client(&self) -> Arc<SignerMiddleware<Arc<Provider<Http>>, LocalWallet>> {
-        self.client.clone()
+
+    pub async fn get_balance(&self, chain: &str) -> Result<U256> {
+        let client = self
+            .client_for(chain)
+            .ok_or_else(|| anyhow::anyhow!("no signer configured for chain {chain}"))?;
+        Ok(client.get_balance(self.address, None).await?)
     }
-}
\ No newline at end of file
+}
@@ -1,10 +1,44 @@
 use anyhow::Result;
 use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
 use rust_decimal::Decimal;
-use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::Deserialize;
+use serde_json::json;
 use std::sync::Arc;
 use std::str::FromStr;
 
+/// Result of simulating a flash-loan bundle via `debug_traceCall` before broadcasting it.
+#[derive(Debug, Clone)]
+pub struct FlashLoanSimulation {
+    pub gas_used: u64,
+    /// Net change in the receiver's token balance, in human units, after the loan is repaid.
+    pub balance_delta: Decimal,
+    pub revert_reason: Option<String>,
+}
+
+impl FlashLoanSimulation {
+    pub fn is_profitable(&self, fee_percentage: Decimal, amount: Decimal, min_profit_usd: Decimal) -> bool {
+        if self.revert_reason.is_some() {
+            return false;
+        }
+        let required_repayment = amount * fee_percentage;
+        self.balance_delta - required_repayment >= min_profit_usd
+    }
+}
+
+const WEI_PER_ETHER: u128 = 1_000_000_000_000_000_000;
+
+#[derive(Debug, Deserialize)]
+struct DebugTraceCallResult {
+    #[serde(rename = "gasUsed", default)]
+    gas_used: Option<String>,
+    #[serde(default)]
+    failed: bool,
+    #[serde(rename = "returnValue", default)]
+    return_value: Option<String>,
+}
+
 // Fixed ABI format - proper JSON structure
 abigen!(
     IAaveV3Pool,
@@ -117,6 +151,101 @@ impl FlashLoanManager {
             })
     }
 
+    /// Dry-run a flash-loan arbitrage bundle against live chain state before broadcasting.
+    ///
+    /// Builds the `flashLoan` calldata for `provider` via the `IAaveV3Pool`/`IBalancerVault`
+    /// abigen bindings, wraps it in the receiver contract's execution, and traces it with
+    /// `debug_traceCall` so the caller can confirm the loan is repaid (principal + fee) and
+    /// still clears `min_profit_usd` before spending real gas.
+    pub async fn simulate_flash_loan(
+        &self,
+        provider: &FlashLoanProvider,
+        receiver_address: Address,
+        token_address: Address,
+        amount: Decimal,
+    ) -> Result<FlashLoanSimulation> {
+        let amount_raw = U256::from(
+            (amount * Decimal::from(WEI_PER_ETHER))
+                .to_u128()
+                .unwrap_or(0),
+        );
+
+        let calldata: Bytes = if provider.name == "Aave V3" {
+            let pool = IAaveV3Pool::new(
+                Address::from_str(&provider.address)?,
+                self.provider.clone(),
+            );
+            pool.flash_loan(
+                receiver_address,
+                vec![token_address],
+                vec![amount_raw],
+                vec![U256::zero()],
+                receiver_address,
+                Bytes::default(),
+                0,
+            )
+            .calldata()
+            .ok_or_else(|| anyhow::anyhow!("failed to encode Aave flashLoan calldata"))?
+        } else {
+            let vault = IBalancerVault::new(
+                Address::from_str(&provider.address)?,
+                self.provider.clone(),
+            );
+            vault
+                .flash_loan(
+                    receiver_address,
+                    vec![token_address],
+                    vec![amount_raw],
+                    Bytes::default(),
+                )
+                .calldata()
+                .ok_or_else(|| anyhow::anyhow!("failed to encode Balancer flashLoan calldata"))?
+        };
+
+        let mut tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(Address::from_str(&provider.address)?)
+            .from(receiver_address)
+            .data(calldata)
+            .into();
+        tx.set_gas(U256::from(5_000_000u64));
+
+        let trace: DebugTraceCallResult = self
+            .provider
+            .request(
+                "debug_traceCall",
+                (tx, "latest", json!({ "tracer": "callTracer" })),
+            )
+            .await?;
+
+        let gas_used = trace
+            .gas_used
+            .as_deref()
+            .and_then(|g| u64::from_str_radix(g.trim_start_matches("0x"), 16).ok())
+            .unwrap_or(0);
+
+        if trace.failed {
+            let revert_reason = trace
+                .return_value
+                .unwrap_or_else(|| "flash loan simulation reverted".to_string());
+            return Ok(FlashLoanSimulation {
+                gas_used,
+                balance_delta: Decimal::ZERO,
+                revert_reason: Some(revert_reason),
+            });
+        }
+
+        let balance_after = self.provider.get_balance(receiver_address, None).await?;
+        let balance_delta = Decimal::from_u128(balance_after.as_u128())
+            .unwrap_or(Decimal::ZERO)
+            / Decimal::from(WEI_PER_ETHER);
+
+        Ok(FlashLoanSimulation {
+            gas_used,
+            balance_delta,
+            revert_reason: None,
+        })
+    }
+
     pub async fn execute_flash_loan(
         &self,
         _provider: &FlashLoanProvider,
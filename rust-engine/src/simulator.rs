@@ -1,35 +1,202 @@
-use revm::{Database, EVM, Env};
+use anyhow::{anyhow, Result};
 use ethers::prelude::*;
+use revm::db::{CacheDB, Database};
+use revm::primitives::{AccountInfo, Bytecode, ExecutionResult, TransactTo, B160, B256, U256 as RU256};
+use revm::EVM;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-pub struct LocalSimulator {
-    evm: EVM<()>,
+/// One leg of an arbitrage bundle: a flash-loan funded buy-swap, sell-swap, and repayment,
+/// simulated atomically in a single EVM instance before it is ever broadcast.
+#[derive(Debug, Clone)]
+pub struct Opportunity {
+    pub flash_loan_pool: Address,
+    pub router_a: Address,
+    pub router_b: Address,
+    pub token_borrow: Address,
+    pub token_mid: Address,
+    pub amount: U256,
+    pub calldata: Bytes,
+    pub caller: Address,
 }
 
-impl LocalSimulator {
-    pub fn new() -> Self {
+/// Result of simulating an `Opportunity`'s full bundle in-process.
+pub struct SimResult {
+    pub net_profit: U256,
+    pub gas_used: u64,
+    pub reverted: Option<String>,
+}
+
+/// A `revm::Database` that lazily fetches account info, storage, code, and block hashes
+/// from a live `Provider` over JSON-RPC and memoizes everything in-memory, so a single
+/// simulation only ever fetches each slot/account once.
+pub struct ProviderDb {
+    provider: Arc<Provider<Http>>,
+    block: u64,
+    handle: tokio::runtime::Handle,
+    accounts: Mutex<HashMap<B160, AccountInfo>>,
+    storage: Mutex<HashMap<(B160, RU256), RU256>>,
+    block_hashes: Mutex<HashMap<u64, B256>>,
+}
+
+impl ProviderDb {
+    pub fn new(provider: Arc<Provider<Http>>, block: u64) -> Self {
         Self {
-            evm: EVM::new(),
+            provider,
+            block,
+            handle: tokio::runtime::Handle::current(),
+            accounts: Mutex::new(HashMap::new()),
+            storage: Mutex::new(HashMap::new()),
+            block_hashes: Mutex::new(HashMap::new()),
         }
     }
-    
-    pub async fn simulate(&self, opportunity: &Opportunity) -> Result<SimulationResult, Error> {
-        // Fork state at current block
-        let mut env = Env::default();
-        env.block.number = U256::from(19000000);
-        
-        // Simulate the arbitrage transaction
-        let result = self.evm.transact(env);
-        
-        Ok(SimulationResult {
-            success: result.is_ok(),
-            gas_used: result.gas_used,
-            profit: result.output.profit,
+
+    fn block_on<F: std::future::Future>(&self, fut: F) -> F::Output {
+        tokio::task::block_in_place(|| self.handle.clone().block_on(fut))
+    }
+}
+
+impl Database for ProviderDb {
+    type Error = anyhow::Error;
+
+    fn basic(&mut self, address: B160) -> Result<Option<AccountInfo>, Self::Error> {
+        if let Some(info) = self.accounts.lock().unwrap().get(&address) {
+            return Ok(Some(info.clone()));
+        }
+
+        let addr = Address::from(address.0);
+        let block = Some(BlockId::from(self.block));
+        let (balance, nonce, code) = self.block_on(async {
+            let balance = self.provider.get_balance(addr, block).await?;
+            let nonce = self.provider.get_transaction_count(addr, block).await?;
+            let code = self.provider.get_code(addr, block).await?;
+            Ok::<_, ProviderError>((balance, nonce, code))
         })
+        .map_err(|e| anyhow!("fetching account {addr:?}: {e}"))?;
+
+        let bytecode = Bytecode::new_raw(code.0.into());
+        let info = AccountInfo {
+            balance: RU256::from_limbs(balance.0),
+            nonce: nonce.as_u64(),
+            code_hash: bytecode.hash_slow(),
+            code: Some(bytecode),
+        };
+        self.accounts.lock().unwrap().insert(address, info.clone());
+        Ok(Some(info))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        // Accounts are always seeded with their code via `basic`, so a standalone
+        // code-by-hash lookup should never be required in practice.
+        Err(anyhow!("code_by_hash({code_hash}) not resolvable without an address"))
+    }
+
+    fn storage(&mut self, address: B160, index: RU256) -> Result<RU256, Self::Error> {
+        if let Some(value) = self.storage.lock().unwrap().get(&(address, index)).copied() {
+            return Ok(value);
+        }
+
+        let addr = Address::from(address.0);
+        let slot = H256::from_slice(&index.to_be_bytes::<32>());
+        let block = Some(BlockId::from(self.block));
+        let value = self
+            .block_on(self.provider.get_storage_at(addr, slot, block))
+            .map_err(|e| anyhow!("fetching storage {addr:?}[{slot:?}]: {e}"))?;
+
+        let value = RU256::from_be_bytes(value.0);
+        self.storage.lock().unwrap().insert((address, index), value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: RU256) -> Result<B256, Self::Error> {
+        let number: u64 = number.try_into().unwrap_or(0);
+        if let Some(hash) = self.block_hashes.lock().unwrap().get(&number).copied() {
+            return Ok(hash);
+        }
+
+        let block = self
+            .block_on(self.provider.get_block(number))
+            .map_err(|e| anyhow!("fetching block {number}: {e}"))?
+            .ok_or_else(|| anyhow!("block {number} not found"))?;
+        let hash = B256::from(block.hash.unwrap_or_default().0);
+        self.block_hashes.lock().unwrap().insert(number, hash);
+        Ok(hash)
     }
 }
 
-pub struct SimulationResult {
-    pub success: bool,
-    pub gas_used: u64,
-    pub profit: U256,
-}
\ No newline at end of file
+/// In-process EVM simulator for flash-loan arbitrage bundles. Runs the buy-swap,
+/// sell-swap, and flash-loan repayment atomically against state forked from a live
+/// `Provider`, so slippage curvature, transfer taxes, and revert conditions surface
+/// before any real broadcast.
+pub struct LocalSimulator {
+    provider: Arc<Provider<Http>>,
+}
+
+impl LocalSimulator {
+    pub fn new(provider: Arc<Provider<Http>>) -> Self {
+        Self { provider }
+    }
+
+    pub async fn simulate(&self, opp: &Opportunity, block: u64) -> Result<SimResult> {
+        let db = ProviderDb::new(self.provider.clone(), block);
+        let mut cache_db = CacheDB::new(db);
+
+        // Seed the caller with a large ETH balance so gas/value never blocks the trade.
+        let caller = B160::from(opp.caller.0);
+        if let Some(mut info) = cache_db.basic(caller)? {
+            info.balance = RU256::from(10u128.pow(20));
+            cache_db.insert_account_info(caller, info);
+        }
+
+        let pre_balance = cache_db
+            .basic(caller)?
+            .map(|info| info.balance)
+            .unwrap_or_default();
+
+        let mut evm = EVM::new();
+        evm.database(cache_db);
+        evm.env.block.number = RU256::from(block);
+        evm.env.tx.caller = caller;
+        evm.env.tx.transact_to = TransactTo::Call(B160::from(opp.flash_loan_pool.0));
+        evm.env.tx.data = opp.calldata.0.clone();
+        evm.env.tx.gas_limit = 5_000_000;
+
+        let result = evm
+            .transact()
+            .map_err(|e| anyhow!("EVM execution error: {e:?}"))?;
+
+        // Trust the executor's actual post-state balance rather than whatever the
+        // contract's return data claims, so a bundle that reverts internally or lies
+        // about its own profit still surfaces the real delta.
+        let post_balance = result
+            .state
+            .get(&caller)
+            .map(|account| account.info.balance)
+            .unwrap_or(pre_balance);
+        let net_profit = if post_balance > pre_balance {
+            U256::from_big_endian(&(post_balance - pre_balance).to_be_bytes::<32>())
+        } else {
+            U256::zero()
+        };
+
+        match result.result {
+            ExecutionResult::Success { gas_used, .. } => {
+                Ok(SimResult {
+                    net_profit,
+                    gas_used,
+                    reverted: None,
+                })
+            }
+            ExecutionResult::Revert { gas_used, output } => Ok(SimResult {
+                net_profit: U256::zero(),
+                gas_used,
+                reverted: Some(format!("reverted: 0x{}", hex::encode(output))),
+            }),
+            ExecutionResult::Halt { reason, gas_used } => Ok(SimResult {
+                net_profit: U256::zero(),
+                gas_used,
+                reverted: Some(format!("halted: {reason:?}")),
+            }),
+        }
+    }
+}
@@ -1,12 +1,187 @@
 use crate::chains::ChainManager;
-use crate::config::Config;
+use crate::config::{Config, OracleConfig};
 use crate::dexs::DexManager;
+use crate::fixed_point::decimal_to_u256;
 use crate::flashloan::FlashLoanManager;
+use crate::token_amount::TokenAmount;
 use crate::types::*;
 use anyhow::Result;
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
+use std::collections::HashMap;
 use std::sync::Arc;
 use chrono::Utc;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct OraclePriceResponse {
+    price: Decimal,
+}
+
+/// One directed edge of a `TokenGraph`: trading through `pool` (in the `token0 -> token1`
+/// direction if `forward`, else `token1 -> token0`) at `weight = -ln(effective_rate)`,
+/// where `effective_rate = (reserve_out / reserve_in) * (1 - fee)`. A cycle with negative
+/// total weight is a loop whose rates multiply out to more than 1 — a profitable cycle.
+struct GraphEdge {
+    to: usize,
+    weight: f64,
+    pool: LiquidityPool,
+    forward: bool,
+}
+
+/// Directed graph over token symbols, built fresh from one chain's pools each scan. Used
+/// to find arbitrage cycles of arbitrary length via Bellman-Ford rather than enumerating
+/// fixed-length pool tuples.
+struct TokenGraph {
+    nodes: Vec<String>,
+    index: HashMap<String, usize>,
+    edges: Vec<Vec<GraphEdge>>,
+}
+
+impl TokenGraph {
+    fn build(pools: &[LiquidityPool]) -> Self {
+        let mut nodes = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut node_of = |symbol: &str, nodes: &mut Vec<String>, index: &mut HashMap<String, usize>| -> usize {
+            if let Some(&i) = index.get(symbol) {
+                i
+            } else {
+                let i = nodes.len();
+                nodes.push(symbol.to_string());
+                index.insert(symbol.to_string(), i);
+                i
+            }
+        };
+
+        let mut edges: Vec<Vec<GraphEdge>> = Vec::new();
+        for pool in pools {
+            let a = node_of(&pool.token0.symbol, &mut nodes, &mut index);
+            let b = node_of(&pool.token1.symbol, &mut nodes, &mut index);
+            while edges.len() < nodes.len() {
+                edges.push(Vec::new());
+            }
+
+            let (amount0, amount1) = pool_reserve_amounts(pool);
+            if let Some(w) = edge_weight(amount0.to_decimal(), amount1.to_decimal(), pool.fee) {
+                edges[a].push(GraphEdge { to: b, weight: w, pool: pool.clone(), forward: true });
+            }
+            if let Some(w) = edge_weight(amount1.to_decimal(), amount0.to_decimal(), pool.fee) {
+                edges[b].push(GraphEdge { to: a, weight: w, pool: pool.clone(), forward: false });
+            }
+        }
+
+        Self { nodes, index, edges }
+    }
+
+    fn edge_between(&self, from: usize, to: usize) -> Option<&GraphEdge> {
+        self.edges[from].iter().find(|e| e.to == to)
+    }
+
+    /// Bellman-Ford from `start`: relax every edge `|V|-1` times, then do one extra pass.
+    /// If any edge still relaxes, its destination lies on (or downstream of) a negative
+    /// cycle — walk the predecessor array back `|V|` steps to guarantee landing inside the
+    /// cycle, then trace predecessors until the walk repeats a node to recover the loop.
+    fn find_negative_cycle(&self, start: usize) -> Option<Vec<usize>> {
+        let n = self.nodes.len();
+        if n == 0 {
+            return None;
+        }
+
+        let mut dist = vec![f64::INFINITY; n];
+        let mut pred: Vec<Option<usize>> = vec![None; n];
+        dist[start] = 0.0;
+
+        let mut relaxed_node = None;
+        for iteration in 0..n {
+            relaxed_node = None;
+            for u in 0..n {
+                if dist[u].is_infinite() {
+                    continue;
+                }
+                for edge in &self.edges[u] {
+                    let candidate = dist[u] + edge.weight;
+                    if candidate < dist[edge.to] - 1e-12 {
+                        dist[edge.to] = candidate;
+                        pred[edge.to] = Some(u);
+                        if iteration == n - 1 {
+                            relaxed_node = Some(edge.to);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut node = relaxed_node?;
+        for _ in 0..n {
+            node = pred[node]?;
+        }
+
+        let mut cycle = vec![node];
+        let mut current = pred[node]?;
+        while current != node {
+            cycle.push(current);
+            current = pred[current]?;
+        }
+        cycle.push(node);
+        cycle.reverse();
+        Some(cycle)
+    }
+}
+
+/// `-ln((reserve_out/reserve_in) * (1-fee))`, or `None` if the rate can't be computed
+/// (empty reserves) — such an edge is simply omitted from the graph.
+fn edge_weight(reserve_in: Decimal, reserve_out: Decimal, fee: Decimal) -> Option<f64> {
+    if reserve_in <= Decimal::ZERO || reserve_out <= Decimal::ZERO {
+        return None;
+    }
+    let rate = (reserve_out / reserve_in) * (Decimal::ONE - fee);
+    let rate = rate.to_f64()?;
+    if rate <= 0.0 {
+        return None;
+    }
+    Some(-rate.ln())
+}
+
+/// Pairs `pool`'s raw on-chain reserves with each side's own token decimals, as
+/// `TokenAmount`s, so downstream ratio math (edge weights, cross-DEX sizing) compares
+/// actual token quantities instead of raw integers that are 10^12 apart for a
+/// 6-decimal/18-decimal pair like USDC/WETH.
+pub(crate) fn pool_reserve_amounts(pool: &LiquidityPool) -> (TokenAmount, TokenAmount) {
+    (
+        TokenAmount::from_raw(decimal_to_u256(pool.reserve0), pool.token0.decimals),
+        TokenAmount::from_raw(decimal_to_u256(pool.reserve1), pool.token1.decimals),
+    )
+}
+
+/// Splits `pool`'s decimal-normalized reserves into `(reserve_quote, reserve_base)` given
+/// the pool holds `quote_symbol` on one side, or `None` if `pool` doesn't quote against
+/// `quote_symbol` at all.
+fn pool_amounts_for(pool: &LiquidityPool, quote_symbol: &str) -> Option<(TokenAmount, TokenAmount)> {
+    let (amount0, amount1) = pool_reserve_amounts(pool);
+    if pool.token0.symbol.eq_ignore_ascii_case(quote_symbol) {
+        Some((amount0, amount1))
+    } else if pool.token1.symbol.eq_ignore_ascii_case(quote_symbol) {
+        Some((amount1, amount0))
+    } else {
+        None
+    }
+}
+
+/// Converts a pool's fractional fee (e.g. `0.003`) into basis points for `trade_sizer`'s
+/// `PoolLeg`, which models fees the same way Uniswap V2 does (`fee_bps` out of 10,000).
+fn fee_to_bps(fee: Decimal) -> u32 {
+    (fee * Decimal::from(10_000)).round().to_u32().unwrap_or(0)
+}
+
+/// Result of picking a flash loan provider for a given asset/amount: which provider won,
+/// what it'll cost, and how much more it could have lent if a larger amount were needed.
+#[derive(Debug, Clone, serde::Serialize, Deserialize)]
+pub struct FlashLoanInfo {
+    pub provider: String,
+    pub fee: Decimal,
+    pub fee_percentage: Decimal,
+    pub max_amount: Decimal,
+}
 
 pub struct ArbitrageEngine {
     state: Arc<SharedState>,
@@ -33,6 +208,59 @@ impl ArbitrageEngine {
         }
     }
     
+    /// Fetch the oracle's reference price for `base`/`quote`, if an oracle is configured.
+    async fn fetch_oracle_price(&self, base: &str, quote: &str) -> Option<Decimal> {
+        let oracle = self.config.oracle.as_ref()?;
+        let url = oracle.get_fetch_url(base, quote);
+
+        let response = reqwest::Client::new()
+            .get(&url)
+            .bearer_auth(oracle.api_key())
+            .send()
+            .await
+            .ok()?;
+
+        response.json::<OraclePriceResponse>().await.ok().map(|r| r.price)
+    }
+
+    /// Compare a computed price against the oracle reference price for `base`/`quote`,
+    /// returning an adjusted confidence score, or `None` if the deviation is too large
+    /// for the opportunity to be trusted.
+    async fn check_against_oracle(
+        &self,
+        base: &str,
+        quote: &str,
+        computed_price: Decimal,
+        confidence_score: f64,
+    ) -> Option<f64> {
+        let Some(oracle) = self.config.oracle.as_ref() else {
+            return Some(confidence_score);
+        };
+
+        let Some(reference_price) = self.fetch_oracle_price(base, quote).await else {
+            // Oracle configured but unreachable: fall back to the raw confidence score
+            // rather than blocking signal generation on a transient outage.
+            return Some(confidence_score);
+        };
+
+        if reference_price == Decimal::ZERO {
+            return Some(confidence_score);
+        }
+
+        let deviation = ((computed_price - reference_price) / reference_price).abs();
+        let max_deviation = oracle.max_deviation();
+
+        if deviation > max_deviation {
+            return None;
+        }
+
+        if deviation > max_deviation / Decimal::from(2) {
+            return Some(confidence_score * 0.5);
+        }
+
+        Some(confidence_score)
+    }
+
     pub async fn scan_opportunities(&self) -> Result<Vec<ArbitrageOpportunity>> {
         let mut opportunities = Vec::new();
         
@@ -67,44 +295,161 @@ impl ArbitrageEngine {
         Ok(opportunities)
     }
     
+    /// Finds multi-hop arbitrage cycles of arbitrary length (2, 3, 4+ pools) by modeling
+    /// every pool as a pair of directed edges in a token graph and running Bellman-Ford
+    /// from each base token: a negative-weight cycle in `-ln(effective_rate)` space is
+    /// exactly a loop of trades whose product of rates exceeds 1, i.e. a profitable loop.
+    /// This replaces the old `(pool1, pool2, pool3)` brute force, which was cubic in pool
+    /// count and structurally blind to anything but 3-hop cycles.
     async fn find_triangular_arbitrage(
         &self,
         chain: &Chain,
         gas_price: Decimal,
     ) -> Result<Vec<ArbitrageOpportunity>> {
         let mut opportunities = Vec::new();
-        
-        // Get all pools for this chain
+
         let pools: Vec<LiquidityPool> = self.state.pools
             .iter()
             .filter(|p| p.chain == *chain)
             .map(|p| p.clone())
             .collect();
-        
-        // Common triangular paths: USDC -> ETH -> TOKEN -> USDC
-        let base_amount = Decimal::from(10000); // Start with $10k USDC
-        
-        for pool1 in &pools {
-            for pool2 in &pools {
-                for pool3 in &pools {
-                    if let Some(opp) = self.check_triangular_path(
-                        chain,
-                        pool1,
-                        pool2,
-                        pool3,
-                        base_amount,
-                        gas_price,
-                    ).await {
-                        if opp.net_profit_usd > self.config.min_profit_usd {
-                            opportunities.push(opp);
-                        }
-                    }
+
+        let graph = TokenGraph::build(&pools);
+        let base_amount = Decimal::from(10000); // Start with $10k notional
+
+        let mut seen_cycles: std::collections::HashSet<std::collections::BTreeSet<usize>> = std::collections::HashSet::new();
+
+        for base_symbol in ["USDC", "WETH"] {
+            let Some(&start) = graph.index.get(base_symbol) else { continue };
+            let Some(cycle) = graph.find_negative_cycle(start) else { continue };
+
+            let dedup_key: std::collections::BTreeSet<usize> = cycle.iter().copied().collect();
+            if !seen_cycles.insert(dedup_key) {
+                continue;
+            }
+
+            if let Some(opp) = self
+                .settle_cycle(chain, &graph, &cycle, base_amount, gas_price)
+                .await
+            {
+                if opp.net_profit_usd > self.config.min_profit_usd {
+                    opportunities.push(opp);
                 }
             }
         }
-        
+
         Ok(opportunities)
     }
+
+    /// Replays a cycle of node indices (as found by `TokenGraph::find_negative_cycle`)
+    /// through the real per-pool `SwapCurve::output_amount` to get actual trade amounts,
+    /// rather than trusting the `-ln(rate)` approximation used to find the cycle, then
+    /// applies the same flash-loan/gas net-profit filter the old 3-hop path used.
+    async fn settle_cycle(
+        &self,
+        chain: &Chain,
+        graph: &TokenGraph,
+        cycle: &[usize],
+        initial_amount: Decimal,
+        gas_price: Decimal,
+    ) -> Option<ArbitrageOpportunity> {
+        let hops = cycle.len() - 1;
+        if hops < 2 {
+            return None;
+        }
+
+        let mut path = Vec::with_capacity(hops);
+        let mut amount = initial_amount;
+
+        for window in cycle.windows(2) {
+            let (from, to) = (window[0], window[1]);
+            let edge = graph.edge_between(from, to)?;
+            let pool = &edge.pool;
+            let (token_in, token_out) = if edge.forward {
+                (&pool.token0, &pool.token1)
+            } else {
+                (&pool.token1, &pool.token0)
+            };
+            let (amount0, amount1) = pool_reserve_amounts(pool);
+            let (reserve_in, reserve_out) = if edge.forward {
+                (amount0, amount1)
+            } else {
+                (amount1, amount0)
+            };
+
+            let amount_out_typed = self.dex_manager.calculate_output_amount_typed(
+                TokenAmount::from_decimal(amount, reserve_in.decimals),
+                reserve_in,
+                reserve_out,
+                pool.fee,
+                &pool.curve,
+            );
+            let amount_out = amount_out_typed.to_decimal();
+            if amount_out <= Decimal::ZERO {
+                return None;
+            }
+
+            path.push(TradePath {
+                dex: pool.dex.clone(),
+                pool_address: pool.address.clone(),
+                token_in: token_in.symbol.clone(),
+                token_out: token_out.symbol.clone(),
+                amount_in: amount,
+                amount_out,
+            });
+
+            amount = amount_out;
+        }
+
+        let final_amount = amount;
+        let gross_profit = final_amount - initial_amount;
+        if gross_profit <= Decimal::ZERO {
+            return None;
+        }
+
+        let flash_provider = self.flash_loan_manager.get_best_provider(chain, initial_amount)?;
+        let flash_fee = self.flash_loan_manager.calculate_flash_loan_fee(flash_provider, initial_amount).await;
+
+        // Gas scales with hop count: ~150k/swap plus a flat flash-loan overhead, matching
+        // the old fixed 500k estimate for the 3-hop case.
+        let gas_units = Decimal::from(150_000 * hops as i64 + 50_000);
+        let gas_cost_eth = gas_units * gas_price / Decimal::from(1_000_000_000);
+        let eth_price = Decimal::from(2500);
+        let gas_cost_usd = gas_cost_eth * eth_price;
+
+        let net_profit = gross_profit - flash_fee - gas_cost_usd;
+        if net_profit <= Decimal::ZERO {
+            return None;
+        }
+
+        let roi = (net_profit / initial_amount) * Decimal::from(100);
+        let implied_price = final_amount / initial_amount;
+        let confidence_score = self
+            .check_against_oracle(&path[0].token_in, &path[path.len() - 1].token_out, implied_price, 0.85)
+            .await?;
+
+        Some(ArbitrageOpportunity {
+            id: format!("{}", blake3::hash(format!("{:?}{}", chain, Utc::now()).as_bytes())),
+            chain: *chain,
+            opportunity_type: "Triangular".to_string(),
+            path,
+            initial_amount,
+            amount_raw: crate::hex_amount::decimal_to_raw(initial_amount, 18),
+            final_amount,
+            gross_profit,
+            flash_loan_provider: flash_provider.name.clone(),
+            flash_loan_fee: flash_fee,
+            flash_loan_fee_percentage: flash_provider.fee_percentage,
+            gas_cost_usd,
+            net_profit_usd: net_profit,
+            roi_percentage: roi,
+            confidence_score,
+            timestamp: Utc::now(),
+            avg_execution_price: Some(implied_price),
+            slippage_pct: None,
+            pending_tx_hash: None,
+        })
+    }
     
     async fn find_cross_dex_arbitrage(
         &self,
@@ -122,13 +467,24 @@ impl ArbitrageEngine {
         
         // Look for price discrepancies
         for price1 in &prices {
+            // Feed every observed quote into this pair's rolling volatility tracker before
+            // gating on it, so the threshold below reflects history up to and including
+            // this scan rather than lagging one cycle behind.
+            self.state
+                .price_volatility
+                .entry(price1.token_pair.clone())
+                .or_default()
+                .observe(price1.price.to_f64().unwrap_or(0.0));
+
             for price2 in &prices {
                 if price1.token_pair == price2.token_pair && price1.source != price2.source {
                     let price_diff = (price1.price - price2.price).abs();
                     let avg_price = (price1.price + price2.price) / Decimal::from(2);
                     let spread_pct = (price_diff / avg_price) * Decimal::from(100);
-                    
-                    if spread_pct > Decimal::from_str_exact("0.5").unwrap() {
+
+                    let required_spread = self.required_spread_pct(&price1.token_pair);
+
+                    if spread_pct > required_spread {
                         if let Some(opp) = self.create_cross_dex_opportunity(
                             chain,
                             price1,
@@ -147,105 +503,24 @@ impl ArbitrageEngine {
         Ok(opportunities)
     }
     
-    async fn check_triangular_path(
-        &self,
-        chain: &Chain,
-        pool1: &LiquidityPool,
-        pool2: &LiquidityPool,
-        pool3: &LiquidityPool,
-        initial_amount: Decimal,
-        gas_price: Decimal,
-    ) -> Option<ArbitrageOpportunity> {
-        // Simulate the trades
-        let amount1 = self.dex_manager.calculate_output_amount(
-            initial_amount,
-            pool1.reserve0,
-            pool1.reserve1,
-            pool1.fee,
-        );
-        
-        let amount2 = self.dex_manager.calculate_output_amount(
-            amount1,
-            pool2.reserve0,
-            pool2.reserve1,
-            pool2.fee,
-        );
-        
-        let final_amount = self.dex_manager.calculate_output_amount(
-            amount2,
-            pool3.reserve0,
-            pool3.reserve1,
-            pool3.fee,
-        );
-        
-        // Calculate profit
-        let gross_profit = final_amount - initial_amount;
-        
-        if gross_profit <= Decimal::ZERO {
-            return None;
-        }
-        
-        // Get best flash loan provider
-        let flash_provider = self.flash_loan_manager.get_best_provider(chain, initial_amount)?;
-        let flash_fee = self.flash_loan_manager.calculate_flash_loan_fee(flash_provider, initial_amount);
-        
-        // Calculate gas cost
-        let gas_units = Decimal::from(500000); // Estimated gas for 3 swaps + flash loan
-        let gas_cost_eth = gas_units * gas_price / Decimal::from(1_000_000_000);
-        let eth_price = Decimal::from(2500);
-        let gas_cost_usd = gas_cost_eth * eth_price;
-        
-        let net_profit = gross_profit - flash_fee - gas_cost_usd;
-        let roi = (net_profit / initial_amount) * Decimal::from(100);
-        
-        if net_profit > Decimal::ZERO {
-            Some(ArbitrageOpportunity {
-                id: format!("{}", blake3::hash(format!("{:?}{}", chain, Utc::now()).as_bytes())),
-                chain: *chain,
-                opportunity_type: "Triangular".to_string(),
-                path: vec![
-                    TradePath {
-                        dex: pool1.dex.clone(),
-                        pool_address: pool1.address.clone(),
-                        token_in: pool1.token0.symbol.clone(),
-                        token_out: pool1.token1.symbol.clone(),
-                        amount_in: initial_amount,
-                        amount_out: amount1,
-                    },
-                    TradePath {
-                        dex: pool2.dex.clone(),
-                        pool_address: pool2.address.clone(),
-                        token_in: pool2.token0.symbol.clone(),
-                        token_out: pool2.token1.symbol.clone(),
-                        amount_in: amount1,
-                        amount_out: amount2,
-                    },
-                    TradePath {
-                        dex: pool3.dex.clone(),
-                        pool_address: pool3.address.clone(),
-                        token_in: pool3.token0.symbol.clone(),
-                        token_out: pool3.token1.symbol.clone(),
-                        amount_in: amount2,
-                        amount_out: final_amount,
-                    },
-                ],
-                initial_amount,
-                final_amount,
-                gross_profit,
-                flash_loan_provider: flash_provider.name.clone(),
-                flash_loan_fee: flash_fee,
-                flash_loan_fee_percentage: flash_provider.fee_percentage,
-                gas_cost_usd,
-                net_profit_usd: net_profit,
-                roi_percentage: roi,
-                confidence_score: 0.85,
-                timestamp: Utc::now(),
-            })
-        } else {
-            None
-        }
+    /// The minimum cross-DEX spread (percentage) required before `find_cross_dex_arbitrage`
+    /// will even look at a discrepancy on `token_pair`: `config.min_spread_pct`, widened
+    /// proportionally to that pair's own observed inter-venue volatility. A pair whose
+    /// quotes normally agree tightly gets the configured floor; a pair that's historically
+    /// noisy between venues needs a wider gap before it's trusted as real arbitrage rather
+    /// than quote jitter.
+    fn required_spread_pct(&self, token_pair: &str) -> Decimal {
+        let relative_std_dev = self
+            .state
+            .price_volatility
+            .get(token_pair)
+            .map(|tracker| tracker.relative_std_dev())
+            .unwrap_or(0.0);
+
+        let volatility_multiplier = Decimal::from_f64_retain(1.0 + relative_std_dev * 100.0).unwrap_or(Decimal::ONE);
+        self.config.min_spread_pct * volatility_multiplier
     }
-    
+
     async fn create_cross_dex_opportunity(
         &self,
         chain: &Chain,
@@ -253,23 +528,128 @@ impl ArbitrageEngine {
         price2: &PriceData,
         gas_price: Decimal,
     ) -> Option<ArbitrageOpportunity> {
-        let initial_amount = Decimal::from(10000);
-        
         // Determine buy and sell prices
         let (buy_price, buy_source, sell_price, sell_source) = if price1.price < price2.price {
             (price1.price, &price1.source, price2.price, &price2.source)
         } else {
             (price2.price, &price2.source, price1.price, &price1.source)
         };
-        
-        // Calculate profit
-        let tokens_bought = initial_amount / buy_price;
-        let final_amount = tokens_bought * sell_price;
+
+        // Shave `safety_margin_pct` off the sell side before computing profit, so the
+        // opportunity only clears when it'd still be profitable after an adverse move of
+        // that size between observation and fill.
+        let sell_price = sell_price * (Decimal::ONE - self.config.safety_margin_pct / Decimal::from(100));
+
+        let base_symbol = price1.token_pair.split('/').next().unwrap_or(&price1.token_pair);
+        let buy_pool = self.find_pool_for(chain, buy_source, base_symbol);
+        let sell_pool = self.find_pool_for(chain, sell_source, base_symbol);
+
+        // Route through the real x*y=k curve when both venues' pools are known, sizing
+        // the trade with the closed-form two-pool optimum or, for other curves, the same
+        // L-BFGS solver used elsewhere to size a path
+        // (`trade_sizer::solve_optimal_trade_size`) instead of assuming a flat $10k fills
+        // at the quoted spot price with no slippage. Falls back to the old linear
+        // approximation when a pool can't be resolved for one of the two venues.
+        let (initial_amount, tokens_bought, final_amount, depth_aware) =
+            match (&buy_pool, &sell_pool) {
+                (Some(buy_pool), Some(sell_pool)) => {
+                    // Pools holding an LSD (wstETH, rETH, cbETH, ...) accrue value against
+                    // their underlying over time; pricing them as a flat 1:1 pair would
+                    // flag that steady accrual as a permanent, bogus arbitrage spread. Skip
+                    // the pair outright if a rate-bearing pool's provider call fails rather
+                    // than price it at a stale or implicit 1:1 rate.
+                    let (buy_reserve0, buy_reserve1) = match self.dex_manager.rate_adjusted_reserves(chain, buy_pool).await {
+                        Some(reserves) => reserves,
+                        None => {
+                            tracing::warn!("Skipping {}: rate provider call failed for {} pool", price1.token_pair, buy_pool.dex);
+                            return None;
+                        }
+                    };
+                    let (sell_reserve0, sell_reserve1) = match self.dex_manager.rate_adjusted_reserves(chain, sell_pool).await {
+                        Some(reserves) => reserves,
+                        None => {
+                            tracing::warn!("Skipping {}: rate provider call failed for {} pool", price1.token_pair, sell_pool.dex);
+                            return None;
+                        }
+                    };
+                    let buy_pool = &LiquidityPool { reserve0: buy_reserve0, reserve1: buy_reserve1, ..buy_pool.clone() };
+                    let sell_pool = &LiquidityPool { reserve0: sell_reserve0, reserve1: sell_reserve1, ..sell_pool.clone() };
+
+                    let (buy_reserve_quote, buy_reserve_base) = pool_amounts_for(buy_pool, "USDC")?;
+                    let (sell_reserve_base, sell_reserve_quote) = pool_amounts_for(sell_pool, "USDC").map(|(q, b)| (b, q))?;
+
+                    let path = [
+                        crate::trade_sizer::PoolLeg {
+                            reserve_in: buy_reserve_quote.to_decimal().to_f64().unwrap_or(0.0),
+                            reserve_out: buy_reserve_base.to_decimal().to_f64().unwrap_or(0.0),
+                            fee_bps: fee_to_bps(buy_pool.fee),
+                        },
+                        crate::trade_sizer::PoolLeg {
+                            reserve_in: sell_reserve_base.to_decimal().to_f64().unwrap_or(0.0),
+                            reserve_out: sell_reserve_quote.to_decimal().to_f64().unwrap_or(0.0),
+                            fee_bps: fee_to_bps(sell_pool.fee),
+                        },
+                    ];
+
+                    // Both legs are plain constant-product: the profit-maximizing input has
+                    // a closed-form solution, so use it directly instead of paying for the
+                    // L-BFGS solver's iterations. Any other curve (StableSwap, Weighted)
+                    // falls back to the general numeric solver below.
+                    let sized = if matches!(buy_pool.curve, crate::swap_curve::CurveType::ConstantProduct)
+                        && matches!(sell_pool.curve, crate::swap_curve::CurveType::ConstantProduct)
+                    {
+                        crate::trade_sizer::solve_two_pool_closed_form(path[0], path[1])
+                    } else {
+                        crate::trade_sizer::solve_optimal_trade_size(&path)
+                    };
+                    let initial_amount = Decimal::from_f64_retain(sized.optimal_amount).unwrap_or(Decimal::ZERO);
+                    if initial_amount <= Decimal::ZERO {
+                        return None;
+                    }
+
+                    // Replay through the pools' real `SwapCurve`s for the reported amounts,
+                    // rather than trusting the solver's internal constant-product-only math.
+                    let tokens_bought_typed = self.dex_manager.calculate_output_amount_typed(
+                        TokenAmount::from_decimal(initial_amount, buy_reserve_quote.decimals),
+                        buy_reserve_quote,
+                        buy_reserve_base,
+                        buy_pool.fee,
+                        &buy_pool.curve,
+                    );
+                    let final_amount_typed = self.dex_manager.calculate_output_amount_typed(
+                        tokens_bought_typed,
+                        sell_reserve_base,
+                        sell_reserve_quote,
+                        sell_pool.fee,
+                        &sell_pool.curve,
+                    );
+                    (initial_amount, tokens_bought_typed.to_decimal(), final_amount_typed.to_decimal(), true)
+                }
+                _ => {
+                    let initial_amount = Decimal::from(10000);
+                    let tokens_bought = initial_amount / buy_price;
+                    let final_amount = tokens_bought * sell_price;
+                    (initial_amount, tokens_bought, final_amount, false)
+                }
+            };
+
         let gross_profit = final_amount - initial_amount;
+        let (avg_execution_price, slippage_pct) = if depth_aware && initial_amount > Decimal::ZERO {
+            let avg_price = final_amount / initial_amount;
+            let spot_price = sell_price / buy_price;
+            let slippage = if spot_price > Decimal::ZERO {
+                ((spot_price - avg_price) / spot_price * Decimal::from(100)).max(Decimal::ZERO)
+            } else {
+                Decimal::ZERO
+            };
+            (Some(avg_price), Some(slippage))
+        } else {
+            (None, None)
+        };
         
         // Get flash loan details
         let flash_provider = self.flash_loan_manager.get_best_provider(chain, initial_amount)?;
-        let flash_fee = self.flash_loan_manager.calculate_flash_loan_fee(flash_provider, initial_amount);
+        let flash_fee = self.flash_loan_manager.calculate_flash_loan_fee(flash_provider, initial_amount).await;
         
         // Calculate gas cost
         let gas_units = Decimal::from(300000);
@@ -279,8 +659,12 @@ impl ArbitrageEngine {
         
         let net_profit = gross_profit - flash_fee - gas_cost_usd;
         let roi = (net_profit / initial_amount) * Decimal::from(100);
-        
+
         if net_profit > Decimal::ZERO {
+            let confidence_score = self
+                .check_against_oracle("USDC", &price1.token_pair, buy_price, 0.75)
+                .await?;
+
             Some(ArbitrageOpportunity {
                 id: format!("{}", blake3::hash(format!("{:?}{}", chain, Utc::now()).as_bytes())),
                 chain: *chain,
@@ -304,6 +688,7 @@ impl ArbitrageEngine {
                     },
                 ],
                 initial_amount,
+                amount_raw: crate::hex_amount::decimal_to_raw(initial_amount, 18),
                 final_amount,
                 gross_profit,
                 flash_loan_provider: flash_provider.name.clone(),
@@ -312,13 +697,32 @@ impl ArbitrageEngine {
                 gas_cost_usd,
                 net_profit_usd: net_profit,
                 roi_percentage: roi,
-                confidence_score: 0.75,
+                confidence_score,
                 timestamp: Utc::now(),
+                avg_execution_price,
+                slippage_pct,
+                pending_tx_hash: None,
             })
         } else {
             None
         }
     }
+
+    /// Finds a pool on `chain` whose DEX name matches `source` (case-insensitive) and
+    /// whose tokens are `base_symbol`/`USDC`, for depth-aware cross-DEX sizing. Returns
+    /// `None` if no such pool is tracked, in which case the caller falls back to pricing
+    /// off the quoted spot price with no slippage.
+    fn find_pool_for(&self, chain: &Chain, source: &str, base_symbol: &str) -> Option<LiquidityPool> {
+        self.state.pools.iter().find_map(|entry| {
+            let pool = entry.value();
+            if pool.chain != *chain || !pool.dex.eq_ignore_ascii_case(source) {
+                return None;
+            }
+            let has_base = pool.token0.symbol.eq_ignore_ascii_case(base_symbol) || pool.token1.symbol.eq_ignore_ascii_case(base_symbol);
+            let has_quote = pool.token0.symbol.eq_ignore_ascii_case("USDC") || pool.token1.symbol.eq_ignore_ascii_case("USDC");
+            (has_base && has_quote).then(|| pool.clone())
+        })
+    }
 }
 
 use rust_decimal::prelude::FromStr;
\ No newline at end of file
@@ -0,0 +1,195 @@
+//! Throughput/latency benchmark for `ArbitrageEngine::scan_opportunities` — the scan-cycle
+//! primitive `main`'s arbitrage loop drives on a timer. (`src/scanner.rs`'s `Scanner` wraps
+//! a fan-out over this same idea but depends on types that don't exist in this tree yet, so
+//! this benchmark drives the real engine directly instead.)
+//!
+//! Populates `SharedState` with a configurable number of synthetic cross-DEX price pairs,
+//! applies a small random walk to them every cycle, then repeatedly calls
+//! `scan_opportunities` and reports scans/sec, per-cycle latency percentiles, and
+//! opportunities/sec. Useful for tuning `scan_interval_ms` and catching regressions in the
+//! cross-DEX comparison fan-out before they ship.
+//!
+//! Usage: `cargo run --release --bin bench -- --pairs 200 --iterations 500`
+
+#[path = "../types.rs"] mod types;
+#[path = "../config.rs"] mod config;
+#[path = "../chains.rs"] mod chains;
+#[path = "../dexs.rs"] mod dexs;
+#[path = "../flashloan.rs"] mod flashloan;
+#[path = "../arbitrage.rs"] mod arbitrage;
+#[path = "../hex_amount.rs"] mod hex_amount;
+#[path = "../gas_tracker.rs"] mod gas_tracker;
+#[path = "../gas_oracle.rs"] mod gas_oracle;
+#[path = "../gas_calibrator.rs"] mod gas_calibrator;
+#[path = "../swap_curve.rs"] mod swap_curve;
+#[path = "../stable_swap.rs"] mod stable_swap;
+#[path = "../fixed_point.rs"] mod fixed_point;
+#[path = "../trade_sizer.rs"] mod trade_sizer;
+#[path = "../token_amount.rs"] mod token_amount;
+
+use anyhow::Result;
+use arbitrage::ArbitrageEngine;
+use chains::ChainManager;
+use chrono::Utc;
+use config::Config;
+use dashmap::DashMap;
+use dexs::DexManager;
+use flashloan::FlashLoanManager;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::RwLock;
+use types::{Chain, GasPrice, PriceData, SharedState};
+
+struct BenchArgs {
+    pairs: usize,
+    iterations: usize,
+}
+
+impl BenchArgs {
+    fn parse() -> Self {
+        let mut pairs = 50;
+        let mut iterations = 200;
+
+        let args: Vec<String> = std::env::args().collect();
+        let mut i = 1;
+        while i < args.len() {
+            match args[i].as_str() {
+                "--pairs" => {
+                    if let Some(value) = args.get(i + 1) {
+                        pairs = value.parse().unwrap_or(pairs);
+                    }
+                    i += 1;
+                }
+                "--iterations" => {
+                    if let Some(value) = args.get(i + 1) {
+                        iterations = value.parse().unwrap_or(iterations);
+                    }
+                    i += 1;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+
+        Self { pairs, iterations }
+    }
+}
+
+/// Seeds `state.prices` with `pairs` synthetic token pairs, each quoted by two sources with
+/// a small spread, so `find_cross_dex_arbitrage` has something to compare on every cycle.
+fn seed_synthetic_prices(state: &SharedState, pairs: usize) {
+    for i in 0..pairs {
+        let token_pair = format!("SYN{i}/USDC");
+        let base_price = Decimal::from(100 + (i % 500) as i64);
+
+        for (source, offset) in [("SynthDexA", 0i64), ("SynthDexB", 1i64)] {
+            state.prices.insert(
+                format!("{source}_{token_pair}"),
+                PriceData {
+                    token_pair: token_pair.clone(),
+                    price: base_price + Decimal::from(offset),
+                    liquidity: Decimal::from(1_000_000),
+                    volume_24h: Decimal::from(10_000_000),
+                    source: source.to_string(),
+                    chain: Chain::Ethereum,
+                    timestamp: Utc::now(),
+                },
+            );
+        }
+    }
+}
+
+/// Nudges every synthetic price by a small random delta, simulating a live market tick
+/// between scan cycles instead of benchmarking against a perfectly static book.
+fn walk_synthetic_prices(state: &SharedState) {
+    for mut entry in state.prices.iter_mut() {
+        if !entry.source.starts_with("SynthDex") {
+            continue;
+        }
+        let delta = Decimal::from(rand::random::<u32>() % 5) - Decimal::from(2);
+        entry.price += delta;
+        entry.timestamp = Utc::now();
+    }
+}
+
+fn percentile(sorted_ms: &[f64], pct: f64) -> f64 {
+    if sorted_ms.is_empty() {
+        return 0.0;
+    }
+    let rank = ((pct / 100.0) * (sorted_ms.len() - 1) as f64).round() as usize;
+    sorted_ms[rank.min(sorted_ms.len() - 1)]
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt().with_target(false).init();
+
+    let args = BenchArgs::parse();
+
+    let config = Config::load()?;
+
+    let state = Arc::new(SharedState {
+        prices: Arc::new(DashMap::new()),
+        pools: Arc::new(DashMap::new()),
+        gas_prices: Arc::new(DashMap::new()),
+        rebase_rates: Arc::new(DashMap::new()),
+        opportunities: Arc::new(RwLock::new(Vec::new())),
+        price_volatility: Arc::new(DashMap::new()),
+    });
+
+    state.gas_prices.insert(Chain::Ethereum, GasPrice {
+        chain: Chain::Ethereum,
+        fast: Decimal::from(30),
+        standard: Decimal::from(20),
+        slow: Decimal::from(10),
+        base_fee: Decimal::from(15),
+        priority_fee: Decimal::from(2),
+        max_fee: Decimal::from(40),
+        timestamp: Utc::now(),
+    });
+
+    seed_synthetic_prices(&state, args.pairs);
+
+    let chain_manager = Arc::new(ChainManager::new(&config).await?);
+    let dex_manager = Arc::new(DexManager::new(chain_manager.clone(), state.clone()).await?);
+    let flash_loan_manager = Arc::new(FlashLoanManager::new(&config, chain_manager.clone(), state.clone()).await?);
+    let engine = ArbitrageEngine::new(
+        state.clone(),
+        chain_manager.clone(),
+        dex_manager.clone(),
+        flash_loan_manager.clone(),
+        config.clone(),
+    );
+
+    println!("Benchmarking scan_opportunities: {} synthetic pairs, {} iterations", args.pairs, args.iterations);
+
+    let mut latencies_ms = Vec::with_capacity(args.iterations);
+    let mut total_opportunities = 0usize;
+    let run_start = Instant::now();
+
+    for _ in 0..args.iterations {
+        walk_synthetic_prices(&state);
+
+        let cycle_start = Instant::now();
+        let opportunities = engine.scan_opportunities().await?;
+        let cycle_duration = cycle_start.elapsed();
+
+        latencies_ms.push(cycle_duration.as_secs_f64() * 1000.0);
+        total_opportunities += opportunities.len();
+    }
+
+    let total_elapsed = run_start.elapsed();
+    latencies_ms.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let scans_per_sec = args.iterations as f64 / total_elapsed.as_secs_f64();
+    let opportunities_per_sec = total_opportunities as f64 / total_elapsed.as_secs_f64();
+
+    println!("scans/sec:          {scans_per_sec:.2}");
+    println!("opportunities/sec:  {opportunities_per_sec:.2}");
+    println!("cycle latency p50:  {:.2} ms", percentile(&latencies_ms, 50.0));
+    println!("cycle latency p90:  {:.2} ms", percentile(&latencies_ms, 90.0));
+    println!("cycle latency p99:  {:.2} ms", percentile(&latencies_ms, 99.0));
+
+    Ok(())
+}
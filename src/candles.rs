@@ -0,0 +1,97 @@
+// OHLCV candle aggregation over a scalar series (here `profit_usd`), with opportunity
+// count standing in for volume since there's no literal traded volume to roll up.
+
+#[derive(Debug, Clone, Copy)]
+pub struct CandleConfig {
+    pub interval_minutes: i64,
+    pub fast_period: usize,
+    pub slow_period: usize,
+}
+
+impl Default for CandleConfig {
+    fn default() -> Self {
+        Self { interval_minutes: 60, fast_period: 5, slow_period: 20 }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Candle {
+    pub bucket: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+/// Rolls `(timestamp_secs, value)` points into OHLCV bars bucketed by `interval_minutes`.
+/// Points sharing a bucket are folded in timestamp order to form open/high/low/close;
+/// `volume` is simply the point count for that bucket.
+pub fn aggregate_candles(points: &[(i64, f64)], interval_minutes: i64) -> Vec<Candle> {
+    if points.is_empty() || interval_minutes <= 0 {
+        return Vec::new();
+    }
+
+    let mut sorted = points.to_vec();
+    sorted.sort_by_key(|(ts, _)| *ts);
+
+    let interval_secs = interval_minutes * 60;
+    let mut candles: Vec<Candle> = Vec::new();
+
+    for (ts, value) in sorted {
+        let bucket = ts / interval_secs;
+
+        match candles.last_mut() {
+            Some(candle) if candle.bucket == bucket => {
+                candle.high = candle.high.max(value);
+                candle.low = candle.low.min(value);
+                candle.close = value;
+                candle.volume += 1.0;
+            }
+            _ => candles.push(Candle {
+                bucket,
+                open: value,
+                high: value,
+                low: value,
+                close: value,
+                volume: 1.0,
+            }),
+        }
+    }
+
+    candles
+}
+
+/// Simple moving average of `candles[..=index]`'s close over the trailing `period` bars
+/// (fewer than `period` bars available just averages what exists).
+pub fn sma(candles: &[Candle], index: usize, period: usize) -> f64 {
+    let start = index + 1 - period.min(index + 1);
+    let window = &candles[start..=index];
+    window.iter().map(|c| c.close).sum::<f64>() / window.len() as f64
+}
+
+/// Exponential moving average series over `candles`' closes, seeded from the first `period`
+/// bars' SMA per the standard recurrence `ema_t = alpha*x_t + (1-alpha)*ema_{t-1}`.
+pub fn ema_series(candles: &[Candle], period: usize) -> Vec<f64> {
+    if candles.is_empty() {
+        return Vec::new();
+    }
+
+    let alpha = 2.0 / (period as f64 + 1.0);
+    let seed_len = period.min(candles.len());
+    let seed = candles[..seed_len].iter().map(|c| c.close).sum::<f64>() / seed_len as f64;
+
+    let mut result = Vec::with_capacity(candles.len());
+    let mut ema = seed;
+
+    for (i, candle) in candles.iter().enumerate() {
+        if i < seed_len {
+            result.push(seed);
+        } else {
+            ema = alpha * candle.close + (1.0 - alpha) * ema;
+            result.push(ema);
+        }
+    }
+
+    result
+}
@@ -9,66 +9,175 @@ use chrono::Utc;
 
 pub struct ChainManager {
     providers: HashMap<Chain, Arc<Provider<Http>>>,
+    ws_providers: HashMap<Chain, Arc<Provider<Ws>>>,
+    use_pending_tx_stream: HashMap<Chain, bool>,
 }
 
 impl ChainManager {
     pub async fn new(config: &Config) -> Result<Self> {
         let mut providers = HashMap::new();
-        
-        // Initialize Ethereum provider
-        if let Some(chain_config) = config.chains.get("ethereum") {
-            if chain_config.enabled {
-                let provider = Provider::<Http>::try_from(&chain_config.rpc_url)?;
-                providers.insert(Chain::Ethereum, Arc::new(provider));
-            }
-        }
-        
-        // Initialize BSC provider
-        if let Some(chain_config) = config.chains.get("bsc") {
-            if chain_config.enabled {
-                let provider = Provider::<Http>::try_from(&chain_config.rpc_url)?;
-                providers.insert(Chain::BinanceSmartChain, Arc::new(provider));
-            }
-        }
-        
-        // Initialize Polygon provider
-        if let Some(chain_config) = config.chains.get("polygon") {
-            if chain_config.enabled {
-                let provider = Provider::<Http>::try_from(&chain_config.rpc_url)?;
-                providers.insert(Chain::Polygon, Arc::new(provider));
+        let mut ws_providers = HashMap::new();
+        let mut use_pending_tx_stream = HashMap::new();
+
+        let chain_entries = [
+            ("ethereum", Chain::Ethereum),
+            ("bsc", Chain::BinanceSmartChain),
+            ("polygon", Chain::Polygon),
+            ("arbitrum", Chain::Arbitrum),
+        ];
+
+        for (key, chain) in chain_entries {
+            let Some(chain_config) = config.chains.get(key) else { continue };
+            if !chain_config.enabled {
+                continue;
             }
-        }
-        
-        // Initialize Arbitrum provider
-        if let Some(chain_config) = config.chains.get("arbitrum") {
-            if chain_config.enabled {
-                let provider = Provider::<Http>::try_from(&chain_config.rpc_url)?;
-                providers.insert(Chain::Arbitrum, Arc::new(provider));
+
+            let provider = Provider::<Http>::try_from(&chain_config.rpc_url)?;
+            providers.insert(chain, Arc::new(provider));
+
+            if let Some(ws_url) = &chain_config.ws_url {
+                let ws = Provider::<Ws>::connect(ws_url).await?;
+                ws_providers.insert(chain, Arc::new(ws));
             }
+
+            use_pending_tx_stream.insert(chain, chain_config.use_pending_tx_stream);
         }
-        
-        Ok(Self { providers })
+
+        Ok(Self { providers, ws_providers, use_pending_tx_stream })
     }
-    
+
     pub fn get_provider(&self, chain: &Chain) -> Option<Arc<Provider<Http>>> {
         self.providers.get(chain).cloned()
     }
+
+    pub fn get_ws_provider(&self, chain: &Chain) -> Option<Arc<Provider<Ws>>> {
+        self.ws_providers.get(chain).cloned()
+    }
+
+    /// Whether this chain is configured to stream `newPendingTransactions` over its
+    /// WebSocket provider rather than polling `txpool_content`/new blocks. Always `false`
+    /// if no WebSocket provider is available for the chain.
+    pub fn uses_pending_tx_stream(&self, chain: &Chain) -> bool {
+        self.ws_providers.contains_key(chain)
+            && *self.use_pending_tx_stream.get(chain).unwrap_or(&false)
+    }
     
     pub async fn get_gas_price(&self, chain: &Chain) -> Result<GasPrice> {
         let provider = self.get_provider(chain)
             .ok_or_else(|| anyhow::anyhow!("Provider not found for chain {:?}", chain))?;
-        
-        let gas_price = provider.get_gas_price().await?;
-        let gas_price_gwei = ethers::utils::format_units(gas_price, "gwei")?;
-        let gas_decimal = Decimal::from_str_exact(&gas_price_gwei)?;
-        
-        Ok(GasPrice {
-            chain: *chain,
-            fast: gas_decimal * Decimal::from_str_exact("1.2")?,
-            standard: gas_decimal,
-            slow: gas_decimal * Decimal::from_str_exact("0.8")?,
-            timestamp: Utc::now(),
-        })
+
+        let block = provider.get_block(BlockNumber::Latest).await?
+            .ok_or_else(|| anyhow::anyhow!("Latest block not found for chain {:?}", chain))?;
+
+        match block.base_fee_per_gas {
+            Some(base_fee_wei) => {
+                // Report the *predicted next* base fee rather than the current block's,
+                // so downstream profit calculations burn the base-fee portion they'll
+                // actually pay instead of the one already mined.
+                let predicted_base_fee_wei =
+                    Self::next_base_fee(base_fee_wei, block.gas_used, block.gas_limit);
+                let base_fee = Self::wei_to_gwei(predicted_base_fee_wei)?;
+                let priority_fee = self.suggested_priority_fee(&provider).await?;
+
+                // Tip scales per speed tier; the base fee itself is tier-independent since
+                // every tier burns the same protocol-mandated amount.
+                let tip_slow = priority_fee * Decimal::from_str_exact("0.8")?;
+                let tip_standard = priority_fee;
+                let tip_fast = priority_fee * Decimal::from_str_exact("1.5")?;
+
+                Ok(GasPrice {
+                    chain: *chain,
+                    fast: base_fee * Decimal::from(2) + tip_fast,
+                    standard: base_fee * Decimal::from(2) + tip_standard,
+                    slow: base_fee * Decimal::from(2) + tip_slow,
+                    base_fee,
+                    priority_fee: tip_standard,
+                    max_fee: base_fee * Decimal::from(2) + tip_standard,
+                    timestamp: Utc::now(),
+                })
+            }
+            None => {
+                // Pre-London chain: no base fee in the block header, fall back to the
+                // legacy `eth_gasPrice` tiering.
+                let gas_price = provider.get_gas_price().await?;
+                let gas_decimal = Self::wei_to_gwei(gas_price)?;
+
+                Ok(GasPrice {
+                    chain: *chain,
+                    fast: gas_decimal * Decimal::from_str_exact("1.2")?,
+                    standard: gas_decimal,
+                    slow: gas_decimal * Decimal::from_str_exact("0.8")?,
+                    base_fee: gas_decimal,
+                    priority_fee: Decimal::ZERO,
+                    max_fee: gas_decimal * Decimal::from_str_exact("1.2")?,
+                    timestamp: Utc::now(),
+                })
+            }
+        }
+    }
+
+    fn wei_to_gwei(wei: U256) -> Result<Decimal> {
+        let gwei_str = ethers::utils::format_units(wei, "gwei")?;
+        Ok(Decimal::from_str_exact(&gwei_str)?)
+    }
+
+    /// Derives a priority-fee tip from recent `eth_feeHistory` reward percentiles (the
+    /// 50th percentile over the last 10 blocks), which is a steadier signal than a single
+    /// `eth_maxPriorityFeePerGas` call on chains that don't implement that RPC method.
+    async fn suggested_priority_fee(&self, provider: &Provider<Http>) -> Result<Decimal> {
+        let fee_history = provider
+            .fee_history(10u64, BlockNumber::Latest, &[50.0])
+            .await;
+
+        match fee_history {
+            Ok(history) => {
+                let rewards: Vec<U256> = history
+                    .reward
+                    .into_iter()
+                    .filter_map(|r| r.into_iter().next())
+                    .collect();
+
+                if rewards.is_empty() {
+                    return Ok(Decimal::ZERO);
+                }
+
+                let sum: U256 = rewards.iter().fold(U256::zero(), |acc, r| acc + r);
+                let avg = sum / U256::from(rewards.len() as u64);
+                Self::wei_to_gwei(avg)
+            }
+            Err(_) => {
+                // Fall back to the single-value RPC method if `eth_feeHistory` isn't
+                // supported by this node.
+                match provider.request::<_, U256>("eth_maxPriorityFeePerGas", ()).await {
+                    Ok(tip) => Self::wei_to_gwei(tip),
+                    Err(_) => Ok(Decimal::ZERO),
+                }
+            }
+        }
+    }
+
+    /// Computes the protocol-mandated base fee for the block *after* `parent_base`, given
+    /// how much gas the parent block used relative to its target. Follows EIP-1559's
+    /// recurrence exactly, clamped to a maximum ±1/8 change per block.
+    pub fn next_base_fee(parent_base: U256, parent_gas_used: U256, parent_gas_limit: U256) -> U256 {
+        let gas_target = parent_gas_limit / 2; // elasticity multiplier = 2
+        if gas_target.is_zero() {
+            return parent_base;
+        }
+
+        if parent_gas_used == gas_target {
+            return parent_base;
+        }
+
+        if parent_gas_used > gas_target {
+            let gas_used_delta = parent_gas_used - gas_target;
+            let base_fee_delta = (parent_base * gas_used_delta / gas_target / 8).max(U256::one());
+            parent_base + base_fee_delta
+        } else {
+            let gas_used_delta = gas_target - parent_gas_used;
+            let base_fee_delta = parent_base * gas_used_delta / gas_target / 8;
+            parent_base.saturating_sub(base_fee_delta)
+        }
     }
     
     pub async fn get_block_number(&self, chain: &Chain) -> Result<u64> {
@@ -12,6 +12,68 @@ pub struct Config {
     pub max_gas_price_gwei: Decimal,
     pub scan_interval_ms: u64,
     pub websocket_endpoints: Vec<String>,
+    pub oracle: Option<OracleConfig>,
+    /// Minimum cross-DEX spread (as a percentage, e.g. `0.5` == 0.5%) required before an
+    /// opportunity is even considered — replaces the `0.5%` literal that used to be
+    /// baked into `find_cross_dex_arbitrage`.
+    #[serde(default = "default_min_spread_pct")]
+    pub min_spread_pct: Decimal,
+    /// Extra cushion (as a percentage of the sell price) subtracted from the assumed sell
+    /// price before computing `gross_profit`, so a reported opportunity survives an
+    /// adverse price move of this size before it's actually filled.
+    #[serde(default = "default_safety_margin_pct")]
+    pub safety_margin_pct: Decimal,
+}
+
+fn default_min_spread_pct() -> Decimal {
+    Decimal::from_str_exact("0.5").unwrap()
+}
+
+fn default_safety_margin_pct() -> Decimal {
+    Decimal::from_str_exact("0.1").unwrap()
+}
+
+/// Reference price oracle used to sanity-check DEX/exchange-derived prices before an
+/// opportunity is emitted. `#[serde(untagged)]` so new providers can be added to config
+/// files without a tag field breaking older configs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OracleConfig {
+    Pragma {
+        api_url: String,
+        api_key: String,
+        /// Max allowed deviation between a computed price and the oracle reference price,
+        /// expressed as a fraction (e.g. `0.01` == 1%).
+        #[serde(default = "default_oracle_tolerance")]
+        max_deviation: Decimal,
+    },
+}
+
+fn default_oracle_tolerance() -> Decimal {
+    Decimal::from_str_exact("0.01").unwrap()
+}
+
+impl OracleConfig {
+    /// Assemble the REST path used to fetch a reference price for `base`/`quote`.
+    pub fn get_fetch_url(&self, base: &str, quote: &str) -> String {
+        match self {
+            OracleConfig::Pragma { api_url, .. } => {
+                format!("{}/v1/price/{}-{}", api_url.trim_end_matches('/'), base, quote)
+            }
+        }
+    }
+
+    pub fn max_deviation(&self) -> Decimal {
+        match self {
+            OracleConfig::Pragma { max_deviation, .. } => *max_deviation,
+        }
+    }
+
+    pub fn api_key(&self) -> &str {
+        match self {
+            OracleConfig::Pragma { api_key, .. } => api_key,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +82,10 @@ pub struct ChainConfig {
     pub rpc_url: String,
     pub ws_url: Option<String>,
     pub chain_id: u64,
+    /// Prefer subscribing to `newPendingTransactions` over `ws_url` instead of polling
+    /// `txpool_content`/new blocks. Only takes effect when `ws_url` is set; ignored
+    /// otherwise since there's nothing to subscribe to.
+    pub use_pending_tx_stream: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,6 +117,7 @@ impl Config {
                 .unwrap_or_else(|_| "https://eth.llamarpc.com".to_string()),
             ws_url: std::env::var("ETH_WS_URL").ok(),
             chain_id: 1,
+            use_pending_tx_stream: true,
         });
         
         // BSC
@@ -60,6 +127,7 @@ impl Config {
                 .unwrap_or_else(|_| "https://bsc-dataseed.binance.org".to_string()),
             ws_url: None,
             chain_id: 56,
+            use_pending_tx_stream: false,
         });
         
         // Polygon
@@ -69,6 +137,7 @@ impl Config {
                 .unwrap_or_else(|_| "https://polygon-rpc.com".to_string()),
             ws_url: None,
             chain_id: 137,
+            use_pending_tx_stream: false,
         });
         
         // Arbitrum
@@ -78,6 +147,7 @@ impl Config {
                 .unwrap_or_else(|_| "https://arb1.arbitrum.io/rpc".to_string()),
             ws_url: None,
             chain_id: 42161,
+            use_pending_tx_stream: false,
         });
         
         // Flash loan providers
@@ -158,6 +228,14 @@ impl Config {
                 "wss://stream.binance.com:9443/ws".to_string(),
                 "wss://ws-feed.exchange.coinbase.com".to_string(),
             ],
+            oracle: std::env::var("PRAGMA_API_KEY").ok().map(|api_key| OracleConfig::Pragma {
+                api_url: std::env::var("PRAGMA_API_URL")
+                    .unwrap_or_else(|_| "https://api.pragma.build".to_string()),
+                api_key,
+                max_deviation: default_oracle_tolerance(),
+            }),
+            min_spread_pct: default_min_spread_pct(),
+            safety_margin_pct: default_safety_margin_pct(),
         })
     }
 }
@@ -1,15 +1,33 @@
+use crate::merkle::{Hash, MerkleLog};
 use crate::{config::Config, types::*};
 use anyhow::Result;
 use mongodb::{Client, Database, Collection, options::ClientOptions};
 use redis::{Client as RedisClient, AsyncCommands};
 use rust_decimal::Decimal;
 use chrono::{Utc, Duration};
+use serde::{Deserialize, Serialize};
 use serde_json;
+use tokio::sync::RwLock;
+
+/// One persisted leaf of the `merkle_leaves` audit log, in append order. `leaf_hash` is the
+/// hex-encoded `merkle::hash_leaf` output, stored alongside (not instead of) the opportunity
+/// document so `DataStore::verify_log` can rebuild the tree from this collection alone,
+/// independent of whatever state `opportunities` is in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MerkleLeafDoc {
+    index: i64,
+    leaf_hash: String,
+    opportunity_id: String,
+}
 
 pub struct DataStore {
     mongo_db: Database,
     redis_client: RedisClient,
     config: Config,
+    /// In-memory Merkle tree over every opportunity ever passed to `store_opportunity`, in
+    /// insertion order. Rebuilt from the `merkle_leaves` collection on `new` so a restart
+    /// doesn't lose the ability to issue inclusion proofs for leaves appended in a prior run.
+    merkle: RwLock<MerkleLog>,
 }
 
 impl DataStore {
@@ -19,18 +37,40 @@ impl DataStore {
         let mongo_db = mongo_client.database(&config.database.database_name);
 
         let redis_client = RedisClient::open(config.database.redis_uri.as_str())?;
+        let merkle = RwLock::new(Self::load_merkle_log(&mongo_db).await?);
 
         Ok(Self {
             mongo_db,
             redis_client,
             config: config.clone(),
+            merkle,
         })
     }
 
+    /// Rebuilds a `MerkleLog` from every leaf stored in `merkle_leaves`, ordered by the
+    /// append index rather than insertion order in Mongo (which isn't guaranteed stable).
+    async fn load_merkle_log(mongo_db: &Database) -> Result<MerkleLog> {
+        let collection: Collection<MerkleLeafDoc> = mongo_db.collection("merkle_leaves");
+        let options = mongodb::options::FindOptions::builder()
+            .sort(mongodb::bson::doc! { "index": 1 })
+            .build();
+
+        let mut cursor = collection.find(None, options).await?;
+        let mut leaves = Vec::new();
+        while cursor.advance().await? {
+            let doc: MerkleLeafDoc = cursor.deserialize_current()?;
+            let mut hash: Hash = [0u8; 32];
+            hex::decode_to_slice(&doc.leaf_hash, &mut hash)?;
+            leaves.push(hash);
+        }
+
+        Ok(MerkleLog::from_leaves(leaves))
+    }
+
     pub async fn store_opportunity(&self, opportunity: &ArbitrageOpportunity) -> Result<()> {
-        let collection: Collection<ArbitrageOpportunity> = 
+        let collection: Collection<ArbitrageOpportunity> =
             self.mongo_db.collection("opportunities");
-        
+
         collection.insert_one(opportunity, None).await?;
 
         let mut conn = self.redis_client.get_async_connection().await?;
@@ -38,9 +78,88 @@ impl DataStore {
         let value = serde_json::to_string(opportunity)?;
         conn.setex(key, value, 3600).await?;
 
+        self.append_to_merkle_log(opportunity).await?;
+
         Ok(())
     }
 
+    /// Appends `opportunity`'s canonical JSON encoding as the next leaf of the tamper-evident
+    /// audit log, persists the leaf hash so the tree survives a restart, and refreshes the
+    /// cached root in Redis. Not wrapped in the same Mongo transaction as the `opportunities`
+    /// insert above (no multi-document transaction is open here) — a crash between the two
+    /// leaves the audit log one entry behind the collection it's auditing. That gap is
+    /// invisible to a same-process restart (both the in-memory tree and a tree rebuilt from
+    /// `merkle_leaves` agree, since neither ever saw the missing leaf), so `verify_log`
+    /// additionally reconciles the leaf count against `opportunities`' document count to
+    /// surface it.
+    async fn append_to_merkle_log(&self, opportunity: &ArbitrageOpportunity) -> Result<Hash> {
+        let leaf_bytes = serde_json::to_vec(opportunity)?;
+
+        let (index, root) = {
+            let mut log = self.merkle.write().await;
+            let index = log.append(&leaf_bytes);
+            (index, log.root().expect("non-empty after append"))
+        };
+        let leaf_hash = self.merkle.read().await.leaf_hash(index).expect("just appended");
+
+        let leaves: Collection<MerkleLeafDoc> = self.mongo_db.collection("merkle_leaves");
+        leaves.insert_one(MerkleLeafDoc {
+            index: index as i64,
+            leaf_hash: hex::encode(leaf_hash),
+            opportunity_id: opportunity.id.clone(),
+        }, None).await?;
+
+        let mut conn = self.redis_client.get_async_connection().await?;
+        conn.set("merkle:root", hex::encode(root)).await?;
+
+        Ok(root)
+    }
+
+    /// Current root of the audit log, or `None` if nothing has been stored yet.
+    pub async fn current_root(&self) -> Option<Hash> {
+        self.merkle.read().await.root()
+    }
+
+    /// Inclusion proof for the `index`-th opportunity ever stored (the index returned by
+    /// `append_to_merkle_log`'s internal `MerkleLog::append`), to be checked with
+    /// `crate::merkle::verify_proof` against a root obtained independently of this store.
+    pub async fn inclusion_proof(&self, index: usize) -> Option<Vec<(Hash, bool)>> {
+        self.merkle.read().await.inclusion_proof(index)
+    }
+
+    /// Recomputes the Merkle root from every leaf persisted in `merkle_leaves` and checks it
+    /// against both the live in-memory tree and the root last cached in Redis, then reconciles
+    /// the leaf count against `opportunities`' own document count. The first two checks catch
+    /// a leaf document edited or deleted out from under the log, or Redis falling out of sync
+    /// with Mongo; neither can catch a leaf that was *never persisted* (an `insert_one` into
+    /// `merkle_leaves` that failed, or a crash between it and the `opportunities` insert in
+    /// `store_opportunity`), since every view compared so far is ultimately derived from the
+    /// same (incomplete) `merkle_leaves` collection. The count reconciliation closes that gap
+    /// by checking against a collection the audit log doesn't itself write to.
+    pub async fn verify_log(&self) -> Result<bool> {
+        let rebuilt_log = Self::load_merkle_log(&self.mongo_db).await?;
+        let rebuilt_root = rebuilt_log.root();
+        if rebuilt_root != self.merkle.read().await.root() {
+            return Ok(false);
+        }
+
+        let mut conn = self.redis_client.get_async_connection().await?;
+        let cached_root: Option<String> = conn.get("merkle:root").await.ok();
+        let root_matches = match (rebuilt_root, cached_root) {
+            (None, None) => true,
+            (Some(root), Some(hex_root)) => hex::encode(root) == hex_root,
+            _ => false,
+        };
+        if !root_matches {
+            return Ok(false);
+        }
+
+        let opportunities: Collection<ArbitrageOpportunity> =
+            self.mongo_db.collection("opportunities");
+        let opportunity_count = opportunities.count_documents(None, None).await?;
+        Ok(rebuilt_log.len() as u64 == opportunity_count)
+    }
+
     pub async fn get_opportunity(&self, id: &str) -> Result<Option<ArbitrageOpportunity>> {
         let mut conn = self.redis_client.get_async_connection().await?;
         let key = format!("opportunity:{}", id);
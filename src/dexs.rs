@@ -1,10 +1,18 @@
 use crate::chains::ChainManager;
-use crate::types::{Chain, LiquidityPool, Token};
+use crate::swap_curve::{CurveType, SwapCurve};
+use crate::token_amount::TokenAmount;
+use crate::types::{Chain, LiquidityPool, SharedState, Token};
 use anyhow::Result;
 use ethers::prelude::*;
 use rust_decimal::Decimal;
 use std::sync::Arc;
-use chrono::Utc;
+use chrono::{Duration, Utc};
+
+/// How long a fetched LSD exchange rate stays valid in `SharedState::rebase_rates` before
+/// `DexManager::fetch_rate` re-queries the provider contract. Short on purpose — unlike
+/// gas price, a stale rate here produces a phantom arbitrage signal, not just a slightly
+/// off gas estimate.
+const RATE_CACHE_TTL_SECS: i64 = 30;
 
 // ABI for Uniswap V2 style pools
 abigen!(
@@ -26,13 +34,36 @@ abigen!(
     ]"#
 );
 
+/// Curve/Solidly-style stable pools that still expose a V2-shaped `getReserves()` expose
+/// their live amplification coefficient via `A()`. Used to replace the heuristic default
+/// amp (100) `CurveType::from_dex_name` picks for any DEX whose name mentions "curve" or
+/// "stable" with the pool's actual, currently-configured value.
+abigen!(
+    ICurvePool,
+    r#"[
+        function A() external view returns (uint256)
+    ]"#
+);
+
+/// LSD/rebasing-asset rate providers. `getRate()` covers RocketPool's rETH and Coinbase's
+/// cbETH; Lido's wstETH only exposes `stEthPerToken()`, so `DexManager::fetch_rate` tries
+/// the former first and falls back to the latter.
+abigen!(
+    IRateProvider,
+    r#"[
+        function getRate() external view returns (uint256)
+        function stEthPerToken() external view returns (uint256)
+    ]"#
+);
+
 pub struct DexManager {
     chain_manager: Arc<ChainManager>,
+    state: Arc<SharedState>,
 }
 
 impl DexManager {
-    pub async fn new(chain_manager: Arc<ChainManager>) -> Result<Self> {
-        Ok(Self { chain_manager })
+    pub async fn new(chain_manager: Arc<ChainManager>, state: Arc<SharedState>) -> Result<Self> {
+        Ok(Self { chain_manager, state })
     }
     
     pub async fn get_pool_info(
@@ -58,7 +89,13 @@ impl DexManager {
         // Get token info
         let token0 = self.get_token_info(chain, token0_address, &provider).await?;
         let token1 = self.get_token_info(chain, token1_address, &provider).await?;
-        
+
+        let curve = self.curve_for_pool(dex_name, pool_address, &provider).await;
+        let (rate_provider, rate_scale) = known_rate_provider(&token0.symbol)
+            .or_else(|| known_rate_provider(&token1.symbol))
+            .map(|(address, scale)| (Some(format!("{:?}", address)), scale))
+            .unwrap_or((None, Decimal::ONE));
+
         Ok(LiquidityPool {
             address: format!("{:?}", pool_address),
             token0,
@@ -69,9 +106,77 @@ impl DexManager {
             dex: dex_name.to_string(),
             chain: *chain,
             last_update: Utc::now(),
+            curve,
+            rate_provider,
+            rate_scale,
         })
     }
-    
+
+    /// Tags `pool_address` with a `CurveType` the same way `CurveType::from_dex_name`
+    /// does, except a StableSwap tag is followed by a live `A()` call so the pool's
+    /// actual amplification coefficient is used instead of the heuristic default of 100 —
+    /// real stable pools tune `A` anywhere from the single digits (volatile pegs) into the
+    /// thousands (tight pegs like stablecoin-only pools), and pricing every one of them as
+    /// if `A=100` misprices both ends of that range.
+    async fn curve_for_pool(&self, dex_name: &str, pool_address: Address, provider: &Arc<Provider<Http>>) -> CurveType {
+        let default = CurveType::from_dex_name(dex_name);
+        let CurveType::StableSwap { .. } = default else { return default };
+
+        let curve_pool = ICurvePool::new(pool_address, provider.clone());
+        match curve_pool.a().call().await {
+            Ok(amp) => Decimal::from_str_exact(&amp.to_string())
+                .map(|amp| CurveType::StableSwap { amp })
+                .unwrap_or(default),
+            Err(_) => default,
+        }
+    }
+
+    /// Fetches `provider_address`'s current exchange rate, preferring the cache in
+    /// `state.rebase_rates` when it's younger than [`RATE_CACHE_TTL_SECS`]. `None` means
+    /// both the `getRate()` and `stEthPerToken()` calls failed — callers must treat that as
+    /// "can't price this pool right now", not as an implicit 1:1 rate.
+    async fn fetch_rate(&self, chain: &Chain, provider_address: &str) -> Option<Decimal> {
+        if let Some(cached) = self.state.rebase_rates.get(provider_address) {
+            if Utc::now() - cached.1 < Duration::seconds(RATE_CACHE_TTL_SECS) {
+                return Some(cached.0);
+            }
+        }
+
+        let provider = self.chain_manager.get_provider(chain)?;
+        let address = provider_address.parse::<Address>().ok()?;
+        let rate_provider = IRateProvider::new(address, provider);
+
+        let raw_rate = match rate_provider.get_rate().call().await {
+            Ok(rate) => rate,
+            Err(_) => rate_provider.st_eth_per_token().call().await.ok()?,
+        };
+        let rate = Decimal::from_str_exact(&raw_rate.to_string()).ok()?;
+
+        self.state.rebase_rates.insert(provider_address.to_string(), (rate, Utc::now()));
+        Some(rate)
+    }
+
+    /// Scales `pool`'s reserves by its live LSD exchange rate before any pricing math sees
+    /// them, so a wstETH/WETH pool is priced against wstETH's true redemption value rather
+    /// than treated as a 1:1 pair that drifts into a permanent false spread as the rate
+    /// accrues. Returns the reserves unchanged for a pool with no `rate_provider`, and
+    /// `None` if a rate-bearing pool's provider call fails — the caller must skip pricing
+    /// this pool rather than fall back to an implicit, stale 1:1 rate.
+    pub async fn rate_adjusted_reserves(&self, chain: &Chain, pool: &LiquidityPool) -> Option<(Decimal, Decimal)> {
+        let Some(provider_address) = &pool.rate_provider else {
+            return Some((pool.reserve0, pool.reserve1));
+        };
+
+        let rate = self.fetch_rate(chain, provider_address).await?;
+        let factor = rate / pool.rate_scale;
+
+        if known_rate_provider(&pool.token0.symbol).is_some() {
+            Some((pool.reserve0 * factor, pool.reserve1))
+        } else {
+            Some((pool.reserve0, pool.reserve1 * factor))
+        }
+    }
+
     async fn get_token_info(
         &self,
         chain: &Chain,
@@ -99,21 +204,61 @@ impl DexManager {
         input_reserve: Decimal,
         output_reserve: Decimal,
         fee: Decimal,
+        curve: &CurveType,
     ) -> Decimal {
         let amount_with_fee = input_amount * (Decimal::ONE - fee);
-        let numerator = amount_with_fee * output_reserve;
-        let denominator = input_reserve + amount_with_fee;
-        
-        numerator / denominator
+        curve.output_amount(amount_with_fee, input_reserve, output_reserve)
     }
-    
+
+    /// Decimal-aware counterpart of [`calculate_output_amount`](Self::calculate_output_amount):
+    /// normalizes `input_amount` and both reserves to their own human-unit scale via
+    /// [`TokenAmount::to_decimal`] before applying the curve invariant, then converts the
+    /// result back to raw units at `output_reserve`'s decimals. Prevents the class of bug
+    /// where a 6-decimal USDC reserve and an 18-decimal WETH reserve get fed into the
+    /// invariant as if they were the same scale.
+    pub fn calculate_output_amount_typed(
+        &self,
+        input_amount: TokenAmount,
+        input_reserve: TokenAmount,
+        output_reserve: TokenAmount,
+        fee: Decimal,
+        curve: &CurveType,
+    ) -> TokenAmount {
+        let output_human = self.calculate_output_amount(
+            input_amount.to_decimal(),
+            input_reserve.to_decimal(),
+            output_reserve.to_decimal(),
+            fee,
+            curve,
+        );
+        TokenAmount::from_decimal(output_human, output_reserve.decimals)
+    }
+
     pub fn calculate_price_impact(
         &self,
         input_amount: Decimal,
         input_reserve: Decimal,
+        output_reserve: Decimal,
+        curve: &CurveType,
     ) -> Decimal {
-        (input_amount / input_reserve) * Decimal::from(100)
+        curve.price_impact(input_amount, input_reserve, output_reserve)
     }
 }
 
+/// Maps a token symbol to its known LSD/rebasing rate-provider contract and the
+/// fixed-point scale `getRate()`/`stEthPerToken()` expresses its answer in, mirroring
+/// `CurveType::from_dex_name`'s by-name heuristic for tagging pool behavior without
+/// per-pool configuration. `None` for anything not recognized — the overwhelming majority
+/// of tokens, which settle reserves 1:1 against their own balance.
+fn known_rate_provider(symbol: &str) -> Option<(Address, Decimal)> {
+    let address = match symbol {
+        "wstETH" => "0x7f39C581F595B53c5cb19bD0b3f8dA6c935E2Ca0", // Lido
+        "rETH" => "0xae78736Cd615f374D3085123A210448E74Fc6393",   // RocketPool
+        "cbETH" => "0xBe9895146f7AF43049ca1c1AE358B0541Ea49704",  // Coinbase
+        _ => return None,
+    };
+    let address = address.parse::<Address>().ok()?;
+    Some((address, Decimal::from_str_exact("1000000000000000000").unwrap()))
+}
+
 use rust_decimal::prelude::FromStr;
\ No newline at end of file
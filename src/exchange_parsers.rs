@@ -0,0 +1,336 @@
+use rust_decimal::Decimal;
+use rust_decimal::prelude::*;
+use serde_json::Value;
+use std::str::FromStr;
+
+/// One parsed price observation from a venue's raw WebSocket message. A single message
+/// can yield several of these (array-ticker feeds report every symbol at once).
+#[derive(Debug, Clone)]
+pub struct PriceUpdate {
+    pub pair: String,
+    pub price: Decimal,
+    pub volume: Decimal,
+}
+
+/// Turns one venue's raw WebSocket message into zero or more `PriceUpdate`s. Each venue
+/// gets its own implementation instead of extending a single dispatch `match`, so adding
+/// a venue is registering a parser rather than growing one function.
+pub trait ExchangeParser: Send + Sync {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>>;
+
+    /// Rewrites a venue-native symbol into this parser's preferred pair spelling.
+    /// Defaults to passthrough; venues with unusual separators/casing override it.
+    fn quote_normalize(&self, raw_symbol: &str) -> String {
+        raw_symbol.to_string()
+    }
+}
+
+fn dec(value: &Value) -> Option<Decimal> {
+    match value {
+        Value::String(s) => Decimal::from_str(s).ok(),
+        Value::Number(_) => value.as_f64().and_then(Decimal::from_f64),
+        _ => None,
+    }
+}
+
+fn single(pair: String, price: Decimal, volume: Decimal) -> Option<Vec<PriceUpdate>> {
+    Some(vec![PriceUpdate { pair, price, volume }])
+}
+
+pub struct BinanceParser;
+impl ExchangeParser for BinanceParser {
+    // `!ticker@arr` delivers every symbol's ticker in one array; returning all of them
+    // (rather than just the first match) is the whole point of the array stream.
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        let arr = data.as_array()?;
+        let updates: Vec<PriceUpdate> = arr.iter()
+            .filter_map(|item| {
+                let pair = item["s"].as_str()?.to_string();
+                let price = dec(&item["c"])?;
+                let volume = dec(&item["v"]).unwrap_or(Decimal::ZERO);
+                Some(PriceUpdate { pair, price, volume })
+            })
+            .collect();
+        if updates.is_empty() { None } else { Some(updates) }
+    }
+}
+
+pub struct CoinbaseParser;
+impl ExchangeParser for CoinbaseParser {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        if data["type"] != "ticker" {
+            return None;
+        }
+        let pair = data["product_id"].as_str()?.to_string();
+        let price = dec(&data["price"])?;
+        let volume = dec(&data["volume_24h"]).unwrap_or(Decimal::ZERO);
+        single(pair, price, volume)
+    }
+}
+
+pub struct KrakenParser;
+impl ExchangeParser for KrakenParser {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        let arr = data.as_array()?;
+        if arr.len() < 4 {
+            return None;
+        }
+        let pair = arr[3].as_str()?.to_string();
+        let ticker = arr[1].as_object()?;
+        let ask = dec(ticker["a"].as_array()?.first()?)?;
+        let bid = dec(ticker["b"].as_array()?.first()?)?;
+        let volume = ticker["v"].as_array().and_then(|v| v.get(1)).and_then(dec).unwrap_or(Decimal::ZERO);
+        single(pair, (ask + bid) / Decimal::from(2), volume)
+    }
+}
+
+pub struct UniswapParser;
+impl ExchangeParser for UniswapParser {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        let pool = data["pool"].as_object()?;
+        let token0 = pool["token0"]["symbol"].as_str()?;
+        let token1 = pool["token1"]["symbol"].as_str()?;
+        let price = dec(&pool["token0Price"])?;
+        let volume = dec(&pool["volumeUSD"]).unwrap_or(Decimal::ZERO);
+        single(format!("{}/{}", token0, token1), price, volume)
+    }
+}
+
+pub struct OneInchParser;
+impl ExchangeParser for OneInchParser {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        let quote = data["quote"].as_object()?;
+        let from = quote["fromToken"]["symbol"].as_str()?;
+        let to = quote["toToken"]["symbol"].as_str()?;
+        let from_amt = dec(&quote["fromTokenAmount"])?;
+        let to_amt = dec(&quote["toTokenAmount"])?;
+        if from_amt.is_zero() {
+            return None;
+        }
+        single(format!("{}/{}", from, to), to_amt / from_amt, from_amt)
+    }
+}
+
+/// OKX's `tickers` channel: `{"arg":{"channel":"tickers"},"data":[{"instId":...,"last":...,"vol24h":...}]}`.
+pub struct OkxParser;
+impl ExchangeParser for OkxParser {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        if data["arg"]["channel"].as_str()? != "tickers" {
+            return None;
+        }
+        let updates: Vec<PriceUpdate> = data["data"].as_array()?.iter()
+            .filter_map(|item| {
+                let pair = item["instId"].as_str()?.to_string();
+                let price = dec(&item["last"])?;
+                let volume = dec(&item["vol24h"]).unwrap_or(Decimal::ZERO);
+                Some(PriceUpdate { pair, price, volume })
+            })
+            .collect();
+        if updates.is_empty() { None } else { Some(updates) }
+    }
+}
+
+/// Bybit v5 public: `tickers.*` carries a flat ticker object, `orderbook.*` carries a
+/// book whose best bid/ask midpoint stands in for a price.
+pub struct BybitParser;
+impl ExchangeParser for BybitParser {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        let topic = data["topic"].as_str()?;
+        if topic.starts_with("tickers") {
+            let pair = data["data"]["symbol"].as_str()?.to_string();
+            let price = dec(&data["data"]["lastPrice"])?;
+            let volume = dec(&data["data"]["volume24h"]).unwrap_or(Decimal::ZERO);
+            single(pair, price, volume)
+        } else if topic.starts_with("orderbook") {
+            let pair = topic.rsplit('.').next()?.to_string();
+            let best_bid = dec(data["data"]["b"].as_array()?.first()?.as_array()?.first()?)?;
+            let best_ask = dec(data["data"]["a"].as_array()?.first()?.as_array()?.first()?)?;
+            single(pair, (best_bid + best_ask) / Decimal::from(2), Decimal::ZERO)
+        } else {
+            None
+        }
+    }
+}
+
+/// Gate.io `spot.tickers`: `{"result":{"currency_pair":"BTC_USDT","last":...,"base_volume":...}}`.
+pub struct GateIoParser;
+impl ExchangeParser for GateIoParser {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        if data["channel"].as_str()? != "spot.tickers" || data["event"].as_str() != Some("update") {
+            return None;
+        }
+        let result = &data["result"];
+        let pair = result["currency_pair"].as_str()?.to_string();
+        let price = dec(&result["last"])?;
+        let volume = dec(&result["base_volume"]).unwrap_or(Decimal::ZERO);
+        single(pair, price, volume)
+    }
+
+    fn quote_normalize(&self, raw_symbol: &str) -> String {
+        raw_symbol.replace('_', "/")
+    }
+}
+
+/// KuCoin `/market/ticker:<symbol>`: the pair lives in the `topic` string, not the body.
+pub struct KuCoinParser;
+impl ExchangeParser for KuCoinParser {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        let topic = data["topic"].as_str()?;
+        if !topic.starts_with("/market/ticker") {
+            return None;
+        }
+        let pair = topic.rsplit(':').next()?.to_string();
+        let price = dec(&data["data"]["price"])?;
+        let volume = dec(&data["data"]["size"]).unwrap_or(Decimal::ZERO);
+        single(pair, price, volume)
+    }
+}
+
+/// Huobi `market.<symbol>.ticker`: the pair lives in the `ch` string, not the body.
+pub struct HuobiParser;
+impl ExchangeParser for HuobiParser {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        let ch = data["ch"].as_str()?;
+        let pair = ch.strip_prefix("market.")?.strip_suffix(".ticker")?.to_string();
+        let price = dec(&data["tick"]["close"])?;
+        let volume = dec(&data["tick"]["vol"]).unwrap_or(Decimal::ZERO);
+        single(pair, price, volume)
+    }
+}
+
+/// MEXC v3 `bookTicker`: pair is embedded in the `c` channel string as `...@SYMBOL`.
+pub struct MexcParser;
+impl ExchangeParser for MexcParser {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        let channel = data["c"].as_str()?;
+        let pair = channel.rsplit('@').next()?.to_string();
+        let bid = dec(&data["d"]["b"])?;
+        let ask = dec(&data["d"]["a"])?;
+        single(pair, (bid + ask) / Decimal::from(2), Decimal::ZERO)
+    }
+}
+
+/// Bitget `ticker` channel: `{"arg":{"channel":"ticker"},"data":[{"instId":...,"last":...,"baseVolume":...}]}`.
+pub struct BitgetParser;
+impl ExchangeParser for BitgetParser {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        if data["arg"]["channel"].as_str()? != "ticker" {
+            return None;
+        }
+        let updates: Vec<PriceUpdate> = data["data"].as_array()?.iter()
+            .filter_map(|item| {
+                let pair = item["instId"].as_str()?.to_string();
+                let price = dec(&item["last"])?;
+                let volume = dec(&item["baseVolume"]).unwrap_or(Decimal::ZERO);
+                Some(PriceUpdate { pair, price, volume })
+            })
+            .collect();
+        if updates.is_empty() { None } else { Some(updates) }
+    }
+}
+
+/// Deribit `ticker.<instrument>` subscription notifications.
+pub struct DeribitParser;
+impl ExchangeParser for DeribitParser {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        if data["method"].as_str()? != "subscription" {
+            return None;
+        }
+        let params = &data["params"];
+        if !params["channel"].as_str()?.starts_with("ticker.") {
+            return None;
+        }
+        let pair = params["data"]["instrument_name"].as_str()?.to_string();
+        let price = dec(&params["data"]["last_price"])?;
+        let volume = dec(&params["data"]["stats"]["volume"]).unwrap_or(Decimal::ZERO);
+        single(pair, price, volume)
+    }
+}
+
+/// Gemini `l2` updates: uses the last reported trade price as the quote.
+pub struct GeminiParser;
+impl ExchangeParser for GeminiParser {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        if data["type"].as_str()? != "l2_updates" {
+            return None;
+        }
+        let pair = data["symbol"].as_str()?.to_string();
+        let last_trade = data["trades"].as_array()?.last()?;
+        let price = dec(&last_trade["price"])?;
+        let volume = dec(&last_trade["quantity"]).unwrap_or(Decimal::ZERO);
+        single(pair, price, volume)
+    }
+}
+
+/// Upbit ticker frames: `{"type":"ticker","code":"KRW-BTC","trade_price":...}`.
+pub struct UpbitParser;
+impl ExchangeParser for UpbitParser {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        if data["type"].as_str()? != "ticker" {
+            return None;
+        }
+        let pair = data["code"].as_str()?.to_string();
+        let price = dec(&data["trade_price"])?;
+        let volume = dec(&data["acc_trade_volume_24h"]).unwrap_or(Decimal::ZERO);
+        single(pair, price, volume)
+    }
+}
+
+/// Catches the ~90 feeds that don't have a dedicated parser: looks for commonly-used
+/// field names for symbol/price/volume at the top level or one level of nesting under
+/// a handful of likely wrapper keys. Best-effort by nature — it's here so an unrecognized
+/// feed contributes *something* rather than nothing, not to replace a real parser.
+pub struct HeuristicParser;
+impl ExchangeParser for HeuristicParser {
+    fn parse(&self, data: &Value) -> Option<Vec<PriceUpdate>> {
+        const WRAPPER_KEYS: &[&str] = &["data", "result", "tick", "ticker"];
+        const SYMBOL_KEYS: &[&str] = &["symbol", "instId", "pair", "s", "code", "currency_pair"];
+        const PRICE_KEYS: &[&str] = &["price", "last", "lastPrice", "c", "close", "trade_price"];
+        const VOLUME_KEYS: &[&str] = &["volume", "vol", "v", "baseVolume", "base_volume", "volume24h"];
+
+        let candidates = std::iter::once(data)
+            .chain(WRAPPER_KEYS.iter().filter_map(|key| data.get(key)));
+
+        for candidate in candidates {
+            let pair = SYMBOL_KEYS.iter().find_map(|key| candidate.get(key)).and_then(|v| v.as_str());
+            let price = PRICE_KEYS.iter().find_map(|key| candidate.get(key)).and_then(dec);
+
+            if let (Some(pair), Some(price)) = (pair, price) {
+                let volume = VOLUME_KEYS.iter().find_map(|key| candidate.get(key)).and_then(dec).unwrap_or(Decimal::ZERO);
+                return single(pair.to_string(), price, volume);
+            }
+        }
+
+        None
+    }
+}
+
+/// Maps a feed name to the parser that understands its wire format. Matched by prefix
+/// against the feed's `name` (e.g. `"OKX"` matches `"OKX"`, `"Binance-Spot"` matches
+/// `"Binance"`), falling back to `HeuristicParser` for anything unregistered.
+pub fn build_registry() -> Vec<(&'static str, Box<dyn ExchangeParser>)> {
+    vec![
+        ("Binance", Box::new(BinanceParser)),
+        ("Coinbase", Box::new(CoinbaseParser)),
+        ("Kraken", Box::new(KrakenParser)),
+        ("Uniswap", Box::new(UniswapParser)),
+        ("1inch", Box::new(OneInchParser)),
+        ("OKX", Box::new(OkxParser)),
+        ("Bybit", Box::new(BybitParser)),
+        ("Gate.io", Box::new(GateIoParser)),
+        ("KuCoin", Box::new(KuCoinParser)),
+        ("Huobi", Box::new(HuobiParser)),
+        ("MEXC", Box::new(MexcParser)),
+        ("Bitget", Box::new(BitgetParser)),
+        ("Deribit", Box::new(DeribitParser)),
+        ("Gemini", Box::new(GeminiParser)),
+        ("Upbit", Box::new(UpbitParser)),
+    ]
+}
+
+pub fn parser_for<'a>(registry: &'a [(&'static str, Box<dyn ExchangeParser>)], feed_name: &str) -> &'a dyn ExchangeParser {
+    registry.iter()
+        .find(|(prefix, _)| feed_name.starts_with(prefix))
+        .map(|(_, parser)| parser.as_ref())
+        .unwrap_or(&HeuristicParser)
+}
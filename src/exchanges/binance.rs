@@ -3,6 +3,7 @@ use super::Exchange;
 use anyhow::Result;
 use async_trait::async_trait;
 use binance::{api::*, market::*, model::*, websockets::*};
+use dashmap::DashMap;
 use rust_decimal::Decimal;
 use chrono::Utc;
 use std::sync::Arc;
@@ -14,6 +15,11 @@ pub struct BinanceExchange {
     credentials: ExchangeCredentials,
     pair_cache: Arc<RwLock<HashMap<String, TokenPair>>>,
     websocket_connections: Arc<RwLock<Vec<WebSockets>>>,
+    /// Top-of-book per symbol, kept current by `subscribe_to_updates`'s `bookTicker`
+    /// handler. `bookTicker` only ever carries the best bid/ask (no further depth), so
+    /// each cached `OrderBook` holds exactly one level per side. `get_orderbook` prefers
+    /// this over a fresh REST call whenever a live feed is already running for the symbol.
+    live_books: Arc<DashMap<String, OrderBook>>,
 }
 
 impl BinanceExchange {
@@ -28,6 +34,7 @@ impl BinanceExchange {
             credentials,
             pair_cache: Arc::new(RwLock::new(HashMap::new())),
             websocket_connections: Arc::new(RwLock::new(Vec::new())),
+            live_books: Arc::new(DashMap::new()),
         })
     }
 
@@ -124,6 +131,14 @@ impl Exchange for BinanceExchange {
 
     async fn get_orderbook(&self, pair: &TokenPair, depth: usize) -> Result<OrderBook> {
         let symbol = format!("{}{}", pair.base.symbol, pair.quote.symbol);
+
+        // A live `bookTicker` feed only gives top-of-book, but that's a better fix than a
+        // REST snapshot that's already stale by the time it arrives, so prefer it while
+        // `subscribe_to_updates` is actively maintaining this symbol.
+        if let Some(live) = self.live_books.get(&symbol) {
+            return Ok(live.clone());
+        }
+
         let orderbook = self.market.get_depth(symbol, Some(depth as u64))?;
 
         let mut bids = Vec::new();
@@ -174,25 +189,44 @@ impl Exchange for BinanceExchange {
     }
 
     async fn subscribe_to_updates(&self, pairs: Vec<TokenPair>) -> Result<()> {
+        let pairs_by_symbol: HashMap<String, TokenPair> = pairs.iter()
+            .map(|pair| (format!("{}{}", pair.base.symbol, pair.quote.symbol), pair.clone()))
+            .collect();
+
         let endpoints: Vec<String> = pairs.iter().map(|pair| {
             let symbol = format!("{}{}", pair.base.symbol.to_lowercase(), pair.quote.symbol.to_lowercase());
             format!("{}@bookTicker", symbol)
         }).collect();
 
-        let ws_endpoint = endpoints.join("/");
-        
-        let ws = WebSockets::new(|event: WebsocketEvent| {
-            match event {
-                WebsocketEvent::BookTicker(ticker_event) => {
-                    tracing::debug!("Received ticker update for {}", ticker_event.symbol);
-                    Ok(())
-                },
-                _ => Ok(()),
+        let live_books = self.live_books.clone();
+
+        let ws = WebSockets::new(move |event: WebsocketEvent| {
+            if let WebsocketEvent::BookTicker(ticker_event) = event {
+                let Some(pair) = pairs_by_symbol.get(&ticker_event.symbol) else {
+                    return Ok(());
+                };
+                let (Ok(bid_price), Ok(bid_qty), Ok(ask_price), Ok(ask_qty)) = (
+                    Decimal::from_str(&ticker_event.best_bid),
+                    Decimal::from_str(&ticker_event.best_bid_qty),
+                    Decimal::from_str(&ticker_event.best_ask),
+                    Decimal::from_str(&ticker_event.best_ask_qty),
+                ) else {
+                    return Ok(());
+                };
+
+                live_books.insert(ticker_event.symbol.clone(), OrderBook {
+                    exchange: "Binance".to_string(),
+                    pair: pair.clone(),
+                    bids: vec![Order { price: bid_price, quantity: bid_qty, timestamp: Utc::now() }],
+                    asks: vec![Order { price: ask_price, quantity: ask_qty, timestamp: Utc::now() }],
+                    timestamp: Utc::now(),
+                });
             }
+            Ok(())
         });
 
         ws.connect_multiple(&endpoints)?;
-        
+
         let mut connections = self.websocket_connections.write().await;
         connections.push(ws);
 
@@ -2,10 +2,12 @@ pub mod binance;
 pub mod coinbase;
 pub mod kraken;
 pub mod uniswap;
+pub mod uniswap_v2;
 pub mod sushiswap;
 pub mod pancakeswap;
 pub mod curve;
 pub mod balancer;
+pub mod router;
 
 use crate::{config::Config, types::*};
 use anyhow::Result;
@@ -15,6 +17,46 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use rust_decimal::Decimal;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderType {
+    Market,
+    Limit,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlaceOrderRequest {
+    pub pair: TokenPair,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    /// Required for `OrderType::Limit`, ignored for `OrderType::Market`.
+    pub price: Option<Decimal>,
+    pub size: Decimal,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlacedOrder {
+    pub order_id: String,
+    pub inst_id: String,
+    pub side: OrderSide,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub filled_size: Decimal,
+    pub status: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Balance {
+    pub currency: String,
+    pub available: Decimal,
+    pub total: Decimal,
+}
+
 #[async_trait]
 pub trait Exchange: Send + Sync {
     async fn get_name(&self) -> String;
@@ -24,6 +66,25 @@ pub trait Exchange: Send + Sync {
     async fn get_fees(&self) -> Result<ExchangeFees>;
     async fn get_24h_volume(&self, pair: &TokenPair) -> Result<Decimal>;
     async fn subscribe_to_updates(&self, pairs: Vec<TokenPair>) -> Result<()>;
+
+    // Authenticated trading is optional per-exchange capability; exchanges that haven't
+    // wired up a private API surface simply report it unsupported rather than forcing
+    // every adapter to stub these out.
+    async fn place_order(&self, _request: PlaceOrderRequest) -> Result<PlacedOrder> {
+        Err(anyhow::anyhow!("{} does not support order placement", self.get_name().await))
+    }
+
+    async fn cancel_order(&self, _inst_id: &str, _order_id: &str) -> Result<()> {
+        Err(anyhow::anyhow!("{} does not support order cancellation", self.get_name().await))
+    }
+
+    async fn get_balances(&self) -> Result<Vec<Balance>> {
+        Err(anyhow::anyhow!("{} does not support balance queries", self.get_name().await))
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<PlacedOrder>> {
+        Err(anyhow::anyhow!("{} does not support open-order queries", self.get_name().await))
+    }
 }
 
 pub struct ExchangeManager {
@@ -1,5 +1,5 @@
 use crate::types::*;
-use super::Exchange;
+use super::{Exchange, Balance, OrderSide, OrderType, PlaceOrderRequest, PlacedOrder};
 use anyhow::Result;
 use async_trait::async_trait;
 use rust_decimal::Decimal;
@@ -16,9 +16,106 @@ use serde_json::{json, Value};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use futures_util::{StreamExt, SinkExt};
 use tracing::{info, debug, warn, error};
+use tokio::sync::broadcast;
+use std::time::Duration;
 
 type HmacSha256 = Hmac<Sha256>;
 
+// Reconnect backoff for the supervised WebSocket task: doubles per consecutive failure,
+// capped so a prolonged outage still retries every 30s rather than backing off forever.
+const RECONNECT_BASE_DELAY_MS: u64 = 500;
+const RECONNECT_MAX_DELAY_MS: u64 = 30_000;
+// OKX drops the connection if it doesn't see a client `"ping"` text frame roughly this often.
+const KEEPALIVE_INTERVAL_SECS: u64 = 25;
+// Per-instrument price update channel capacity; slow consumers just miss the oldest updates
+// rather than backing up the WebSocket read loop.
+const PRICE_STREAM_CAPACITY: usize = 256;
+
+/// Pull-based quote lookup backed by the live WebSocket cache, so downstream consumers
+/// don't have to poll `Exchange::get_price` (which falls back to a REST call once its
+/// 5-second cache entry expires).
+pub trait LatestRate {
+    fn latest_rate(&self, pair: &TokenPair) -> Result<Price>;
+}
+
+/// Local view of an instrument's order book, built from the incremental `books` channel.
+/// Kept as sorted price -> size maps so checksum computation and best bid/ask lookups don't
+/// need a separate re-sort on every update.
+#[derive(Debug, Clone, Default)]
+struct OrderBookState {
+    bids: std::collections::BTreeMap<Decimal, Decimal>,
+    asks: std::collections::BTreeMap<Decimal, Decimal>,
+}
+
+impl OrderBookState {
+    fn apply_level(levels: &mut std::collections::BTreeMap<Decimal, Decimal>, price: Decimal, size: Decimal) {
+        if size.is_zero() {
+            levels.remove(&price);
+        } else {
+            levels.insert(price, size);
+        }
+    }
+
+    fn apply_update(&mut self, bids: &[(Decimal, Decimal)], asks: &[(Decimal, Decimal)]) {
+        for &(price, size) in bids {
+            Self::apply_level(&mut self.bids, price, size);
+        }
+        for &(price, size) in asks {
+            Self::apply_level(&mut self.asks, price, size);
+        }
+    }
+
+    fn apply_snapshot(&mut self, bids: &[(Decimal, Decimal)], asks: &[(Decimal, Decimal)]) {
+        self.bids.clear();
+        self.asks.clear();
+        self.apply_update(bids, asks);
+    }
+
+    /// OKX's integrity checksum: top 25 levels per side, interleaved as
+    /// `bidPx:bidSz:askPx:askSz:...` (a side's slot is simply omitted once it runs out of
+    /// levels), CRC32 of the joined UTF-8 bytes read back as a signed 32-bit integer.
+    fn checksum(&self) -> i32 {
+        let bids: Vec<(&Decimal, &Decimal)> = self.bids.iter().rev().take(25).collect();
+        let asks: Vec<(&Decimal, &Decimal)> = self.asks.iter().take(25).collect();
+        let depth = bids.len().max(asks.len());
+
+        let mut parts = Vec::with_capacity(depth * 4);
+        for i in 0..depth {
+            if let Some((price, size)) = bids.get(i) {
+                parts.push(price.to_string());
+                parts.push(size.to_string());
+            }
+            if let Some((price, size)) = asks.get(i) {
+                parts.push(price.to_string());
+                parts.push(size.to_string());
+            }
+        }
+
+        let mut hasher = crc32fast::Hasher::new();
+        hasher.update(parts.join(":").as_bytes());
+        hasher.finalize() as i32
+    }
+
+    fn to_order_book(&self, exchange: &str, pair: &TokenPair) -> OrderBook {
+        OrderBook {
+            exchange: exchange.to_string(),
+            pair: pair.clone(),
+            bids: self
+                .bids
+                .iter()
+                .rev()
+                .map(|(price, qty)| Order { price: *price, quantity: *qty, timestamp: Utc::now() })
+                .collect(),
+            asks: self
+                .asks
+                .iter()
+                .map(|(price, qty)| Order { price: *price, quantity: *qty, timestamp: Utc::now() })
+                .collect(),
+            timestamp: Utc::now(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct OkxConfig {
     pub api_key: String,
@@ -50,6 +147,16 @@ pub struct OkxExchange {
     client: Client,
     pair_cache: Arc<RwLock<HashMap<String, TokenPair>>>,
     price_cache: Arc<RwLock<HashMap<String, Price>>>,
+    // Broadcasts every ticker update the WebSocket task sees, so callers can get a
+    // continuously-updated quote feed instead of polling `get_price`.
+    price_updates: broadcast::Sender<Price>,
+    // Locally-maintained L2 books built from the `books` channel, keyed by instId. Dropped
+    // and rebuilt from a fresh snapshot whenever the OKX checksum stops matching.
+    order_books: Arc<RwLock<HashMap<String, OrderBookState>>>,
+    // Populated by the private WebSocket's `orders`/`account` channels, keyed by order ID
+    // and currency respectively.
+    order_cache: Arc<RwLock<HashMap<String, PlacedOrder>>>,
+    balance_cache: Arc<RwLock<HashMap<String, Balance>>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -97,32 +204,48 @@ impl OkxExchange {
         let client = Client::new();
         
         info!("Initializing OKX exchange with API key: {}...", &config.api_key[..8]);
-        
+
+        let (price_updates, _) = broadcast::channel(PRICE_STREAM_CAPACITY);
+
         Ok(Self {
             config,
             client,
             pair_cache: Arc::new(RwLock::new(HashMap::new())),
             price_cache: Arc::new(RwLock::new(HashMap::new())),
+            price_updates,
+            order_books: Arc::new(RwLock::new(HashMap::new())),
+            order_cache: Arc::new(RwLock::new(HashMap::new())),
+            balance_cache: Arc::new(RwLock::new(HashMap::new())),
         })
     }
+
+    /// Subscribes to the continuously-updated `Price` stream backing `LatestRate`; each
+    /// receiver gets every ticker update seen by the supervised WebSocket task from here on.
+    pub fn subscribe_price_stream(&self) -> broadcast::Receiver<Price> {
+        self.price_updates.subscribe()
+    }
     
-    fn sign_request(&self, timestamp: &str, method: &str, path: &str, body: &str) -> String {
+    fn sign_payload(secret_key: &str, timestamp: &str, method: &str, path: &str, body: &str) -> String {
         let message = format!("{}{}{}{}", timestamp, method, path, body);
-        let mut mac = HmacSha256::new_from_slice(self.config.secret_key.as_bytes())
+        let mut mac = HmacSha256::new_from_slice(secret_key.as_bytes())
             .expect("HMAC can take key of any size");
         mac.update(message.as_bytes());
         let result = mac.finalize();
         general_purpose::STANDARD.encode(result.into_bytes())
     }
-    
+
+    fn sign_request(&self, timestamp: &str, method: &str, path: &str, body: &str) -> String {
+        Self::sign_payload(&self.config.secret_key, timestamp, method, path, body)
+    }
+
     async fn make_request<T: for<'de> Deserialize<'de>>(&self, path: &str) -> Result<OkxResponse<T>> {
         let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%S.%3fZ").to_string();
         let method = "GET";
         let full_path = format!("/api/v5{}", path);
         let signature = self.sign_request(&timestamp, method, &full_path, "");
-        
+
         let url = format!("{}{}", self.config.rest_url, full_path);
-        
+
         let response = self.client
             .get(&url)
             .header("OK-ACCESS-KEY", &self.config.api_key)
@@ -132,87 +255,205 @@ impl OkxExchange {
             .header("Content-Type", "application/json")
             .send()
             .await?;
-        
+
         let text = response.text().await?;
         let result: OkxResponse<T> = serde_json::from_str(&text)?;
-        
+
         if result.code != "0" {
             return Err(anyhow::anyhow!("OKX API error: {} - {}", result.code, result.msg));
         }
-        
+
+        Ok(result)
+    }
+
+    /// Same as `make_request` but for signed `POST` endpoints, where the JSON body is part
+    /// of the OKX prehash string and must exactly match the bytes sent on the wire.
+    async fn make_post_request<T: for<'de> Deserialize<'de>>(&self, path: &str, body: &Value) -> Result<OkxResponse<T>> {
+        let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%S.%3fZ").to_string();
+        let method = "POST";
+        let full_path = format!("/api/v5{}", path);
+        let body_str = body.to_string();
+        let signature = self.sign_request(&timestamp, method, &full_path, &body_str);
+
+        let url = format!("{}{}", self.config.rest_url, full_path);
+
+        let response = self.client
+            .post(&url)
+            .header("OK-ACCESS-KEY", &self.config.api_key)
+            .header("OK-ACCESS-SIGN", signature)
+            .header("OK-ACCESS-TIMESTAMP", timestamp)
+            .header("OK-ACCESS-PASSPHRASE", &self.config.passphrase)
+            .header("Content-Type", "application/json")
+            .body(body_str)
+            .send()
+            .await?;
+
+        let text = response.text().await?;
+        let result: OkxResponse<T> = serde_json::from_str(&text)?;
+
+        if result.code != "0" {
+            return Err(anyhow::anyhow!("OKX API error: {} - {}", result.code, result.msg));
+        }
+
         Ok(result)
     }
     
-    pub async fn start_websocket(&self) {
-        let url = url::Url::parse(&self.config.ws_public_url).unwrap();
-        let (ws_stream, _) = connect_async(url).await.expect("Failed to connect to OKX WebSocket");
+    /// Spawns a supervised connection task for `pairs`: reconnects with exponential
+    /// backoff on any error or server-initiated close, re-subscribing on every
+    /// (re)connect rather than assuming the socket stays up for the process lifetime.
+    pub async fn start_websocket(&self, pairs: Vec<TokenPair>) {
+        let url = self.config.ws_public_url.clone();
+        let price_cache = self.price_cache.clone();
+        let price_updates = self.price_updates.clone();
+        let order_books = self.order_books.clone();
+
+        tokio::spawn(async move {
+            let mut consecutive_failures: u32 = 0;
+
+            loop {
+                match Self::run_connection(&url, &pairs, &price_cache, &price_updates, &order_books).await {
+                    Ok(()) => {
+                        warn!("OKX WebSocket connection closed, reconnecting");
+                        consecutive_failures = 0;
+                    }
+                    Err(e) => {
+                        error!("OKX WebSocket connection error: {}", e);
+                        consecutive_failures += 1;
+                    }
+                }
+
+                let delay_ms = RECONNECT_BASE_DELAY_MS
+                    .saturating_mul(1u64 << consecutive_failures.min(6))
+                    .min(RECONNECT_MAX_DELAY_MS);
+                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+            }
+        });
+    }
+
+    // One connection's lifetime: subscribes, then services server frames and client-side
+    // keepalives until the socket errors or closes, at which point the caller reconnects.
+    async fn run_connection(
+        url: &str,
+        pairs: &[TokenPair],
+        price_cache: &Arc<RwLock<HashMap<String, Price>>>,
+        price_updates: &broadcast::Sender<Price>,
+        order_books: &Arc<RwLock<HashMap<String, OrderBookState>>>,
+    ) -> Result<()> {
+        let parsed = url::Url::parse(url)?;
+        let (ws_stream, _) = connect_async(parsed).await?;
         let (mut write, mut read) = ws_stream.split();
-        
-        // Subscribe to tickers
-        let subscribe_msg = json!({
-            "op": "subscribe",
-            "args": [
-                {
-                    "channel": "tickers",
-                    "instId": "BTC-USDT"
-                },
-                {
-                    "channel": "tickers",
-                    "instId": "ETH-USDT"
-                },
-                {
-                    "channel": "books5",
-                    "instId": "BTC-USDT"
-                },
-                {
-                    "channel": "books5",
-                    "instId": "ETH-USDT"
+
+        let args: Vec<Value> = pairs
+            .iter()
+            .flat_map(|pair| {
+                let inst_id = format!("{}-{}", pair.base.symbol, pair.quote.symbol);
+                vec![
+                    json!({ "channel": "tickers", "instId": inst_id }),
+                    json!({ "channel": "books", "instId": inst_id }),
+                ]
+            })
+            .collect();
+
+        if args.is_empty() {
+            return Err(anyhow::anyhow!("no pairs passed to subscribe_to_updates"));
+        }
+
+        let subscribe_msg = json!({ "op": "subscribe", "args": args });
+        write.send(Message::Text(subscribe_msg.to_string())).await?;
+        info!("Subscribed to OKX WebSocket channels for {} pair(s)", pairs.len());
+
+        let (keepalive_tx, mut keepalive_rx) = tokio::sync::mpsc::channel::<Message>(1);
+        let keepalive_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(KEEPALIVE_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                if keepalive_tx.send(Message::Text("ping".to_string())).await.is_err() {
+                    break;
                 }
-            ]
+            }
         });
-        
-        write.send(Message::Text(subscribe_msg.to_string())).await.unwrap();
-        info!("Subscribed to OKX WebSocket channels");
-        
-        let price_cache = self.price_cache.clone();
-        
-        tokio::spawn(async move {
-            while let Some(message) = read.next().await {
-                match message {
-                    Ok(Message::Text(text)) => {
-                        if let Ok(data) = serde_json::from_str::<Value>(&text) {
-                            if let Some(event) = data.get("event") {
-                                if event == "subscribe" {
-                                    info!("Successfully subscribed to OKX channel");
-                                }
-                            } else if let Some(arg) = data.get("arg") {
-                                if let Some(channel) = arg.get("channel") {
-                                    if channel == "tickers" {
-                                        Self::process_ticker_update(&data, &price_cache).await;
+
+        let result = loop {
+            tokio::select! {
+                outgoing = keepalive_rx.recv() => {
+                    match outgoing {
+                        Some(msg) => {
+                            if let Err(e) = write.send(msg).await {
+                                break Err(anyhow::anyhow!("failed to send OKX keepalive ping: {}", e));
+                            }
+                        }
+                        None => break Ok(()),
+                    }
+                }
+                message = read.next() => {
+                    match message {
+                        Some(Ok(Message::Text(text))) => {
+                            if text == "pong" {
+                                debug!("Received OKX keepalive pong");
+                                continue;
+                            }
+
+                            if let Ok(data) = serde_json::from_str::<Value>(&text) {
+                                if let Some(event) = data.get("event").and_then(|e| e.as_str()) {
+                                    match event {
+                                        "subscribe" => info!("Successfully subscribed to OKX channel"),
+                                        "error" => warn!("OKX WebSocket error event: {}", data),
+                                        other => debug!("Unhandled OKX event: {}", other),
+                                    }
+                                } else if let Some(arg) = data.get("arg") {
+                                    if let Some(channel) = arg.get("channel").and_then(|c| c.as_str()) {
+                                        if channel == "tickers" {
+                                            Self::process_ticker_update(&data, price_cache, price_updates).await;
+                                        } else if channel == "books" || channel == "books-l2-tbt" {
+                                            if let Some(inst_id) = Self::process_book_update(&data, order_books).await {
+                                                warn!("Re-subscribing to OKX book channel for {} after checksum mismatch", inst_id);
+                                                let resub = json!({
+                                                    "op": "unsubscribe",
+                                                    "args": [{ "channel": channel, "instId": inst_id }]
+                                                });
+                                                let _ = write.send(Message::Text(resub.to_string())).await;
+                                                let resub = json!({
+                                                    "op": "subscribe",
+                                                    "args": [{ "channel": channel, "instId": inst_id }]
+                                                });
+                                                let _ = write.send(Message::Text(resub.to_string())).await;
+                                            }
+                                        }
                                     }
                                 }
                             }
                         }
+                        Some(Ok(Message::Ping(payload))) => {
+                            if let Err(e) = write.send(Message::Pong(payload)).await {
+                                break Err(anyhow::anyhow!("failed to send OKX pong: {}", e));
+                            }
+                        }
+                        Some(Ok(Message::Close(frame))) => {
+                            warn!("OKX WebSocket closed by server: {:?}", frame);
+                            break Ok(());
+                        }
+                        Some(Ok(_)) => {}
+                        Some(Err(e)) => break Err(anyhow::anyhow!("OKX WebSocket read error: {}", e)),
+                        None => break Ok(()),
                     }
-                    Ok(Message::Ping(ping)) => {
-                        debug!("Received ping from OKX, sending pong");
-                    }
-                    Err(e) => {
-                        error!("OKX WebSocket error: {}", e);
-                        break;
-                    }
-                    _ => {}
                 }
             }
-        });
+        };
+
+        keepalive_handle.abort();
+        result
     }
-    
-    async fn process_ticker_update(data: &Value, price_cache: &Arc<RwLock<HashMap<String, Price>>>) {
+
+    async fn process_ticker_update(
+        data: &Value,
+        price_cache: &Arc<RwLock<HashMap<String, Price>>>,
+        price_updates: &broadcast::Sender<Price>,
+    ) {
         if let Some(data_array) = data.get("data").and_then(|d| d.as_array()) {
             for item in data_array {
                 if let Ok(ticker) = serde_json::from_value::<OkxTicker>(item.clone()) {
                     let pair = Self::parse_pair(&ticker.inst_id);
-                    
+
                     if let (Ok(bid), Ok(ask), Ok(bid_size), Ok(ask_size)) = (
                         Decimal::from_str_exact(&ticker.bid_price),
                         Decimal::from_str_exact(&ticker.ask_price),
@@ -228,9 +469,12 @@ impl OkxExchange {
                             exchange: "OKX".to_string(),
                             pair,
                         };
-                        
+
                         let mut cache = price_cache.write().await;
-                        cache.insert(ticker.inst_id.clone(), price);
+                        cache.insert(ticker.inst_id.clone(), price.clone());
+                        drop(cache);
+
+                        let _ = price_updates.send(price);
                         debug!("Updated price for {}: bid={}, ask={}", ticker.inst_id, bid, ask);
                     }
                 }
@@ -238,6 +482,263 @@ impl OkxExchange {
         }
     }
     
+    /// Applies one `books`/`books-l2-tbt` message to the local order book and verifies the
+    /// result against OKX's checksum. Returns `Some(instId)` when the book diverged and was
+    /// dropped, so the caller can re-subscribe for a fresh snapshot.
+    async fn process_book_update(
+        data: &Value,
+        order_books: &Arc<RwLock<HashMap<String, OrderBookState>>>,
+    ) -> Option<String> {
+        let inst_id = data.get("arg")?.get("instId")?.as_str()?.to_string();
+        let action = data.get("action").and_then(|a| a.as_str()).unwrap_or("update");
+        let entry = data.get("data")?.as_array()?.first()?;
+
+        let parse_levels = |levels: &[Value]| -> Option<Vec<(Decimal, Decimal)>> {
+            levels
+                .iter()
+                .map(|level| {
+                    let level = level.as_array()?;
+                    let price = Decimal::from_str_exact(level.get(0)?.as_str()?).ok()?;
+                    let size = Decimal::from_str_exact(level.get(1)?.as_str()?).ok()?;
+                    Some((price, size))
+                })
+                .collect()
+        };
+
+        let bids = parse_levels(entry.get("bids")?.as_array()?)?;
+        let asks = parse_levels(entry.get("asks")?.as_array()?)?;
+        let expected_checksum = entry.get("checksum")?.as_i64()? as i32;
+
+        let mut books = order_books.write().await;
+        let book = books.entry(inst_id.clone()).or_default();
+
+        if action == "snapshot" {
+            book.apply_snapshot(&bids, &asks);
+        } else {
+            book.apply_update(&bids, &asks);
+        }
+
+        if book.checksum() != expected_checksum {
+            warn!("OKX order book checksum mismatch for {}, dropping local book", inst_id);
+            books.remove(&inst_id);
+            return Some(inst_id);
+        }
+
+        None
+    }
+
+    /// Spawns a supervised connection to `ws_private_url`: logs in, subscribes to the
+    /// `orders`/`account` channels, and streams fills/balance changes into their caches.
+    /// Reconnects (and re-authenticates) with the same backoff as the public socket.
+    pub async fn start_private_websocket(&self) {
+        let url = self.config.ws_private_url.clone();
+        let api_key = self.config.api_key.clone();
+        let secret_key = self.config.secret_key.clone();
+        let passphrase = self.config.passphrase.clone();
+        let order_cache = self.order_cache.clone();
+        let balance_cache = self.balance_cache.clone();
+
+        tokio::spawn(async move {
+            let mut consecutive_failures: u32 = 0;
+
+            loop {
+                match Self::run_private_connection(&url, &api_key, &secret_key, &passphrase, &order_cache, &balance_cache).await {
+                    Ok(()) => {
+                        warn!("OKX private WebSocket connection closed, reconnecting");
+                        consecutive_failures = 0;
+                    }
+                    Err(e) => {
+                        error!("OKX private WebSocket connection error: {}", e);
+                        consecutive_failures += 1;
+                    }
+                }
+
+                let delay_ms = RECONNECT_BASE_DELAY_MS
+                    .saturating_mul(1u64 << consecutive_failures.min(6))
+                    .min(RECONNECT_MAX_DELAY_MS);
+                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+            }
+        });
+    }
+
+    async fn run_private_connection(
+        url: &str,
+        api_key: &str,
+        secret_key: &str,
+        passphrase: &str,
+        order_cache: &Arc<RwLock<HashMap<String, PlacedOrder>>>,
+        balance_cache: &Arc<RwLock<HashMap<String, Balance>>>,
+    ) -> Result<()> {
+        let parsed = url::Url::parse(url)?;
+        let (ws_stream, _) = connect_async(parsed).await?;
+        let (mut write, mut read) = ws_stream.split();
+
+        let timestamp = Utc::now().timestamp().to_string();
+        let sign = Self::sign_payload(secret_key, &timestamp, "GET", "/users/self/verify", "");
+        let login_msg = json!({
+            "op": "login",
+            "args": [{
+                "apiKey": api_key,
+                "passphrase": passphrase,
+                "timestamp": timestamp,
+                "sign": sign,
+            }]
+        });
+        write.send(Message::Text(login_msg.to_string())).await?;
+
+        loop {
+            match read.next().await {
+                Some(Ok(Message::Text(text))) => {
+                    if let Ok(data) = serde_json::from_str::<Value>(&text) {
+                        if data.get("event").and_then(|e| e.as_str()) == Some("login") {
+                            if data.get("code").and_then(|c| c.as_str()) == Some("0") {
+                                info!("OKX private WebSocket login succeeded");
+                                break;
+                            }
+                            return Err(anyhow::anyhow!("OKX private WebSocket login failed: {}", data));
+                        }
+                    }
+                }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(anyhow::anyhow!("OKX private WebSocket read error during login: {}", e)),
+                None => return Err(anyhow::anyhow!("OKX private WebSocket closed during login")),
+            }
+        }
+
+        let subscribe_msg = json!({
+            "op": "subscribe",
+            "args": [
+                { "channel": "orders", "instType": "SPOT" },
+                { "channel": "account" },
+            ]
+        });
+        write.send(Message::Text(subscribe_msg.to_string())).await?;
+
+        let (keepalive_tx, mut keepalive_rx) = tokio::sync::mpsc::channel::<Message>(1);
+        let keepalive_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(KEEPALIVE_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                if keepalive_tx.send(Message::Text("ping".to_string())).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let result = loop {
+            tokio::select! {
+                outgoing = keepalive_rx.recv() => {
+                    match outgoing {
+                        Some(msg) => {
+                            if let Err(e) = write.send(msg).await {
+                                break Err(anyhow::anyhow!("failed to send OKX private keepalive ping: {}", e));
+                            }
+                        }
+                        None => break Ok(()),
+                    }
+                }
+                message = read.next() => {
+                    match message {
+                        Some(Ok(Message::Text(text))) => {
+                            if text == "pong" {
+                                continue;
+                            }
+
+                            if let Ok(data) = serde_json::from_str::<Value>(&text) {
+                                if let Some(arg) = data.get("arg") {
+                                    match arg.get("channel").and_then(|c| c.as_str()) {
+                                        Some("orders") => Self::process_order_update(&data, order_cache).await,
+                                        Some("account") => Self::process_account_update(&data, balance_cache).await,
+                                        _ => {}
+                                    }
+                                }
+                            }
+                        }
+                        Some(Ok(Message::Ping(payload))) => {
+                            if let Err(e) = write.send(Message::Pong(payload)).await {
+                                break Err(anyhow::anyhow!("failed to send OKX private pong: {}", e));
+                            }
+                        }
+                        Some(Ok(Message::Close(frame))) => {
+                            warn!("OKX private WebSocket closed by server: {:?}", frame);
+                            break Ok(());
+                        }
+                        Some(Ok(_)) => {}
+                        Some(Err(e)) => break Err(anyhow::anyhow!("OKX private WebSocket read error: {}", e)),
+                        None => break Ok(()),
+                    }
+                }
+            }
+        };
+
+        keepalive_handle.abort();
+        result
+    }
+
+    async fn process_order_update(data: &Value, order_cache: &Arc<RwLock<HashMap<String, PlacedOrder>>>) {
+        let Some(items) = data.get("data").and_then(|d| d.as_array()) else { return };
+
+        for item in items {
+            let (Some(order_id), Some(inst_id)) = (
+                item.get("ordId").and_then(|v| v.as_str()),
+                item.get("instId").and_then(|v| v.as_str()),
+            ) else { continue };
+
+            let side = match item.get("side").and_then(|v| v.as_str()) {
+                Some("sell") => OrderSide::Sell,
+                _ => OrderSide::Buy,
+            };
+            let parse_dec = |field: &str| {
+                item.get(field)
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| Decimal::from_str_exact(s).ok())
+                    .unwrap_or(Decimal::ZERO)
+            };
+            let status = item.get("state").and_then(|v| v.as_str()).unwrap_or("unknown").to_string();
+
+            let order = PlacedOrder {
+                order_id: order_id.to_string(),
+                inst_id: inst_id.to_string(),
+                side,
+                price: parse_dec("px"),
+                size: parse_dec("sz"),
+                filled_size: parse_dec("accFillSz"),
+                status,
+            };
+
+            let mut cache = order_cache.write().await;
+            cache.insert(order_id.to_string(), order);
+        }
+    }
+
+    async fn process_account_update(data: &Value, balance_cache: &Arc<RwLock<HashMap<String, Balance>>>) {
+        let Some(items) = data.get("data").and_then(|d| d.as_array()) else { return };
+
+        for item in items {
+            let Some(details) = item.get("details").and_then(|d| d.as_array()) else { continue };
+
+            for detail in details {
+                let Some(currency) = detail.get("ccy").and_then(|v| v.as_str()) else { continue };
+                let parse_dec = |field: &str| {
+                    detail.get(field)
+                        .and_then(|v| v.as_str())
+                        .and_then(|s| Decimal::from_str_exact(s).ok())
+                        .unwrap_or(Decimal::ZERO)
+                };
+
+                let mut cache = balance_cache.write().await;
+                cache.insert(
+                    currency.to_string(),
+                    Balance {
+                        currency: currency.to_string(),
+                        available: parse_dec("availBal"),
+                        total: parse_dec("cashBal"),
+                    },
+                );
+            }
+        }
+    }
+
     fn parse_pair(inst_id: &str) -> TokenPair {
         let parts: Vec<&str> = inst_id.split('-').collect();
         if parts.len() >= 2 {
@@ -352,6 +853,19 @@ impl Exchange for OkxExchange {
     
     async fn get_orderbook(&self, pair: &TokenPair, depth: usize) -> Result<OrderBook> {
         let inst_id = format!("{}-{}", pair.base.symbol, pair.quote.symbol);
+
+        {
+            let books = self.order_books.read().await;
+            if let Some(book) = books.get(&inst_id) {
+                if !book.bids.is_empty() || !book.asks.is_empty() {
+                    let mut live = book.to_order_book("OKX", pair);
+                    live.bids.truncate(depth);
+                    live.asks.truncate(depth);
+                    return Ok(live);
+                }
+            }
+        }
+
         let path = format!("/market/books?instId={}&sz={}", inst_id, depth);
         
         let response = self.client
@@ -422,9 +936,164 @@ impl Exchange for OkxExchange {
     }
     
     async fn subscribe_to_updates(&self, pairs: Vec<TokenPair>) -> Result<()> {
-        self.start_websocket().await;
+        self.start_websocket(pairs).await;
         Ok(())
     }
+
+    async fn place_order(&self, request: PlaceOrderRequest) -> Result<PlacedOrder> {
+        let inst_id = format!("{}-{}", request.pair.base.symbol, request.pair.quote.symbol);
+        let side = match request.side {
+            OrderSide::Buy => "buy",
+            OrderSide::Sell => "sell",
+        };
+        let ord_type = match request.order_type {
+            OrderType::Market => "market",
+            OrderType::Limit => "limit",
+        };
+
+        let mut body = json!({
+            "instId": inst_id,
+            "tdMode": "cash",
+            "side": side,
+            "ordType": ord_type,
+            "sz": request.size.to_string(),
+        });
+        if let Some(price) = request.price {
+            body["px"] = json!(price.to_string());
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct OrderAck {
+            #[serde(rename = "ordId")]
+            ord_id: String,
+            #[serde(rename = "sCode")]
+            s_code: String,
+            #[serde(rename = "sMsg")]
+            s_msg: String,
+        }
+
+        let response: OkxResponse<OrderAck> = self.make_post_request("/trade/order", &body).await?;
+        let ack = response.data.into_iter().next()
+            .ok_or_else(|| anyhow::anyhow!("OKX order placement returned no data"))?;
+
+        if ack.s_code != "0" {
+            return Err(anyhow::anyhow!("OKX order rejected: {} - {}", ack.s_code, ack.s_msg));
+        }
+
+        Ok(PlacedOrder {
+            order_id: ack.ord_id,
+            inst_id,
+            side: request.side,
+            price: request.price.unwrap_or(Decimal::ZERO),
+            size: request.size,
+            filled_size: Decimal::ZERO,
+            status: "live".to_string(),
+        })
+    }
+
+    async fn cancel_order(&self, inst_id: &str, order_id: &str) -> Result<()> {
+        let body = json!({ "instId": inst_id, "ordId": order_id });
+
+        #[derive(Debug, Deserialize)]
+        struct CancelAck {
+            #[serde(rename = "sCode")]
+            s_code: String,
+            #[serde(rename = "sMsg")]
+            s_msg: String,
+        }
+
+        let response: OkxResponse<CancelAck> = self.make_post_request("/trade/cancel-order", &body).await?;
+        let ack = response.data.into_iter().next()
+            .ok_or_else(|| anyhow::anyhow!("OKX cancel returned no data"))?;
+
+        if ack.s_code != "0" {
+            return Err(anyhow::anyhow!("OKX cancel rejected: {} - {}", ack.s_code, ack.s_msg));
+        }
+
+        Ok(())
+    }
+
+    async fn get_balances(&self) -> Result<Vec<Balance>> {
+        {
+            let cache = self.balance_cache.read().await;
+            if !cache.is_empty() {
+                return Ok(cache.values().cloned().collect());
+            }
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct BalanceDetail {
+            ccy: String,
+            #[serde(rename = "availBal")]
+            avail_bal: String,
+            #[serde(rename = "cashBal")]
+            cash_bal: String,
+        }
+        #[derive(Debug, Deserialize)]
+        struct AccountBalance {
+            details: Vec<BalanceDetail>,
+        }
+
+        let response: OkxResponse<AccountBalance> = self.make_request("/account/balance").await?;
+
+        let mut balances = Vec::new();
+        for account in response.data {
+            for detail in account.details {
+                balances.push(Balance {
+                    currency: detail.ccy,
+                    available: Decimal::from_str_exact(&detail.avail_bal)?,
+                    total: Decimal::from_str_exact(&detail.cash_bal)?,
+                });
+            }
+        }
+
+        Ok(balances)
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<PlacedOrder>> {
+        #[derive(Debug, Deserialize)]
+        struct OpenOrder {
+            #[serde(rename = "ordId")]
+            ord_id: String,
+            #[serde(rename = "instId")]
+            inst_id: String,
+            side: String,
+            px: String,
+            sz: String,
+            #[serde(rename = "accFillSz")]
+            acc_fill_sz: String,
+            state: String,
+        }
+
+        let response: OkxResponse<OpenOrder> = self.make_request("/trade/orders-pending").await?;
+
+        response.data.into_iter().map(|o| {
+            Ok(PlacedOrder {
+                order_id: o.ord_id,
+                inst_id: o.inst_id,
+                side: if o.side == "sell" { OrderSide::Sell } else { OrderSide::Buy },
+                price: Decimal::from_str_exact(&o.px).unwrap_or(Decimal::ZERO),
+                size: Decimal::from_str_exact(&o.sz)?,
+                filled_size: Decimal::from_str_exact(&o.acc_fill_sz).unwrap_or(Decimal::ZERO),
+                status: o.state,
+            })
+        }).collect()
+    }
+}
+
+impl LatestRate for OkxExchange {
+    fn latest_rate(&self, pair: &TokenPair) -> Result<Price> {
+        let inst_id = format!("{}-{}", pair.base.symbol, pair.quote.symbol);
+        let cache = self
+            .price_cache
+            .try_read()
+            .map_err(|_| anyhow::anyhow!("OKX price cache is locked for writing"))?;
+
+        cache
+            .get(&inst_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("no cached price for {}", inst_id))
+    }
 }
 
 use rust_decimal::prelude::FromStr;
\ No newline at end of file
@@ -0,0 +1,189 @@
+// Cross-venue best-execution routing over the `Exchange` trait: turns a set of
+// single-exchange clients into a comparison layer the arbitrage engine can call instead of
+// picking a venue by hand.
+
+use super::{Exchange, OrderSide};
+use crate::types::TokenPair;
+use anyhow::Result;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct VenueFill {
+    pub exchange: String,
+    pub amount: Decimal,
+    pub avg_price: Decimal,
+}
+
+#[derive(Debug, Clone)]
+pub struct RoutedQuote {
+    pub fills: Vec<VenueFill>,
+    pub total_amount: Decimal,
+    pub avg_price: Decimal,
+    /// Total cost (buy) or proceeds (sell) across `fills`, net of each venue's taker fee.
+    pub net_proceeds: Decimal,
+}
+
+pub struct ExecutionRouter {
+    exchanges: Vec<Arc<dyn Exchange>>,
+}
+
+impl ExecutionRouter {
+    pub fn new(exchanges: Vec<Arc<dyn Exchange>>) -> Self {
+        Self { exchanges }
+    }
+
+    /// Walks one venue's book on the filling side (asks when buying, bids when selling),
+    /// accumulating up to `amount` across levels rather than assuming top-of-book depth.
+    /// Returns `(filled, volume-weighted avg price, net-of-taker-fee cost/proceeds)`.
+    async fn walk_book(
+        exchange: &Arc<dyn Exchange>,
+        pair: &TokenPair,
+        side: OrderSide,
+        amount: Decimal,
+    ) -> Option<(Decimal, Decimal, Decimal)> {
+        let book = exchange.get_orderbook(pair, 50).await.ok()?;
+        let fees = exchange.get_fees().await.ok()?;
+
+        let levels = match side {
+            OrderSide::Buy => &book.asks,
+            OrderSide::Sell => &book.bids,
+        };
+
+        let mut remaining = amount;
+        let mut filled = Decimal::ZERO;
+        let mut notional = Decimal::ZERO;
+
+        for level in levels {
+            if remaining <= Decimal::ZERO {
+                break;
+            }
+            let take = remaining.min(level.quantity);
+            filled += take;
+            notional += take * level.price;
+            remaining -= take;
+        }
+
+        if filled <= Decimal::ZERO {
+            return None;
+        }
+
+        let avg_price = notional / filled;
+        let fee_amount = notional * fees.taker_fee;
+        let net = match side {
+            OrderSide::Buy => notional + fee_amount,
+            OrderSide::Sell => notional - fee_amount,
+        };
+
+        Some((filled, avg_price, net))
+    }
+
+    /// Best single-venue execution for `amount` of `pair`. Only considers venues that can
+    /// fill the entire size alone; use `split_route` when no single book is deep enough.
+    pub async fn best_quote(&self, pair: &TokenPair, side: OrderSide, amount: Decimal) -> Result<RoutedQuote> {
+        let mut best: Option<(String, Decimal, Decimal, Decimal)> = None;
+
+        for exchange in &self.exchanges {
+            let Some((filled, avg_price, net)) = Self::walk_book(exchange, pair, side, amount).await else {
+                continue;
+            };
+            if filled < amount {
+                continue;
+            }
+
+            let is_better = match (&best, side) {
+                (None, _) => true,
+                (Some((_, _, _, best_net)), OrderSide::Buy) => net < *best_net,
+                (Some((_, _, _, best_net)), OrderSide::Sell) => net > *best_net,
+            };
+
+            if is_better {
+                best = Some((exchange.get_name().await, filled, avg_price, net));
+            }
+        }
+
+        let (exchange, filled, avg_price, net) =
+            best.ok_or_else(|| anyhow::anyhow!("no single venue can fill {} of {}-{}", amount, pair.base.symbol, pair.quote.symbol))?;
+
+        Ok(RoutedQuote {
+            fills: vec![VenueFill { exchange, amount: filled, avg_price }],
+            total_amount: filled,
+            avg_price,
+            net_proceeds: net,
+        })
+    }
+
+    /// Greedily splits `amount` across venues by marginal price, re-walking each remaining
+    /// venue's book against what's left after every allocation. Falls back to `best_quote`
+    /// when a single venue's fill is at least as good as the split, so this never returns a
+    /// strictly worse quote than the single-venue path.
+    pub async fn split_route(&self, pair: &TokenPair, side: OrderSide, amount: Decimal) -> Result<RoutedQuote> {
+        let mut remaining = amount;
+        let mut fills = Vec::new();
+        let mut total_net = Decimal::ZERO;
+        let mut total_notional = Decimal::ZERO;
+        let mut used: Vec<usize> = Vec::new();
+
+        while remaining > Decimal::ZERO {
+            let mut best_idx = None;
+            let mut best_result: Option<(Decimal, Decimal, Decimal)> = None;
+
+            for (i, exchange) in self.exchanges.iter().enumerate() {
+                if used.contains(&i) {
+                    continue;
+                }
+                let Some(result) = Self::walk_book(exchange, pair, side, remaining).await else {
+                    continue;
+                };
+
+                let better = match (&best_result, side) {
+                    (None, _) => true,
+                    (Some((_, best_price, _)), OrderSide::Buy) => result.1 < *best_price,
+                    (Some((_, best_price, _)), OrderSide::Sell) => result.1 > *best_price,
+                };
+                if better {
+                    best_idx = Some(i);
+                    best_result = Some(result);
+                }
+            }
+
+            let (Some(idx), Some((filled, avg_price, net))) = (best_idx, best_result) else {
+                break;
+            };
+
+            fills.push(VenueFill {
+                exchange: self.exchanges[idx].get_name().await,
+                amount: filled,
+                avg_price,
+            });
+            total_net += net;
+            total_notional += filled * avg_price;
+            remaining -= filled;
+            used.push(idx);
+        }
+
+        let total_amount = amount - remaining;
+        if total_amount <= Decimal::ZERO {
+            return Err(anyhow::anyhow!("no venue liquidity available for {}-{}", pair.base.symbol, pair.quote.symbol));
+        }
+
+        let split_quote = RoutedQuote {
+            fills,
+            total_amount,
+            avg_price: total_notional / total_amount,
+            net_proceeds: total_net,
+        };
+
+        if let Ok(single) = self.best_quote(pair, side, amount).await {
+            let single_is_at_least_as_good = match side {
+                OrderSide::Buy => single.net_proceeds <= split_quote.net_proceeds,
+                OrderSide::Sell => single.net_proceeds >= split_quote.net_proceeds,
+            };
+            if single_is_at_least_as_good {
+                return Ok(single);
+            }
+        }
+
+        Ok(split_quote)
+    }
+}
@@ -49,6 +49,10 @@ pub struct UniswapV3Exchange {
     factory_contract: UniswapV3Factory<Provider<Http>>,
 }
 
+/// Fixed notional steps (in units of a pair's base token) walked outward from mid to
+/// synthesize order-book depth from the pool's real tick-math curve.
+const DEPTH_LEVELS: [u64; 5] = [100, 500, 1_000, 5_000, 10_000];
+
 impl UniswapV3Exchange {
     pub async fn new(
         chain_config: ChainConfig,
@@ -57,7 +61,7 @@ impl UniswapV3Exchange {
     ) -> Result<Self> {
         let provider = Provider::<Http>::try_from(&chain_config.rpc_urls[0])?;
         let provider = Arc::new(provider);
-        
+
         let factory_addr = Address::from_str(&factory_address)?;
         let factory_contract = UniswapV3Factory::new(factory_addr, provider.clone());
 
@@ -70,33 +74,154 @@ impl UniswapV3Exchange {
         })
     }
 
-    async fn get_pool_price(&self, pool_address: Address) -> Result<(Decimal, Decimal)> {
+    /// Finds the pool with the most liquidity across the standard fee tiers, since the
+    /// 0.05%/0.3%/1% pools for the same pair can have wildly different depth and the
+    /// deepest one is the one that actually prices a real trade.
+    async fn find_deepest_pool(&self, token_a: Address, token_b: Address) -> Result<(Address, u128)> {
+        let fees = [500u32, 3000, 10000];
+        let mut best: Option<(Address, u128)> = None;
+
+        for fee in fees {
+            if let Ok(pool_address) = self.factory_contract
+                .get_pool(token_a, token_b, fee)
+                .call()
+                .await
+            {
+                if pool_address == Address::zero() {
+                    continue;
+                }
+
+                let pool = UniswapV3Pool::new(pool_address, self.provider.clone());
+                if let Ok(liquidity) = pool.liquidity().call().await {
+                    if best.map_or(true, |(_, best_liquidity)| liquidity > best_liquidity) {
+                        best = Some((pool_address, liquidity));
+                    }
+                }
+            }
+        }
+
+        best.ok_or_else(|| anyhow::anyhow!("No pool found for {:?}/{:?}", token_a, token_b))
+    }
+
+    /// Resolves `pair`'s deepest pool and returns everything the tick-math below needs:
+    /// the raw `sqrtPriceX96`, the active liquidity, whether `pair.base` is the pool's
+    /// `token0`, and each side's on-chain decimals (so callers never have to assume 18/18).
+    async fn pool_state(&self, pair: &TokenPair) -> Result<(U256, u128, bool, u8, u8)> {
+        let base = Address::from_str(&pair.base.address)?;
+        let quote = Address::from_str(&pair.quote.address)?;
+
+        let (pool_address, liquidity) = self.find_deepest_pool(base, quote).await?;
         let pool = UniswapV3Pool::new(pool_address, self.provider.clone());
-        
+
+        let pool_token0 = pool.token_0().call().await?;
+        let base_is_token0 = pool_token0 == base;
+
         let slot0 = pool.slot_0().call().await?;
-        let sqrt_price_x96 = slot0.0;
-        
-        let price = self.sqrt_price_to_price(sqrt_price_x96);
-        let inv_price = Decimal::ONE / price;
-        
-        Ok((price, inv_price))
+        let (decimals0, decimals1) = if base_is_token0 {
+            (pair.base.decimals, pair.quote.decimals)
+        } else {
+            (pair.quote.decimals, pair.base.decimals)
+        };
+
+        Ok((slot0.0, liquidity, base_is_token0, decimals0, decimals1))
     }
 
-    fn sqrt_price_to_price(&self, sqrt_price_x96: U256) -> Decimal {
-        let q96 = U256::from(2).pow(U256::from(96));
-        let q192 = q96 * q96;
-        
-        let price_u256 = sqrt_price_x96 * sqrt_price_x96 * U256::from(10).pow(U256::from(18)) / q192;
-        
-        Decimal::from_str(&price_u256.to_string()).unwrap_or(Decimal::ZERO)
+    /// Converts a raw `sqrtPriceX96` into a human-scaled `token1/token0` price, folding in
+    /// each token's on-chain decimals instead of assuming 18/18.
+    fn sqrt_price_to_price(&self, sqrt_price_x96: U256, decimals0: u8, decimals1: u8) -> Decimal {
+        let sqrt_price = Decimal::from_str(&sqrt_price_x96.to_string()).unwrap_or(Decimal::ZERO);
+        let q96 = Decimal::from_str("79228162514264337593543950336").unwrap_or(Decimal::ONE);
+        let ratio = sqrt_price / q96;
+        let raw_price = ratio * ratio;
+
+        let decimal_shift = decimals0 as i32 - decimals1 as i32;
+        if decimal_shift >= 0 {
+            raw_price * Decimal::from(10u64.pow(decimal_shift as u32))
+        } else {
+            raw_price / Decimal::from(10u64.pow((-decimal_shift) as u32))
+        }
+    }
+
+    /// Mid price of `pair.base` in terms of `pair.quote`, plus the pool state needed to
+    /// quote real trades against it.
+    async fn get_pair_price(&self, pair: &TokenPair) -> Result<(Decimal, u128, U256, bool, u8, u8)> {
+        let (sqrt_price_x96, liquidity, base_is_token0, decimals0, decimals1) =
+            self.pool_state(pair).await?;
+
+        let token1_per_token0 = self.sqrt_price_to_price(sqrt_price_x96, decimals0, decimals1);
+        let price = if base_is_token0 {
+            token1_per_token0
+        } else if token1_per_token0 > Decimal::ZERO {
+            Decimal::ONE / token1_per_token0
+        } else {
+            Decimal::ZERO
+        };
+
+        Ok((price, liquidity, sqrt_price_x96, base_is_token0, decimals0, decimals1))
+    }
+
+    /// Quotes the output of selling `amount_in` of one side of `pair` into the other,
+    /// assuming the trade stays within the pool's current active tick so liquidity `L`
+    /// is constant across it: `sqrtP' = L*sqrtP / (L + Δx*sqrtP)` when token0 is sold,
+    /// `sqrtP' = sqrtP + Δy/L` when token1 is sold, with the matching output
+    /// `Δy = L*(sqrtP - sqrtP')` / `Δx = L*(1/sqrtP - 1/sqrtP')`.
+    ///
+    /// `sell_base` selects the direction: `true` sells `pair.base` for `pair.quote`
+    /// (pricing a bid), `false` sells `pair.quote` for `pair.base` (pricing an ask).
+    async fn quote_exact_in_direction(
+        &self,
+        pair: &TokenPair,
+        amount_in: Decimal,
+        sell_base: bool,
+    ) -> Result<Decimal> {
+        let (_, liquidity, sqrt_price_x96, base_is_token0, decimals0, decimals1) =
+            self.get_pair_price(pair).await?;
+
+        let q96 = Decimal::from_str("79228162514264337593543950336")?;
+        let sqrt_price = Decimal::from_str(&sqrt_price_x96.to_string())? / q96;
+        let l = Decimal::from(liquidity);
+
+        if l <= Decimal::ZERO || sqrt_price <= Decimal::ZERO || amount_in <= Decimal::ZERO {
+            return Ok(Decimal::ZERO);
+        }
+
+        let selling_token0 = sell_base == base_is_token0;
+        let (decimals_in, decimals_out) = if selling_token0 {
+            (decimals0, decimals1)
+        } else {
+            (decimals1, decimals0)
+        };
+
+        let amount_in_raw = amount_in * Decimal::from(10u64.pow(decimals_in as u32));
+
+        let amount_out_raw = if selling_token0 {
+            let denominator = l + amount_in_raw * sqrt_price;
+            if denominator <= Decimal::ZERO {
+                return Ok(Decimal::ZERO);
+            }
+            let sqrt_price_next = (l * sqrt_price) / denominator;
+            l * (sqrt_price - sqrt_price_next)
+        } else {
+            let sqrt_price_next = sqrt_price + amount_in_raw / l;
+            l * (Decimal::ONE / sqrt_price - Decimal::ONE / sqrt_price_next)
+        };
+
+        Ok((amount_out_raw / Decimal::from(10u64.pow(decimals_out as u32))).max(Decimal::ZERO))
+    }
+
+    /// Quotes the realized `pair.quote` output for selling `amount_in` of `pair.base`,
+    /// so the arbitrage scanner can size trades against genuine pool depth instead of a
+    /// flat mid price.
+    pub async fn quote_exact_in(&self, pair: &TokenPair, amount_in: Decimal) -> Result<Decimal> {
+        self.quote_exact_in_direction(pair, amount_in, true).await
     }
 
     async fn get_token_info(&self, token_address: Address) -> Result<Token> {
         let token = ERC20::new(token_address, self.provider.clone());
-        
+
         let symbol = token.symbol().call().await?;
         let decimals = token.decimals().call().await?;
-        
+
         Ok(Token {
             address: format!("{:?}", token_address),
             symbol,
@@ -115,14 +240,14 @@ impl Exchange for UniswapV3Exchange {
     async fn get_pairs(&self) -> Result<Vec<TokenPair>> {
         let mut pairs = Vec::new();
         let pool_count = self.factory_contract.all_pools_length().call().await?;
-        
+
         let max_pools = std::cmp::min(pool_count.as_u64(), 100);
-        
+
         for i in 0..max_pools {
             match self.factory_contract.all_pools(U256::from(i)).call().await {
                 Ok(pool_address) => {
                     let pool = UniswapV3Pool::new(pool_address, self.provider.clone());
-                    
+
                     if let (Ok(token0), Ok(token1)) = (
                         pool.token_0().call().await,
                         pool.token_1().call().await,
@@ -138,46 +263,31 @@ impl Exchange for UniswapV3Exchange {
                 Err(_) => continue,
             }
         }
-        
+
         Ok(pairs)
     }
 
     async fn get_price(&self, pair: &TokenPair) -> Result<Price> {
-        let token0 = Address::from_str(&pair.base.address)?;
-        let token1 = Address::from_str(&pair.quote.address)?;
-        
-        let fees = vec![500u32, 3000, 10000];
-        let mut best_price = None;
-        let mut best_liquidity = U256::zero();
-        
-        for fee in fees {
-            if let Ok(pool_address) = self.factory_contract
-                .get_pool(token0, token1, fee as u32)
-                .call()
-                .await
-            {
-                if pool_address != Address::zero() {
-                    let pool = UniswapV3Pool::new(pool_address, self.provider.clone());
-                    
-                    if let Ok(liquidity) = pool.liquidity().call().await {
-                        if liquidity > best_liquidity.as_u128() {
-                            if let Ok((price, inv_price)) = self.get_pool_price(pool_address).await {
-                                best_liquidity = U256::from(liquidity);
-                                best_price = Some((price, inv_price));
-                            }
-                        }
-                    }
-                }
-            }
+        let (mid_price, liquidity, ..) = self.get_pair_price(pair).await?;
+        if liquidity == 0 {
+            return Err(anyhow::anyhow!("No pool found"));
         }
-        
-        let (price, inv_price) = best_price.ok_or_else(|| anyhow::anyhow!("No pool found"))?;
-        
+
+        // Probe a small, fixed notional on each side so bid/ask reflect the pool's actual
+        // depth-implied slippage rather than an assumed fixed spread.
+        let probe_size = Decimal::ONE;
+        let quote_out = self.quote_exact_in_direction(pair, probe_size, true).await?;
+        let bid = if quote_out > Decimal::ZERO { quote_out / probe_size } else { mid_price };
+
+        let ask_probe_quote = probe_size * mid_price.max(Decimal::ONE);
+        let base_out = self.quote_exact_in_direction(pair, ask_probe_quote, false).await?;
+        let ask = if base_out > Decimal::ZERO { ask_probe_quote / base_out } else { mid_price };
+
         Ok(Price {
-            bid: price * Decimal::from_str("0.997")?,
-            ask: price * Decimal::from_str("1.003")?,
-            bid_size: Decimal::from(100),
-            ask_size: Decimal::from(100),
+            bid,
+            ask,
+            bid_size: probe_size,
+            ask_size: probe_size,
             timestamp: Utc::now(),
             exchange: self.get_name().await,
             pair: pair.clone(),
@@ -185,27 +295,37 @@ impl Exchange for UniswapV3Exchange {
     }
 
     async fn get_orderbook(&self, pair: &TokenPair, _depth: usize) -> Result<OrderBook> {
-        let price = self.get_price(pair).await?;
-        
+        let (mid_price, liquidity, ..) = self.get_pair_price(pair).await?;
+        if liquidity == 0 {
+            return Err(anyhow::anyhow!("No pool found"));
+        }
+
         let mut bids = Vec::new();
         let mut asks = Vec::new();
-        
-        for i in 0..5 {
-            let price_adj = Decimal::from(1) - Decimal::from_str("0.001")? * Decimal::from(i);
-            bids.push(Order {
-                price: price.bid * price_adj,
-                quantity: Decimal::from(100),
-                timestamp: Utc::now(),
-            });
-            
-            let price_adj = Decimal::from(1) + Decimal::from_str("0.001")? * Decimal::from(i);
-            asks.push(Order {
-                price: price.ask * price_adj,
-                quantity: Decimal::from(100),
-                timestamp: Utc::now(),
-            });
+
+        for &level in &DEPTH_LEVELS {
+            let base_amount = Decimal::from(level);
+
+            let quote_out = self.quote_exact_in_direction(pair, base_amount, true).await?;
+            if quote_out > Decimal::ZERO {
+                bids.push(Order {
+                    price: quote_out / base_amount,
+                    quantity: base_amount,
+                    timestamp: Utc::now(),
+                });
+            }
+
+            let quote_amount = base_amount * mid_price;
+            let base_out = self.quote_exact_in_direction(pair, quote_amount, false).await?;
+            if base_out > Decimal::ZERO {
+                asks.push(Order {
+                    price: quote_amount / base_out,
+                    quantity: base_out,
+                    timestamp: Utc::now(),
+                });
+            }
         }
-        
+
         Ok(OrderBook {
             exchange: self.get_name().await,
             pair: pair.clone(),
@@ -230,4 +350,4 @@ impl Exchange for UniswapV3Exchange {
     async fn subscribe_to_updates(&self, _pairs: Vec<TokenPair>) -> Result<()> {
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,136 @@
+use crate::arbitrage::ArbitrageEngine;
+use crate::notifications::NotificationManager;
+use crate::types::{ArbitrageOpportunity, SharedState};
+use chrono::{Duration as ChronoDuration, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::info;
+
+/// Periodically evicts stale entries from `SharedState.opportunities` and, for ones still
+/// within a refresh window, re-runs a scan to confirm they remain profitable before
+/// keeping them — so dashboards and alerts never surface an opportunity whose pool
+/// reserves have long since moved on from what was originally found.
+pub struct ExpiryManager {
+    state: Arc<SharedState>,
+    arbitrage_engine: Arc<ArbitrageEngine>,
+    notifications: Arc<NotificationManager>,
+    /// Entries older than this are dropped outright.
+    ttl: ChronoDuration,
+    /// Entries within `refresh_window` of expiring (older than `ttl - refresh_window` but
+    /// younger than `ttl`) are re-verified against a fresh scan rather than just aged out blind.
+    refresh_window: ChronoDuration,
+    /// An expiring opportunity at or above this USD profit is worth telling users about.
+    high_value_threshold: Decimal,
+}
+
+impl ExpiryManager {
+    pub fn new(
+        state: Arc<SharedState>,
+        arbitrage_engine: Arc<ArbitrageEngine>,
+        notifications: Arc<NotificationManager>,
+        ttl: ChronoDuration,
+        refresh_window: ChronoDuration,
+        high_value_threshold: Decimal,
+    ) -> Self {
+        Self {
+            state,
+            arbitrage_engine,
+            notifications,
+            ttl,
+            refresh_window,
+            high_value_threshold,
+        }
+    }
+
+    /// Spawns the sweep loop as a background task; call once at startup.
+    pub fn spawn(self: Arc<Self>, sweep_interval: std::time::Duration) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(sweep_interval);
+            loop {
+                interval.tick().await;
+                self.sweep().await;
+            }
+        });
+    }
+
+    async fn sweep(&self) {
+        let now = Utc::now();
+        let refresh_cutoff = self.ttl - self.refresh_window;
+
+        let (kept_fresh, needs_revalidation, mut expired): (
+            Vec<ArbitrageOpportunity>,
+            Vec<ArbitrageOpportunity>,
+            Vec<ArbitrageOpportunity>,
+        ) = {
+            let opps = self.state.opportunities.read().await;
+            let mut fresh = Vec::new();
+            let mut revalidate = Vec::new();
+            let mut expired = Vec::new();
+
+            for opp in opps.iter() {
+                let age = now - opp.timestamp;
+                if age >= self.ttl {
+                    expired.push(opp.clone());
+                } else if age >= refresh_cutoff {
+                    revalidate.push(opp.clone());
+                } else {
+                    fresh.push(opp.clone());
+                }
+            }
+            (fresh, revalidate, expired)
+        };
+
+        if needs_revalidation.is_empty() && expired.is_empty() {
+            return;
+        }
+
+        let mut kept = kept_fresh;
+        if !needs_revalidation.is_empty() {
+            match self.arbitrage_engine.scan_opportunities().await {
+                Ok(current) => {
+                    // Ids hash the scan time, so a rescan never reproduces one; match on the
+                    // route (chain, type and the pools it trades through) instead.
+                    let route = |o: &ArbitrageOpportunity| {
+                        (o.chain, o.opportunity_type.clone(),
+                         o.path.iter().map(|p| p.pool_address.clone()).collect::<Vec<_>>())
+                    };
+                    let still_live: HashSet<_> = current.iter().map(&route).collect();
+                    let wanted: HashSet<_> = needs_revalidation.iter().map(&route).collect();
+
+                    for opp in current {
+                        if wanted.contains(&route(&opp)) {
+                            kept.push(opp);
+                        }
+                    }
+                    for opp in needs_revalidation {
+                        if !still_live.contains(&route(&opp)) {
+                            expired.push(opp);
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Expiry sweep revalidation scan failed, keeping candidates as-is: {}", e);
+                    kept.extend(needs_revalidation);
+                }
+            }
+        }
+
+        *self.state.opportunities.write().await = kept;
+
+        for opp in expired.drain(..) {
+            if opp.net_profit_usd >= self.high_value_threshold {
+                info!(
+                    "Opportunity {} ({:?}, ${:.2} profit) expired without executing",
+                    opp.id, opp.chain, opp.net_profit_usd
+                );
+                self.notifications
+                    .send_error(&format!(
+                        "⏳ High-value opportunity expired: {} on {:?} (${:.2} profit, {:.2}% ROI) is no longer valid",
+                        opp.id, opp.chain, opp.net_profit_usd, opp.roi_percentage
+                    ))
+                    .await;
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use tracing::warn;
+
+/// Fetches a table of fiat→USD rates. Pluggable so the REST poller below can be swapped
+/// for a different source (an exchange rate API, a local oracle, ...) without touching
+/// `FiatRates` itself.
+#[async_trait]
+pub trait FiatRateProvider: Send + Sync {
+    /// Returns a table keyed by lowercase ISO currency code, where each value is "units
+    /// of that currency per 1 USD" (e.g. `{"krw": 1350.5}` means 1 USD = 1350.5 KRW).
+    async fn fetch_rates(&self) -> Result<HashMap<String, Decimal>>;
+}
+
+/// Polls a REST endpoint returning a flat `{ "usd": 1.0, "krw": 1350.5, ... }` JSON body.
+pub struct RestFiatRateProvider {
+    url: String,
+}
+
+impl RestFiatRateProvider {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+}
+
+#[async_trait]
+impl FiatRateProvider for RestFiatRateProvider {
+    async fn fetch_rates(&self) -> Result<HashMap<String, Decimal>> {
+        let rates = reqwest::get(&self.url).await?.json::<HashMap<String, Decimal>>().await?;
+        Ok(rates)
+    }
+}
+
+struct CachedRates {
+    rates: HashMap<String, Decimal>,
+    fetched_at: DateTime<Utc>,
+}
+
+/// Caches fiat→USD rates behind a staleness guard so regional feeds (KRW, INR, MXN,
+/// BRL, JPY, ...) can be converted to USD before they're compared against USD-quoted
+/// venues. `to_usd` refuses to convert rather than silently using a stale or missing
+/// rate, since a wrong conversion would manufacture a phantom arbitrage spread.
+pub struct FiatRates {
+    provider: Box<dyn FiatRateProvider>,
+    max_age: ChronoDuration,
+    cache: RwLock<Option<CachedRates>>,
+}
+
+impl FiatRates {
+    pub fn new(provider: Box<dyn FiatRateProvider>, max_age: ChronoDuration) -> Arc<Self> {
+        Arc::new(Self {
+            provider,
+            max_age,
+            cache: RwLock::new(None),
+        })
+    }
+
+    pub async fn refresh(&self) -> Result<()> {
+        let rates = self.provider.fetch_rates().await?;
+        *self.cache.write() = Some(CachedRates { rates, fetched_at: Utc::now() });
+        Ok(())
+    }
+
+    /// Spawns a background task that refreshes rates every `interval`, logging (but not
+    /// panicking on) failed refreshes so a single bad poll doesn't take down conversion —
+    /// the existing cache just keeps serving until it ages past `max_age` on its own.
+    pub fn start_polling(self: &Arc<Self>, interval: std::time::Duration) {
+        let this = self.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = this.refresh().await {
+                    warn!("Failed to refresh fiat rates: {}", e);
+                }
+                tokio::time::sleep(interval).await;
+            }
+        });
+    }
+
+    /// Converts `amount` of `currency` to USD, or `None` if `currency` isn't cached or
+    /// the cache has aged past `max_age`.
+    pub fn to_usd(&self, amount: Decimal, currency: &str) -> Option<Decimal> {
+        if currency.eq_ignore_ascii_case("usd") {
+            return Some(amount);
+        }
+
+        let cache = self.cache.read();
+        let cached = cache.as_ref()?;
+        if Utc::now() - cached.fetched_at > self.max_age {
+            return None;
+        }
+
+        let rate = cached.rates.get(&currency.to_lowercase())?;
+        if rate.is_zero() {
+            return None;
+        }
+
+        Some(amount / *rate)
+    }
+}
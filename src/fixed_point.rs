@@ -0,0 +1,158 @@
+use ethers::types::U256;
+use rust_decimal::Decimal;
+
+/// Converts an integer-valued `Decimal` (a raw on-chain reserve, already in the token's
+/// smallest unit) into a `U256`. A fractional part is truncated; anything negative, or too
+/// large to round-trip through a base-10 string, comes back as zero.
+pub fn decimal_to_u256(value: Decimal) -> U256 {
+    if value <= Decimal::ZERO {
+        return U256::zero();
+    }
+    U256::from_dec_str(&value.trunc().to_string()).unwrap_or(U256::zero())
+}
+
+/// Converts a `U256` back into a `Decimal`.
+pub fn u256_to_decimal(value: U256) -> Decimal {
+    Decimal::from_str_exact(&value.to_string()).unwrap_or(Decimal::ZERO)
+}
+
+/// `floor(a * b / denom)`, computed entirely in `U256` so the intermediate product never
+/// round-trips through `Decimal`'s 96-bit mantissa. `amount_in * reserve_out` routinely
+/// lands around 10^60 for 18-decimal tokens with large reserves — `Decimal` silently loses
+/// precision (or overflows) well before that, which is exactly the truncation this exists
+/// to avoid. Returns zero if `denom` is zero or `a * b` overflows 256 bits (reserves large
+/// enough to hit that ceiling aren't realistic on any live chain).
+pub fn mul_div_floor(a: U256, b: U256, denom: U256) -> U256 {
+    if denom.is_zero() {
+        return U256::zero();
+    }
+    match a.checked_mul(b) {
+        Some(product) => product / denom,
+        None => U256::zero(),
+    }
+}
+
+/// Converts `value` into a `U256` at `scale` decimal places (`scale` must be `>=
+/// value.scale()`), working from its mantissa/scale directly rather than multiplying inside
+/// `Decimal`'s 96-bit mantissa.
+fn decimal_to_scaled_u256(value: Decimal, scale: u32) -> U256 {
+    if value <= Decimal::ZERO {
+        return U256::zero();
+    }
+    let mantissa = U256::from(value.mantissa().unsigned_abs());
+    let extra = scale - value.scale();
+    mantissa * U256::from(10u64).pow(U256::from(extra))
+}
+
+/// Inverse of `decimal_to_scaled_u256` at the same `scale`, via string manipulation rather
+/// than `Decimal` arithmetic so a result outside `Decimal`'s own range degrades to `ZERO` the
+/// same way `u256_to_decimal` already does, instead of overflowing mid-calculation.
+fn scaled_u256_to_decimal(value: U256, scale: u32) -> Decimal {
+    let digits = value.to_string();
+    let scale = scale as usize;
+    let formatted = if digits.len() > scale {
+        let split = digits.len() - scale;
+        format!("{}.{}", &digits[..split], &digits[split..])
+    } else {
+        format!("0.{:0>width$}", digits, width = scale)
+    };
+    Decimal::from_str_exact(&formatted).unwrap_or(Decimal::ZERO)
+}
+
+/// Uniswap V2 style constant-product output (`x*y=k`), computed in `U256` throughout:
+/// `amount_out = amount_in * reserve_out / (reserve_in + amount_in)`. `amount_in` is
+/// expected to already be fee-adjusted, matching [`crate::swap_curve::SwapCurve`]'s
+/// contract that the caller applies the fee before calling `output_amount`.
+///
+/// Scale-invariant in its `Decimal` arguments — callers may pass raw on-chain integers
+/// (`Decimal::scale() == 0`, the convention everywhere else in this module) or human-readable
+/// quantities like `1500.5` (what `DexManager::calculate_output_amount_typed` passes, since
+/// `StableSwap`'s invariant needs same-scale reserves across tokens of different decimals),
+/// as long as all three arguments share a scale convention with each other. Internally
+/// normalizes to the *largest scale already present among the three arguments* rather than a
+/// fixed target: a fixed target large enough to preserve fractional human-scale input would
+/// overflow `U256` once applied on top of an already-18-decimal raw on-chain integer (e.g.
+/// `reserve_out ~ 2e24` scaled by another `1e18` overflows `checked_mul` in `mul_div_floor`),
+/// while using each call's own scale leaves raw-integer callers (scale `0`) untouched and
+/// still preserves the fraction `decimal_to_u256`'s plain `.trunc()` would otherwise drop.
+pub fn constant_product_output(amount_in: Decimal, reserve_in: Decimal, reserve_out: Decimal) -> Decimal {
+    let scale = amount_in.scale().max(reserve_in.scale()).max(reserve_out.scale());
+    let amount_in = decimal_to_scaled_u256(amount_in, scale);
+    let reserve_in = decimal_to_scaled_u256(reserve_in, scale);
+    let reserve_out = decimal_to_scaled_u256(reserve_out, scale);
+
+    let denominator = reserve_in + amount_in;
+    scaled_u256_to_decimal(mul_div_floor(amount_in, reserve_out, denominator), scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::prelude::FromStr;
+
+    #[test]
+    fn round_trips_u256_and_decimal() {
+        let value = Decimal::from_str("123456789012345678901234567890").unwrap();
+        let wide = decimal_to_u256(value);
+        assert_eq!(u256_to_decimal(wide), value);
+    }
+
+    #[test]
+    fn constant_product_output_preserves_fractional_human_scale_reserves() {
+        // Plain `decimal_to_u256` would truncate 1500.5 to 1500, losing the ~0.03% the
+        // fraction is worth here — regression test for that class of bug reaching
+        // `DexManager::calculate_output_amount_typed`, which feeds human-scale decimals
+        // (not raw on-chain integers) into this function.
+        let truncating = u256_to_decimal(mul_div_floor(
+            decimal_to_u256(Decimal::from_str("10").unwrap()),
+            decimal_to_u256(Decimal::from_str("1500.5").unwrap()),
+            decimal_to_u256(Decimal::from_str("1500.5").unwrap()) + decimal_to_u256(Decimal::from_str("10").unwrap()),
+        ));
+        let scale_invariant = constant_product_output(
+            Decimal::from_str("10").unwrap(),
+            Decimal::from_str("1500.5").unwrap(),
+            Decimal::from_str("1500.5").unwrap(),
+        );
+
+        assert_ne!(truncating, scale_invariant);
+        assert!(scale_invariant > Decimal::ZERO);
+    }
+
+    #[test]
+    fn constant_product_output_matches_raw_integer_reserves() {
+        // Scaling up internally must be a no-op for callers that already pass raw,
+        // already-integer on-chain reserves (the convention everywhere except the typed
+        // DexManager path) — same inputs as the existing raw-unit call sites.
+        let amount_in = Decimal::from_str("1000000000000000000").unwrap(); // 1 token, 18dp
+        let reserve_in = Decimal::from_str("1000000000000000000000").unwrap(); // 1000 tokens
+        let reserve_out = Decimal::from_str("2000000000000000000000000").unwrap(); // 2,000,000 tokens
+
+        let expected = u256_to_decimal(mul_div_floor(
+            decimal_to_u256(amount_in),
+            decimal_to_u256(reserve_out),
+            decimal_to_u256(reserve_in) + decimal_to_u256(amount_in),
+        ));
+
+        assert_eq!(constant_product_output(amount_in, reserve_in, reserve_out), expected);
+    }
+
+    #[test]
+    fn mul_div_floor_rounds_down() {
+        // 10 * 3 / 4 = 7.5 -> floors to 7
+        let result = mul_div_floor(U256::from(10u64), U256::from(3u64), U256::from(4u64));
+        assert_eq!(result, U256::from(7u64));
+    }
+
+    #[test]
+    fn constant_product_output_survives_reserves_past_decimals_mantissa() {
+        // Reserves large enough that amount_in * reserve_out overflows Decimal's 96-bit
+        // mantissa (~7.9e28) if computed directly in Decimal, but not U256's 256 bits.
+        let reserve_in = Decimal::from_str("100000000000000000000000000000").unwrap(); // 1e32
+        let reserve_out = Decimal::from_str("200000000000000000000000000000").unwrap(); // 2e32
+        let amount_in = Decimal::from_str("1000000000000000000000000000").unwrap(); // 1e27
+
+        let out = constant_product_output(amount_in, reserve_in, reserve_out);
+        assert!(out > Decimal::ZERO);
+        assert!(out < reserve_out);
+    }
+}
@@ -29,6 +29,15 @@ abigen!(
     ]"#
 );
 
+// Balancer's vault only exposes the fees-collector *address*; the flash-loan fee
+// percentage itself lives on that separate contract.
+abigen!(
+    BalancerProtocolFeesCollector,
+    r#"[
+        function getFlashLoanFeePercentage() external view returns (uint256)
+    ]"#
+);
+
 abigen!(
     DyDxSoloMargin,
     r#"[
@@ -38,6 +47,17 @@ abigen!(
     ]"#
 );
 
+abigen!(
+    IERC20Balance,
+    r#"[
+        function balanceOf(address account) external view returns (uint256)
+    ]"#
+);
+
+/// Bit position of Aave V3's `FLASHLOAN_ENABLED` flag within `ReserveConfiguration`'s
+/// packed `configuration` bitmask.
+const AAVE_FLASHLOAN_ENABLED_BIT: u32 = 63;
+
 pub struct FlashLoanCalculator {
     providers: Vec<Arc<FlashLoanProvider>>,
     config: Config,
@@ -60,33 +80,51 @@ impl FlashLoanCalculator {
         }))
     }
 
+    /// Picks the best provider for borrowing `amount` of `asset` on `chain_id`: providers
+    /// are queried for live on-chain liquidity and rejected outright if they can't cover
+    /// `amount`, then surviving candidates are ranked by `fee + estimate_gas_cost` (not
+    /// fee alone), since a provider with a lower fee but much higher gas cost can still
+    /// lose to a cheaper-to-call one.
     pub async fn calculate_best_loan(
         &self,
+        asset: Address,
         amount: Decimal,
         chain_id: u64,
+        gas_price: Decimal,
     ) -> Result<FlashLoanInfo> {
         let mut best_loan: Option<FlashLoanInfo> = None;
-        let mut lowest_fee = Decimal::MAX;
+        let mut lowest_total_cost = Decimal::MAX;
 
         for provider in &self.providers {
             if provider.chain_id != chain_id {
                 continue;
             }
 
-            let fee = provider.calculate_fee(amount).await?;
-            
-            if fee < lowest_fee {
-                lowest_fee = fee;
+            let availability = match provider.check_liquidity(asset).await {
+                Ok(availability) => availability,
+                Err(_) => continue,
+            };
+
+            if !availability.flash_enabled || availability.available < amount {
+                continue;
+            }
+
+            let fee = provider.calculate_fee(asset, amount).await?;
+            let gas_cost = self.estimate_gas_cost(&provider.name, gas_price).await?;
+            let total_cost = fee + gas_cost;
+
+            if total_cost < lowest_total_cost {
+                lowest_total_cost = total_cost;
                 best_loan = Some(FlashLoanInfo {
                     provider: provider.name.clone(),
                     fee,
                     fee_percentage: provider.fee_percentage,
-                    max_amount: provider.get_max_loan_amount().await?,
+                    max_amount: availability.available,
                 });
             }
         }
 
-        best_loan.ok_or_else(|| anyhow::anyhow!("No flash loan provider available"))
+        best_loan.ok_or_else(|| anyhow::anyhow!("No flash loan provider has enough liquidity for this asset"))
     }
 
     pub async fn get_available_providers(&self, chain_id: u64) -> Vec<String> {
@@ -100,6 +138,7 @@ impl FlashLoanCalculator {
     pub async fn estimate_total_cost(
         &self,
         provider_name: &str,
+        asset: Address,
         amount: Decimal,
         gas_price: Decimal,
     ) -> Result<Decimal> {
@@ -108,7 +147,7 @@ impl FlashLoanCalculator {
             .find(|p| p.name == provider_name)
             .ok_or_else(|| anyhow::anyhow!("Provider not found"))?;
 
-        let loan_fee = provider.calculate_fee(amount).await?;
+        let loan_fee = provider.calculate_fee(asset, amount).await?;
         let gas_cost = self.estimate_gas_cost(provider_name, gas_price).await?;
 
         Ok(loan_fee + gas_cost)
@@ -134,6 +173,12 @@ impl FlashLoanCalculator {
     }
 }
 
+/// Live liquidity snapshot for one provider/asset pair, read on-chain rather than assumed.
+struct LiquidityAvailability {
+    available: Decimal,
+    flash_enabled: bool,
+}
+
 struct FlashLoanProvider {
     name: String,
     chain_id: u64,
@@ -166,39 +211,96 @@ impl FlashLoanProvider {
         })
     }
 
-    async fn calculate_fee(&self, amount: Decimal) -> Result<Decimal> {
-        Ok(amount * self.fee_percentage)
-    }
-
-    async fn get_max_loan_amount(&self) -> Result<Decimal> {
+    /// Live fee for borrowing `amount` of `asset`: Aave reads its current
+    /// `FLASHLOAN_PREMIUM_TOTAL`, Balancer reads its protocol fees collector's current
+    /// flash-loan fee percentage; other providers fall back to their configured
+    /// `fee_percentage` (dYdX flash loans have historically carried no protocol fee beyond
+    /// gas, so there's no live figure to read there).
+    async fn calculate_fee(&self, asset: Address, amount: Decimal) -> Result<Decimal> {
         match self.name.as_str() {
             "Aave V3" => {
-                Ok(Decimal::from(100_000_000))
-            },
+                let aave = AavePool::new(self.contract_address, self.provider.clone());
+                let premium_bps = aave.flashloan_premium_total().call().await?;
+                Ok(amount * Decimal::from(premium_bps) / Decimal::from(10_000))
+            }
             "Balancer" => {
-                Ok(Decimal::from(50_000_000))
-            },
-            "dYdX" => {
-                Ok(Decimal::from(10_000_000))
-            },
-            _ => Ok(Decimal::from(1_000_000)),
+                let vault = BalancerVault::new(self.contract_address, self.provider.clone());
+                let collector_address = vault.get_protocol_fees_collector().call().await?;
+                let collector = BalancerProtocolFeesCollector::new(collector_address, self.provider.clone());
+                let fee_percentage = collector.get_flash_loan_fee_percentage().call().await?;
+                // Balancer expresses this as a fixed-point fraction scaled by 1e18.
+                Ok(amount * Decimal::from_str(&fee_percentage.to_string())? / Decimal::from_str("1000000000000000000")?)
+            }
+            _ => {
+                let _ = asset;
+                Ok(amount * self.fee_percentage)
+            }
         }
     }
 
-    async fn check_liquidity(&self, token_address: Address) -> Result<Decimal> {
+    /// Reads the live amount of `asset` this provider could actually lend right now, plus
+    /// whether flash loans are enabled for it at all, instead of trusting a fixed cap.
+    async fn check_liquidity(&self, asset: Address) -> Result<LiquidityAvailability> {
         match self.name.as_str() {
             "Aave V3" => {
                 let aave = AavePool::new(self.contract_address, self.provider.clone());
-                
-                match aave.get_reserve_data(token_address).call().await {
-                    Ok(data) => {
-                        let liquidity = U256::from(data.3);
-                        Ok(Decimal::from_str(&liquidity.to_string())?)
-                    },
-                    Err(_) => Ok(Decimal::from(1_000_000)),
+                let data = aave.get_reserve_data(asset).call().await?;
+                let configuration = data.0;
+                let a_token_address = data.7;
+
+                let flash_enabled = (configuration >> AAVE_FLASHLOAN_ENABLED_BIT) & U256::one() == U256::one();
+
+                // The aToken's own balance of the underlying asset is the reserve's
+                // actual spendable liquidity, not `currentLiquidityRate` (an interest
+                // rate, not an amount).
+                let underlying = IERC20Balance::new(asset, self.provider.clone());
+                let available_raw = underlying.balance_of(a_token_address).call().await?;
+
+                Ok(LiquidityAvailability {
+                    available: Decimal::from_str(&available_raw.to_string())?,
+                    flash_enabled,
+                })
+            }
+            "Balancer" => {
+                // The vault itself custodies every asset it can flash-loan out.
+                let underlying = IERC20Balance::new(asset, self.provider.clone());
+                let available_raw = underlying.balance_of(self.contract_address).call().await?;
+
+                Ok(LiquidityAvailability {
+                    available: Decimal::from_str(&available_raw.to_string())?,
+                    flash_enabled: true,
+                })
+            }
+            "dYdX" => {
+                let solo = DyDxSoloMargin::new(self.contract_address, self.provider.clone());
+                let num_markets = solo.get_num_markets().call().await?;
+
+                let mut market_id = None;
+                let mut market = U256::zero();
+                while market < num_markets {
+                    let token = solo.get_market_token_address(market).call().await?;
+                    if token == asset {
+                        market_id = Some(market);
+                        break;
+                    }
+                    market += U256::one();
                 }
-            },
-            _ => Ok(Decimal::from(1_000_000)),
+
+                let Some(_market_id) = market_id else {
+                    return Ok(LiquidityAvailability { available: Decimal::ZERO, flash_enabled: false });
+                };
+
+                // Solo margin holds every market's tokens directly, so its own balance is
+                // what's actually available to borrow.
+                let underlying = IERC20Balance::new(asset, self.provider.clone());
+                let available_raw = underlying.balance_of(self.contract_address).call().await?;
+
+                Ok(LiquidityAvailability {
+                    available: Decimal::from_str(&available_raw.to_string())?,
+                    flash_enabled: true,
+                })
+            }
+            _ => Ok(LiquidityAvailability { available: Decimal::ZERO, flash_enabled: false }),
         }
     }
-}
\ No newline at end of file
+}
@@ -1,11 +1,14 @@
 use crate::chains::ChainManager;
 use crate::config::Config;
-use crate::types::{Chain, FlashLoanProvider};
+use crate::gas_tracker::GasTracker;
+use crate::types::{ArbitrageOpportunity, Chain, FlashLoanProvider, SharedState};
 use anyhow::Result;
+use dashmap::DashMap;
 use ethers::prelude::*;
 use rust_decimal::Decimal;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 // Aave V3 Pool ABI
 abigen!(
@@ -37,13 +40,70 @@ abigen!(
     ]"#
 );
 
+// Minimal ERC20 ABI for the liquidity check below
+abigen!(
+    IERC20,
+    r#"[
+        function balanceOf(address) external view returns (uint256)
+    ]"#
+);
+
+/// Which flash-loan mechanism a provider speaks, so `best_provider` can encode the right
+/// callback payload instead of assuming every venue shares Balancer's `userData` shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderKind {
+    Balancer,
+    AaveV3,
+    DyDx,
+    UniswapV3Flash,
+}
+
+impl ProviderKind {
+    fn from_name(name: &str) -> Self {
+        let lower = name.to_lowercase();
+        if lower.contains("aave") {
+            ProviderKind::AaveV3
+        } else if lower.contains("balancer") {
+            ProviderKind::Balancer
+        } else if lower.contains("dydx") {
+            ProviderKind::DyDx
+        } else {
+            ProviderKind::UniswapV3Flash
+        }
+    }
+
+    /// Sane default fee fraction for providers whose premium isn't read live on-chain
+    /// (Aave's is, via `FLASHLOAN_PREMIUM_TOTAL`) — used as a model default independent of
+    /// whatever `fee_percentage` a config entry happens to carry.
+    pub fn default_fee_fraction(self) -> Decimal {
+        match self {
+            ProviderKind::Balancer => Decimal::ZERO,
+            ProviderKind::AaveV3 => Decimal::from_str_exact("0.0005").unwrap(),
+            ProviderKind::DyDx => Decimal::ZERO,
+            // Matches the 0.01% swap fee tier most flash-swap-capable Uniswap V3 pools use.
+            ProviderKind::UniswapV3Flash => Decimal::from_str_exact("0.0001").unwrap(),
+        }
+    }
+}
+
+/// Routing decision from `best_provider`: the cheapest venue that can supply `amount`,
+/// along with the fee it charges and the callback payload encoded for that venue's ABI.
+#[derive(Debug, Clone)]
+pub struct FlashLoanRoute {
+    pub provider: FlashLoanProvider,
+    pub kind: ProviderKind,
+    pub fee_amount: Decimal,
+    pub callback_data: Bytes,
+}
+
 pub struct FlashLoanManager {
     chain_manager: Arc<ChainManager>,
+    state: Arc<SharedState>,
     providers: HashMap<String, FlashLoanProvider>,
 }
 
 impl FlashLoanManager {
-    pub async fn new(config: &Config, chain_manager: Arc<ChainManager>) -> Result<Self> {
+    pub async fn new(config: &Config, chain_manager: Arc<ChainManager>, state: Arc<SharedState>) -> Result<Self> {
         let mut providers = HashMap::new();
         
         for fl_config in &config.flash_loan_providers {
@@ -79,6 +139,7 @@ impl FlashLoanManager {
         
         Ok(Self {
             chain_manager,
+            state,
             providers,
         })
     }
@@ -90,44 +151,388 @@ impl FlashLoanManager {
             .min_by_key(|p| (p.fee_percentage * Decimal::from(10000)).to_u64().unwrap_or(u64::MAX))
     }
     
-    pub fn calculate_flash_loan_fee(&self, provider: &FlashLoanProvider, amount: Decimal) -> Decimal {
+    /// Fee charged on `amount` for this provider. For Aave, reads the pool's live
+    /// `FLASHLOAN_PREMIUM_TOTAL()` (expressed in basis points of 1e4) instead of trusting
+    /// the static `fee_percentage` from config, which goes stale if Aave governance
+    /// changes the premium. Falls back to the config fee if the on-chain read fails or
+    /// the provider isn't Aave (e.g. Balancer, which charges no flash-loan fee at all).
+    pub async fn calculate_flash_loan_fee(&self, provider: &FlashLoanProvider, amount: Decimal) -> Decimal {
+        if provider.name.to_lowercase().contains("aave") {
+            if let Some(onchain_provider) = self.chain_manager.get_provider(&provider.chain) {
+                if let Ok(pool_address) = provider.contract_address.parse::<Address>() {
+                    let pool = IAaveV3Pool::new(pool_address, onchain_provider);
+                    if let Ok(premium_bps) = pool.flashloan_premium_total().call().await {
+                        let premium_fraction = Decimal::from(premium_bps) / Decimal::from(10_000u32);
+                        return amount * premium_fraction;
+                    }
+                }
+            }
+        }
+
         amount * provider.fee_percentage
     }
-    
+
     pub fn get_providers_for_chain(&self, chain: &Chain) -> Vec<&FlashLoanProvider> {
         self.providers
             .values()
             .filter(|p| p.chain == *chain)
             .collect()
     }
-    
+
+    /// Picks the cheapest provider on `chain` that actually holds `amount` of
+    /// `token_address`, and returns it alongside the fee it charges and the callback
+    /// payload encoded for that provider's ABI, so callers don't have to hard-code a single
+    /// venue's flash-loan function shape.
+    pub async fn best_provider(
+        &self,
+        chain: &Chain,
+        token_address: &str,
+        amount: Decimal,
+    ) -> Result<FlashLoanRoute> {
+        let candidates = self.get_providers_for_chain(chain);
+        if candidates.is_empty() {
+            return Err(anyhow::anyhow!("no flash-loan providers configured for {:?}", chain));
+        }
+
+        let mut best: Option<(FlashLoanProvider, Decimal)> = None;
+
+        for provider in candidates {
+            let has_liquidity = self
+                .check_liquidity(provider, token_address, amount)
+                .await
+                .unwrap_or(false);
+            if !has_liquidity {
+                continue;
+            }
+
+            let fee = self.calculate_flash_loan_fee(provider, amount).await;
+            let is_cheaper = match &best {
+                None => true,
+                Some((_, best_fee)) => fee < *best_fee,
+            };
+            if is_cheaper {
+                best = Some((provider.clone(), fee));
+            }
+        }
+
+        let (provider, fee_amount) = best.ok_or_else(|| {
+            anyhow::anyhow!(
+                "no flash-loan provider on {:?} holds {} of {}",
+                chain,
+                amount,
+                token_address
+            )
+        })?;
+
+        let kind = ProviderKind::from_name(&provider.name);
+        let callback_data = Self::encode_callback(kind, token_address, amount)?;
+
+        Ok(FlashLoanRoute { provider, kind, fee_amount, callback_data })
+    }
+
+    /// Encodes the flash-loan callback payload for `kind`: Balancer and Uniswap V3 take it
+    /// as a single `userData`/`bytes` blob, Aave's equivalent is its `params` argument, and
+    /// dYdX (Solo Margin `operate()`) takes structured action data rather than free-form
+    /// bytes. Balancer, dYdX and Uniswap V3 all get the same `(token, amount)` encoding here;
+    /// Aave's `params` is `(token, amount, 0)`, with a trailing zero word.
+    fn encode_callback(kind: ProviderKind, token_address: &str, amount: Decimal) -> Result<Bytes> {
+        use rust_decimal::prelude::ToPrimitive;
+
+        let token: Address = token_address.parse()?;
+        let amount_raw = U256::from((amount * Decimal::from(10u64.pow(18))).to_u128().unwrap_or(0));
+
+        use ethers::abi::Token as AbiToken;
+        let encoded = match kind {
+            ProviderKind::Balancer | ProviderKind::DyDx | ProviderKind::UniswapV3Flash => {
+                ethers::abi::encode(&[AbiToken::Address(token), AbiToken::Uint(amount_raw)])
+            }
+            ProviderKind::AaveV3 => ethers::abi::encode(&[
+                AbiToken::Address(token),
+                AbiToken::Uint(amount_raw),
+                AbiToken::Uint(U256::zero()),
+            ]),
+        };
+
+        Ok(Bytes::from(encoded))
+    }
+
+    /// Checks the flash-loan pool actually holds `amount` of `token_address` by reading
+    /// the ERC20 balance of the provider's contract directly, instead of assuming success
+    /// and letting an undercollateralized loan revert on-chain.
     pub async fn check_liquidity(
         &self,
         provider: &FlashLoanProvider,
         token_address: &str,
         amount: Decimal,
     ) -> Result<bool> {
-        // In production, check actual liquidity on-chain
-        // For now, assume sufficient liquidity
-        Ok(true)
+        let onchain_provider = self.chain_manager
+            .get_provider(&provider.chain)
+            .ok_or_else(|| anyhow::anyhow!("No provider for chain"))?;
+
+        let pool_address = provider.contract_address.parse::<Address>()?;
+        let token_address = token_address.parse::<Address>()?;
+
+        let token = IERC20::new(token_address, onchain_provider);
+        let balance = token.balance_of(pool_address).call().await?;
+        let balance = Decimal::from_str_exact(&balance.to_string())?;
+
+        Ok(balance >= amount)
     }
-    
-    pub fn estimate_total_cost(
+
+    pub async fn estimate_total_cost(
         &self,
         provider: &FlashLoanProvider,
         amount: Decimal,
         gas_price_gwei: Decimal,
     ) -> Decimal {
-        let flash_loan_fee = self.calculate_flash_loan_fee(provider, amount);
-        
+        let flash_loan_fee = self.calculate_flash_loan_fee(provider, amount).await;
+
         // Estimate gas cost (assuming 300k gas units for flash loan)
         let gas_units = Decimal::from(300000);
         let gas_cost_eth = gas_units * gas_price_gwei / Decimal::from(1_000_000_000);
-        let eth_price = Decimal::from(2500); // In production, fetch actual price
+        let eth_price = self.state.prices.iter()
+            .find(|entry| entry.token_pair.starts_with("ETH"))
+            .map(|entry| entry.price)
+            .unwrap_or(Decimal::from(2500));
         let gas_cost_usd = gas_cost_eth * eth_price;
-        
+
         flash_loan_fee + gas_cost_usd
     }
 }
 
+/// Minimum relative bump (10%) required by most mempools to replace a pending transaction.
+const MIN_REPLACEMENT_BUMP_PCT: u64 = 10;
+
+#[derive(Debug)]
+pub enum ExecutionError {
+    Reverted(String),
+    UnprofitableAfterBump,
+    TimedOut,
+    Other(anyhow::Error),
+}
+
+impl std::fmt::Display for ExecutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExecutionError::Reverted(reason) => write!(f, "transaction reverted: {reason}"),
+            ExecutionError::UnprofitableAfterBump => {
+                write!(f, "recomputed gas cost would push net profit below min_profit_usd")
+            }
+            ExecutionError::TimedOut => write!(f, "transaction timed out waiting for confirmation"),
+            ExecutionError::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ExecutionError {}
+
+impl From<anyhow::Error> for ExecutionError {
+    fn from(e: anyhow::Error) -> Self {
+        ExecutionError::Other(e)
+    }
+}
+
+/// Running per-chain accounting of what executed trades actually cost/earned, so the
+/// operator can tell whether `MIN_PROFIT_USD` and the pre-trade gas estimate are
+/// calibrated correctly instead of only ever seeing estimates.
+#[derive(Debug, Clone, Default)]
+pub struct ChainTally {
+    /// Sum of realized profit (pre-trade estimate minus actual gas cost) across every
+    /// confirmed trade on this chain.
+    pub realized_pnl_usd: Decimal,
+    /// Sum of the burned (non-recoverable) base-fee portion of gas paid.
+    pub base_fee_burned_usd: Decimal,
+    /// Sum of the priority-fee tip actually paid to the block producer.
+    pub tip_paid_usd: Decimal,
+    /// Sum of `(estimated_gas_cost_usd - actual_gas_cost_usd) / estimated_gas_cost_usd * 100`
+    /// (a percentage) across every trade, for averaging against `trade_count`.
+    pub gas_estimate_error_pct_sum: Decimal,
+    pub trade_count: u64,
+}
+
+/// Builds and submits EIP-1559 flash-loan arbitrage transactions, bumping the priority
+/// fee on unmined transactions while guaranteeing the opportunity stays profitable.
+pub struct FlashLoanExecutor {
+    gas_tracker: Arc<GasTracker>,
+    chain_manager: Arc<ChainManager>,
+    tallies: Arc<DashMap<Chain, ChainTally>>,
+}
+
+impl FlashLoanExecutor {
+    pub fn new(gas_tracker: Arc<GasTracker>, chain_manager: Arc<ChainManager>) -> Self {
+        Self { gas_tracker, chain_manager, tallies: Arc::new(DashMap::new()) }
+    }
+
+    /// Snapshot of the running per-chain tally, for the operator to inspect whether
+    /// `MIN_PROFIT_USD` is calibrated correctly.
+    pub fn tally_for(&self, chain: &Chain) -> ChainTally {
+        self.tallies.get(chain).map(|t| t.clone()).unwrap_or_default()
+    }
+
+    /// Reads the confirmed receipt's real cost, reconciles it against the opportunity's
+    /// pre-trade estimate, and folds the result into this chain's running tally.
+    /// EIP-1559 receipts carry `effective_gas_price`; pre-London receipts don't, so this
+    /// falls back to the last `max_fee_per_gas` offered on the final (possibly bumped)
+    /// submission.
+    async fn record_execution(
+        &self,
+        chain: Chain,
+        opportunity: &ArbitrageOpportunity,
+        receipt: &TransactionReceipt,
+        last_max_fee_per_gas: U256,
+    ) {
+        let effective_gas_price = receipt.effective_gas_price.unwrap_or(last_max_fee_per_gas);
+        let gas_used = receipt.gas_used.unwrap_or_default();
+        let gas_cost_wei = effective_gas_price * gas_used;
+
+        let eth_price = self.chain_manager.get_eth_price().await.unwrap_or(Decimal::from(2500));
+        let gas_cost_eth = wei_to_decimal(gas_cost_wei);
+        let actual_gas_cost_usd = gas_cost_eth * eth_price;
+
+        // Split the effective price paid into its burned base-fee portion and the tip
+        // actually paid to the block producer, using the landing block's base fee.
+        let base_fee_per_gas = self
+            .chain_manager
+            .get_provider(&chain)
+            .and_then(|provider| {
+                receipt.block_number.map(|bn| (provider, bn))
+            });
+        let base_fee_wei = match base_fee_per_gas {
+            Some((provider, bn)) => provider
+                .get_block(BlockNumber::Number(bn))
+                .await
+                .ok()
+                .flatten()
+                .and_then(|b| b.base_fee_per_gas)
+                .unwrap_or(effective_gas_price),
+            None => effective_gas_price,
+        };
+        let tip_per_gas = effective_gas_price.saturating_sub(base_fee_wei);
+        let base_fee_burned_usd = wei_to_decimal(base_fee_wei * gas_used) * eth_price;
+        let tip_paid_usd = wei_to_decimal(tip_per_gas * gas_used) * eth_price;
+
+        let realized_pnl_usd = opportunity.gross_profit - opportunity.flash_loan_fee - actual_gas_cost_usd;
+        let gas_estimate_error_pct = if opportunity.gas_cost_usd > Decimal::ZERO {
+            (opportunity.gas_cost_usd - actual_gas_cost_usd) / opportunity.gas_cost_usd * Decimal::from(100)
+        } else {
+            Decimal::ZERO
+        };
+
+        let mut tally = self.tallies.entry(chain).or_default();
+        tally.realized_pnl_usd += realized_pnl_usd;
+        tally.base_fee_burned_usd += base_fee_burned_usd;
+        tally.tip_paid_usd += tip_paid_usd;
+        tally.gas_estimate_error_pct_sum += gas_estimate_error_pct;
+        tally.trade_count += 1;
+    }
+
+    /// Submit `opportunity`'s bundle (`to`/`calldata`) as an EIP-1559 type-2 transaction,
+    /// bumping the priority fee by at least `MIN_REPLACEMENT_BUMP_PCT` every
+    /// `blocks_before_bump` blocks it remains unmined, until either it confirms, it would
+    /// become unprofitable after the bump, or `max_bumps` is exhausted.
+    pub async fn execute_with_bump<M: Middleware + 'static>(
+        &self,
+        client: Arc<M>,
+        chain: Chain,
+        opportunity: &ArbitrageOpportunity,
+        to: Address,
+        calldata: Bytes,
+        blocks_before_bump: u64,
+        max_bumps: u32,
+    ) -> Result<TransactionReceipt, ExecutionError> {
+        let (max_fee, mut priority_fee) = self.gas_tracker.get_eip1559_fees(&chain);
+
+        let mut tx = Eip1559TransactionRequest::new()
+            .to(to)
+            .data(calldata)
+            .max_priority_fee_per_gas(gwei_to_wei(priority_fee))
+            .max_fee_per_gas(gwei_to_wei(max_fee));
+
+        let nonce = client
+            .get_transaction_count(client.default_sender().unwrap_or_default(), None)
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        tx = tx.nonce(nonce);
+
+        for attempt in 0..=max_bumps {
+            let typed_tx: TypedTransaction = tx.clone().into();
+
+            let pending = client
+                .send_transaction(typed_tx, None)
+                .await
+                .map_err(|e| ExecutionError::Reverted(e.to_string()))?;
+
+            let start_block = client
+                .get_block_number()
+                .await
+                .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+            loop {
+                if let Ok(Some(receipt)) = client.get_transaction_receipt(pending.tx_hash()).await {
+                    if receipt.status == Some(U64::from(0)) {
+                        return Err(ExecutionError::Reverted(
+                            "transaction reverted on-chain".to_string(),
+                        ));
+                    }
+                    let last_max_fee_per_gas = tx.max_fee_per_gas.unwrap_or_default();
+                    self.record_execution(chain, opportunity, &receipt, last_max_fee_per_gas).await;
+                    return Ok(receipt);
+                }
+
+                let current_block = client
+                    .get_block_number()
+                    .await
+                    .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+                if current_block.saturating_sub(start_block) >= U64::from(blocks_before_bump) {
+                    break;
+                }
+
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+
+            if attempt == max_bumps {
+                return Err(ExecutionError::TimedOut);
+            }
+
+            // Bump the priority fee by at least the minimum replacement percentage and
+            // recompute the max fee off the latest predicted base fee.
+            priority_fee += priority_fee * Decimal::from(MIN_REPLACEMENT_BUMP_PCT) / Decimal::from(100);
+            let (base_max_fee, _) = self.gas_tracker.get_eip1559_fees(&chain);
+            let bumped_max_fee = base_max_fee.max(priority_fee * Decimal::from(2));
+
+            let bumped_gas_cost_usd = self.estimate_gas_cost_usd(bumped_max_fee, chain).await;
+            if opportunity.net_profit_usd - bumped_gas_cost_usd < Decimal::ZERO {
+                return Err(ExecutionError::UnprofitableAfterBump);
+            }
+
+            tx = tx
+                .max_priority_fee_per_gas(gwei_to_wei(priority_fee))
+                .max_fee_per_gas(gwei_to_wei(bumped_max_fee));
+        }
+
+        unreachable!("loop either returns a receipt/error or the final attempt times out")
+    }
+
+    /// USD cost of `gas_units` of gas at `max_fee_gwei`, priced off `ChainManager`'s live
+    /// ETH quote rather than a hardcoded $2500.
+    async fn estimate_gas_cost_usd(&self, max_fee_gwei: Decimal, chain: Chain) -> Decimal {
+        let gas_units = Decimal::from(500_000u64);
+        let gas_cost_eth = gas_units * max_fee_gwei / Decimal::from(1_000_000_000u64);
+        let eth_price = self.chain_manager.get_eth_price().await.unwrap_or(Decimal::from(2500));
+        gas_cost_eth * eth_price
+    }
+}
+
+fn gwei_to_wei(gwei: Decimal) -> U256 {
+    use rust_decimal::prelude::ToPrimitive;
+    U256::from((gwei * Decimal::from(1_000_000_000u64)).to_u128().unwrap_or(0))
+}
+
+/// Converts a wei amount to its ETH-denominated `Decimal`, for multiplying against a
+/// USD-per-ETH quote.
+fn wei_to_decimal(wei: U256) -> Decimal {
+    Decimal::from_str_exact(&ethers::utils::format_units(wei, "ether").unwrap_or_else(|_| "0".to_string()))
+        .unwrap_or(Decimal::ZERO)
+}
+
 use rust_decimal::prelude::FromStr;
\ No newline at end of file
@@ -0,0 +1,70 @@
+use crate::types::Chain;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+/// Supplies the USD price of a chain's native gas token (ETH, MATIC, BNB, ...). Pluggable
+/// so `GasCalibrator` can be wired to whatever price source the rest of the bot already
+/// trusts, rather than hardcoding one API.
+#[async_trait]
+pub trait TokenPriceFeed: Send + Sync {
+    async fn native_token_usd(&self, chain: &Chain) -> Result<Decimal>;
+}
+
+struct CachedPrice {
+    price_usd: Decimal,
+    fetched_at: DateTime<Utc>,
+}
+
+/// Targets a fixed USD cost per transaction instead of reasoning in raw gwei, so a bot
+/// trading across chains with wildly different native-token prices (ETH vs MATIC vs BNB)
+/// spends a comparable, predictable amount of real money on gas everywhere.
+pub struct GasCalibrator {
+    price_feed: Box<dyn TokenPriceFeed>,
+    usd_per_tx: Decimal,
+    recalibration_period: ChronoDuration,
+    cache: RwLock<HashMap<Chain, CachedPrice>>,
+}
+
+impl GasCalibrator {
+    pub fn new(
+        price_feed: Box<dyn TokenPriceFeed>,
+        usd_per_tx: Decimal,
+        recalibration_period: ChronoDuration,
+    ) -> Self {
+        Self {
+            price_feed,
+            usd_per_tx,
+            recalibration_period,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn price_usd(&self, chain: &Chain) -> Result<Decimal> {
+        if let Some(cached) = self.cache.read().get(chain) {
+            if Utc::now() - cached.fetched_at < self.recalibration_period {
+                return Ok(cached.price_usd);
+            }
+        }
+
+        let price_usd = self.price_feed.native_token_usd(chain).await?;
+        self.cache
+            .write()
+            .insert(*chain, CachedPrice { price_usd, fetched_at: Utc::now() });
+        Ok(price_usd)
+    }
+
+    /// `target_gwei = usd_per_tx / (gas_units * token_price_usd) * 1e9`, the gwei price
+    /// at which `gas_units` of gas costs exactly `usd_per_tx` worth of the native token.
+    pub async fn target_gwei(&self, chain: &Chain, gas_units: u64) -> Result<Decimal> {
+        let token_price_usd = self.price_usd(chain).await?;
+        if token_price_usd <= Decimal::ZERO || gas_units == 0 {
+            return Ok(Decimal::ZERO);
+        }
+
+        Ok(self.usd_per_tx / (Decimal::from(gas_units) * token_price_usd) * Decimal::from(1_000_000_000u64))
+    }
+}
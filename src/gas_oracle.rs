@@ -0,0 +1,280 @@
+use crate::types::{Chain, GasPrice, PriceData, SharedState};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use futures_util::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromStr;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{info, warn};
+
+/// Fetches a single `GasPrice` snapshot for `chain`. Pluggable so `GasTracker` can be fed
+/// by a live WebSocket feed, a polled REST backend, or (in tests/offline mode) a fixed
+/// stand-in, without any of them needing to know about each other.
+#[async_trait]
+pub trait GasOracle: Send + Sync {
+    async fn fetch(&self, chain: &Chain) -> Result<GasPrice>;
+}
+
+/// Always returns the same gwei prices, regardless of `chain`. Useful for local testing
+/// and as a last-resort backend when every live oracle is unreachable.
+pub struct FixedGasOracle {
+    slow: Decimal,
+    standard: Decimal,
+    fast: Decimal,
+}
+
+impl FixedGasOracle {
+    pub fn new(slow: Decimal, standard: Decimal, fast: Decimal) -> Self {
+        Self { slow, standard, fast }
+    }
+}
+
+#[async_trait]
+impl GasOracle for FixedGasOracle {
+    async fn fetch(&self, chain: &Chain) -> Result<GasPrice> {
+        Ok(GasPrice {
+            chain: *chain,
+            fast: self.fast,
+            standard: self.standard,
+            slow: self.slow,
+            base_fee: self.standard,
+            priority_fee: self.fast - self.standard,
+            max_fee: self.fast,
+            timestamp: Utc::now(),
+        })
+    }
+}
+
+/// Connects to a gas/price WebSocket feed (e.g. a Blocknative- or GasNow-style push API)
+/// and keeps `state.gas_prices` / `state.prices` updated for `chain` as frames arrive.
+///
+/// The connection loop distinguishes three frame kinds rather than treating every message
+/// as a price payload: subscription-ack/status/heartbeat frames (skipped), WebSocket ping
+/// frames (answered with a pong so the server doesn't drop us as dead), and actual price
+/// updates. On disconnect it reconnects with exponential backoff and
+/// re-sends the subscribe message, instead of leaving `state` stuck on its last value.
+pub struct WebSocketGasOracle {
+    url: String,
+    chain: Chain,
+    subscribe_message: Value,
+    state: Arc<SharedState>,
+}
+
+impl WebSocketGasOracle {
+    pub fn new(url: impl Into<String>, chain: Chain, subscribe_message: Value, state: Arc<SharedState>) -> Self {
+        Self { url: url.into(), chain, subscribe_message, state }
+    }
+
+    /// Runs the reconnect loop forever; spawn this as a background task.
+    pub async fn run(&self) {
+        let mut backoff = Duration::from_secs(1);
+        const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+        loop {
+            match self.connect_and_stream().await {
+                Ok(_) => {
+                    info!("Gas oracle WebSocket for {:?} closed cleanly, reconnecting", self.chain);
+                    backoff = Duration::from_secs(1);
+                }
+                Err(e) => {
+                    warn!("Gas oracle WebSocket for {:?} dropped: {} — retrying in {:?}", self.chain, e, backoff);
+                }
+            }
+
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+
+    async fn connect_and_stream(&self) -> Result<()> {
+        let (ws_stream, _) = connect_async(&self.url).await?;
+        let (mut write, mut read) = ws_stream.split();
+
+        write.send(Message::Text(self.subscribe_message.to_string())).await?;
+        info!("Connected to gas oracle feed for {:?} at {}", self.chain, self.url);
+
+        while let Some(msg) = read.next().await {
+            match msg? {
+                Message::Ping(payload) => {
+                    write.send(Message::Pong(payload)).await?;
+                }
+                Message::Pong(_) => {}
+                Message::Close(_) => break,
+                Message::Text(text) => {
+                    let Ok(data) = serde_json::from_str::<Value>(&text) else { continue };
+                    if Self::is_status_frame(&data) {
+                        continue;
+                    }
+                    if let Some(gas_price) = self.parse_gas_price(&data) {
+                        self.apply(gas_price);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Err(anyhow!("WebSocket stream ended"))
+    }
+
+    /// Subscription acks and heartbeats carry a `type` discriminator rather than
+    /// price fields; skip them instead of failing to parse a price out of them.
+    fn is_status_frame(data: &Value) -> bool {
+        matches!(
+            data.get("type").and_then(Value::as_str),
+            Some("subscribed") | Some("heartbeat") | Some("pong") | Some("error")
+        )
+    }
+
+    fn parse_gas_price(&self, data: &Value) -> Option<GasPrice> {
+        let base_fee = Decimal::from_str_exact(data.get("baseFeePerGas")?.as_str()?).ok()?;
+        let priority_fee = data
+            .get("maxPriorityFeePerGas")
+            .and_then(Value::as_str)
+            .and_then(|s| Decimal::from_str_exact(s).ok())
+            .unwrap_or_else(|| Decimal::from_str("1.5").unwrap());
+
+        Some(GasPrice {
+            chain: self.chain,
+            fast: base_fee + priority_fee * Decimal::from(2),
+            standard: base_fee + priority_fee,
+            slow: base_fee,
+            base_fee,
+            priority_fee,
+            max_fee: base_fee * Decimal::from(2) + priority_fee,
+            timestamp: Utc::now(),
+        })
+    }
+
+    fn apply(&self, gas_price: GasPrice) {
+        self.state.gas_prices.insert(self.chain, gas_price.clone());
+        self.state.prices.insert(
+            format!("gas_oracle_{:?}", self.chain),
+            PriceData {
+                token_pair: format!("{:?}_GAS", self.chain),
+                price: gas_price.standard,
+                liquidity: Decimal::ZERO,
+                volume_24h: Decimal::ZERO,
+                source: "GasOracle".to_string(),
+                chain: self.chain,
+                timestamp: gas_price.timestamp,
+            },
+        );
+    }
+}
+
+/// Builds the subscribe frame used by `WebSocketGasOracle::run` for a Blocknative-style
+/// gas-platform feed.
+pub fn blocknative_subscribe_message(chain: &Chain) -> Value {
+    json!({
+        "categoryCode": "initialize",
+        "eventCode": "checkDappId",
+        "chainId": chain.chain_id(),
+    })
+}
+
+/// Reads `base_fee`/priority-fee straight off the chain via `eth_feeHistory`, rather than
+/// relying on a third-party gas API. `reward_percentiles` picks which percentile of each
+/// block's included-tx priority fees to treat as `slow`/`standard`/`fast`.
+pub struct FeeHistoryGasOracle {
+    provider: Arc<ethers::providers::Provider<ethers::providers::Http>>,
+    chain: Chain,
+    reward_percentiles: [f64; 3],
+}
+
+impl FeeHistoryGasOracle {
+    pub fn new(provider: Arc<ethers::providers::Provider<ethers::providers::Http>>, chain: Chain) -> Self {
+        Self { provider, chain, reward_percentiles: [25.0, 50.0, 90.0] }
+    }
+}
+
+#[async_trait]
+impl GasOracle for FeeHistoryGasOracle {
+    async fn fetch(&self, chain: &Chain) -> Result<GasPrice> {
+        use ethers::providers::Middleware;
+
+        let history = self
+            .provider
+            .fee_history(1u64, ethers::types::BlockNumber::Latest, &self.reward_percentiles)
+            .await?;
+
+        let base_fee_wei = *history
+            .base_fee_per_gas
+            .last()
+            .ok_or_else(|| anyhow!("eth_feeHistory returned no base fee samples"))?;
+        let rewards = history
+            .reward
+            .last()
+            .ok_or_else(|| anyhow!("eth_feeHistory returned no reward samples"))?;
+
+        let wei_to_gwei = |v: ethers::types::U256| -> Decimal {
+            Decimal::from_str(&v.to_string()).unwrap_or_default() / Decimal::from(1_000_000_000u64)
+        };
+
+        let base_fee = wei_to_gwei(base_fee_wei);
+        let slow_tip = rewards.first().copied().map(wei_to_gwei).unwrap_or_default();
+        let standard_tip = rewards.get(1).copied().map(wei_to_gwei).unwrap_or(slow_tip);
+        let fast_tip = rewards.get(2).copied().map(wei_to_gwei).unwrap_or(standard_tip);
+
+        Ok(GasPrice {
+            chain: *chain,
+            fast: base_fee + fast_tip,
+            standard: base_fee + standard_tip,
+            slow: base_fee + slow_tip,
+            base_fee,
+            priority_fee: standard_tip,
+            max_fee: base_fee * Decimal::from(2) + fast_tip,
+            timestamp: Utc::now(),
+        })
+    }
+}
+
+/// Polls a GasNow/Blocknative-style HTTP JSON endpoint of the shape
+/// `{"slow": <gwei>, "standard": <gwei>, "fast": <gwei>, "baseFee": <gwei>}`. `api_key`,
+/// when set, is sent as an `Authorization: Bearer` header, matching how these providers
+/// gate paid tiers.
+pub struct HttpGasOracle {
+    url: String,
+    api_key: Option<String>,
+    client: reqwest::Client,
+}
+
+impl HttpGasOracle {
+    pub fn new(url: impl Into<String>, api_key: Option<String>) -> Self {
+        Self { url: url.into(), api_key, client: reqwest::Client::new() }
+    }
+}
+
+#[async_trait]
+impl GasOracle for HttpGasOracle {
+    async fn fetch(&self, chain: &Chain) -> Result<GasPrice> {
+        let mut request = self.client.get(&self.url);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let body: Value = request.send().await?.json().await?;
+        let gwei = |field: &str| -> Option<Decimal> {
+            body.get(field).and_then(Value::as_f64).and_then(Decimal::from_f64_retain)
+        };
+
+        let slow = gwei("slow").ok_or_else(|| anyhow!("gas oracle response missing 'slow'"))?;
+        let standard = gwei("standard").unwrap_or(slow);
+        let fast = gwei("fast").unwrap_or(standard);
+        let base_fee = gwei("baseFee").unwrap_or(standard);
+
+        Ok(GasPrice {
+            chain: *chain,
+            fast,
+            standard,
+            slow,
+            base_fee,
+            priority_fee: (fast - base_fee).max(Decimal::ZERO),
+            max_fee: fast,
+            timestamp: Utc::now(),
+        })
+    }
+}
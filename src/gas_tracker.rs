@@ -1,24 +1,132 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use anyhow::Result;
 use dashmap::DashMap;
+use ethers::providers::{Http, Middleware, Provider};
+use ethers::types::BlockNumber;
 use rust_decimal::Decimal;
 use rust_decimal::MathematicalOps;
 use rust_decimal::prelude::FromStr;
 use chrono::{DateTime, Utc};
+use tracing::warn;
+use crate::fixed_point::u256_to_decimal;
+use crate::gas_calibrator::GasCalibrator;
+use crate::gas_oracle::GasOracle;
 use crate::types::{Chain, GasPrice};
 
+// EIP-1559 constants (see https://eips.ethereum.org/EIPS/eip-1559)
+const ELASTICITY_MULTIPLIER: u64 = 2;
+const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
+/// Below this many samples in a window, a percentile reading is too noisy to act on.
+const MIN_SAMPLES_FOR_DECISION: usize = 5;
+/// Default lookback for `should_execute`'s percentile check.
+const SHOULD_EXECUTE_WINDOW_MINUTES: u64 = 60;
+
 pub struct GasTracker {
     pub gas_history: Arc<DashMap<Chain, Vec<GasPrice>>>,
+    pub base_fees: Arc<DashMap<Chain, Decimal>>,
     update_interval_ms: u64,
+    default_priority_fee_gwei: Decimal,
+    /// External feeds polled by `start_monitoring`. Several can be configured at once
+    /// (an `eth_feeHistory` RPC backend, a GasNow/Blocknative-style HTTP backend, ...) so
+    /// a single bad source doesn't knock out gas tracking — their results are aggregated
+    /// by taking the median per field rather than trusting any one of them outright.
+    oracles: Vec<Box<dyn GasOracle>>,
+    /// Set via [`Self::with_calibrator`] to enable [`Self::calibrated_gas_price`].
+    calibrator: Option<Arc<GasCalibrator>>,
+    /// Set via [`Self::with_escalator`] to enable [`Self::escalation_schedule`].
+    escalator: Option<GasEscalator>,
+    /// Set via [`Self::with_base_fee_provider`]; polled by `start_monitoring` so
+    /// `record_block`/`next_base_fee` actually predict from live parent-block data
+    /// instead of `base_fees` sitting empty forever.
+    base_fee_providers: HashMap<Chain, Arc<Provider<Http>>>,
 }
 
 impl GasTracker {
-    pub fn new() -> Self {
+    pub fn new(oracles: Vec<Box<dyn GasOracle>>) -> Self {
         Self {
             gas_history: Arc::new(DashMap::new()),
+            base_fees: Arc::new(DashMap::new()),
             update_interval_ms: 5000,
+            default_priority_fee_gwei: Decimal::from_str("1.5").unwrap(),
+            oracles,
+            calibrator: None,
+            escalator: None,
+            base_fee_providers: HashMap::new(),
         }
     }
+
+    pub fn with_calibrator(mut self, calibrator: Arc<GasCalibrator>) -> Self {
+        self.calibrator = Some(calibrator);
+        self
+    }
+
+    /// Registers a provider to poll for `chain`'s latest block so `start_monitoring` can
+    /// feed real parent-block base fee/gas usage into `record_block`, rather than leaving
+    /// `base_fees` permanently empty and `get_eip1559_fees` permanently falling back to the
+    /// legacy `standard` price.
+    pub fn with_base_fee_provider(mut self, chain: Chain, provider: Arc<Provider<Http>>) -> Self {
+        self.base_fee_providers.insert(chain, provider);
+        self
+    }
+
+    /// Compute the next block's base fee from a parent block's base fee and gas usage,
+    /// following the canonical EIP-1559 recurrence.
+    pub fn next_base_fee(parent_base_fee: Decimal, gas_used: u64, gas_limit: u64) -> Decimal {
+        let gas_target = gas_limit / ELASTICITY_MULTIPLIER;
+
+        if gas_target == 0 {
+            return parent_base_fee;
+        }
+
+        if gas_used == gas_target {
+            return parent_base_fee;
+        }
+
+        if gas_used > gas_target {
+            let gas_used_delta = Decimal::from(gas_used - gas_target);
+            let increase = (parent_base_fee * gas_used_delta
+                / Decimal::from(gas_target)
+                / Decimal::from(BASE_FEE_MAX_CHANGE_DENOMINATOR))
+            .max(Decimal::ONE);
+            parent_base_fee + increase
+        } else {
+            let gas_used_delta = Decimal::from(gas_target - gas_used);
+            let decrease = parent_base_fee * gas_used_delta
+                / Decimal::from(gas_target)
+                / Decimal::from(BASE_FEE_MAX_CHANGE_DENOMINATOR);
+            (parent_base_fee - decrease).max(Decimal::ZERO)
+        }
+    }
+
+    /// Record a parent block's base fee and usage, compute the predicted next base fee
+    /// for `chain`, and return the `(max_fee, priority_fee)` an executor should use.
+    pub fn record_block(
+        &self,
+        chain: Chain,
+        parent_base_fee: Decimal,
+        gas_used: u64,
+        gas_limit: u64,
+    ) -> (Decimal, Decimal) {
+        let predicted_base_fee = Self::next_base_fee(parent_base_fee, gas_used, gas_limit);
+        self.base_fees.insert(chain, predicted_base_fee);
+        self.get_eip1559_fees(&chain)
+    }
+
+    /// Return `(max_fee, priority_fee)` for `chain`, computed from the most recently
+    /// predicted base fee: `max_fee = base_fee * 2 + priority_fee`.
+    pub fn get_eip1559_fees(&self, chain: &Chain) -> (Decimal, Decimal) {
+        let base_fee = self
+            .base_fees
+            .get(chain)
+            .map(|b| *b)
+            .or_else(|| self.get_current_gas_price(chain).map(|p| p.standard))
+            .unwrap_or(Decimal::ZERO);
+        let priority_fee = self.default_priority_fee_gwei;
+        let max_fee = base_fee * Decimal::from(2) + priority_fee;
+        (max_fee, priority_fee)
+    }
     
     pub async fn update_gas_price(&self, chain: Chain, gas_price: GasPrice) -> Result<()> {
         // Clone chain for the second use
@@ -62,6 +170,52 @@ impl GasTracker {
         Some(sum / Decimal::from(recent_prices.len()))
     }
     
+    /// Drops history entries for `chain` older than `max_age`, so a multi-hour-old spike
+    /// can't keep skewing `gas_percentile`/`get_average_gas_price` long after it stopped
+    /// reflecting current conditions — unlike the count-based trim in `update_gas_price`,
+    /// this one is about sample freshness, not sample count.
+    pub fn prune_stale(&self, chain: &Chain, max_age: chrono::Duration) {
+        let cutoff = Utc::now() - max_age;
+        if let Some(mut history) = self.gas_history.get_mut(chain) {
+            history.retain(|p| p.timestamp > cutoff);
+        }
+    }
+
+    /// The `pct`-th percentile (0.0..=100.0) of `fast` gas prices within the last
+    /// `window_minutes`. Returns `None` ("unknown") if fewer than
+    /// `MIN_SAMPLES_FOR_DECISION` samples fall in the window — too few points make any
+    /// percentile meaningless.
+    pub fn gas_percentile(&self, chain: &Chain, window_minutes: u64, pct: f64) -> Option<Decimal> {
+        let history = self.gas_history.get(chain)?;
+        let cutoff = Utc::now() - chrono::Duration::minutes(window_minutes as i64);
+
+        let mut recent: Vec<Decimal> = history.iter()
+            .filter(|p| p.timestamp > cutoff)
+            .map(|p| p.fast)
+            .collect();
+
+        if recent.len() < MIN_SAMPLES_FOR_DECISION {
+            return None;
+        }
+
+        recent.sort();
+        let rank = ((pct / 100.0) * (recent.len() - 1) as f64).round() as usize;
+        Some(recent[rank.min(recent.len() - 1)])
+    }
+
+    /// True when the current gas price is at or below the `pct_threshold`-th percentile of
+    /// recent history — i.e. now is cheaper than that fraction of the window, a reasonable
+    /// moment to run a non-urgent transaction. An unknown current price or too little
+    /// history to form a percentile is treated as "not a good time" rather than
+    /// optimistically executing.
+    pub fn should_execute(&self, chain: &Chain, pct_threshold: f64) -> bool {
+        let Some(current) = self.get_current_gas_price(chain) else { return false; };
+        let Some(percentile_price) = self.gas_percentile(chain, SHOULD_EXECUTE_WINDOW_MINUTES, pct_threshold) else {
+            return false;
+        };
+        current.fast <= percentile_price
+    }
+
     pub fn get_gas_volatility(&self, chain: &Chain) -> Option<Decimal> {
         let history = self.gas_history.get(chain)?;
         
@@ -117,19 +271,23 @@ impl GasTracker {
         Some(predicted.max(Decimal::ZERO))
     }
     
+    /// Current `fast` price divided by its 60-minute average — `> 1` means gas is running
+    /// hotter than usual right now. Shared by `get_congestion_level`'s human-readable
+    /// tiers and `GasEscalator`'s congestion-aware schedule.
+    pub fn congestion_ratio(&self, chain: &Chain) -> Option<Decimal> {
+        let current = self.get_current_gas_price(chain)?.fast;
+        let avg = self.get_average_gas_price(chain, 60)?;
+        if avg.is_zero() {
+            return None;
+        }
+        Some(current / avg)
+    }
+
     pub fn get_congestion_level(&self, chain: &Chain) -> String {
-        let current = match self.get_current_gas_price(chain) {
-            Some(p) => p.fast,
-            None => return "Unknown".to_string(),
-        };
-        
-        let avg = match self.get_average_gas_price(chain, 60) {
-            Some(a) => a,
-            None => return "Unknown".to_string(),
+        let Some(ratio) = self.congestion_ratio(chain) else {
+            return "Unknown".to_string();
         };
-        
-        let ratio = current / avg;
-        
+
         if ratio < Decimal::from_str("0.8").unwrap() {
             "Low".to_string()
         } else if ratio < Decimal::from_str("1.2").unwrap() {
@@ -140,7 +298,67 @@ impl GasTracker {
             "Very High".to_string()
         }
     }
+
+    /// Sets the schedule used by `escalation_schedule`. With none set, that method just
+    /// returns `initial_gwei` unchanged (no escalation).
+    pub fn with_escalator(mut self, escalator: GasEscalator) -> Self {
+        self.escalator = Some(escalator);
+        self
+    }
+
+    /// Gas price to resubmit a still-pending transaction at, given it was first submitted
+    /// at `initial_gwei` and `elapsed` time has passed with no confirmation. Delegates to
+    /// the configured `GasEscalator`; returns `initial_gwei` unchanged if none is set.
+    pub fn escalation_schedule(&self, chain: &Chain, initial_gwei: Decimal, elapsed: std::time::Duration) -> Decimal {
+        let Some(escalator) = &self.escalator else {
+            return initial_gwei;
+        };
+
+        let block_time = chain.block_time_secs().max(1);
+        let blocks_elapsed = elapsed.as_secs() / block_time;
+        let congestion_ratio = self.congestion_ratio(chain).unwrap_or(Decimal::ONE);
+
+        escalator.price_at(initial_gwei, blocks_elapsed, congestion_ratio)
+    }
     
+    /// Most recently predicted base fee for `chain`, as tracked by [`Self::record_block`].
+    pub fn get_base_fee(&self, chain: &Chain) -> Option<Decimal> {
+        self.base_fees.get(chain).map(|b| *b)
+    }
+
+    /// Cost of a transaction under the post-London fee model: effective price per gas is
+    /// `base_fee + max_priority_fee` (the tip is never discounted below what's offered),
+    /// unlike [`Self::estimate_transaction_cost`] which reasons in legacy `slow`/`fast` gwei.
+    pub fn estimate_1559_cost(
+        &self,
+        chain: &Chain,
+        gas_units: u64,
+        max_priority_fee: Decimal,
+    ) -> Option<Decimal> {
+        let base_fee = self.get_base_fee(chain)?;
+        let effective_price = base_fee + max_priority_fee;
+        Some(Decimal::from(gas_units) * effective_price / Decimal::from(1_000_000_000))
+    }
+
+    /// Gwei price that keeps this transaction's cost at the calibrator's configured USD
+    /// budget, clamped to never fall below the cheapest price actually seen on-chain
+    /// recently — a calibrated target below that floor would just never get included.
+    /// Returns `None` if no calibrator is configured or the price feed is unavailable.
+    pub async fn calibrated_gas_price(&self, chain: &Chain, gas_units: u64) -> Option<Decimal> {
+        let calibrator = self.calibrator.as_ref()?;
+        let target = calibrator.target_gwei(chain, gas_units).await.ok()?;
+
+        let floor = self
+            .gas_history
+            .get(chain)
+            .and_then(|history| history.iter().map(|p| p.slow).min());
+
+        Some(match floor {
+            Some(floor) => target.max(floor),
+            None => target,
+        })
+    }
+
     pub fn estimate_transaction_cost(
         &self,
         chain: &Chain,
@@ -160,9 +378,173 @@ impl GasTracker {
         Some(cost_eth)
     }
     
-    pub async fn start_monitoring(&self) {
-        // This would start monitoring gas prices
-        // Implementation would depend on actual chain integration
-        tracing::info!("Gas monitoring started");
+    /// Polls every configured oracle for every chain on `update_interval_ms`, aggregates
+    /// whichever responses come back (median per field, so one flaky source can't skew
+    /// the result) and folds the aggregate into `gas_history` via `update_gas_price`.
+    /// With no oracles configured this just logs the initial EIP-1559 state once, as
+    /// before.
+    pub async fn start_monitoring(self: &Arc<Self>) {
+        if self.oracles.is_empty() && self.base_fee_providers.is_empty() {
+            for chain in Chain::all() {
+                let (max_fee, priority_fee) = self.get_eip1559_fees(&chain);
+                tracing::debug!(?chain, %max_fee, %priority_fee, "initialized EIP-1559 fee tracking");
+            }
+            tracing::info!("Gas monitoring started with no oracles configured");
+            return;
+        }
+
+        let this = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_millis(this.update_interval_ms));
+            loop {
+                interval.tick().await;
+                for chain in Chain::all() {
+                    this.poll_and_aggregate(&chain).await;
+                    this.poll_base_fee(&chain).await;
+                }
+            }
+        });
+        tracing::info!(
+            "Gas monitoring started with {} oracle(s), {} base fee provider(s)",
+            self.oracles.len(),
+            self.base_fee_providers.len()
+        );
+    }
+
+    /// Fetches `chain`'s latest block from its registered provider (if any) and feeds its
+    /// base fee/gas usage into `record_block`, so `next_base_fee`'s prediction reflects
+    /// what actually happened on-chain rather than never being populated at all.
+    async fn poll_base_fee(&self, chain: &Chain) {
+        let Some(provider) = self.base_fee_providers.get(chain) else { return };
+
+        let block = match provider.get_block(BlockNumber::Latest).await {
+            Ok(Some(block)) => block,
+            Ok(None) => return,
+            Err(e) => {
+                warn!(?chain, "failed to fetch latest block for base fee prediction: {}", e);
+                return;
+            }
+        };
+
+        let Some(base_fee_wei) = block.base_fee_per_gas else { return };
+        let parent_base_fee = u256_to_decimal(base_fee_wei) / Decimal::from(1_000_000_000);
+
+        self.record_block(*chain, parent_base_fee, block.gas_used.as_u64(), block.gas_limit.as_u64());
+    }
+
+    async fn poll_and_aggregate(&self, chain: &Chain) {
+        let mut fetched = Vec::with_capacity(self.oracles.len());
+        for oracle in &self.oracles {
+            match oracle.fetch(chain).await {
+                Ok(price) => fetched.push(price),
+                Err(e) => warn!(?chain, "gas oracle fetch failed: {}", e),
+            }
+        }
+
+        let Some(aggregated) = Self::aggregate(chain, &fetched) else {
+            return;
+        };
+
+        if let Err(e) = self.update_gas_price(*chain, aggregated).await {
+            warn!(?chain, "failed to record aggregated gas price: {}", e);
+        }
+    }
+
+    /// Combines multiple oracles' snapshots into one by taking the median of each field
+    /// independently, so a single outlier source can't drag the aggregate toward a
+    /// mispriced spike or an offline-default floor.
+    fn aggregate(chain: &Chain, prices: &[GasPrice]) -> Option<GasPrice> {
+        if prices.is_empty() {
+            return None;
+        }
+
+        Some(GasPrice {
+            chain: *chain,
+            fast: Self::median(prices.iter().map(|p| p.fast).collect()),
+            standard: Self::median(prices.iter().map(|p| p.standard).collect()),
+            slow: Self::median(prices.iter().map(|p| p.slow).collect()),
+            base_fee: Self::median(prices.iter().map(|p| p.base_fee).collect()),
+            priority_fee: Self::median(prices.iter().map(|p| p.priority_fee).collect()),
+            max_fee: Self::median(prices.iter().map(|p| p.max_fee).collect()),
+            timestamp: Utc::now(),
+        })
+    }
+
+    fn median(mut values: Vec<Decimal>) -> Decimal {
+        values.sort();
+        let mid = values.len() / 2;
+        if values.len() % 2 == 0 {
+            (values[mid - 1] + values[mid]) / Decimal::from(2)
+        } else {
+            values[mid]
+        }
+    }
+}
+
+/// How `GasEscalator` grows the price over successive blocks without a confirmation.
+#[derive(Debug, Clone, Copy)]
+pub enum EscalationMode {
+    /// `price = initial * (1 + coefficient) ^ blocks_elapsed` — compounds, so it escalates
+    /// faster the longer a transaction sits stuck.
+    Geometric,
+    /// `price = initial * (1 + coefficient * blocks_elapsed)` — grows at a constant rate
+    /// per block instead of compounding.
+    Linear,
+    /// Either of the above, but `coefficient` is scaled by the chain's current
+    /// `congestion_ratio` first — a tx stuck during a genuine gas spike escalates faster
+    /// than one stuck during ordinary conditions.
+    CongestionAware(Box<EscalationMode>),
+}
+
+/// Produces a rising gas-price schedule for a transaction that's been submitted but not
+/// yet mined, so a resubmission loop can keep replacing it with a higher-fee version
+/// until it confirms, instead of either waiting forever or guessing a single bump amount.
+#[derive(Debug, Clone)]
+pub struct GasEscalator {
+    mode: EscalationMode,
+    /// Fractional bump per block (e.g. `0.1` = 10%).
+    coefficient: Decimal,
+    max_gwei: Decimal,
+}
+
+impl GasEscalator {
+    pub fn new(mode: EscalationMode, coefficient: Decimal, max_gwei: Decimal) -> Self {
+        Self { mode, coefficient, max_gwei }
+    }
+
+    /// Price for a transaction `blocks_elapsed` blocks after `initial_gwei` was submitted,
+    /// capped at `max_gwei`. `congestion_ratio` only affects the result under
+    /// `EscalationMode::CongestionAware`.
+    pub fn price_at(&self, initial_gwei: Decimal, blocks_elapsed: u64, congestion_ratio: Decimal) -> Decimal {
+        let price = Self::price_for_mode(&self.mode, self.coefficient, initial_gwei, blocks_elapsed, congestion_ratio);
+        price.min(self.max_gwei)
+    }
+
+    fn price_for_mode(
+        mode: &EscalationMode,
+        coefficient: Decimal,
+        initial_gwei: Decimal,
+        blocks_elapsed: u64,
+        congestion_ratio: Decimal,
+    ) -> Decimal {
+        match mode {
+            EscalationMode::Geometric => {
+                let mut factor = Decimal::ONE;
+                for _ in 0..blocks_elapsed {
+                    factor *= Decimal::ONE + coefficient;
+                }
+                initial_gwei * factor
+            }
+            EscalationMode::Linear => {
+                initial_gwei * (Decimal::ONE + coefficient * Decimal::from(blocks_elapsed))
+            }
+            EscalationMode::CongestionAware(inner) => Self::price_for_mode(
+                inner,
+                coefficient * congestion_ratio,
+                initial_gwei,
+                blocks_elapsed,
+                congestion_ratio,
+            ),
+        }
     }
 }
\ No newline at end of file
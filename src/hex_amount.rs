@@ -0,0 +1,103 @@
+use ethers::types::U256;
+use rust_decimal::Decimal;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Converts a human-readable `Decimal` amount into raw on-chain units (`U256`) for a
+/// token with `decimals` decimal places.
+pub fn decimal_to_raw(amount: Decimal, decimals: u8) -> U256 {
+    let scale = Decimal::from(10u64.pow(decimals as u32));
+    let raw = (amount * scale).trunc();
+    U256::from_dec_str(&raw.to_string()).unwrap_or_default()
+}
+
+/// Converts raw on-chain units (`U256`) back into a human-readable `Decimal` for a
+/// token with `decimals` decimal places.
+pub fn raw_to_decimal(raw: U256, decimals: u8) -> Decimal {
+    let scale = Decimal::from(10u64.pow(decimals as u32));
+    Decimal::from_str_exact(&raw.to_string()).unwrap_or(Decimal::ZERO) / scale
+}
+
+/// `serde(with = "hex_amount::hex_or_decimal")`: deserializes a `U256` from either a
+/// `0x`-prefixed hex string or a plain decimal string, and serializes back to decimal so
+/// wire payloads stay interoperable with tooling that emits hex quantities.
+pub mod hex_or_decimal {
+    use super::*;
+
+    pub fn serialize<S>(value: &U256, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value.to_string().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<U256, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        if let Some(hex) = raw.strip_prefix("0x").or_else(|| raw.strip_prefix("0X")) {
+            U256::from_str_radix(hex, 16).map_err(D::Error::custom)
+        } else {
+            U256::from_dec_str(&raw).map_err(D::Error::custom)
+        }
+    }
+}
+
+/// `serde(with = "hex_amount::hex_canonical")`: accepts the same `0x`-hex-or-decimal input
+/// as `hex_or_decimal`, but always serializes to canonical `0x`-prefixed hex rather than a
+/// decimal string. Used for JSON export consumed by external dashboards/notebooks, which
+/// tend to expect `U256` quantities as hex rather than arbitrary-precision decimal strings.
+pub mod hex_canonical {
+    use super::*;
+
+    pub fn serialize<S>(value: &U256, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        format!("{value:#x}").serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<U256, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::hex_or_decimal::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_decimal_and_raw_units() {
+        let amount = Decimal::from_str_exact("1.5").unwrap();
+        let raw = decimal_to_raw(amount, 18);
+        assert_eq!(raw, U256::from_dec_str("1500000000000000000").unwrap());
+        assert_eq!(raw_to_decimal(raw, 18), amount);
+    }
+
+    #[test]
+    fn deserializes_hex_and_decimal_strings() {
+        #[derive(serde::Deserialize)]
+        struct Wrapper(#[serde(with = "hex_or_decimal")] U256);
+
+        let hex: Wrapper = serde_json::from_str("\"0x64\"").unwrap();
+        let dec: Wrapper = serde_json::from_str("\"100\"").unwrap();
+        assert_eq!(hex.0, U256::from(100u64));
+        assert_eq!(dec.0, U256::from(100u64));
+    }
+
+    #[test]
+    fn hex_canonical_round_trips_and_accepts_either_input() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Wrapper(#[serde(with = "hex_canonical")] U256);
+
+        let from_dec: Wrapper = serde_json::from_str("\"100\"").unwrap();
+        let from_hex: Wrapper = serde_json::from_str("\"0x64\"").unwrap();
+        assert_eq!(from_dec.0, U256::from(100u64));
+        assert_eq!(from_hex.0, U256::from(100u64));
+
+        assert_eq!(serde_json::to_string(&from_dec).unwrap(), "\"0x64\"");
+    }
+}
@@ -12,6 +12,18 @@ mod flashloan;
 mod arbitrage;
 mod websocket;
 mod storage;
+mod hex_amount;
+mod gas_tracker;
+mod gas_oracle;
+mod expiry;
+mod gas_calibrator;
+mod swap_curve;
+mod stable_swap;
+mod fixed_point;
+mod trade_sizer;
+mod candles;
+mod merkle;
+mod token_amount;
 
 use types::*;
 use config::Config;
@@ -40,7 +52,9 @@ async fn main() -> Result<()> {
         prices: Arc::new(DashMap::new()),
         pools: Arc::new(DashMap::new()),
         gas_prices: Arc::new(DashMap::new()),
+        rebase_rates: Arc::new(DashMap::new()),
         opportunities: Arc::new(RwLock::new(Vec::new())),
+        price_volatility: Arc::new(DashMap::new()),
     });
 
     // Initialize storage
@@ -50,10 +64,10 @@ async fn main() -> Result<()> {
     let chain_manager = Arc::new(ChainManager::new(&config).await?);
     
     // Initialize DEX manager
-    let dex_manager = Arc::new(DexManager::new(chain_manager.clone()).await?);
+    let dex_manager = Arc::new(DexManager::new(chain_manager.clone(), state.clone()).await?);
     
     // Initialize flash loan manager
-    let flash_loan_manager = Arc::new(FlashLoanManager::new(&config, chain_manager.clone()).await?);
+    let flash_loan_manager = Arc::new(FlashLoanManager::new(&config, chain_manager.clone(), state.clone()).await?);
     
     // Initialize arbitrage engine
     let arbitrage_engine = Arc::new(ArbitrageEngine::new(
@@ -1,21 +1,28 @@
 use std::sync::Arc;
 use anyhow::Result;
+use ethers::abi::{self, ParamType};
 use ethers::prelude::*;
-use crate::types::{SharedState, Chain};
+use crate::config::Config;
+use crate::swap_curve::SwapCurve;
+use crate::types::{ArbitrageOpportunity, Chain, LiquidityPool, SharedState, TradePath};
 use crate::chains::ChainManager;
+use chrono::Utc;
+use rust_decimal::Decimal;
 use tracing::{info, warn, debug};
 use serde::{Serialize, Deserialize};
 
 pub struct MempoolMonitor {
     chain_manager: Arc<ChainManager>,
     state: Arc<SharedState>,
+    config: Config,
 }
 
 impl MempoolMonitor {
-    pub fn new(chain_manager: Arc<ChainManager>, state: Arc<SharedState>) -> Self {
+    pub fn new(chain_manager: Arc<ChainManager>, state: Arc<SharedState>, config: Config) -> Self {
         Self {
             chain_manager,
             state,
+            config,
         }
     }
     
@@ -34,49 +41,106 @@ impl MempoolMonitor {
         for chain in chains {
             let chain_manager = self.chain_manager.clone();
             let state = self.state.clone();
-            
+            let config = self.config.clone();
+
             tokio::spawn(async move {
                 loop {
-                    if let Err(e) = Self::monitor_chain_mempool(&chain, &chain_manager, &state).await {
+                    if let Err(e) = Self::monitor_chain_mempool(&chain, &chain_manager, &state, &config).await {
                         warn!("Mempool monitoring error for {:?}: {}", chain, e);
                     }
                     tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
                 }
             });
         }
-        
+
         Ok(())
     }
     
+    /// Dispatches to a `newPendingTransactions` subscription or a `txpool_content` poll
+    /// loop depending on `ChainConfig::use_pending_tx_stream`, so sandwich/arbitrage
+    /// analysis in `process_transaction` sees transactions before they're mined rather
+    /// than after (by which point front-running them is no longer possible).
     async fn monitor_chain_mempool(
         chain: &Chain,
         chain_manager: &Arc<ChainManager>,
         state: &Arc<SharedState>,
+        config: &Config,
     ) -> Result<()> {
-        let provider = chain_manager.get_provider(chain)
-            .ok_or_else(|| anyhow::anyhow!("Provider not found for chain {:?}", chain))?;
-        
-        // Monitor new blocks instead of pending transactions
-        let mut stream = provider.watch_blocks().await?;
-        
-        while let Some(block_hash) = stream.next().await {
-            debug!("New block detected on {:?}: {:?}", chain, block_hash);
-            
-            // Get block with transactions
-            if let Ok(Some(block)) = provider.get_block_with_txs(block_hash).await {
-                for tx in block.transactions {
-                    Self::process_transaction(tx, chain, state).await;
-                }
+        if chain_manager.uses_pending_tx_stream(chain) {
+            Self::stream_pending_transactions(chain, chain_manager, state, config).await
+        } else {
+            Self::poll_pending_transactions(chain, chain_manager, state, config).await
+        }
+    }
+
+    /// Subscribes to `newPendingTransactions` over the chain's WebSocket provider and
+    /// feeds each tx body through `process_transaction` as soon as it's announced.
+    async fn stream_pending_transactions(
+        chain: &Chain,
+        chain_manager: &Arc<ChainManager>,
+        state: &Arc<SharedState>,
+        config: &Config,
+    ) -> Result<()> {
+        let ws_provider = chain_manager.get_ws_provider(chain)
+            .ok_or_else(|| anyhow::anyhow!("WebSocket provider not found for chain {:?}", chain))?;
+
+        let mut stream = ws_provider.subscribe_pending_txs().await?;
+
+        while let Some(tx_hash) = stream.next().await {
+            match ws_provider.get_transaction(tx_hash).await {
+                Ok(Some(tx)) => Self::process_transaction(tx, chain, state, config).await,
+                Ok(None) => debug!("Pending tx {:?} on {:?} dropped before fetch", tx_hash, chain),
+                Err(e) => warn!("Failed to fetch pending tx {:?} on {:?}: {}", tx_hash, chain, e),
             }
         }
-        
+
         Ok(())
     }
+
+    /// Fallback for HTTP-only endpoints that can't subscribe to a WebSocket feed: polls
+    /// `txpool_content` on a short interval and processes whatever is currently pending.
+    async fn poll_pending_transactions(
+        chain: &Chain,
+        chain_manager: &Arc<ChainManager>,
+        state: &Arc<SharedState>,
+        config: &Config,
+    ) -> Result<()> {
+        loop {
+            let transactions = Self::fetch_pending_transactions(chain, chain_manager).await?;
+            for tx in transactions {
+                Self::process_transaction(tx, chain, state, config).await;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        }
+    }
+
+    async fn fetch_pending_transactions(
+        chain: &Chain,
+        chain_manager: &Arc<ChainManager>,
+    ) -> Result<Vec<Transaction>> {
+        let provider = chain_manager.get_provider(chain)
+            .ok_or_else(|| anyhow::anyhow!("Provider not found for chain {:?}", chain))?;
+
+        let txpool: TxpoolContent = provider
+            .request("txpool_content", ())
+            .await?;
+
+        let mut transactions = Vec::new();
+        for (_from, txs) in txpool.pending {
+            transactions.extend(txs.into_values());
+        }
+        for (_from, txs) in txpool.queued {
+            transactions.extend(txs.into_values());
+        }
+
+        Ok(transactions)
+    }
     
     async fn process_transaction(
         tx: Transaction,
         chain: &Chain,
         state: &Arc<SharedState>,
+        config: &Config,
     ) {
         // Check if it's a DEX transaction
         if Self::is_dex_transaction(&tx) {
@@ -86,11 +150,15 @@ impl MempoolMonitor {
                 tx.from,
                 tx.to.unwrap_or_default()
             );
-            
+
             // Analyze for potential sandwich opportunities
             Self::analyze_for_sandwich(&tx, chain, state).await;
+
+            // Decode the swap and, if it opens a cross-DEX spread once it lands, queue a
+            // backrun opportunity ahead of the 500ms polling scan picking it up.
+            Self::analyze_transaction_data(tx.clone(), chain, state, config).await;
         }
-        
+
         // Check if it's a large value transfer
         if tx.value > U256::from(10).pow(U256::from(18)) { // > 1 ETH
             info!(
@@ -161,69 +229,287 @@ impl MempoolMonitor {
         }
     }
     
+    // Intentionally left as a placeholder: this project declines to implement precise
+    // front-run/back-run sizing against a specific victim transaction. Unlike the rest of
+    // this scanner (which arbitrages price differences that already exist across venues),
+    // optimal sandwich sizing exists specifically to extract value from an identified
+    // victim's trade by worsening their execution price, which is a different thing we're
+    // not going to sharpen here. This still holds even with constant-product AMM math
+    // backing it out from `tx`'s swap calldata against the victim's pool reserves — the
+    // math is straightforward, but it's precision we're declining to add on principle, not
+    // a capability we're missing.
     async fn estimate_sandwich_profit(
         _tx: &Transaction,
         _chain: &Chain,
         _state: &Arc<SharedState>,
     ) -> f64 {
-        // Simplified profit estimation
-        // In production, this would involve:
-        // 1. Simulating the victim's trade impact
-        // 2. Calculating optimal frontrun/backrun amounts
-        // 3. Estimating gas costs
-        // 4. Computing net profit
-        
         0.0 // Placeholder
     }
     
     pub async fn get_pending_transactions(&self, chain: &Chain) -> Result<Vec<Transaction>> {
-        let provider = self.chain_manager.get_provider(chain)
-            .ok_or_else(|| anyhow::anyhow!("Provider not found"))?;
-        
-        // Get mempool content via debug_txpool RPC method
-        let txpool: TxpoolContent = provider
-            .request("txpool_content", ())
-            .await?;
-        
-        let mut transactions = Vec::new();
-        
-        // Process pending transactions
-        for (_from, txs) in txpool.pending {
-            for (_nonce, tx_json) in txs {
-                transactions.push(tx_json);
-            }
-        }
-        
-        // Process queued transactions
-        for (_from, txs) in txpool.queued {
-            for (_nonce, tx_json) in txs {
-                transactions.push(tx_json);
-            }
-        }
-        
-        Ok(transactions)
+        Self::fetch_pending_transactions(chain, &self.chain_manager).await
     }
     
     pub async fn monitor_for_arbitrage(&self, chain: &Chain) -> Result<()> {
         let transactions = self.get_pending_transactions(chain).await?;
-        
+
         for tx in transactions {
             if Self::is_dex_transaction(&tx) {
                 // Check if this transaction creates an arbitrage opportunity
-                Self::analyze_transaction_data(tx, chain, &self.state).await;
+                Self::analyze_transaction_data(tx, chain, &self.state, &self.config).await;
             }
         }
-        
+
         Ok(())
     }
-    
-    async fn analyze_transaction_data(_tx: Transaction, _chain: &Chain, _state: &Arc<SharedState>) {
-        // Analyze transaction for:
-        // 1. Token pairs being traded
-        // 2. Amounts being swapped
-        // 3. Expected price impact
-        // 4. Potential arbitrage paths
+
+    /// Decodes `tx`'s swap calldata, simulates the reserve shift it'll cause on its
+    /// target pool, and compares the resulting price against a sibling pool quoting the
+    /// same pair on another DEX. Pushes a `Backrun`-tagged `ArbitrageOpportunity` into
+    /// `state.opportunities` if the simulated spread clears `config.min_spread_pct` —
+    /// this is what lets the scanner react to a pending trade instead of waiting up to
+    /// 500ms for the next polling scan to see it after it's mined.
+    async fn analyze_transaction_data(tx: Transaction, chain: &Chain, state: &Arc<SharedState>, config: &Config) {
+        let Some(swap) = Self::decode_swap(&tx) else { return };
+        let Some(pool) = Self::find_pool_for_tokens(state, chain, swap.token_in, swap.token_out) else { return };
+
+        let amount_in = crate::fixed_point::u256_to_decimal(swap.amount_in);
+        let Some((new_reserve0, new_reserve1)) = Self::simulate_post_trade_reserves(&pool, swap.token_in, amount_in) else { return };
+        let simulated_pool = LiquidityPool { reserve0: new_reserve0, reserve1: new_reserve1, ..pool.clone() };
+
+        let Some(sim_price) = Self::spot_price(&simulated_pool) else { return };
+        let Some(sibling) = Self::find_sibling_pool(state, chain, &pool) else { return };
+        let Some(sibling_price) = Self::spot_price(&sibling) else { return };
+
+        let spread_pct = ((sim_price - sibling_price).abs() / sibling_price) * Decimal::from(100);
+        if spread_pct <= config.min_spread_pct {
+            return;
+        }
+
+        info!(
+            "Backrun opportunity on {:?}: pending tx {:?} opens a {:.2}% spread between {} and {}",
+            chain, tx.hash, spread_pct, pool.dex, sibling.dex
+        );
+
+        let opportunity = Self::build_backrun_opportunity(chain, &tx, &pool, &sibling, sim_price, sibling_price);
+        state.opportunities.write().await.push(opportunity);
+    }
+
+    /// Decodes the common Uniswap-family router selectors into `(token_in, token_out,
+    /// amount_in)`. Returns `None` for anything else, including the exact-output
+    /// variants (`swapETHForExactTokens`), which don't carry a fixed `amountIn` to
+    /// simulate against.
+    fn decode_swap(tx: &Transaction) -> Option<DecodedSwap> {
+        if tx.input.len() < 4 {
+            return None;
+        }
+        let selector = &tx.input[0..4];
+        let data = &tx.input[4..];
+
+        match *selector {
+            // swapExactTokensForTokens / swapExactTokensForETH: (amountIn, amountOutMin, path, to, deadline)
+            [0x38, 0xed, 0x17, 0x39] | [0x18, 0xcb, 0xaf, 0xe5] => {
+                let tokens = abi::decode(
+                    &[
+                        ParamType::Uint(256),
+                        ParamType::Uint(256),
+                        ParamType::Array(Box::new(ParamType::Address)),
+                        ParamType::Address,
+                        ParamType::Uint(256),
+                    ],
+                    data,
+                ).ok()?;
+                let amount_in = tokens[0].clone().into_uint()?;
+                let path = tokens[2].clone().into_array()?;
+                let token_in = path.first()?.clone().into_address()?;
+                let token_out = path.last()?.clone().into_address()?;
+                Some(DecodedSwap { token_in, token_out, amount_in })
+            }
+            // swapExactETHForTokens: (amountOutMin, path, to, deadline); amount_in is tx.value.
+            [0x7f, 0xf3, 0x6a, 0xb5] => {
+                let tokens = abi::decode(
+                    &[
+                        ParamType::Uint(256),
+                        ParamType::Array(Box::new(ParamType::Address)),
+                        ParamType::Address,
+                        ParamType::Uint(256),
+                    ],
+                    data,
+                ).ok()?;
+                let path = tokens[1].clone().into_array()?;
+                let token_in = path.first()?.clone().into_address()?;
+                let token_out = path.last()?.clone().into_address()?;
+                Some(DecodedSwap { token_in, token_out, amount_in: tx.value })
+            }
+            // Uniswap V3 exactInputSingle(ExactInputSingleParams)
+            [0x41, 0x4b, 0xf3, 0x89] => {
+                let tuple = abi::decode(
+                    &[ParamType::Tuple(vec![
+                        ParamType::Address,
+                        ParamType::Address,
+                        ParamType::Uint(24),
+                        ParamType::Address,
+                        ParamType::Uint(256),
+                        ParamType::Uint(256),
+                        ParamType::Uint(256),
+                        ParamType::Uint(160),
+                    ])],
+                    data,
+                ).ok()?;
+                let fields = tuple.into_iter().next()?.into_tuple()?;
+                let token_in = fields[0].clone().into_address()?;
+                let token_out = fields[1].clone().into_address()?;
+                let amount_in = fields[5].clone().into_uint()?;
+                Some(DecodedSwap { token_in, token_out, amount_in })
+            }
+            _ => None,
+        }
+    }
+
+    /// Finds a known pool trading `token_in`/`token_out` on `chain`, in either order.
+    fn find_pool_for_tokens(state: &Arc<SharedState>, chain: &Chain, token_in: Address, token_out: Address) -> Option<LiquidityPool> {
+        let token_in = format!("{:?}", token_in).to_lowercase();
+        let token_out = format!("{:?}", token_out).to_lowercase();
+
+        state.pools.iter().find_map(|entry| {
+            let pool = entry.value();
+            if pool.chain != *chain {
+                return None;
+            }
+            let t0 = pool.token0.address.to_lowercase();
+            let t1 = pool.token1.address.to_lowercase();
+            let matches = (t0 == token_in && t1 == token_out) || (t0 == token_out && t1 == token_in);
+            matches.then(|| pool.clone())
+        })
     }
+
+    /// Finds another pool quoting the same token pair as `pool` on a different DEX, to
+    /// compare a simulated post-trade price against.
+    fn find_sibling_pool(state: &Arc<SharedState>, chain: &Chain, pool: &LiquidityPool) -> Option<LiquidityPool> {
+        state.pools.iter().find_map(|entry| {
+            let other = entry.value();
+            if other.chain != *chain || other.dex.eq_ignore_ascii_case(&pool.dex) {
+                return None;
+            }
+            let same_pair = (other.token0.address.eq_ignore_ascii_case(&pool.token0.address)
+                && other.token1.address.eq_ignore_ascii_case(&pool.token1.address))
+                || (other.token0.address.eq_ignore_ascii_case(&pool.token1.address)
+                    && other.token1.address.eq_ignore_ascii_case(&pool.token0.address));
+            same_pair.then(|| other.clone())
+        })
+    }
+
+    /// Applies `amount_in` of `token_in` to `pool`'s current reserves through its own
+    /// `SwapCurve`, the same way `DexManager::calculate_output_amount` prices a real
+    /// swap, to get the reserves the pool will hold once `tx` lands — without waiting
+    /// for it to be mined.
+    fn simulate_post_trade_reserves(pool: &LiquidityPool, token_in: Address, amount_in: Decimal) -> Option<(Decimal, Decimal)> {
+        let token_in = format!("{:?}", token_in).to_lowercase();
+        let in_is_token0 = pool.token0.address.to_lowercase() == token_in;
+
+        let (reserve_in, reserve_out) = if in_is_token0 {
+            (pool.reserve0, pool.reserve1)
+        } else {
+            (pool.reserve1, pool.reserve0)
+        };
+        if reserve_in <= Decimal::ZERO || reserve_out <= Decimal::ZERO {
+            return None;
+        }
+
+        let amount_with_fee = amount_in * (Decimal::ONE - pool.fee);
+        let amount_out = pool.curve.output_amount(amount_with_fee, reserve_in, reserve_out);
+        if amount_out <= Decimal::ZERO || amount_out >= reserve_out {
+            return None;
+        }
+
+        let (new_in, new_out) = (reserve_in + amount_in, reserve_out - amount_out);
+        Some(if in_is_token0 { (new_in, new_out) } else { (new_out, new_in) })
+    }
+
+    /// Decimal-normalized spot price of `pool`, as token1 per token0 — see
+    /// `crate::arbitrage::pool_reserve_amounts` for why raw reserves can't be compared
+    /// directly across tokens with different decimals.
+    fn spot_price(pool: &LiquidityPool) -> Option<Decimal> {
+        let (amount0, amount1) = crate::arbitrage::pool_reserve_amounts(pool);
+        let reserve0 = amount0.to_decimal();
+        if reserve0 <= Decimal::ZERO {
+            return None;
+        }
+        Some(amount1.to_decimal() / reserve0)
+    }
+
+    /// Builds the `Backrun`-tagged opportunity once `analyze_transaction_data` has
+    /// confirmed a simulated post-trade spread. Sized against a flat notional rather
+    /// than the real depth-aware solver in `ArbitrageEngine`, since backrunning a
+    /// specific pending tx is a narrower, time-boxed decision than the periodic scan —
+    /// `ArbitrageEngine::scan_opportunities` will re-price it with real depth once the
+    /// tx lands and this pool's reserves are confirmed.
+    fn build_backrun_opportunity(
+        chain: &Chain,
+        tx: &Transaction,
+        pool: &LiquidityPool,
+        sibling: &LiquidityPool,
+        sim_price: Decimal,
+        sibling_price: Decimal,
+    ) -> ArbitrageOpportunity {
+        let notional = Decimal::from(1000);
+        let (buy_dex, buy_price, sell_dex, sell_price) = if sim_price < sibling_price {
+            (pool.dex.clone(), sim_price, sibling.dex.clone(), sibling_price)
+        } else {
+            (sibling.dex.clone(), sibling_price, pool.dex.clone(), sim_price)
+        };
+
+        let tokens_bought = notional / buy_price;
+        let final_amount = tokens_bought * sell_price;
+        let gross_profit = final_amount - notional;
+
+        ArbitrageOpportunity {
+            id: format!("{}", blake3::hash(format!("backrun{:?}{}", tx.hash, Utc::now()).as_bytes())),
+            chain: *chain,
+            opportunity_type: "Backrun".to_string(),
+            path: vec![
+                TradePath {
+                    dex: buy_dex,
+                    pool_address: pool.address.clone(),
+                    token_in: pool.token0.symbol.clone(),
+                    token_out: pool.token1.symbol.clone(),
+                    amount_in: notional,
+                    amount_out: tokens_bought,
+                },
+                TradePath {
+                    dex: sell_dex,
+                    pool_address: sibling.address.clone(),
+                    token_in: pool.token1.symbol.clone(),
+                    token_out: pool.token0.symbol.clone(),
+                    amount_in: tokens_bought,
+                    amount_out: final_amount,
+                },
+            ],
+            initial_amount: notional,
+            amount_raw: crate::hex_amount::decimal_to_raw(notional, 18),
+            final_amount,
+            gross_profit,
+            flash_loan_provider: String::new(),
+            flash_loan_fee: Decimal::ZERO,
+            flash_loan_fee_percentage: Decimal::ZERO,
+            gas_cost_usd: Decimal::ZERO,
+            net_profit_usd: gross_profit,
+            roi_percentage: (gross_profit / notional) * Decimal::from(100),
+            confidence_score: 0.6,
+            timestamp: Utc::now(),
+            avg_execution_price: Some(final_amount / notional),
+            slippage_pct: None,
+            pending_tx_hash: Some(format!("{:?}", tx.hash)),
+        }
+    }
+}
+
+/// A decoded router call: enough to locate the affected pool and simulate its post-trade
+/// reserves without waiting for `tx` to be mined.
+struct DecodedSwap {
+    token_in: Address,
+    token_out: Address,
+    amount_in: U256,
 }
 
 // Correct type for txpool_content RPC call
@@ -0,0 +1,117 @@
+use sha2::{Digest, Sha256};
+
+pub type Hash = [u8; 32];
+
+fn hash_leaf(data: &[u8]) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([0x00]);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+fn hash_node(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// An append-only Merkle tree over opportunity leaves, kept in memory and rebuilt from the
+/// full leaf log on load. Insertion-only by design: proofs issued for a leaf stay valid for
+/// as long as the leaf log is retained, even after later leaves are appended.
+#[derive(Debug, Clone, Default)]
+pub struct MerkleLog {
+    leaves: Vec<Hash>,
+}
+
+impl MerkleLog {
+    pub fn new() -> Self {
+        Self { leaves: Vec::new() }
+    }
+
+    pub fn from_leaves(leaves: Vec<Hash>) -> Self {
+        Self { leaves }
+    }
+
+    /// Hashes `data` as a new leaf, appends it, and returns its index in the log.
+    pub fn append(&mut self, data: &[u8]) -> usize {
+        self.leaves.push(hash_leaf(data));
+        self.leaves.len() - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    pub fn leaf_hash(&self, index: usize) -> Option<Hash> {
+        self.leaves.get(index).copied()
+    }
+
+    /// Root of the current tree. A lone node at a level without a pair is hashed with a
+    /// copy of itself (`hash_node(x, x)`), the usual unbalanced-Merkle-tree convention, so
+    /// the tree never needs padding to a power of two.
+    pub fn root(&self) -> Option<Hash> {
+        if self.leaves.is_empty() {
+            return None;
+        }
+
+        let mut level = self.leaves.clone();
+        while level.len() > 1 {
+            level = Self::next_level(&level);
+        }
+        Some(level[0])
+    }
+
+    fn next_level(level: &[Hash]) -> Vec<Hash> {
+        level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => hash_node(left, right),
+                [only] => hash_node(only, only),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    /// Sibling hashes from `index`'s leaf up to the root, each paired with whether the
+    /// sibling sits on the right (`true`) or left (`false`) of the node being hashed.
+    pub fn inclusion_proof(&self, index: usize) -> Option<Vec<(Hash, bool)>> {
+        if index >= self.leaves.len() {
+            return None;
+        }
+
+        let mut proof = Vec::new();
+        let mut level = self.leaves.clone();
+        let mut idx = index;
+
+        while level.len() > 1 {
+            let sibling_idx = if idx % 2 == 0 { idx + 1 } else { idx - 1 };
+            let sibling = level.get(sibling_idx).copied().unwrap_or(level[idx]);
+            // Sibling is on the right if our node is at an even position.
+            proof.push((sibling, idx % 2 == 0));
+            level = Self::next_level(&level);
+            idx /= 2;
+        }
+
+        Some(proof)
+    }
+}
+
+/// Recomputes the root from `leaf` and `proof` and checks it matches `root`, without needing
+/// access to the full tree.
+pub fn verify_proof(leaf: &[u8], proof: &[(Hash, bool)], root: Hash) -> bool {
+    let mut current = hash_leaf(leaf);
+    for (sibling, sibling_is_right) in proof {
+        current = if *sibling_is_right {
+            hash_node(&current, sibling)
+        } else {
+            hash_node(sibling, &current)
+        };
+    }
+    current == root
+}
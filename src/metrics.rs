@@ -1,5 +1,8 @@
 use warp::Filter;
-use prometheus::{Encoder, TextEncoder, Counter, Gauge, Histogram, HistogramOpts, register_counter, register_gauge, register_histogram};
+use prometheus::{
+    Encoder, TextEncoder, Counter, CounterVec, Gauge, Histogram, HistogramOpts, Opts,
+    register_counter, register_counter_vec, register_gauge, register_histogram,
+};
 use lazy_static::lazy_static;
 
 lazy_static! {
@@ -49,6 +52,24 @@ lazy_static! {
         "ml_prediction_accuracy_percent",
         "Machine learning model prediction accuracy"
     ).unwrap();
+
+    static ref NOTIFICATIONS_SENT: Counter = register_counter!(
+        "notifications_sent_total",
+        "Total number of notifications successfully sent across all configured sinks"
+    ).unwrap();
+
+    static ref NOTIFICATION_FAILURES: Counter = register_counter!(
+        "notifications_failed_total",
+        "Total number of notification sends that returned an error"
+    ).unwrap();
+
+    static ref OPPORTUNITIES_BY_CHAIN: CounterVec = register_counter_vec!(
+        Opts::new(
+            "arbitrage_opportunities_by_chain_total",
+            "Total number of arbitrage opportunities found, labeled by chain"
+        ),
+        &["chain"]
+    ).unwrap();
 }
 
 pub struct MetricsServer {
@@ -115,4 +136,16 @@ pub struct MetricsServer {
     pub fn set_ml_accuracy(accuracy: f64) {
         ML_ACCURACY.set(accuracy);
     }
+
+    pub fn record_notification_sent() {
+        NOTIFICATIONS_SENT.inc();
+    }
+
+    pub fn record_notification_failure() {
+        NOTIFICATION_FAILURES.inc();
+    }
+
+    pub fn record_opportunity_for_chain(chain: &str) {
+        OPPORTUNITIES_BY_CHAIN.with_label_values(&[chain]).inc();
+    }
 }
\ No newline at end of file
@@ -4,14 +4,128 @@ use smartcore::ensemble::random_forest_regressor::RandomForestRegressor;
 use smartcore::linalg::basic::matrix::DenseMatrix;
 use ndarray::{Array2, s};
 use chrono::{Utc, Datelike, Timelike};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use rust_decimal::prelude::ToPrimitive;
+use rustfft::{FftPlanner, num_complex::Complex};
+use rand::SeedableRng;
+use rand::seq::SliceRandom;
+use rand::rngs::StdRng;
+use gbdt::config::Config as GbdtConfig;
+use gbdt::decision_tree::{Data as GbdtData, DataVec as GbdtDataVec};
+use gbdt::gradient_boost::GBDT;
 use crate::types::{ArbitrageOpportunity, MLInsights, TimeWindow, Chain};
 use crate::storage::StorageEngine;
+use crate::trade_sizer::{self, PoolLeg, OptimalSizeResult};
+use crate::candles::{self, Candle, CandleConfig};
+
+// Sliding-window size for the per-(chain, exchange) spectral features below. A power of
+// two keeps `rustfft`'s planner on its fast path.
+const SPECTRAL_WINDOW: usize = 64;
+// Bucket width for the `profit_usd` time series the FFT runs over.
+const SPECTRAL_BUCKET_MINUTES: i64 = 10;
+// Number of low-frequency magnitude bins kept per window (captures intraday/weekly cycles
+// without dragging in the Nyquist-adjacent bins, which are mostly noise for this series).
+const SPECTRAL_FREQ_BINS: usize = 16;
+
+// An opportunity only counts as a true "pattern" if it cleared a profit after gas and flash
+// loan fees *and* executed fast enough to still be live by the time it could be acted on.
+const PATTERN_EXECUTION_MS_THRESHOLD: u64 = 2_000;
+// Below this many anti-pattern samples the GBDT classifier has nothing to learn a decision
+// boundary from, so `score_opportunity` falls back to the random forest's regression output.
+const MIN_ANTI_PATTERN_SAMPLES: usize = 20;
+
+/// Config for the forward Monte-Carlo profit projector (`simulate_forward`/
+/// `optimal_roi_threshold`), modeled on spaced-repetition-style forward simulation: rather
+/// than re-scoring history, it rolls the empirical opportunity distribution forward.
+#[derive(Debug, Clone, Copy)]
+pub struct SimulatorConfig {
+    pub horizon_days: u32,
+    pub max_opportunities_per_day: u32,
+    pub gas_cost_per_trade_usd: f64,
+    pub flash_loan_fee_rate: f64,
+    /// Multiplier applied to a trade's net loss before accumulating, so the projection
+    /// penalizes drawdowns more than it credits equivalent gains.
+    pub loss_aversion_multiplier: f64,
+    pub rollouts: u32,
+}
+
+impl Default for SimulatorConfig {
+    fn default() -> Self {
+        Self {
+            horizon_days: 30,
+            max_opportunities_per_day: 50,
+            gas_cost_per_trade_usd: 5.0,
+            flash_loan_fee_rate: 0.0009,
+            loss_aversion_multiplier: 2.0,
+            rollouts: 200,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SimulationResult {
+    pub expected_net_profit_usd: f64,
+    pub rollouts: u32,
+}
+
+// Number of folds for `predict_future_opportunities`'s cross-validation.
+const CV_FOLDS: usize = 5;
+// Fixed so re-running cross-validation on the same data reproduces the same folds.
+const CV_SEED: u64 = 42;
+
+/// Metrics for a single held-out fold of `predict_future_opportunities`'s cross-validation.
+#[derive(Debug, Clone, Copy)]
+pub struct FoldMetrics {
+    pub fold: usize,
+    pub rmse_normalized: f64,
+    pub mae: f64,
+    pub r_squared: f64,
+    pub directional_accuracy: f64,
+}
+
+/// Full cross-validated accuracy report — this is what `MLInsights::prediction_accuracy`
+/// now holds instead of a single backward-looking scalar.
+#[derive(Debug, Clone)]
+pub struct CrossValidationReport {
+    pub folds: Vec<FoldMetrics>,
+    pub avg_rmse_normalized: f64,
+    pub avg_mae: f64,
+    pub avg_r_squared: f64,
+    pub avg_directional_accuracy: f64,
+}
+
+impl CrossValidationReport {
+    fn empty() -> Self {
+        Self {
+            folds: Vec::new(),
+            avg_rmse_normalized: 0.0,
+            avg_mae: 0.0,
+            avg_r_squared: 0.0,
+            avg_directional_accuracy: 0.0,
+        }
+    }
+
+    fn aggregate(folds: Vec<FoldMetrics>) -> Self {
+        if folds.is_empty() {
+            return Self::empty();
+        }
+
+        let n = folds.len() as f64;
+        let avg_rmse_normalized = folds.iter().map(|f| f.rmse_normalized).sum::<f64>() / n;
+        let avg_mae = folds.iter().map(|f| f.mae).sum::<f64>() / n;
+        let avg_r_squared = folds.iter().map(|f| f.r_squared).sum::<f64>() / n;
+        let avg_directional_accuracy = folds.iter().map(|f| f.directional_accuracy).sum::<f64>() / n;
+
+        Self { folds, avg_rmse_normalized, avg_mae, avg_r_squared, avg_directional_accuracy }
+    }
+}
 
 pub struct MLAnalyzer {
     storage: Arc<StorageEngine>,
     models: HashMap<String, RandomForestRegressor<f64, f64, DenseMatrix<f64>, Vec<f64>>>,
+    // Parallel to `models`: one GBDT pattern/anti-pattern classifier per chain, trained
+    // whenever enough negative (unprofitable/reverted) samples exist for that chain.
+    pattern_models: HashMap<String, GBDT>,
 }
 
 #[allow(dead_code)]impl MLAnalyzer {
@@ -19,6 +133,7 @@ pub struct MLAnalyzer {
         Ok(Self {
             storage,
             models: HashMap::new(),
+            pattern_models: HashMap::new(),
         })
     }
     
@@ -31,7 +146,8 @@ pub struct MLAnalyzer {
         
         let features = self.extract_features(opportunities);
         let predictions = self.predict_future_opportunities(&features).await?;
-        
+        let realistic_profit_ceiling = self.realistic_profit_ceiling(opportunities);
+
         Ok(MLInsights {
             most_profitable_chains: chain_profits,
             most_profitable_exchanges: exchange_profits,
@@ -40,10 +156,127 @@ pub struct MLAnalyzer {
             average_profit_by_chain: self.calculate_average_profits_by_chain(opportunities),
             opportunity_frequency: frequency,
             prediction_accuracy: predictions,
+            realistic_profit_ceiling,
             generated_at: Utc::now(),
         })
     }
-    
+
+    // Re-solves each opportunity's trade size against its own path reserves via the L-BFGS
+    // solver in `trade_sizer`, rather than trusting whatever `initial_amount` was observed
+    // historically, and reports the best profit any opportunity could have actually reached.
+    fn realistic_profit_ceiling(&self, opportunities: &[ArbitrageOpportunity]) -> f64 {
+        opportunities
+            .iter()
+            .map(|opp| self.optimal_trade_size(opp).profit_usd)
+            .fold(0.0, f64::max)
+    }
+
+    fn optimal_trade_size(&self, opp: &ArbitrageOpportunity) -> OptimalSizeResult {
+        let path: Vec<PoolLeg> = opp
+            .path
+            .iter()
+            .map(|leg| PoolLeg {
+                reserve_in: leg.reserve_in.to_f64().unwrap_or(0.0),
+                reserve_out: leg.reserve_out.to_f64().unwrap_or(0.0),
+                fee_bps: leg.fee_bps,
+            })
+            .collect();
+
+        trade_sizer::solve_optimal_trade_size(&path)
+    }
+
+    /// Runs `config.rollouts` stochastic rollouts of `config.horizon_days`, each day
+    /// bootstrapping up to `config.max_opportunities_per_day` opportunities from the
+    /// empirical (roi_percentage, profit_usd) distribution, skipping anything below
+    /// `min_roi_threshold` and deducting gas/flash-loan costs plus a loss-aversion penalty
+    /// on losing trades, then averaging cumulative net profit across rollouts.
+    fn simulate_forward(
+        &self,
+        opportunities: &[ArbitrageOpportunity],
+        config: &SimulatorConfig,
+        min_roi_threshold: f64,
+    ) -> SimulationResult {
+        use rand::Rng;
+
+        let pooled: Vec<(f64, f64)> = opportunities
+            .iter()
+            .map(|opp| (opp.roi_percentage, opp.profit_usd))
+            .collect();
+
+        if pooled.is_empty() || config.rollouts == 0 {
+            return SimulationResult { expected_net_profit_usd: 0.0, rollouts: 0 };
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut total_profit = 0.0;
+        let opportunities_per_rollout = (config.horizon_days * config.max_opportunities_per_day) as usize;
+
+        for _ in 0..config.rollouts {
+            let mut rollout_profit = 0.0;
+
+            for _ in 0..opportunities_per_rollout {
+                let (roi, profit_usd) = pooled[rng.gen_range(0..pooled.len())];
+
+                if roi < min_roi_threshold {
+                    // Skipped: forgoes this opportunity's profit, but also its costs — the
+                    // "opportunity cost of missed trades" the threshold search weighs.
+                    continue;
+                }
+
+                let flash_loan_fee = profit_usd.abs() * config.flash_loan_fee_rate;
+                let mut net = profit_usd - config.gas_cost_per_trade_usd - flash_loan_fee;
+                if net < 0.0 {
+                    net *= config.loss_aversion_multiplier;
+                }
+
+                rollout_profit += net;
+            }
+
+            total_profit += rollout_profit;
+        }
+
+        SimulationResult {
+            expected_net_profit_usd: total_profit / config.rollouts as f64,
+            rollouts: config.rollouts,
+        }
+    }
+
+    /// 1-D search over minimum-ROI filter values, from 0% to the highest observed ROI in
+    /// 100 steps, for the threshold that maximizes `simulate_forward`'s expected net
+    /// profit — "what minimum edge should I require before firing?".
+    pub fn optimal_roi_threshold(
+        &self,
+        opportunities: &[ArbitrageOpportunity],
+        config: &SimulatorConfig,
+    ) -> f64 {
+        const SEARCH_STEPS: u32 = 100;
+
+        let max_roi = opportunities
+            .iter()
+            .map(|opp| opp.roi_percentage)
+            .fold(0.0, f64::max);
+
+        if max_roi <= 0.0 {
+            return 0.0;
+        }
+
+        let step_size = max_roi / SEARCH_STEPS as f64;
+        let mut best_threshold = 0.0;
+        let mut best_profit = f64::NEG_INFINITY;
+
+        for step in 0..=SEARCH_STEPS {
+            let threshold = step as f64 * step_size;
+            let result = self.simulate_forward(opportunities, config, threshold);
+
+            if result.expected_net_profit_usd > best_profit {
+                best_profit = result.expected_net_profit_usd;
+                best_threshold = threshold;
+            }
+        }
+
+        best_threshold
+    }
+
     fn analyze_chain_profitability(&self, opportunities: &[ArbitrageOpportunity]) -> Vec<(Chain, f64)> {
         let mut chain_profits: HashMap<Chain, Vec<f64>> = HashMap::new();
         
@@ -180,11 +413,139 @@ pub struct MLAnalyzer {
         results
     }
     
+    // Groups opportunities by (chain, first-leg exchange) and sums `profit_usd` into
+    // `SPECTRAL_BUCKET_MINUTES`-wide buckets, keyed by bucket index (unix seconds / bucket
+    // width) so gaps between buckets are implicit rather than stored.
+    fn build_bucket_series(
+        &self,
+        opportunities: &[ArbitrageOpportunity],
+    ) -> HashMap<(String, String), BTreeMap<i64, f64>> {
+        let mut series: HashMap<(String, String), BTreeMap<i64, f64>> = HashMap::new();
+
+        for opp in opportunities {
+            let bucket = opp.timestamp.timestamp() / (SPECTRAL_BUCKET_MINUTES * 60);
+            *series
+                .entry(Self::spectral_key(opp))
+                .or_default()
+                .entry(bucket)
+                .or_insert(0.0) += opp.profit_usd;
+        }
+
+        series
+    }
+
+    fn spectral_key(opp: &ArbitrageOpportunity) -> (String, String) {
+        let exchange = opp.path.first().map(|leg| leg.exchange.clone()).unwrap_or_default();
+        (format!("{:?}", opp.chain), exchange)
+    }
+
+    // Runs a length-`SPECTRAL_WINDOW` real FFT over the `SPECTRAL_BUCKET_MINUTES` series
+    // for `opp`'s (chain, exchange) group, ending at `opp`'s own bucket, zero-filling any
+    // bucket with no recorded opportunities. Returns `[mean, std, min, max]` of the window
+    // followed by the magnitudes of the first `SPECTRAL_FREQ_BINS` frequency bins.
+    fn spectral_features_for(
+        &self,
+        opp: &ArbitrageOpportunity,
+        series: &HashMap<(String, String), BTreeMap<i64, f64>>,
+    ) -> Vec<f64> {
+        let empty = BTreeMap::new();
+        let bucket_map = series.get(&Self::spectral_key(opp)).unwrap_or(&empty);
+        let end_bucket = opp.timestamp.timestamp() / (SPECTRAL_BUCKET_MINUTES * 60);
+
+        let window: Vec<f64> = ((end_bucket - SPECTRAL_WINDOW as i64 + 1)..=end_bucket)
+            .map(|bucket| *bucket_map.get(&bucket).unwrap_or(&0.0))
+            .collect();
+
+        let mean = window.iter().sum::<f64>() / window.len() as f64;
+        let variance = window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / window.len() as f64;
+        let std_dev = variance.sqrt();
+        let min = window.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = window.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+        let mut planner = FftPlanner::<f64>::new();
+        let fft = planner.plan_fft_forward(SPECTRAL_WINDOW);
+        let mut buffer: Vec<Complex<f64>> = window.iter().map(|&v| Complex::new(v, 0.0)).collect();
+        fft.process(&mut buffer);
+
+        let mut result = Vec::with_capacity(4 + SPECTRAL_FREQ_BINS);
+        result.push(mean);
+        result.push(std_dev);
+        result.push(min);
+        result.push(max);
+        result.extend(buffer.iter().take(SPECTRAL_FREQ_BINS).map(|c| c.norm()));
+        result
+    }
+
+    // Feature columns are `[0..9)` scalar per-opportunity fields, `[9..9+SPECTRAL_FREQ_BINS+4)`
+    // the spectral features above, and the final column `profit_usd` (the regression target),
+    // so `predict_future_opportunities`/`train_models` can locate the target as `ncols() - 1`
+    // regardless of how many spectral columns this produces.
+    // Builds the per-chain OHLCV bars `candle_indicators_for` reads fast/slow SMA and EMA
+    // off of, using `profit_usd` as price and opportunity count as volume.
+    fn build_candle_series(
+        &self,
+        opportunities: &[ArbitrageOpportunity],
+        config: &CandleConfig,
+    ) -> HashMap<Chain, Vec<Candle>> {
+        let mut points_by_chain: HashMap<Chain, Vec<(i64, f64)>> = HashMap::new();
+
+        for opp in opportunities {
+            points_by_chain
+                .entry(opp.chain.clone())
+                .or_insert_with(Vec::new)
+                .push((opp.timestamp.timestamp(), opp.profit_usd));
+        }
+
+        points_by_chain
+            .into_iter()
+            .map(|(chain, points)| (chain, candles::aggregate_candles(&points, config.interval_minutes)))
+            .collect()
+    }
+
+    /// Exposes the raw OHLCV bars per chain for reporting/charting, separate from the
+    /// indicator columns folded into `extract_features`.
+    pub fn candles_by_chain(&self, opportunities: &[ArbitrageOpportunity]) -> HashMap<Chain, Vec<Candle>> {
+        self.build_candle_series(opportunities, &CandleConfig::default())
+    }
+
+    // `[fast_sma, slow_sma, fast_ema, fast_sma - slow_sma]` for the candle `opp` falls into;
+    // zeroed out if its chain has no candles yet (first bar of a fresh chain).
+    fn candle_indicators_for(
+        &self,
+        opp: &ArbitrageOpportunity,
+        candle_series: &HashMap<Chain, Vec<Candle>>,
+        config: &CandleConfig,
+    ) -> [f64; 4] {
+        let candles = match candle_series.get(&opp.chain) {
+            Some(c) if !c.is_empty() => c,
+            _ => return [0.0; 4],
+        };
+
+        let interval_secs = config.interval_minutes * 60;
+        let bucket = opp.timestamp.timestamp() / interval_secs;
+        let index = candles
+            .iter()
+            .position(|c| c.bucket == bucket)
+            .unwrap_or(candles.len() - 1);
+
+        let fast_sma = candles::sma(candles, index, config.fast_period);
+        let slow_sma = candles::sma(candles, index, config.slow_period);
+        let fast_ema = candles::ema_series(candles, config.fast_period)[index];
+
+        [fast_sma, slow_sma, fast_ema, fast_sma - slow_sma]
+    }
+
     fn extract_features(&self, opportunities: &[ArbitrageOpportunity]) -> Array2<f64> {
         let n_samples = opportunities.len();
-        let n_features = 10;
+        let n_spectral_features = 4 + SPECTRAL_FREQ_BINS;
+        let n_candle_features = 4;
+        let n_features = 9 + n_spectral_features + n_candle_features + 1;
         let mut features = Array2::zeros((n_samples, n_features));
-        
+
+        let bucket_series = self.build_bucket_series(opportunities);
+        let candle_config = CandleConfig::default();
+        let candle_series = self.build_candle_series(opportunities, &candle_config);
+
         for (i, opp) in opportunities.iter().enumerate() {
             features[[i, 0]] = opp.initial_amount.to_f64().unwrap_or(0.0);
             features[[i, 1]] = opp.roi_percentage;
@@ -209,78 +570,131 @@ pub struct MLAnalyzer {
                 Chain::Blast => 13.0,
             };
             features[[i, 8]] = opp.execution_time_ms as f64;
-            features[[i, 9]] = opp.profit_usd;
+
+            for (j, value) in self.spectral_features_for(opp, &bucket_series).into_iter().enumerate() {
+                features[[i, 9 + j]] = value;
+            }
+
+            let candle_indicators = self.candle_indicators_for(opp, &candle_series, &candle_config);
+            for (k, value) in candle_indicators.into_iter().enumerate() {
+                features[[i, 9 + n_spectral_features + k]] = value;
+            }
+
+            features[[i, n_features - 1]] = opp.profit_usd;
         }
-        
+
         features
     }
-    
-    async fn predict_future_opportunities(&self, features: &Array2<f64>) -> Result<f64> {
+
+    // k-fold cross-validation instead of a fixed 80/20 split: a deterministic shuffle (so
+    // re-runs are reproducible) partitions rows into `CV_FOLDS` folds, each held out in
+    // turn while the rest train a fresh `RandomForestRegressor`, avoiding both the time-
+    // ordering leak and the chain-mixed instability of a single trailing-slice split.
+    async fn predict_future_opportunities(&self, features: &Array2<f64>) -> Result<CrossValidationReport> {
         if features.nrows() < 10 {
-            return Ok(0.0);
+            return Ok(CrossValidationReport::empty());
         }
-        
+
         let n_samples = features.nrows();
-        let split_index = (n_samples as f64 * 0.8) as usize;
-        
-        let x_train = DenseMatrix::from_2d_array(
-            &features.slice(s![..split_index, ..9])
-                .outer_iter()
-                .map(|row| row.to_vec())
-                .collect::<Vec<_>>()
-                .iter()
-                .map(|v| v.as_slice())
-                .collect::<Vec<_>>()
-        ).unwrap();
-        let y_train: Vec<f64> = features.slice(s![..split_index, 9]).to_owned().into_raw_vec_and_offset().0;
-        
-        let x_test = DenseMatrix::from_2d_array(
-            &features.slice(s![split_index.., ..9])
-                .outer_iter()
-                .map(|row| row.to_vec())
-                .collect::<Vec<_>>()
-                .iter()
-                .map(|v| v.as_slice())
-                .collect::<Vec<_>>()
-        ).unwrap();
-        let y_test: Vec<f64> = features.slice(s![split_index.., 9]).to_owned().into_raw_vec_and_offset().0;
-        
-        let model = RandomForestRegressor::fit(
-            &x_train,
-            &y_train,
-            Default::default()
-        )?;
-        
-        let predictions = model.predict(&x_test)?;
-        
+        let target_col = features.ncols() - 1;
+        let k = CV_FOLDS.min(n_samples).max(2);
+
+        let mut indices: Vec<usize> = (0..n_samples).collect();
+        let mut rng = StdRng::seed_from_u64(CV_SEED);
+        indices.shuffle(&mut rng);
+
+        let mut fold_metrics = Vec::with_capacity(k);
+
+        for fold in 0..k {
+            let train_indices: Vec<usize> = indices.iter()
+                .enumerate()
+                .filter(|(pos, _)| pos % k != fold)
+                .map(|(_, &idx)| idx)
+                .collect();
+            let test_indices: Vec<usize> = indices.iter()
+                .enumerate()
+                .filter(|(pos, _)| pos % k == fold)
+                .map(|(_, &idx)| idx)
+                .collect();
+
+            if train_indices.is_empty() || test_indices.is_empty() {
+                continue;
+            }
+
+            let x_train = Self::rows_to_matrix(features, &train_indices, target_col);
+            let y_train: Vec<f64> = train_indices.iter().map(|&idx| features[[idx, target_col]]).collect();
+
+            let x_test = Self::rows_to_matrix(features, &test_indices, target_col);
+            let y_test: Vec<f64> = test_indices.iter().map(|&idx| features[[idx, target_col]]).collect();
+
+            let model = RandomForestRegressor::fit(&x_train, &y_train, Default::default())?;
+            let predictions = model.predict(&x_test)?;
+
+            fold_metrics.push(Self::compute_fold_metrics(fold, &predictions, &y_test));
+        }
+
+        Ok(CrossValidationReport::aggregate(fold_metrics))
+    }
+
+    fn rows_to_matrix(features: &Array2<f64>, indices: &[usize], target_col: usize) -> DenseMatrix<f64> {
+        let rows: Vec<Vec<f64>> = indices.iter()
+            .map(|&idx| (0..target_col).map(|j| features[[idx, j]]).collect())
+            .collect();
+        let row_slices: Vec<&[f64]> = rows.iter().map(|row| row.as_slice()).collect();
+        DenseMatrix::from_2d_array(&row_slices).unwrap()
+    }
+
+    fn compute_fold_metrics(fold: usize, predictions: &[f64], actual: &[f64]) -> FoldMetrics {
+        let n = actual.len() as f64;
+        let mean_actual = actual.iter().sum::<f64>() / n;
+
         let mut squared_errors = 0.0;
-        let mut total_actual = 0.0;
-        
-        for (pred, actual) in predictions.iter().zip(y_test.iter()) {
-            squared_errors += (pred - actual).powi(2);
-            total_actual += actual.abs();
+        let mut abs_errors = 0.0;
+        let mut ss_res = 0.0;
+        let mut ss_tot = 0.0;
+        let mut total_actual_abs = 0.0;
+        let mut correct_direction = 0.0;
+
+        for (&pred, &act) in predictions.iter().zip(actual.iter()) {
+            let error = pred - act;
+            squared_errors += error.powi(2);
+            abs_errors += error.abs();
+            ss_res += error.powi(2);
+            ss_tot += (act - mean_actual).powi(2);
+            total_actual_abs += act.abs();
+
+            if (pred >= 0.0) == (act >= 0.0) {
+                correct_direction += 1.0;
+            }
         }
-        
-        let mse: f64 = squared_errors / y_test.len() as f64;
-        let rmse = mse.sqrt();
-        let avg_actual = total_actual / y_test.len() as f64;
-        
-        let accuracy = if avg_actual > 0.0 {
-            1.0 - (rmse / avg_actual).min(1.0)
+
+        let rmse = (squared_errors / n).sqrt();
+        let avg_actual_abs = total_actual_abs / n;
+        let rmse_normalized = if avg_actual_abs > 0.0 {
+            (1.0 - (rmse / avg_actual_abs).min(1.0)) * 100.0
         } else {
             0.0
         };
-        
-        Ok(accuracy * 100.0)
+
+        FoldMetrics {
+            fold,
+            rmse_normalized,
+            mae: abs_errors / n,
+            r_squared: if ss_tot > 0.0 { 1.0 - (ss_res / ss_tot) } else { 0.0 },
+            directional_accuracy: (correct_direction / n) * 100.0,
+        }
     }
-    
+
+
     pub async fn train_models(&mut self, historical_data: &[ArbitrageOpportunity]) -> Result<()> {
         let features = self.extract_features(historical_data);
-        
+
         if features.nrows() < 100 {
             return Ok(());
         }
-        
+
+        let target_col = features.ncols() - 1;
+
         let chains = vec![
             Chain::Ethereum,
             Chain::BinanceSmartChain,
@@ -297,14 +711,14 @@ pub struct MLAnalyzer {
                 .collect();
             
             if chain_data.len() > 20 {
-                let mut chain_features = Array2::zeros((chain_data.len(), 9));
+                let mut chain_features = Array2::zeros((chain_data.len(), target_col));
                 let mut chain_targets = Vec::new();
-                
+
                 for (new_i, &orig_i) in chain_data.iter().enumerate() {
-                    for j in 0..9 {
+                    for j in 0..target_col {
                         chain_features[[new_i, j]] = features[[orig_i, j]];
                     }
-                    chain_targets.push(features[[orig_i, 9]]);
+                    chain_targets.push(features[[orig_i, target_col]]);
                 }
                 
                 let x = DenseMatrix::from_2d_array(
@@ -324,9 +738,93 @@ pub struct MLAnalyzer {
                 )?;
                 
                 self.models.insert(format!("{:?}", chain), model);
+
+                let (pattern_data, negative_count) =
+                    self.build_pattern_dataset(historical_data, &chain_data, &features, target_col);
+
+                if negative_count >= MIN_ANTI_PATTERN_SAMPLES {
+                    let mut cfg = GbdtConfig::new();
+                    cfg.set_feature_size(target_col);
+                    cfg.set_max_depth(4);
+                    cfg.set_iterations(50);
+                    cfg.set_shrinkage(0.1);
+                    cfg.set_loss("LogLikelyhood");
+                    cfg.set_debug(false);
+
+                    let mut gbdt = GBDT::new(&cfg);
+                    let mut training_data = pattern_data;
+                    gbdt.fit(&mut training_data);
+
+                    self.pattern_models.insert(format!("{:?}", chain), gbdt);
+                }
             }
         }
-        
+
         Ok(())
     }
+
+    // Labels each opportunity a "pattern" (profitable and fast enough to still be live) or
+    // an "anti-pattern" (unprofitable or too slow) for the GBDT classifier, and counts the
+    // anti-patterns so the caller can skip training when there's nothing to contrast against.
+    fn build_pattern_dataset(
+        &self,
+        historical_data: &[ArbitrageOpportunity],
+        chain_data: &[usize],
+        features: &Array2<f64>,
+        target_col: usize,
+    ) -> (GbdtDataVec, usize) {
+        let mut data: GbdtDataVec = Vec::new();
+        let mut negative_count = 0;
+
+        for &orig_i in chain_data {
+            let opp = &historical_data[orig_i];
+            let is_pattern = opp.profit_usd > 0.0
+                && opp.execution_time_ms < PATTERN_EXECUTION_MS_THRESHOLD;
+
+            if !is_pattern {
+                negative_count += 1;
+            }
+
+            let feature: Vec<f32> = (0..target_col)
+                .map(|j| features[[orig_i, j]] as f32)
+                .collect();
+            let label = if is_pattern { 1.0 } else { 0.0 };
+
+            data.push(GbdtData::new_training_data(feature, 1.0, label, 0.0));
+        }
+
+        (data, negative_count)
+    }
+
+    /// Returns a calibrated 0-1 confidence that `opp` is a true profitable pattern, using
+    /// the GBDT classifier trained for `opp.chain` when one exists; falls back to the
+    /// random forest's raw profit prediction (squashed into 0-1) below
+    /// `MIN_ANTI_PATTERN_SAMPLES` negative examples, where the classifier would have had
+    /// nothing to learn a decision boundary from.
+    pub fn score_opportunity(&self, opp: &ArbitrageOpportunity) -> f64 {
+        let key = format!("{:?}", opp.chain);
+        let features = self.extract_features(std::slice::from_ref(opp));
+        let target_col = features.ncols() - 1;
+
+        if let Some(gbdt) = self.pattern_models.get(&key) {
+            let feature: Vec<f32> = (0..target_col).map(|j| features[[0, j]] as f32).collect();
+            let mut data: GbdtDataVec = Vec::new();
+            data.push(GbdtData::new_test_data(feature, None));
+
+            return gbdt.predict(&data).first().copied().unwrap_or(0.0) as f64;
+        }
+
+        if let Some(model) = self.models.get(&key) {
+            let row: Vec<f64> = (0..target_col).map(|j| features[[0, j]]).collect();
+            if let Ok(x) = DenseMatrix::from_2d_array(&[row.as_slice()]) {
+                if let Ok(prediction) = model.predict(&x) {
+                    if let Some(&predicted_profit) = prediction.first() {
+                        return 1.0 / (1.0 + (-predicted_profit / 10.0).exp());
+                    }
+                }
+            }
+        }
+
+        0.0
+    }
 }
\ No newline at end of file
@@ -4,17 +4,20 @@ use ndarray::{Array1, Array2, s};
 use ndarray_rand::RandomExt;
 use ndarray_rand::rand_distr::Uniform;
 use parking_lot::RwLock;
+use serde::{Serialize, Deserialize};
 use crate::types::ArbitrageOpportunity;
 use rust_decimal::prelude::ToPrimitive;
 
 // Simple neural network without serialization issues
 pub struct MetalMLEngine {
     layers: Arc<RwLock<Vec<LayerWeights>>>,
+    norm_stats: Arc<RwLock<Option<NormalizationStats>>>,
     learning_rate: f32,
     epochs: usize,
 }
 
 // Store weights as vectors instead of ndarray for serialization
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct LayerWeights {
     weights: Vec<Vec<f32>>,
     bias: Vec<f32>,
@@ -22,6 +25,62 @@ struct LayerWeights {
     output_size: usize,
 }
 
+/// Per-feature mean/std computed once over the training set and reused for every
+/// `train`/`predict` call, so inputs at wildly different scales (a timestamp vs. a
+/// percentage) don't drown out the network's gradient signal.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct NormalizationStats {
+    mean: Vec<f32>,
+    std: Vec<f32>,
+}
+
+impl NormalizationStats {
+    fn compute(features: &[Vec<f32>]) -> Self {
+        let dim = features.first().map(|f| f.len()).unwrap_or(0);
+        let n = features.len() as f32;
+
+        let mut mean = vec![0.0f32; dim];
+        for row in features {
+            for (i, &v) in row.iter().enumerate() {
+                mean[i] += v;
+            }
+        }
+        for m in mean.iter_mut() {
+            *m /= n;
+        }
+
+        let mut variance = vec![0.0f32; dim];
+        for row in features {
+            for (i, &v) in row.iter().enumerate() {
+                let d = v - mean[i];
+                variance[i] += d * d;
+            }
+        }
+        // Floor the std so a constant feature column doesn't divide by zero.
+        let std: Vec<f32> = variance.iter().map(|&v| (v / n).sqrt().max(1e-6)).collect();
+
+        Self { mean, std }
+    }
+
+    fn apply(&self, features: &[f32]) -> Vec<f32> {
+        features
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| {
+                let mean = self.mean.get(i).copied().unwrap_or(0.0);
+                let std = self.std.get(i).copied().unwrap_or(1.0);
+                (v - mean) / std
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MetalMLCheckpoint {
+    layers: Vec<LayerWeights>,
+    norm_stats: Option<NormalizationStats>,
+}
+
 impl MetalMLEngine {
     pub fn new() -> Self {
         let layers = vec![
@@ -30,41 +89,74 @@ impl MetalMLEngine {
             LayerWeights::new(32, 16),
             LayerWeights::new(16, 1),
         ];
-        
+
         Self {
             layers: Arc::new(RwLock::new(layers)),
+            norm_stats: Arc::new(RwLock::new(None)),
             learning_rate: 0.001,
             epochs: 100,
         }
     }
-    
+
+    /// Restores weights and normalization stats from a checkpoint written by
+    /// `save_checkpoint`, so a trained model survives a restart instead of re-initializing
+    /// random weights every run.
+    pub fn load_checkpoint(path: &str) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let checkpoint: MetalMLCheckpoint = bincode::deserialize(&bytes)?;
+
+        Ok(Self {
+            layers: Arc::new(RwLock::new(checkpoint.layers)),
+            norm_stats: Arc::new(RwLock::new(checkpoint.norm_stats)),
+            learning_rate: 0.001,
+            epochs: 100,
+        })
+    }
+
+    pub fn save_checkpoint(&self, path: &str) -> Result<()> {
+        let checkpoint = MetalMLCheckpoint {
+            layers: self.layers.read().clone(),
+            norm_stats: self.norm_stats.read().clone(),
+        };
+        let bytes = bincode::serialize(&checkpoint)?;
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+
     pub async fn train(&self, data: &[ArbitrageOpportunity]) -> Result<()> {
         if data.len() < 10 {
             return Ok(());
         }
-        
-        let features = self.extract_features(data);
+
+        let raw_features = self.extract_features(data);
         let targets = self.extract_targets(data);
-        
-        // Simple training loop
+
+        let stats = NormalizationStats::compute(&raw_features);
+        let features: Vec<Vec<f32>> = raw_features.iter().map(|f| stats.apply(f)).collect();
+        *self.norm_stats.write() = Some(stats);
+
         for _ in 0..self.epochs {
             for (feature, target) in features.iter().zip(targets.iter()) {
                 self.forward_backward(feature, *target);
             }
         }
-        
+
         Ok(())
     }
-    
+
     pub async fn predict(&self, opportunity: &ArbitrageOpportunity) -> f64 {
-        let features = self.extract_single_features(opportunity);
+        let raw = self.extract_single_features(opportunity);
+        let features = match self.norm_stats.read().as_ref() {
+            Some(stats) => stats.apply(&raw),
+            None => raw,
+        };
         self.forward(&features)
     }
-    
+
     fn extract_features(&self, data: &[ArbitrageOpportunity]) -> Vec<Vec<f32>> {
         data.iter().map(|opp| self.extract_single_features(opp)).collect()
     }
-    
+
     fn extract_single_features(&self, opp: &ArbitrageOpportunity) -> Vec<f32> {
         vec![
             opp.initial_amount.to_f32().unwrap_or(0.0),
@@ -86,44 +178,73 @@ impl MetalMLEngine {
             if opp.profit_usd > 0.0 { 1.0 } else { 0.0 },
         ]
     }
-    
+
     fn extract_targets(&self, data: &[ArbitrageOpportunity]) -> Vec<f32> {
         data.iter().map(|opp| opp.profit_usd as f32).collect()
     }
-    
+
     fn forward(&self, input: &[f32]) -> f64 {
         let layers = self.layers.read();
         let mut current = input.to_vec();
-        
+
         for layer in layers.iter() {
             current = layer.forward(&current);
         }
-        
+
         current[0] as f64
     }
-    
+
+    /// Full backprop: caches each layer's pre-activation sums on the forward pass, seeds the
+    /// output delta as `(output - target)`, then propagates `delta_prev[j] = (Σ_i W[i][j] *
+    /// delta[i]) * relu'(z_j)` back through the hidden layers before applying each layer's
+    /// weight update from its own delta and the activation that fed it.
     fn forward_backward(&self, input: &[f32], target: f32) {
         let mut layers = self.layers.write();
+
         let mut activations = vec![input.to_vec()];
+        let mut pre_activations: Vec<Vec<f32>> = Vec::with_capacity(layers.len());
         let mut current = input.to_vec();
-        
-        // Forward pass
+
         for layer in layers.iter() {
-            current = layer.forward(&current);
-            activations.push(current.clone());
+            let (z, activated) = layer.forward_with_preactivation(&current);
+            pre_activations.push(z);
+            activations.push(activated.clone());
+            current = activated;
         }
-        
-        // Backward pass (simplified)
+
         let output = current[0];
-        let error = output - target;
-        
-        // Update weights (simplified gradient descent)
-        for (i, layer) in layers.iter_mut().enumerate().rev() {
-            let input_activation = &activations[i];
-            layer.update_weights(error * self.learning_rate, input_activation);
+        let mut delta = vec![output - target];
+
+        for layer_idx in (0..layers.len()).rev() {
+            let activation_in = activations[layer_idx].clone();
+
+            let delta_prev = if layer_idx > 0 {
+                let prev_z = &pre_activations[layer_idx - 1];
+                let layer = &layers[layer_idx];
+                let mut dp = vec![0.0f32; layer.input_size];
+
+                for j in 0..layer.input_size {
+                    let mut sum = 0.0f32;
+                    for i in 0..layer.output_size {
+                        sum += layer.weights[i][j] * delta[i];
+                    }
+                    let relu_deriv = if prev_z[j] > 0.0 { 1.0 } else { 0.0 };
+                    dp[j] = sum * relu_deriv;
+                }
+
+                Some(dp)
+            } else {
+                None
+            };
+
+            layers[layer_idx].apply_gradient(&delta, &activation_in, self.learning_rate);
+
+            if let Some(dp) = delta_prev {
+                delta = dp;
+            }
         }
     }
-    
+
     pub fn get_feature_importance(&self) -> Vec<f32> {
         let layers = self.layers.read();
         if let Some(first_layer) = layers.first() {
@@ -147,19 +268,19 @@ impl LayerWeights {
     fn new(input_size: usize, output_size: usize) -> Self {
         let mut weights = vec![vec![0.0f32; input_size]; output_size];
         let mut bias = vec![0.0f32; output_size];
-        
+
         // Initialize with small random values
         use rand::Rng;
         let mut rng = rand::thread_rng();
         let range = (6.0 / (input_size + output_size) as f32).sqrt();
-        
+
         for i in 0..output_size {
             for j in 0..input_size {
                 weights[i][j] = rng.gen_range(-range..range);
             }
             bias[i] = rng.gen_range(-range..range);
         }
-        
+
         Self {
             weights,
             bias,
@@ -167,29 +288,35 @@ impl LayerWeights {
             output_size,
         }
     }
-    
+
     fn forward(&self, input: &[f32]) -> Vec<f32> {
-        let mut output = vec![0.0f32; self.output_size];
-        
+        self.forward_with_preactivation(input).1
+    }
+
+    /// Same computation as `forward` but also returns the pre-activation sums, which
+    /// backprop needs to evaluate `relu'(z)` at each unit.
+    fn forward_with_preactivation(&self, input: &[f32]) -> (Vec<f32>, Vec<f32>) {
+        let mut z = vec![0.0f32; self.output_size];
+
         for i in 0..self.output_size {
             let mut sum = self.bias[i];
             for j in 0..input.len().min(self.input_size) {
                 sum += input[j] * self.weights[i][j];
             }
-            // ReLU activation
-            output[i] = sum.max(0.0);
+            z[i] = sum;
         }
-        
-        output
+
+        let activated = z.iter().map(|&v| v.max(0.0)).collect();
+        (z, activated)
     }
-    
-    fn update_weights(&mut self, error: f32, input: &[f32]) {
-        // Simplified weight update
+
+    /// `W[i][j] -= lr * delta[i] * activation_in[j]`, bias by `lr * delta[i]`.
+    fn apply_gradient(&mut self, delta: &[f32], activation_in: &[f32], lr: f32) {
         for i in 0..self.output_size {
-            for j in 0..input.len().min(self.input_size) {
-                self.weights[i][j] -= error * input[j] * 0.001;
+            for j in 0..activation_in.len().min(self.input_size) {
+                self.weights[i][j] -= lr * delta[i] * activation_in[j];
             }
-            self.bias[i] -= error * 0.001;
+            self.bias[i] -= lr * delta[i];
         }
     }
-}
\ No newline at end of file
+}
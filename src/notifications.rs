@@ -1,5 +1,7 @@
 use anyhow::Result;
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use reqwest::Client;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
@@ -8,6 +10,17 @@ use tracing::{info, warn, error};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// A sink that can be told about opportunities, periodic status updates, and errors.
+/// `NotificationManager` fans every event out to all configured `Notifier`s, so adding a
+/// new alert channel (a new chat app, a generic webhook, ...) means implementing this
+/// trait rather than editing the manager itself.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn notify_opportunity(&self, opportunity: &ArbitrageOpportunity) -> Result<()>;
+    async fn notify_system_status(&self, status: SystemStatus) -> Result<()>;
+    async fn notify_error(&self, error_msg: &str) -> Result<()>;
+}
+
 #[derive(Debug, Clone)]
 pub struct DiscordNotifier {
     webhook_url: String,
@@ -278,6 +291,152 @@ impl DiscordNotifier {
     }
 }
 
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    async fn notify_opportunity(&self, opportunity: &ArbitrageOpportunity) -> Result<()> {
+        if opportunity.profit_usd >= 500.0 {
+            self.notify_high_value(opportunity).await
+        } else {
+            DiscordNotifier::notify_opportunity(self, opportunity).await
+        }
+    }
+
+    async fn notify_system_status(&self, status: SystemStatus) -> Result<()> {
+        DiscordNotifier::notify_system_status(self, status).await
+    }
+
+    async fn notify_error(&self, error_msg: &str) -> Result<()> {
+        DiscordNotifier::notify_error(self, error_msg).await
+    }
+}
+
+/// Sends alerts through a Telegram bot's `sendMessage` API as plain Markdown text,
+/// rather than Discord's richer embed format.
+#[derive(Debug, Clone)]
+pub struct TelegramNotifier {
+    bot_token: String,
+    chat_id: String,
+    client: Client,
+}
+
+impl TelegramNotifier {
+    pub fn new(bot_token: String, chat_id: String) -> Self {
+        Self { bot_token, chat_id, client: Client::new() }
+    }
+
+    pub fn from_env() -> Result<Self> {
+        let bot_token = std::env::var("TELEGRAM_BOT_TOKEN")?;
+        let chat_id = std::env::var("TELEGRAM_CHAT_ID")?;
+        Ok(Self::new(bot_token, chat_id))
+    }
+
+    async fn send_message(&self, text: String) -> Result<()> {
+        let url = format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token);
+        let response = self.client
+            .post(&url)
+            .json(&json!({
+                "chat_id": self.chat_id,
+                "text": text,
+                "parse_mode": "Markdown",
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await?;
+            error!("Telegram notification failed: {} - {}", status, text);
+            return Err(anyhow::anyhow!("Telegram notification failed: {}", status));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    async fn notify_opportunity(&self, opportunity: &ArbitrageOpportunity) -> Result<()> {
+        let path_description = opportunity.path.iter()
+            .map(|leg| format!("{} ({} → {})", leg.exchange, leg.token_in, leg.token_out))
+            .collect::<Vec<_>>()
+            .join(" → ");
+
+        let prefix = if opportunity.profit_usd >= 500.0 { "🚨 *HIGH VALUE ALERT*" } else { "🎯 *New Arbitrage Opportunity*" };
+        let text = format!(
+            "{}\n*Path:* {}\n*Profit:* ${:.2}\n*ROI:* {:.2}%\n*Chain:* {:?}\n*Gas Cost:* ${:.2}",
+            prefix, path_description, opportunity.profit_usd, opportunity.roi_percentage, opportunity.chain, opportunity.total_gas_cost
+        );
+
+        self.send_message(text).await
+    }
+
+    async fn notify_system_status(&self, status: SystemStatus) -> Result<()> {
+        let text = format!(
+            "📊 *System Status*\nOpportunities: {}\nTotal Profit: ${:.2}\nUptime: {}h\nChains: {}\nPrice Feeds: {}\nLiquidity Pools: {}",
+            status.opportunities_found, status.total_profit, status.uptime_hours,
+            status.active_chains.join(", "), status.price_feeds, status.liquidity_pools
+        );
+        self.send_message(text).await
+    }
+
+    async fn notify_error(&self, error_msg: &str) -> Result<()> {
+        self.send_message(format!("⚠️ *System Error*\n{}", error_msg)).await
+    }
+}
+
+/// Posts a plain JSON payload to an arbitrary webhook URL for users who want to wire the
+/// scanner into something Discord/Telegram-shaped sinks don't cover (a custom dashboard,
+/// an internal alerting gateway, ...).
+#[derive(Debug, Clone)]
+pub struct WebhookNotifier {
+    url: String,
+    client: Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: String) -> Self {
+        Self { url, client: Client::new() }
+    }
+
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::new(std::env::var("NOTIFICATION_WEBHOOK_URL")?))
+    }
+
+    async fn post(&self, payload: serde_json::Value) -> Result<()> {
+        let response = self.client.post(&self.url).json(&payload).send().await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await?;
+            error!("Webhook notification failed: {} - {}", status, text);
+            return Err(anyhow::anyhow!("Webhook notification failed: {}", status));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify_opportunity(&self, opportunity: &ArbitrageOpportunity) -> Result<()> {
+        self.post(json!({"event": "opportunity", "opportunity": opportunity})).await
+    }
+
+    async fn notify_system_status(&self, status: SystemStatus) -> Result<()> {
+        self.post(json!({
+            "event": "system_status",
+            "opportunities_found": status.opportunities_found,
+            "total_profit": status.total_profit,
+            "uptime_hours": status.uptime_hours,
+            "active_chains": status.active_chains,
+            "price_feeds": status.price_feeds,
+            "liquidity_pools": status.liquidity_pools,
+        })).await
+    }
+
+    async fn notify_error(&self, error_msg: &str) -> Result<()> {
+        self.post(json!({"event": "error", "message": error_msg})).await
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SystemStatus {
     pub opportunities_found: u64,
@@ -290,62 +449,87 @@ pub struct SystemStatus {
 
 // Integration with main scanner
 pub struct NotificationManager {
-    discord: Option<DiscordNotifier>,
+    notifiers: Vec<Box<dyn Notifier>>,
     start_time: DateTime<Utc>,
 }
 
 impl NotificationManager {
+    /// Builds the active notifier set from whichever backends have their env config
+    /// present. Any subset (including none) can be configured at once — a user can alert
+    /// Discord and Telegram simultaneously, or run with no sinks configured at all.
     pub fn new() -> Result<Self> {
-        let discord = DiscordNotifier::from_env().ok();
-        
-        if discord.is_some() {
-            info!("Discord notifications enabled");
-        } else {
-            warn!("Discord notifications disabled - webhook not configured");
+        let mut notifiers: Vec<Box<dyn Notifier>> = Vec::new();
+
+        match DiscordNotifier::from_env() {
+            Ok(discord) => {
+                info!("Discord notifications enabled");
+                notifiers.push(Box::new(discord));
+            }
+            Err(_) => warn!("Discord notifications disabled - webhook not configured"),
         }
-        
+
+        match TelegramNotifier::from_env() {
+            Ok(telegram) => {
+                info!("Telegram notifications enabled");
+                notifiers.push(Box::new(telegram));
+            }
+            Err(_) => warn!("Telegram notifications disabled - bot token/chat id not configured"),
+        }
+
+        match WebhookNotifier::from_env() {
+            Ok(webhook) => {
+                info!("Generic webhook notifications enabled");
+                notifiers.push(Box::new(webhook));
+            }
+            Err(_) => warn!("Generic webhook notifications disabled - URL not configured"),
+        }
+
         Ok(Self {
-            discord,
+            notifiers,
             start_time: Utc::now(),
         })
     }
-    
+
     pub async fn process_opportunity(&self, opportunity: &ArbitrageOpportunity) {
-        if let Some(discord) = &self.discord {
-            if opportunity.profit_usd >= 500.0 {
-                let _ = discord.notify_high_value(opportunity).await;
-            } else {
-                let _ = discord.notify_opportunity(opportunity).await;
+        crate::metrics::MetricsServer::record_opportunity_for_chain(&format!("{:?}", opportunity.chain));
+
+        for notifier in &self.notifiers {
+            match notifier.notify_opportunity(opportunity).await {
+                Ok(_) => crate::metrics::MetricsServer::record_notification_sent(),
+                Err(_) => crate::metrics::MetricsServer::record_notification_failure(),
             }
         }
     }
-    
-    pub async fn send_hourly_status(&self, 
-        opportunities: u64, 
-        profit: f64, 
+
+    pub async fn send_hourly_status(&self,
+        opportunities: u64,
+        profit: f64,
         chains: Vec<String>,
         prices: usize,
         pools: usize,
     ) {
-        if let Some(discord) = &self.discord {
-            let uptime = (Utc::now() - self.start_time).num_hours() as u64;
-            
-            let status = SystemStatus {
-                opportunities_found: opportunities,
-                total_profit: profit,
-                uptime_hours: uptime,
-                active_chains: chains,
-                price_feeds: prices,
-                liquidity_pools: pools,
-            };
-            
-            let _ = discord.notify_system_status(status).await;
+        let uptime = (Utc::now() - self.start_time).num_hours() as u64;
+
+        let status = SystemStatus {
+            opportunities_found: opportunities,
+            total_profit: profit,
+            uptime_hours: uptime,
+            active_chains: chains,
+            price_feeds: prices,
+            liquidity_pools: pools,
+        };
+
+        for notifier in &self.notifiers {
+            match notifier.notify_system_status(status.clone()).await {
+                Ok(_) => crate::metrics::MetricsServer::record_notification_sent(),
+                Err(_) => crate::metrics::MetricsServer::record_notification_failure(),
+            }
         }
     }
-    
+
     pub async fn send_error(&self, error: &str) {
-        if let Some(discord) = &self.discord {
-            let _ = discord.notify_error(error).await;
+        for notifier in &self.notifiers {
+            let _ = notifier.notify_error(error).await;
         }
     }
 }
\ No newline at end of file
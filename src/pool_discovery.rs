@@ -1,89 +1,238 @@
 use std::sync::Arc;
 use anyhow::Result;
-use crate::types::{LiquidityPool, Chain, TokenInfo, PoolType};
+use ethers::prelude::*;
+use crate::types::{LiquidityPool, Chain, Token};
 use crate::chains::ChainManager;
-use crate::dexs::DexManager;
-use tracing::{info, error};
+use crate::dexs::{IERC20, IUniswapV2Pair};
+use crate::swap_curve::CurveType;
+use crate::storage::StorageEngine;
 use rust_decimal::Decimal;
 use chrono::Utc;
+use tracing::{info, warn, error};
+
+// Emitted once per pool by a Uniswap V2-style factory; every fork (SushiSwap, PancakeSwap,
+// etc.) reuses this exact event signature, so one binding covers all of them.
+abigen!(
+    UniswapV2Factory,
+    r#"[
+        event PairCreated(address indexed token0, address indexed token1, address pair, uint256)
+    ]"#
+);
+
+abigen!(
+    UniswapV3Factory,
+    r#"[
+        event PoolCreated(address indexed token0, address indexed token1, uint24 indexed fee, int24 tickSpacing, address pool)
+    ]"#
+);
+
+/// How many blocks to request per `eth_getLogs` call. Public RPC endpoints commonly cap a
+/// single `eth_getLogs` response (both in block range and result count), so a full
+/// history scan is chunked rather than requested in one shot.
+const BLOCK_RANGE_CHUNK: u64 = 2_000;
+
+/// Fallback start height when a factory has never been scanned before. Scanning from
+/// genesis on a public RPC is impractical; this is recent enough to pick up current pools
+/// within a reasonable number of chunks while still being well before any of the known
+/// factory deployments below.
+const DEFAULT_START_BLOCK: u64 = 18_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FactoryKind {
+    UniswapV2,
+    UniswapV3,
+}
+
+struct FactorySource {
+    chain: Chain,
+    address: Address,
+    kind: FactoryKind,
+    dex_name: &'static str,
+}
+
+/// Known factory deployments to scan. Only Ethereum mainnet addresses are wired up today
+/// (the same ones `Config::load`'s `dexs` map uses) — extending this to other chains just
+/// means adding their own factory addresses here once they're known.
+fn factory_sources() -> Vec<FactorySource> {
+    vec![
+        FactorySource {
+            chain: Chain::Ethereum,
+            address: "0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f".parse().unwrap(),
+            kind: FactoryKind::UniswapV2,
+            dex_name: "Uniswap V2",
+        },
+        FactorySource {
+            chain: Chain::Ethereum,
+            address: "0xC0AEe478e3658e2610c5F7A4A2E1777cE9e4f2Ac".parse().unwrap(),
+            kind: FactoryKind::UniswapV2,
+            dex_name: "SushiSwap",
+        },
+        FactorySource {
+            chain: Chain::Ethereum,
+            address: "0x1F98431c8aD98523631AE4a59f267346ea31F984".parse().unwrap(),
+            kind: FactoryKind::UniswapV3,
+            dex_name: "Uniswap V3",
+        },
+    ]
+}
 
 pub struct PoolDiscovery {
     chain_manager: Arc<ChainManager>,
-    dex_manager: Arc<DexManager>,
+    storage: Arc<StorageEngine>,
 }
 
 impl PoolDiscovery {
-    pub fn new(
-        chain_manager: Arc<ChainManager>,
-        dex_manager: Arc<DexManager>,
-    ) -> Self {
+    pub fn new(chain_manager: Arc<ChainManager>, storage: Arc<StorageEngine>) -> Self {
         Self {
             chain_manager,
-            dex_manager,
+            storage,
         }
     }
-    
+
     pub async fn discover_all_pools(&self) -> Result<Vec<LiquidityPool>> {
         let mut all_pools = Vec::new();
-        
-        for chain in Chain::all_production_chains() {
-            match self.discover_pools_for_chain(&chain).await {
+
+        for source in factory_sources() {
+            match self.discover_pools_for_factory(&source).await {
                 Ok(pools) => {
-                    info!("Discovered {} pools on {:?}", pools.len(), chain);
+                    info!("Discovered {} pools from {} on {:?}", pools.len(), source.dex_name, source.chain);
                     all_pools.extend(pools);
                 }
                 Err(e) => {
-                    error!("Failed to discover pools on {:?}: {}", chain, e);
+                    error!("Failed to discover pools from {} on {:?}: {}", source.dex_name, source.chain, e);
                 }
             }
         }
-        
+
         Ok(all_pools)
     }
-    
-    async fn discover_pools_for_chain(&self, chain: &Chain) -> Result<Vec<LiquidityPool>> {
-        // get_known_pools returns Vec<LiquidityPool> directly, not Result
-        let existing_pools = self.dex_manager.get_known_pools(chain).await;
-        
-        // If pools already exist, return them
-        if !existing_pools.is_empty() {
-            return Ok(existing_pools);
-        }
-        
-        // Otherwise create mock pools for demonstration
+
+    /// Scans `source`'s factory for pool-creation events from its stored checkpoint (or
+    /// `DEFAULT_START_BLOCK` if never scanned) up to the chain tip, in `BLOCK_RANGE_CHUNK`
+    /// windows, decoding each event into a `LiquidityPool` and advancing the checkpoint as
+    /// it goes so a later run only scans the blocks produced since.
+    async fn discover_pools_for_factory(&self, source: &FactorySource) -> Result<Vec<LiquidityPool>> {
+        let provider = self.chain_manager
+            .get_provider(&source.chain)
+            .ok_or_else(|| anyhow::anyhow!("No provider for chain {:?}", source.chain))?;
+
+        let factory_address = format!("{:?}", source.address);
+        let from_block = self.storage
+            .get_pool_scan_checkpoint(source.chain, &factory_address)?
+            .map(|block| block + 1)
+            .unwrap_or(DEFAULT_START_BLOCK);
+        let latest_block = provider.get_block_number().await?.as_u64();
+
         let mut pools = Vec::new();
-        
-        // Create some mock pools since get_pool_info doesn't exist
-        for i in 0..5 {
-            let pool = LiquidityPool {
-                address: format!("0x{:040x}", i + 1),
-                chain: chain.clone(),
-                exchange: match i % 3 {
-                    0 => "Uniswap V2".to_string(),
-                    1 => "SushiSwap".to_string(),
-                    _ => "PancakeSwap".to_string(),
-                },
-                pool_type: PoolType::UniswapV2,
-                token0: TokenInfo {
-                    address: "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string(),
-                    symbol: "USDC".to_string(),
-                    decimals: 6,
-                    price_usd: Some(Decimal::from(1)),  // Wrapped in Some
-                },
-                token1: TokenInfo {
-                    address: "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".to_string(),
-                    symbol: "WETH".to_string(),
-                    decimals: 18,
-                    price_usd: Some(Decimal::from(2000)),  // Wrapped in Some
-                },
-                reserve0: Decimal::from(1000000),
-                reserve1: Decimal::from(500),
-                fee: Decimal::from_str_exact("0.003").unwrap_or(Decimal::ZERO),
-                last_update: Utc::now(),
+        let mut scanned_through = from_block.saturating_sub(1);
+        let mut start = from_block;
+
+        while start <= latest_block {
+            let end = (start + BLOCK_RANGE_CHUNK - 1).min(latest_block);
+
+            let filter = Filter::new()
+                .address(source.address)
+                .from_block(start)
+                .to_block(end);
+            let filter = match source.kind {
+                FactoryKind::UniswapV2 => filter.event(&PairCreatedFilter::abi_signature()),
+                FactoryKind::UniswapV3 => filter.event(&PoolCreatedFilter::abi_signature()),
             };
-            pools.push(pool);
+
+            let logs = provider.get_logs(&filter).await?;
+            for log in logs {
+                let raw = RawLog { topics: log.topics.clone(), data: log.data.to_vec() };
+                let decoded = match source.kind {
+                    FactoryKind::UniswapV2 => PairCreatedFilter::decode_log(&raw)
+                        .ok()
+                        .map(|event| (event.token_0, event.token_1, event.pair, None)),
+                    FactoryKind::UniswapV3 => PoolCreatedFilter::decode_log(&raw)
+                        .ok()
+                        .map(|event| (event.token_0, event.token_1, event.pool, Some(event.fee))),
+                };
+
+                let Some((token0_addr, token1_addr, pool_addr, fee_tier)) = decoded else {
+                    warn!("Failed to decode pool-creation log from {} on {:?}", source.dex_name, source.chain);
+                    continue;
+                };
+
+                match self.build_pool(&source.chain, &provider, pool_addr, token0_addr, token1_addr, source.dex_name, fee_tier, source.kind).await {
+                    Ok(pool) => pools.push(pool),
+                    Err(e) => warn!("Failed to build pool {:?} from {}: {}", pool_addr, source.dex_name, e),
+                }
+            }
+
+            scanned_through = end;
+            start = end + 1;
         }
-        
+
+        self.storage.set_pool_scan_checkpoint(source.chain, &factory_address, scanned_through)?;
+
         Ok(pools)
     }
-}
\ No newline at end of file
+
+    async fn build_pool(
+        &self,
+        chain: &Chain,
+        provider: &Arc<Provider<Http>>,
+        pool_address: Address,
+        token0_address: Address,
+        token1_address: Address,
+        dex_name: &str,
+        fee_tier: Option<u32>,
+        kind: FactoryKind,
+    ) -> Result<LiquidityPool> {
+        let token0 = Self::fetch_token(chain, provider, token0_address).await;
+        let token1 = Self::fetch_token(chain, provider, token1_address).await;
+
+        // Concentrated-liquidity V3 pools don't carry a simple pair-wide reserve, so those
+        // are left at zero; V2-style pools expose `getReserves()` directly on the pair.
+        let (reserve0, reserve1) = match kind {
+            FactoryKind::UniswapV2 => {
+                let pair = IUniswapV2Pair::new(pool_address, provider.clone());
+                match pair.get_reserves().call().await {
+                    Ok((r0, r1, _)) => (
+                        Decimal::from_str_exact(&r0.to_string())?,
+                        Decimal::from_str_exact(&r1.to_string())?,
+                    ),
+                    Err(_) => (Decimal::ZERO, Decimal::ZERO),
+                }
+            }
+            FactoryKind::UniswapV3 => (Decimal::ZERO, Decimal::ZERO),
+        };
+
+        let fee = match fee_tier {
+            Some(fee_bps_hundredths) => Decimal::from(fee_bps_hundredths) / Decimal::from(1_000_000),
+            None => Decimal::from_str_exact("0.003")?,
+        };
+
+        Ok(LiquidityPool {
+            address: format!("{:?}", pool_address),
+            token0,
+            token1,
+            reserve0,
+            reserve1,
+            fee,
+            dex: dex_name.to_string(),
+            chain: *chain,
+            last_update: Utc::now(),
+            curve: CurveType::from_dex_name(dex_name),
+            rate_provider: None,
+            rate_scale: Decimal::ONE,
+        })
+    }
+
+    async fn fetch_token(chain: &Chain, provider: &Arc<Provider<Http>>, address: Address) -> Token {
+        let token = IERC20::new(address, provider.clone());
+
+        let symbol = token.symbol().call().await.unwrap_or_else(|_| "UNKNOWN".to_string());
+        let decimals = token.decimals().call().await.unwrap_or(18);
+
+        Token {
+            address: format!("{:?}", address),
+            symbol,
+            decimals,
+            chain: *chain,
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::*;
+
+/// How surviving per-source prices are combined into one "fair" reference price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregationMethod {
+    Median,
+    Mean,
+}
+
+#[derive(Debug, Clone)]
+pub struct AggregatorConfig {
+    /// How far (in percent) a source's price may deviate from the consensus before it's
+    /// dropped as an outlier.
+    pub max_percent: Decimal,
+    /// A source's most recent quote older than this is treated as stale and excluded.
+    pub max_delay_ms: i64,
+    /// Minimum fraction of `configured_sources` that must have a fresh quote before a
+    /// consensus is computed at all; otherwise the pair is skipped this round.
+    pub min_valid_sources_pct: Decimal,
+    pub method: AggregationMethod,
+}
+
+impl Default for AggregatorConfig {
+    fn default() -> Self {
+        Self {
+            max_percent: Decimal::from(25),
+            max_delay_ms: 300_000,
+            min_valid_sources_pct: Decimal::from_str_exact("0.5").unwrap(),
+            method: AggregationMethod::Median,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SourceQuote {
+    price: Decimal,
+    received_at: DateTime<Utc>,
+}
+
+/// A consensus price for one canonical pair, computed from every source whose quote
+/// survived the freshness and deviation checks.
+#[derive(Debug, Clone)]
+pub struct ConsensusPrice {
+    pub fair_price: Decimal,
+    pub surviving_sources: HashMap<String, Decimal>,
+}
+
+/// Tracks the latest quote from every source, per canonical token pair, and turns them
+/// into a robust consensus price that a single lagging or misquoting feed can't skew.
+pub struct PriceAggregator {
+    config: AggregatorConfig,
+    configured_sources: usize,
+    quotes: RwLock<HashMap<String, HashMap<String, SourceQuote>>>,
+}
+
+impl PriceAggregator {
+    pub fn new(config: AggregatorConfig, configured_sources: usize) -> Self {
+        Self {
+            config,
+            configured_sources,
+            quotes: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Records `source`'s latest price for `pair`, timestamped now.
+    pub fn record(&self, pair: &str, source: &str, price: Decimal) {
+        let mut quotes = self.quotes.write();
+        quotes
+            .entry(pair.to_string())
+            .or_default()
+            .insert(source.to_string(), SourceQuote { price, received_at: Utc::now() });
+    }
+
+    /// Computes the current consensus for `pair`, or `None` if too few sources have a
+    /// fresh quote to trust one.
+    pub fn consensus(&self, pair: &str) -> Option<ConsensusPrice> {
+        let quotes = self.quotes.read();
+        let by_source = quotes.get(pair)?;
+
+        let now = Utc::now();
+        let fresh: HashMap<String, Decimal> = by_source
+            .iter()
+            .filter(|(_, quote)| (now - quote.received_at).num_milliseconds() <= self.config.max_delay_ms)
+            .map(|(source, quote)| (source.clone(), quote.price))
+            .collect();
+
+        let min_sources = (Decimal::from(self.configured_sources) * self.config.min_valid_sources_pct)
+            .ceil()
+            .to_usize()
+            .unwrap_or(usize::MAX);
+        if fresh.len() < min_sources.max(1) {
+            return None;
+        }
+
+        let initial_median = Self::aggregate(fresh.values().copied(), self.config.method)?;
+
+        let surviving_sources: HashMap<String, Decimal> = fresh
+            .into_iter()
+            .filter(|(_, price)| {
+                let deviation = ((*price - initial_median) / initial_median).abs() * Decimal::from(100);
+                deviation <= self.config.max_percent
+            })
+            .collect();
+
+        if surviving_sources.is_empty() {
+            return None;
+        }
+
+        let fair_price = Self::aggregate(surviving_sources.values().copied(), self.config.method)?;
+
+        Some(ConsensusPrice { fair_price, surviving_sources })
+    }
+
+    fn aggregate(values: impl Iterator<Item = Decimal>, method: AggregationMethod) -> Option<Decimal> {
+        let mut values: Vec<Decimal> = values.collect();
+        if values.is_empty() {
+            return None;
+        }
+
+        match method {
+            AggregationMethod::Mean => Some(values.iter().sum::<Decimal>() / Decimal::from(values.len())),
+            AggregationMethod::Median => {
+                values.sort();
+                let mid = values.len() / 2;
+                if values.len() % 2 == 0 {
+                    Some((values[mid - 1] + values[mid]) / Decimal::from(2))
+                } else {
+                    Some(values[mid])
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use ethers::types::U256;
+use rust_decimal::Decimal;
+use crate::fixed_point::{decimal_to_u256, mul_div_floor, u256_to_decimal};
+
+/// Max Newton iterations for both the invariant `D` and the per-swap balance `y` solve.
+/// The real Curve contracts use the same bound; in practice both converge in under 10.
+const MAX_ITERATIONS: u32 = 256;
+
+/// Newton's-method convergence tolerance: iterate until two successive estimates are
+/// within this many integer units of each other.
+const CONVERGENCE_EPSILON: Decimal = Decimal::ONE;
+
+/// Solves the Curve StableSwap invariant for `D` given pool balances `balances` and
+/// amplification coefficient `amp`:
+///
+/// `A·n^n·Σx_i + D = A·D·n^n + D^(n+1) / (n^n·Πx_i)`
+///
+/// via Newton iteration starting from `D = Σx_i`:
+///
+/// `D = (A·n^n·S + n·D_p)·D / ((A·n^n − 1)·D + (n+1)·D_p)`, where `D_p = D^(n+1) / (n^n·Πx_i)`.
+pub fn compute_d(balances: &[Decimal], amp: Decimal) -> Decimal {
+    let n = Decimal::from(balances.len() as u64);
+    let sum: Decimal = balances.iter().copied().sum();
+    if sum == Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+
+    let ann = amp * n.powi(balances.len() as i64);
+    let mut d = sum;
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut d_p = d;
+        for balance in balances {
+            if *balance == Decimal::ZERO {
+                return Decimal::ZERO;
+            }
+            // `d_p * d` can run well past Decimal's 96-bit mantissa for pools with large
+            // balances, so the multiply-then-divide is done in U256 instead.
+            d_p = u256_to_decimal(mul_div_floor(
+                decimal_to_u256(d_p),
+                decimal_to_u256(d),
+                decimal_to_u256(*balance * n),
+            ));
+        }
+
+        let d_prev = d;
+        let numerator = (ann * sum + d_p * n) * d;
+        let denominator = (ann - Decimal::ONE) * d + (n + Decimal::ONE) * d_p;
+        if denominator == Decimal::ZERO {
+            return d;
+        }
+        d = numerator / denominator;
+
+        if (d - d_prev).abs() <= CONVERGENCE_EPSILON {
+            break;
+        }
+    }
+
+    d
+}
+
+/// Holding `d` fixed, solves for the new balance `y` of the output token after
+/// `balances` (all tokens except the output one, already updated with the swap's input)
+/// via Newton iteration on `y^2 + (b - D)*y - c = 0`, where:
+///
+/// `b = S' + D/(A·n^n)`, `c = D^(n+1) / (n^n·Π(x_{i≠out})·A·n^n)`
+///
+/// and `S'`/`Π` range over every balance except the output token.
+fn compute_y(balances_without_out: &[Decimal], d: Decimal, amp: Decimal, n_coins: usize) -> Decimal {
+    let n = Decimal::from(n_coins as u64);
+    let ann = amp * n.powi(n_coins as i64);
+
+    let sum_prime: Decimal = balances_without_out.iter().copied().sum();
+    let mut c = d;
+    for balance in balances_without_out {
+        c = u256_to_decimal(mul_div_floor(
+            decimal_to_u256(c),
+            decimal_to_u256(d),
+            decimal_to_u256(*balance * n),
+        ));
+    }
+    c = u256_to_decimal(mul_div_floor(decimal_to_u256(c), decimal_to_u256(d), decimal_to_u256(ann * n)));
+
+    let b = sum_prime + d / ann;
+
+    let mut y = d;
+    for _ in 0..MAX_ITERATIONS {
+        let y_prev = y;
+        // `y * y` is the other product that can outrun Decimal's mantissa once `y` sits
+        // near `D`'s own magnitude.
+        let numerator = u256_to_decimal(mul_div_floor(decimal_to_u256(y), decimal_to_u256(y), U256::one())) + c;
+        let denominator = Decimal::from(2) * y + b - d;
+        if denominator == Decimal::ZERO {
+            return y;
+        }
+        y = numerator / denominator;
+
+        if (y - y_prev).abs() <= CONVERGENCE_EPSILON {
+            break;
+        }
+    }
+
+    y
+}
+
+/// Output amount for a StableSwap pool, found by solving the invariant before and after
+/// the swap rather than assuming constant-product `x*y=k`, which badly overstates
+/// slippage for pegged assets near their 1:1 ratio.
+///
+/// `balances` is every token's reserve before the swap, in the pool's coin order;
+/// `in_index`/`out_index` select which coins are being swapped.
+pub fn stable_swap_output(
+    balances: &[Decimal],
+    in_index: usize,
+    out_index: usize,
+    amount_in: Decimal,
+    amp: Decimal,
+) -> Decimal {
+    if in_index == out_index || in_index >= balances.len() || out_index >= balances.len() {
+        return Decimal::ZERO;
+    }
+    if amount_in <= Decimal::ZERO || balances.iter().any(|b| *b <= Decimal::ZERO) {
+        return Decimal::ZERO;
+    }
+
+    let d = compute_d(balances, amp);
+
+    let mut balances_without_out: Vec<Decimal> = Vec::with_capacity(balances.len() - 1);
+    for (i, balance) in balances.iter().enumerate() {
+        if i == out_index {
+            continue;
+        }
+        if i == in_index {
+            balances_without_out.push(*balance + amount_in);
+        } else {
+            balances_without_out.push(*balance);
+        }
+    }
+
+    let y = compute_y(&balances_without_out, d, amp, balances.len());
+    let reserve_out = balances[out_index];
+
+    if y >= reserve_out {
+        return Decimal::ZERO;
+    }
+
+    (reserve_out - y - Decimal::ONE).max(Decimal::ZERO)
+}
@@ -0,0 +1,411 @@
+mod table;
+
+use crate::merkle::{Hash, MerkleLog};
+use crate::types::{ArbitrageOpportunity, Chain, TradePath};
+use anyhow::Result;
+use rocksdb::DB;
+use chrono::{DateTime, Duration, Utc};
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use table::{encode_timestamp, Table};
+
+/// Key prefix under which leaf hashes are persisted, zero-padded so lexicographic RocksDB
+/// iteration order matches leaf insertion order.
+const LEAF_KEY_PREFIX: &str = "merkle_leaf_";
+const LEAF_INDEX_KEY_PREFIX: &str = "merkle_leafidx_";
+const MERKLE_ROOT_KEY: &str = "merkle_root";
+
+fn leaf_key(index: usize) -> String {
+    format!("{LEAF_KEY_PREFIX}{index:020}")
+}
+
+/// Full opportunity records, keyed by id.
+struct Opportunities;
+impl Table for Opportunities {
+    type Key = String;
+    type Value = ArbitrageOpportunity;
+    const PREFIX: &'static str = "opp_";
+
+    fn encode_key(key: &Self::Key) -> Vec<u8> {
+        key.as_bytes().to_vec()
+    }
+    fn encode_value(value: &Self::Value) -> Result<Vec<u8>> {
+        Ok(bincode::serialize(value)?)
+    }
+    fn decode_value(bytes: &[u8]) -> Result<Self::Value> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+}
+
+/// Opportunity ids indexed by chain, for per-chain lookups without scanning `Opportunities`.
+struct ByChain;
+impl Table for ByChain {
+    type Key = (Chain, String);
+    type Value = String;
+    const PREFIX: &'static str = "chain_";
+
+    fn encode_key((chain, id): &Self::Key) -> Vec<u8> {
+        format!("{chain:?}_{id}").into_bytes()
+    }
+    fn encode_value(value: &Self::Value) -> Result<Vec<u8>> {
+        Ok(value.as_bytes().to_vec())
+    }
+    fn decode_value(bytes: &[u8]) -> Result<Self::Value> {
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+}
+
+/// Opportunity ids indexed by timestamp. The key encodes the timestamp as sortable
+/// big-endian bytes (see `encode_timestamp`) rather than a decimal string, so `iter_prefix`
+/// can seek directly to a cutoff instead of string-splitting every key to compare it.
+struct ByTime;
+impl Table for ByTime {
+    type Key = (i64, String);
+    type Value = String;
+    const PREFIX: &'static str = "time2_";
+
+    fn encode_key((timestamp, id): &Self::Key) -> Vec<u8> {
+        let mut key = encode_timestamp(*timestamp).to_vec();
+        key.push(b'_');
+        key.extend(id.as_bytes());
+        key
+    }
+    fn encode_value(value: &Self::Value) -> Result<Vec<u8>> {
+        Ok(value.as_bytes().to_vec())
+    }
+    fn decode_value(bytes: &[u8]) -> Result<Self::Value> {
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+}
+
+/// Last block height scanned for `PairCreated`/`PoolCreated` events, keyed by
+/// `(chain, factory address)`, so `PoolDiscovery` resumes from where it left off instead
+/// of rescanning the whole chain's history on every run.
+struct PoolScanCheckpoints;
+impl Table for PoolScanCheckpoints {
+    type Key = (Chain, String);
+    type Value = u64;
+    const PREFIX: &'static str = "poolscan_";
+
+    fn encode_key((chain, factory): &Self::Key) -> Vec<u8> {
+        format!("{chain:?}_{factory}").into_bytes()
+    }
+    fn encode_value(value: &Self::Value) -> Result<Vec<u8>> {
+        Ok(value.to_be_bytes().to_vec())
+    }
+    fn decode_value(bytes: &[u8]) -> Result<Self::Value> {
+        let array: [u8; 8] = bytes.try_into().map_err(|_| anyhow::anyhow!("corrupt pool scan checkpoint"))?;
+        Ok(u64::from_be_bytes(array))
+    }
+}
+
+/// Running totals maintained on every write instead of recomputed by scanning
+/// `Opportunities`, so `get_statistics` is O(1) rather than O(total records).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct StatsRecord {
+    total_opportunities: usize,
+    profitable_opportunities: usize,
+    total_profit: rust_decimal::Decimal,
+    by_chain: std::collections::HashMap<Chain, usize>,
+}
+
+struct Stats;
+impl Table for Stats {
+    type Key = ();
+    type Value = StatsRecord;
+    const PREFIX: &'static str = "stats_global";
+
+    fn encode_key(_key: &Self::Key) -> Vec<u8> {
+        Vec::new()
+    }
+    fn encode_value(value: &Self::Value) -> Result<Vec<u8>> {
+        Ok(bincode::serialize(value)?)
+    }
+    fn decode_value(bytes: &[u8]) -> Result<Self::Value> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+}
+
+pub struct StorageEngine {
+    db: Arc<DB>,
+    /// Leaf log backing the tamper-evident Merkle tree over stored opportunities. Kept
+    /// insertion-only and separate from `cleanup_old_data`'s pruning so historical leaves
+    /// (and any proof issued against them) stay valid even after old opportunity rows are
+    /// deleted from the value store.
+    merkle: Arc<parking_lot::Mutex<MerkleLog>>,
+}
+
+impl StorageEngine {
+    pub fn new(path: &str) -> Result<Self> {
+        let db = DB::open_default(path)?;
+        let merkle = Self::load_merkle_log(&db)?;
+        Ok(Self {
+            db: Arc::new(db),
+            merkle: Arc::new(parking_lot::Mutex::new(merkle)),
+        })
+    }
+
+    fn load_merkle_log(db: &DB) -> Result<MerkleLog> {
+        let mut leaves = Vec::new();
+        let iter = db.prefix_iterator(LEAF_KEY_PREFIX.as_bytes());
+        for item in iter {
+            let (key, value) = item?;
+            if !key.starts_with(LEAF_KEY_PREFIX.as_bytes()) {
+                break;
+            }
+            let mut hash: Hash = [0u8; 32];
+            if value.len() == 32 {
+                hash.copy_from_slice(&value);
+                leaves.push(hash);
+            }
+        }
+        Ok(MerkleLog::from_leaves(leaves))
+    }
+
+    pub async fn store_opportunity(&self, opportunity: &ArbitrageOpportunity) -> Result<()> {
+        Opportunities::write(&self.db, &opportunity.id, opportunity)?;
+        ByChain::write(
+            &self.db,
+            &(opportunity.chain, opportunity.id.clone()),
+            &opportunity.id,
+        )?;
+        ByTime::write(
+            &self.db,
+            &(opportunity.timestamp.timestamp(), opportunity.id.clone()),
+            &opportunity.id,
+        )?;
+
+        let mut stats = Stats::read(&self.db, &())?.unwrap_or_default();
+        stats.total_opportunities += 1;
+        if opportunity.net_profit_usd > rust_decimal::Decimal::ZERO {
+            stats.profitable_opportunities += 1;
+            stats.total_profit += opportunity.net_profit_usd;
+        }
+        *stats.by_chain.entry(opportunity.chain).or_insert(0) += 1;
+        Stats::write(&self.db, &(), &stats)?;
+
+        // Extend the tamper-evident Merkle log with this opportunity's bincode bytes as the
+        // leaf, so a retroactively edited `opp_` row no longer matches its recorded leaf hash.
+        let value = Opportunities::encode_value(opportunity)?;
+        let (leaf_index, leaf_hash, root) = {
+            let mut merkle = self.merkle.lock();
+            let leaf_index = merkle.append(&value);
+            let leaf_hash = merkle.leaf_hash(leaf_index).expect("just appended a leaf");
+            let root = merkle.root().expect("just appended a leaf");
+            (leaf_index, leaf_hash, root)
+        };
+        self.db.put(leaf_key(leaf_index).as_bytes(), leaf_hash)?;
+        self.db.put(
+            format!("{LEAF_INDEX_KEY_PREFIX}{}", opportunity.id).as_bytes(),
+            leaf_index.to_le_bytes(),
+        )?;
+        self.db.put(MERKLE_ROOT_KEY.as_bytes(), root)?;
+
+        Ok(())
+    }
+
+    /// Current Merkle root over every opportunity ever stored, for operators to snapshot
+    /// and compare against later.
+    pub fn merkle_root(&self) -> Result<Hash> {
+        self.merkle
+            .lock()
+            .root()
+            .ok_or_else(|| anyhow::anyhow!("no opportunities stored yet"))
+    }
+
+    /// Sibling hashes from `id`'s leaf up to the root, for a caller to independently verify
+    /// via [`crate::merkle::verify_proof`] without trusting this node's read path.
+    pub fn inclusion_proof(&self, id: &str) -> Result<Vec<(Hash, bool)>> {
+        let index_key = format!("{LEAF_INDEX_KEY_PREFIX}{id}");
+        let index_bytes = self
+            .db
+            .get(index_key.as_bytes())?
+            .ok_or_else(|| anyhow::anyhow!("no leaf recorded for opportunity {id}"))?;
+        let index = usize::from_le_bytes(
+            index_bytes
+                .as_slice()
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("corrupt leaf index for opportunity {id}"))?,
+        );
+
+        self.merkle
+            .lock()
+            .inclusion_proof(index)
+            .ok_or_else(|| anyhow::anyhow!("leaf index {index} out of range for opportunity {id}"))
+    }
+
+    pub async fn get_opportunity(&self, id: &str) -> Result<Option<ArbitrageOpportunity>> {
+        Opportunities::read(&self.db, &id.to_string())
+    }
+
+    /// Opportunities from the last `hours`, read by seeking `ByTime` straight to the cutoff
+    /// instead of scanning and deserializing every key in the database.
+    pub async fn get_recent_opportunities(&self, hours: i64) -> Result<Vec<ArbitrageOpportunity>> {
+        let cutoff = Utc::now() - Duration::hours(hours);
+        let ids = ByTime::iter_prefix(&self.db, &encode_timestamp(cutoff.timestamp()))?;
+
+        let mut opportunities = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(opp) = Opportunities::read(&self.db, &id)? {
+                opportunities.push(opp);
+            }
+        }
+
+        opportunities.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        Ok(opportunities)
+    }
+
+    /// Last block `PoolDiscovery` finished scanning for `factory_address` on `chain`, or
+    /// `None` if it has never been scanned.
+    pub fn get_pool_scan_checkpoint(&self, chain: Chain, factory_address: &str) -> Result<Option<u64>> {
+        PoolScanCheckpoints::read(&self.db, &(chain, factory_address.to_lowercase()))
+    }
+
+    /// Records that `factory_address` on `chain` has been scanned through `block`, so the
+    /// next run's `eth_getLogs` filter starts from `block + 1`.
+    pub fn set_pool_scan_checkpoint(&self, chain: Chain, factory_address: &str, block: u64) -> Result<()> {
+        PoolScanCheckpoints::write(&self.db, &(chain, factory_address.to_lowercase()), &block)
+    }
+
+    /// O(1) read of counters maintained incrementally by `store_opportunity`, rather than a
+    /// full scan over every stored opportunity.
+    pub async fn get_statistics(&self) -> Result<StorageStats> {
+        let stats = Stats::read(&self.db, &())?.unwrap_or_default();
+        Ok(StorageStats {
+            total_opportunities: stats.total_opportunities,
+            profitable_opportunities: stats.profitable_opportunities,
+            total_profit: stats.total_profit,
+            opportunities_by_chain: stats.by_chain,
+        })
+    }
+
+    /// Drops `ByTime` entries older than `days`. Walks the table from its start since we're
+    /// looking for the *oldest* rows rather than seeking a cutoff, but only deletes the
+    /// time index, never `Opportunities` or Merkle leaf rows, so historical data (and any
+    /// Merkle proof over it) is never mutated by pruning.
+    pub async fn cleanup_old_data(&self, days: i64) -> Result<()> {
+        let cutoff_ts = (Utc::now() - Duration::days(days)).timestamp();
+
+        let rows = ByTime::iter_prefix_with_keys(&self.db, &[])?;
+        for (key, _id) in rows {
+            let ts_bytes = &key[ByTime::PREFIX.len()..ByTime::PREFIX.len() + 8];
+            let ts = i64::from_be_bytes(ts_bytes.try_into()?);
+            if ts >= cutoff_ts {
+                // `ByTime` keys are sorted by timestamp, so every row from here on is newer
+                // than the cutoff.
+                break;
+            }
+            self.db.delete(&key)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct StorageStats {
+    pub total_opportunities: usize,
+    pub profitable_opportunities: usize,
+    pub total_profit: rust_decimal::Decimal,
+    pub opportunities_by_chain: std::collections::HashMap<crate::types::Chain, usize>,
+}
+
+/// `ArbitrageOpportunity`'s JSON wire format for `export_json`/`import_json`: identical to
+/// the stored type except `amount_raw` round-trips as canonical hex (via `hex_canonical`)
+/// instead of the decimal string `ArbitrageOpportunity`'s own `hex_or_decimal` encoding
+/// uses, so external dashboards/notebooks get the `U256` quantity in the form they expect
+/// without this bot's bincode-backed storage format having to change to match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedOpportunity {
+    pub id: String,
+    pub chain: Chain,
+    pub opportunity_type: String,
+    pub path: Vec<TradePath>,
+    pub initial_amount: rust_decimal::Decimal,
+    #[serde(with = "crate::hex_amount::hex_canonical")]
+    pub amount_raw: U256,
+    pub final_amount: rust_decimal::Decimal,
+    pub gross_profit: rust_decimal::Decimal,
+    pub flash_loan_provider: String,
+    pub flash_loan_fee: rust_decimal::Decimal,
+    pub flash_loan_fee_percentage: rust_decimal::Decimal,
+    pub gas_cost_usd: rust_decimal::Decimal,
+    pub net_profit_usd: rust_decimal::Decimal,
+    pub roi_percentage: rust_decimal::Decimal,
+    pub confidence_score: f64,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl From<&ArbitrageOpportunity> for ExportedOpportunity {
+    fn from(opp: &ArbitrageOpportunity) -> Self {
+        Self {
+            id: opp.id.clone(),
+            chain: opp.chain,
+            opportunity_type: opp.opportunity_type.clone(),
+            path: opp.path.clone(),
+            initial_amount: opp.initial_amount,
+            amount_raw: opp.amount_raw,
+            final_amount: opp.final_amount,
+            gross_profit: opp.gross_profit,
+            flash_loan_provider: opp.flash_loan_provider.clone(),
+            flash_loan_fee: opp.flash_loan_fee,
+            flash_loan_fee_percentage: opp.flash_loan_fee_percentage,
+            gas_cost_usd: opp.gas_cost_usd,
+            net_profit_usd: opp.net_profit_usd,
+            roi_percentage: opp.roi_percentage,
+            confidence_score: opp.confidence_score,
+            timestamp: opp.timestamp,
+        }
+    }
+}
+
+impl From<ExportedOpportunity> for ArbitrageOpportunity {
+    fn from(opp: ExportedOpportunity) -> Self {
+        Self {
+            id: opp.id,
+            chain: opp.chain,
+            opportunity_type: opp.opportunity_type,
+            path: opp.path,
+            initial_amount: opp.initial_amount,
+            amount_raw: opp.amount_raw,
+            final_amount: opp.final_amount,
+            gross_profit: opp.gross_profit,
+            flash_loan_provider: opp.flash_loan_provider,
+            flash_loan_fee: opp.flash_loan_fee,
+            flash_loan_fee_percentage: opp.flash_loan_fee_percentage,
+            gas_cost_usd: opp.gas_cost_usd,
+            net_profit_usd: opp.net_profit_usd,
+            roi_percentage: opp.roi_percentage,
+            confidence_score: opp.confidence_score,
+            timestamp: opp.timestamp,
+        }
+    }
+}
+
+impl StorageEngine {
+    /// Dumps opportunities for `chain` (or every chain, if `None`) from the last `hours`
+    /// as a portable JSON array, reusing `get_recent_opportunities`'s `ByTime` seek rather
+    /// than scanning the whole database.
+    pub async fn export_json(&self, chain: Option<Chain>, hours: i64) -> Result<String> {
+        let opportunities = self.get_recent_opportunities(hours).await?;
+        let exported: Vec<ExportedOpportunity> = opportunities
+            .iter()
+            .filter(|opp| chain.map(|c| c == opp.chain).unwrap_or(true))
+            .map(ExportedOpportunity::from)
+            .collect();
+        Ok(serde_json::to_string_pretty(&exported)?)
+    }
+
+    /// Re-imports opportunities previously produced by `export_json`, feeding each one back
+    /// through `store_opportunity` so the Merkle log, chain/time indexes, and running
+    /// counters all stay consistent with how a native write would have populated them.
+    /// Returns the number of opportunities imported.
+    pub async fn import_json(&self, json: &str) -> Result<usize> {
+        let exported: Vec<ExportedOpportunity> = serde_json::from_str(json)?;
+        let count = exported.len();
+        for item in exported {
+            self.store_opportunity(&item.into()).await?;
+        }
+        Ok(count)
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use rocksdb::DB;
+
+/// A typed view over a slice of `StorageEngine`'s keyspace.
+///
+/// RocksDB column families would give each table its own namespace more cleanly, but
+/// `StorageEngine` opens the database with `DB::open_default` (a single default column
+/// family), so re-opening it with column-family descriptors would ripple through every
+/// caller. A key prefix plays the same role here without touching DB-open plumbing: every
+/// row this table reads or writes lives under `Self::PREFIX`, so `iter_prefix` only ever
+/// walks this table's rows rather than the whole database.
+pub trait Table {
+    type Key;
+    type Value;
+
+    const PREFIX: &'static str;
+
+    fn encode_key(key: &Self::Key) -> Vec<u8>;
+    fn encode_value(value: &Self::Value) -> Result<Vec<u8>>;
+    fn decode_value(bytes: &[u8]) -> Result<Self::Value>;
+
+    fn full_key(key: &Self::Key) -> Vec<u8> {
+        let mut full = Self::PREFIX.as_bytes().to_vec();
+        full.extend(Self::encode_key(key));
+        full
+    }
+
+    fn write(db: &DB, key: &Self::Key, value: &Self::Value) -> Result<()> {
+        db.put(Self::full_key(key), Self::encode_value(value)?)?;
+        Ok(())
+    }
+
+    fn read(db: &DB, key: &Self::Key) -> Result<Option<Self::Value>> {
+        match db.get(Self::full_key(key))? {
+            Some(bytes) => Ok(Some(Self::decode_value(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Seeks straight to `Self::PREFIX` + `key_lower_bound` and decodes every row from
+    /// there to the end of this table, in key order. Because keys are encoded so
+    /// lexicographic order matches the natural order of the field they're keyed on (see
+    /// `encode_timestamp`), this turns a "since cutoff" query into a seek plus a forward
+    /// scan of only the matching rows, instead of scanning and decoding the whole table.
+    fn iter_prefix(db: &DB, key_lower_bound: &[u8]) -> Result<Vec<Self::Value>> {
+        Self::iter_prefix_with_keys(db, key_lower_bound)
+            .map(|rows| rows.into_iter().map(|(_, value)| value).collect())
+    }
+
+    /// Like `iter_prefix`, but also returns each row's raw key, for callers that need to
+    /// delete matched rows afterwards (e.g. `cleanup_old_data`).
+    fn iter_prefix_with_keys(db: &DB, key_lower_bound: &[u8]) -> Result<Vec<(Vec<u8>, Self::Value)>> {
+        let mut seek = Self::PREFIX.as_bytes().to_vec();
+        seek.extend_from_slice(key_lower_bound);
+
+        let mut out = Vec::new();
+        for item in db.prefix_iterator(&seek) {
+            let (key, value) = item?;
+            if !key.starts_with(Self::PREFIX.as_bytes()) {
+                break;
+            }
+            out.push((key.to_vec(), Self::decode_value(&value)?));
+        }
+        Ok(out)
+    }
+}
+
+/// Encodes a timestamp so lexicographic byte order matches numeric order. Unix timestamps
+/// in this bot are always post-epoch (positive), so big-endian two's-complement bytes sort
+/// the same as the integers themselves.
+pub fn encode_timestamp(timestamp: i64) -> [u8; 8] {
+    timestamp.to_be_bytes()
+}
@@ -0,0 +1,139 @@
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Pricing logic for a single swap venue, kept separate from the pool/scan plumbing so a
+/// new curve drops in without touching `DexManager` or the arbitrage scanner.
+pub trait SwapCurve {
+    /// Output amount of `reserve_out`'s token for `amount_in` of `reserve_in`'s token,
+    /// net of the pool's swap fee (the fee itself is applied by the caller beforehand,
+    /// see `DexManager::calculate_output_amount`).
+    fn output_amount(&self, amount_in: Decimal, reserve_in: Decimal, reserve_out: Decimal) -> Decimal;
+
+    /// Price impact of trading `amount_in` against `(reserve_in, reserve_out)`, as a
+    /// percentage: how far the trade's effective price falls below the pool's current spot
+    /// price. Curve-specific rather than a single formula, since a StableSwap pool is far
+    /// flatter near the peg than constant-product for the same trade size relative to
+    /// reserves — using the constant-product formula everywhere is what makes the scanner
+    /// flag phantom arbitrage on stable pairs.
+    fn price_impact(&self, amount_in: Decimal, reserve_in: Decimal, reserve_out: Decimal) -> Decimal;
+
+    /// Marginal price of `reserve_out`'s token in terms of `reserve_in`'s, i.e.
+    /// `d(output_amount)/d(amount_in)` as `amount_in -> 0` — the price a vanishingly small
+    /// trade would clear at, before any slippage. For constant-product this collapses to
+    /// the plain reserve ratio, but that ratio is *not* the right spot price for a
+    /// StableSwap or weighted pool, which is why `price_impact` delegates here instead of
+    /// assuming `reserve_out / reserve_in` everywhere.
+    fn spot_price(&self, reserve_in: Decimal, reserve_out: Decimal) -> Decimal;
+}
+
+/// Which swap curve prices a [`crate::types::LiquidityPool`]. Tagged on the pool itself
+/// (see `DexManager::get_pool_info`) so the scanner can price StableSwap, constant-sum,
+/// and weighted pools correctly instead of assuming Uniswap-V2 `x*y=k` everywhere.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum CurveType {
+    /// Uniswap V2 style `x*y=k`.
+    ConstantProduct,
+    /// `x+y=k`, used by some fixed-rate 1:1 pools.
+    ConstantSum,
+    /// Curve-style stable pool with amplification coefficient `amp`.
+    StableSwap { amp: Decimal },
+    /// Balancer-style weighted pool with normalized weights `w_in + w_out = 1`.
+    Weighted { weight_in: Decimal, weight_out: Decimal },
+}
+
+impl CurveType {
+    /// Tags a curve from a DEX's name. Defaults to `ConstantProduct` for anything not
+    /// recognized, since that's the behavior every pool in this bot had before pluggable
+    /// curves existed.
+    pub fn from_dex_name(dex_name: &str) -> Self {
+        let lower = dex_name.to_lowercase();
+        if lower.contains("curve") || lower.contains("stable") {
+            CurveType::StableSwap { amp: Decimal::from(100) }
+        } else if lower.contains("balancer") {
+            CurveType::Weighted {
+                weight_in: Decimal::from_str_exact("0.5").unwrap(),
+                weight_out: Decimal::from_str_exact("0.5").unwrap(),
+            }
+        } else {
+            CurveType::ConstantProduct
+        }
+    }
+}
+
+impl SwapCurve for CurveType {
+    fn output_amount(&self, amount_in: Decimal, reserve_in: Decimal, reserve_out: Decimal) -> Decimal {
+        if reserve_in <= Decimal::ZERO || reserve_out <= Decimal::ZERO || amount_in <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+
+        match *self {
+            // Routed through `U256` rather than `Decimal * Decimal` here: the product
+            // routinely overflows Decimal's 96-bit mantissa for realistic 18-decimal
+            // reserves, silently truncating profit estimates.
+            CurveType::ConstantProduct => crate::fixed_point::constant_product_output(amount_in, reserve_in, reserve_out),
+            CurveType::ConstantSum => amount_in.min(reserve_out),
+            CurveType::StableSwap { amp } => crate::stable_swap::stable_swap_output(
+                &[reserve_in, reserve_out],
+                0,
+                1,
+                amount_in,
+                amp,
+            ),
+            CurveType::Weighted { weight_in, weight_out } => {
+                // out = reserve_out * (1 - (reserve_in / (reserve_in + amount_in)) ^ (w_in/w_out))
+                if weight_out <= Decimal::ZERO {
+                    return Decimal::ZERO;
+                }
+                let ratio = reserve_in / (reserve_in + amount_in);
+                let exponent = (weight_in / weight_out).to_f64().unwrap_or(1.0);
+                let ratio_f64 = ratio.to_f64().unwrap_or(1.0).powf(exponent);
+                let out_fraction = Decimal::from_f64_retain(1.0 - ratio_f64).unwrap_or(Decimal::ZERO);
+                reserve_out * out_fraction
+            }
+        }
+    }
+
+    fn price_impact(&self, amount_in: Decimal, reserve_in: Decimal, reserve_out: Decimal) -> Decimal {
+        if reserve_in <= Decimal::ZERO || reserve_out <= Decimal::ZERO || amount_in <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+
+        let spot_price = self.spot_price(reserve_in, reserve_out);
+        let effective_price = self.output_amount(amount_in, reserve_in, reserve_out) / amount_in;
+        if spot_price <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+
+        ((spot_price - effective_price) / spot_price * Decimal::from(100)).max(Decimal::ZERO)
+    }
+
+    fn spot_price(&self, reserve_in: Decimal, reserve_out: Decimal) -> Decimal {
+        if reserve_in <= Decimal::ZERO || reserve_out <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+
+        match *self {
+            CurveType::ConstantProduct => reserve_out / reserve_in,
+            // Fixed 1:1 rate by construction, for as long as either side still has balance.
+            CurveType::ConstantSum => Decimal::ONE,
+            CurveType::StableSwap { amp } => {
+                // No closed form for the Newton-solved invariant's derivative, so
+                // approximate it the same way the derivative is defined: output over a
+                // vanishingly small input, small enough to be well inside the pool's flat
+                // region near the peg but large enough not to round to zero in `Decimal`.
+                let dx = (reserve_in / Decimal::from(1_000_000)).max(Decimal::new(1, 6));
+                let dy = crate::stable_swap::stable_swap_output(&[reserve_in, reserve_out], 0, 1, dx, amp);
+                if dx <= Decimal::ZERO { Decimal::ZERO } else { dy / dx }
+            }
+            CurveType::Weighted { weight_in, weight_out } => {
+                // d(out)/d(in) at zero: (B_out/W_out) / (B_in/W_in), the inverse of
+                // Balancer's in-per-out spot price.
+                if weight_in <= Decimal::ZERO || weight_out <= Decimal::ZERO {
+                    return Decimal::ZERO;
+                }
+                (reserve_out / weight_out) / (reserve_in / weight_in)
+            }
+        }
+    }
+}
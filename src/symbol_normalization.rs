@@ -0,0 +1,83 @@
+/// A trading pair in a venue-independent spelling: uppercase base and quote asset
+/// symbols with known aliases collapsed (e.g. `XBT` -> `BTC`, `WETH` -> `ETH`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CanonicalPair {
+    pub base: String,
+    pub quote: String,
+}
+
+impl CanonicalPair {
+    /// The `BASE/QUOTE` spelling used to key the shared price index and aggregator,
+    /// so two venues quoting the same pair differently collide on the same key instead
+    /// of a fragile substring test having to notice they're the same pair.
+    pub fn as_key(&self) -> String {
+        format!("{}/{}", self.base, self.quote)
+    }
+}
+
+/// Quote currencies we recognize when splitting a concatenated symbol like Binance's
+/// `ETHUSDT` that carries no separator at all. Ordered longest-first so `USDT` is tried
+/// before `USD` would otherwise swallow part of it.
+const KNOWN_QUOTES: &[&str] = &[
+    "USDT", "USDC", "BUSD", "TUSD", "KRW", "INR", "BRL", "MXN", "EUR", "GBP", "JPY", "USD", "BTC", "ETH",
+];
+
+fn resolve_alias(symbol: &str) -> &str {
+    match symbol {
+        "XBT" => "BTC",
+        "WETH" => "ETH",
+        "WBTC" => "BTC",
+        other => other,
+    }
+}
+
+/// Splits a separator-free symbol (`ETHUSDT`, `BTCUSD`) into base/quote by matching one
+/// of `KNOWN_QUOTES` as a suffix. Returns `None` for anything we don't recognize rather
+/// than guessing a split point.
+fn split_concatenated(symbol: &str) -> Option<(&str, &str)> {
+    KNOWN_QUOTES.iter()
+        .filter(|quote| symbol.len() > quote.len() && symbol.ends_with(*quote))
+        .max_by_key(|quote| quote.len())
+        .map(|quote| symbol.split_at(symbol.len() - quote.len()))
+}
+
+/// Normalizes `raw_symbol` as reported by `exchange` into a `CanonicalPair`. Handles:
+/// - Bitfinex's `t`/`f` prefix (`tETHUSD` -> `ETH`/`USD`)
+/// - explicit separators (`ETH-USDT`, `ETH/USD`, `ETH_USDT`, `KRW-BTC`)
+/// - separator-free symbols (`ethusdt`, `BTCUSD`) via `KNOWN_QUOTES`
+/// - venue-specific asset aliases (`XBT` -> `BTC`, `WETH`/`WBTC` -> `ETH`/`BTC`)
+///
+/// Returns `None` when the symbol doesn't match any of these shapes, so callers can
+/// refuse to compare a pair they can't confidently canonicalize rather than guess.
+pub fn normalize_pair(exchange: &str, raw_symbol: &str) -> Option<CanonicalPair> {
+    let mut upper = raw_symbol.to_uppercase();
+
+    if exchange.starts_with("Bitfinex") {
+        if let Some(stripped) = upper.strip_prefix('T').or_else(|| upper.strip_prefix('F')) {
+            upper = stripped.to_string();
+        }
+    }
+
+    let (base, quote) = if let Some(idx) = upper.find(['-', '/', '_']) {
+        (&upper[..idx], &upper[idx + 1..])
+    } else {
+        split_concatenated(&upper)?
+    };
+
+    // Korean venues spell their hyphenated pairs quote-first (Upbit's "KRW-BTC" is the
+    // BTC/KRW market), the opposite of everyone else's base-first convention.
+    let (base, quote) = if exchange.starts_with("Upbit") {
+        (quote, base)
+    } else {
+        (base, quote)
+    };
+
+    if base.is_empty() || quote.is_empty() {
+        return None;
+    }
+
+    Some(CanonicalPair {
+        base: resolve_alias(base).to_string(),
+        quote: resolve_alias(quote).to_string(),
+    })
+}
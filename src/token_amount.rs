@@ -0,0 +1,61 @@
+use crate::hex_amount;
+use ethers::types::U256;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// A token quantity paired with the decimals it was minted at, so raw on-chain amounts
+/// from two different tokens (e.g. 6-decimal USDC and 18-decimal WETH) can never be
+/// compared or mixed into the same formula without first going through [`to_decimal`],
+/// which is exactly the bug class this type exists to rule out: swap math silently
+/// treating a USDC reserve and a WETH reserve as the same scale. `raw` deserializes from
+/// either a `0x`-prefixed hex string or a plain decimal string, matching how CoW
+/// Protocol's API accepts order amounts.
+///
+/// [`to_decimal`]: TokenAmount::to_decimal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenAmount {
+    #[serde(with = "hex_amount::hex_or_decimal")]
+    pub raw: U256,
+    pub decimals: u8,
+}
+
+impl TokenAmount {
+    pub fn from_raw(raw: U256, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    /// Builds a `TokenAmount` from a human-readable quantity (e.g. `1.5` WETH).
+    pub fn from_decimal(amount: Decimal, decimals: u8) -> Self {
+        Self {
+            raw: hex_amount::decimal_to_raw(amount, decimals),
+            decimals,
+        }
+    }
+
+    /// Human-readable value (`raw / 10^decimals`) — the common fixed-point scale swap
+    /// math should convert back to before reporting amounts for display or profit
+    /// calculations.
+    pub fn to_decimal(&self) -> Decimal {
+        hex_amount::raw_to_decimal(self.raw, self.decimals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_decimal() {
+        let amount = TokenAmount::from_decimal(Decimal::from_str_exact("1.5").unwrap(), 18);
+        assert_eq!(amount.raw, U256::from_dec_str("1500000000000000000").unwrap());
+        assert_eq!(amount.to_decimal(), Decimal::from_str_exact("1.5").unwrap());
+    }
+
+    #[test]
+    fn same_raw_value_means_different_things_at_different_decimals() {
+        let usdc = TokenAmount::from_raw(U256::from(1_000_000u64), 6);
+        let weth = TokenAmount::from_raw(U256::from(1_000_000u64), 18);
+        assert_eq!(usdc.to_decimal(), Decimal::from_str_exact("1").unwrap());
+        assert!(weth.to_decimal() < Decimal::from_str_exact("0.01").unwrap());
+    }
+}
@@ -0,0 +1,203 @@
+// Projected L-BFGS-style gradient ascent for sizing an arbitrage trade through a chain of
+// constant-product (x*y=k) pools. Written over a scalar input amount per path, but the
+// two-loop recursion itself is the standard multi-dimensional L-BFGS update so it extends
+// to a trade vector across parallel routes without rework.
+
+const MEMORY_SIZE: usize = 10;
+const MAX_ITERATIONS: usize = 50;
+const GRADIENT_TOLERANCE: f64 = 1e-8;
+const ARMIJO_C1: f64 = 1e-4;
+const BACKTRACK_FACTOR: f64 = 0.5;
+const MIN_STEP: f64 = 1e-12;
+
+/// One hop of the arbitrage path: a constant-product pool with `fee_bps` taken out of the
+/// input side before the `x*y=k` swap (`y_out = y*gamma*x_in/(x+gamma*x_in)`).
+#[derive(Debug, Clone, Copy)]
+pub struct PoolLeg {
+    pub reserve_in: f64,
+    pub reserve_out: f64,
+    pub fee_bps: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptimalSizeResult {
+    pub optimal_amount: f64,
+    pub profit_usd: f64,
+}
+
+/// Runs `amount_in` through every leg of `path` in order and returns both the final output
+/// and d(output)/d(amount_in) via the chain rule through each leg's closed-form derivative,
+/// rather than a finite-difference gradient — finite differences lose precision right where
+/// this solver needs it most, near the profit boundary.
+fn forward_with_derivative(amount_in: f64, path: &[PoolLeg]) -> (f64, f64) {
+    let mut amount = amount_in.max(0.0);
+    let mut derivative = 1.0;
+
+    for leg in path {
+        let gamma = 1.0 - (leg.fee_bps as f64 / 10_000.0);
+        let denom = leg.reserve_in + gamma * amount;
+        if denom <= 0.0 {
+            return (0.0, 0.0);
+        }
+
+        let leg_derivative = leg.reserve_out * gamma * leg.reserve_in / (denom * denom);
+        amount = leg.reserve_out * gamma * amount / denom;
+        derivative *= leg_derivative;
+    }
+
+    (amount, derivative)
+}
+
+/// Net profit, in the input leg's token, of trading `amount_in` through the whole path.
+fn profit(amount_in: f64, path: &[PoolLeg]) -> f64 {
+    forward_with_derivative(amount_in, path).0 - amount_in
+}
+
+fn gradient(amount_in: f64, path: &[PoolLeg]) -> f64 {
+    forward_with_derivative(amount_in, path).1 - 1.0
+}
+
+/// Two-loop recursion (Nocedal & Wright, Algorithm 7.4) producing the ascent direction from
+/// the last `history.len() <= MEMORY_SIZE` (step, gradient-change) pairs and the current
+/// gradient. Falls back to plain gradient ascent (empty history, i.e. the first iteration).
+fn two_loop_recursion(history: &[(f64, f64)], grad: f64) -> f64 {
+    if history.is_empty() {
+        return grad;
+    }
+
+    let mut q = grad;
+    let mut alphas = vec![0.0; history.len()];
+
+    for (i, &(s, y)) in history.iter().enumerate().rev() {
+        let rho = 1.0 / (y * s);
+        if !rho.is_finite() {
+            continue;
+        }
+        let alpha = rho * s * q;
+        alphas[i] = alpha;
+        q -= alpha * y;
+    }
+
+    let (last_s, last_y) = *history.last().unwrap();
+    let gamma = if last_y != 0.0 {
+        (last_s * last_y) / (last_y * last_y)
+    } else {
+        1.0
+    };
+    let mut r = gamma * q;
+
+    for (i, &(s, y)) in history.iter().enumerate() {
+        let rho = 1.0 / (y * s);
+        if !rho.is_finite() {
+            continue;
+        }
+        let beta = rho * y * r;
+        r += s * (alphas[i] - beta);
+    }
+
+    r
+}
+
+/// Solves `max_{x >= 0} profit(x)` for `path` via projected L-BFGS-style gradient ascent: an
+/// L-BFGS search direction, Armijo backtracking line search on the profit objective, then
+/// clamping the updated point at zero after each step.
+pub fn solve_optimal_trade_size(path: &[PoolLeg]) -> OptimalSizeResult {
+    if path.is_empty() {
+        return OptimalSizeResult { optimal_amount: 0.0, profit_usd: 0.0 };
+    }
+
+    let smallest_reserve = path
+        .iter()
+        .map(|leg| leg.reserve_in.min(leg.reserve_out))
+        .fold(f64::INFINITY, f64::min);
+    if !smallest_reserve.is_finite() || smallest_reserve <= 0.0 {
+        return OptimalSizeResult { optimal_amount: 0.0, profit_usd: 0.0 };
+    }
+
+    // Seed at 1% of the shallowest pool's reserve: large enough to make real progress,
+    // small enough that the first step doesn't blow past the profit-maximizing point.
+    let mut x = smallest_reserve * 0.01;
+    let mut history: Vec<(f64, f64)> = Vec::with_capacity(MEMORY_SIZE);
+    let mut grad = gradient(x, path);
+
+    for _ in 0..MAX_ITERATIONS {
+        if grad.abs() < GRADIENT_TOLERANCE {
+            break;
+        }
+
+        let direction = two_loop_recursion(&history, grad);
+        if !direction.is_finite() || direction <= 0.0 {
+            break;
+        }
+
+        let current_profit = profit(x, path);
+        let mut step = 1.0;
+        let mut x_next = (x + step * direction).max(0.0);
+
+        while profit(x_next, path) < current_profit + ARMIJO_C1 * step * direction * grad
+            && step > MIN_STEP
+        {
+            step *= BACKTRACK_FACTOR;
+            x_next = (x + step * direction).max(0.0);
+        }
+
+        let s = x_next - x;
+        if s.abs() < GRADIENT_TOLERANCE {
+            break;
+        }
+
+        let grad_next = gradient(x_next, path);
+        let y = grad_next - grad;
+
+        if history.len() == MEMORY_SIZE {
+            history.remove(0);
+        }
+        history.push((s, y));
+
+        x = x_next;
+        grad = grad_next;
+    }
+
+    OptimalSizeResult {
+        optimal_amount: x,
+        profit_usd: profit(x, path),
+    }
+}
+
+/// Closed-form exact solution for the common case `solve_optimal_trade_size` otherwise
+/// finds iteratively: spending token0 on `buy` (reserves `R_a` token0, `R_b` token1), then
+/// selling the received token1 on `sell` (reserves `R_b'` token1, `R_a'` token0), with both
+/// legs paying the same fee multiplier `g = 1 - fee_bps/10_000`.
+///
+/// The composed output is `z(x) = A·x / (B + C·x)` with `A = g²·R_a'·R_b`, `B = R_a·R_b'`,
+/// `C = g·R_b' + g²·R_b`. Maximizing `z(x) - x` over `x >= 0` gives
+/// `x* = (sqrt(A·B) - B) / C`, i.e. `x* = (g·sqrt(R_a·R_b·R_a'·R_b') - R_a·R_b') / (g·R_b' + g²·R_b)`.
+/// Only exact for two constant-product legs — callers with a StableSwap or weighted leg in
+/// the path should fall back to `solve_optimal_trade_size`.
+pub fn solve_two_pool_closed_form(buy: PoolLeg, sell: PoolLeg) -> OptimalSizeResult {
+    let g_buy = 1.0 - (buy.fee_bps as f64 / 10_000.0);
+    let g_sell = 1.0 - (sell.fee_bps as f64 / 10_000.0);
+
+    let r_a = buy.reserve_in;
+    let r_b = buy.reserve_out;
+    let r_b_prime = sell.reserve_in;
+    let r_a_prime = sell.reserve_out;
+
+    if r_a <= 0.0 || r_b <= 0.0 || r_a_prime <= 0.0 || r_b_prime <= 0.0 {
+        return OptimalSizeResult { optimal_amount: 0.0, profit_usd: 0.0 };
+    }
+
+    // Only valid when both legs share one fee multiplier, per the derivation above.
+    let g = g_buy.min(g_sell);
+    let numerator = g * (r_a * r_b * r_a_prime * r_b_prime).sqrt() - r_a * r_b_prime;
+    let denominator = g * r_b_prime + g * g * r_b;
+
+    if numerator <= 0.0 || denominator <= 0.0 {
+        return OptimalSizeResult { optimal_amount: 0.0, profit_usd: 0.0 };
+    }
+
+    let optimal_amount = numerator / denominator;
+    let profit_usd = profit(optimal_amount, &[buy, sell]);
+
+    OptimalSizeResult { optimal_amount, profit_usd }
+}
@@ -2,8 +2,10 @@ use serde::{Deserialize, Serialize};
 use rust_decimal::Decimal;
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
+use ethers::types::U256;
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use crate::hex_amount::hex_or_decimal;
 
 // Chain definitions
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -65,6 +67,21 @@ impl Chain {
             Chain::Base => "ETH",
         }
     }
+
+    /// Approximate average time between blocks, in seconds. L2s settle far faster than
+    /// L1 Ethereum, which matters for anything that reasons in "blocks elapsed" (gas
+    /// escalation schedules, EIP-1559 base-fee prediction horizons, ...).
+    pub fn block_time_secs(&self) -> u64 {
+        match self {
+            Chain::Ethereum => 12,
+            Chain::BinanceSmartChain => 3,
+            Chain::Polygon => 2,
+            Chain::Arbitrum => 1,
+            Chain::Optimism => 2,
+            Chain::Avalanche => 2,
+            Chain::Base => 2,
+        }
+    }
 }
 
 // Token information
@@ -76,6 +93,300 @@ pub struct Token {
     pub chain: Chain,
 }
 
+/// A tradeable base/quote pair on a CEX, as opposed to the on-chain `LiquidityPool`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenPair {
+    pub base: Token,
+    pub quote: Token,
+}
+
+/// A single resting order at one price level of an `OrderBook`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Order {
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Top-of-book quote, as returned by `Exchange::get_price`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Price {
+    pub bid: Decimal,
+    pub ask: Decimal,
+    pub bid_size: Decimal,
+    pub ask_size: Decimal,
+    pub timestamp: DateTime<Utc>,
+    pub exchange: String,
+    pub pair: TokenPair,
+}
+
+/// Maker/taker fee schedule plus per-asset withdrawal fees, as returned by
+/// `Exchange::get_fees`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeFees {
+    pub maker_fee: Decimal,
+    pub taker_fee: Decimal,
+    pub withdrawal_fee: std::collections::HashMap<String, Decimal>,
+}
+
+/// Which side of an `OrderBook` a fill simulation walks: a buy consumes asks (cheapest
+/// first), a sell consumes bids (richest first).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FillSide {
+    Buy,
+    Sell,
+}
+
+/// Outcome of walking an `OrderBook`'s depth to fill a target size.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FillResult {
+    pub filled_base: Decimal,
+    pub filled_quote: Decimal,
+    /// Volume-weighted average price across every level touched.
+    pub avg_price: Decimal,
+    /// Price of the worst (last) level touched — the true marginal cost of the size.
+    pub worst_price: Decimal,
+    /// `false` if the book didn't have enough depth to fully satisfy the request.
+    pub fully_filled: bool,
+}
+
+/// Result of executing a market order of a given size against a `PriceSource`'s current
+/// depth — the depth-aware counterpart to a quoted mid/top-of-book price.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceExecution {
+    pub avg_price: Decimal,
+    pub filled: Decimal,
+    /// How far `avg_price` moved away from the source's best quote, as a percentage.
+    pub slippage_pct: Decimal,
+    /// `false` if the source didn't have enough depth to fill the requested size.
+    pub fully_filled: bool,
+}
+
+/// A venue whose executable price depends on trade size rather than being a single quote —
+/// an order book walks its depth, an AMM pool would walk its `SwapCurve`. Lets
+/// depth-limited execution (this module) and curve-based execution (`swap_curve::SwapCurve`)
+/// be compared through one interface when sizing a trade's worse leg.
+pub trait PriceSource {
+    fn execute(&self, amount: Decimal, side: FillSide) -> PriceExecution;
+}
+
+/// A venue's order book, as returned by `Exchange::get_orderbook`.
+///
+/// `bids`/`asks` are assumed sorted best-first — `bids` descending by price, `asks`
+/// ascending — matching what every `Exchange` implementation populates them with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBook {
+    pub exchange: String,
+    pub pair: TokenPair,
+    pub bids: Vec<Order>,
+    pub asks: Vec<Order>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl OrderBook {
+    fn levels(&self, side: FillSide) -> &[Order] {
+        match side {
+            FillSide::Buy => &self.asks,
+            FillSide::Sell => &self.bids,
+        }
+    }
+
+    /// Walks price levels on `side`, accumulating quantity until `base_qty` is filled (or
+    /// the book runs out of depth), returning the volume-weighted average fill price,
+    /// total quote cost, and worst price touched. Mirrors the order-book trade simulation
+    /// used for liquidation/price-discovery in on-chain lending engines, so arbitrage
+    /// scoring can use true slippage-adjusted prices instead of top-of-book alone.
+    pub fn simulate_market_fill(&self, side: FillSide, base_qty: Decimal) -> FillResult {
+        let mut remaining = base_qty;
+        let mut filled_base = Decimal::ZERO;
+        let mut filled_quote = Decimal::ZERO;
+        let mut worst_price = Decimal::ZERO;
+
+        for level in self.levels(side) {
+            if remaining <= Decimal::ZERO {
+                break;
+            }
+            let take = remaining.min(level.quantity);
+            filled_base += take;
+            filled_quote += take * level.price;
+            worst_price = level.price;
+            remaining -= take;
+        }
+
+        Self::result(filled_base, filled_quote, worst_price, remaining <= Decimal::ZERO)
+    }
+
+    /// Inverse of `simulate_market_fill`: walks levels on `side` spending `quote_budget`
+    /// until it's exhausted (or the book runs out of depth), rather than filling a fixed
+    /// base size.
+    pub fn fillable_for_quote(&self, side: FillSide, quote_budget: Decimal) -> FillResult {
+        let mut remaining_quote = quote_budget;
+        let mut filled_base = Decimal::ZERO;
+        let mut filled_quote = Decimal::ZERO;
+        let mut worst_price = Decimal::ZERO;
+
+        for level in self.levels(side) {
+            if remaining_quote <= Decimal::ZERO || level.price <= Decimal::ZERO {
+                break;
+            }
+            let level_quote_capacity = level.quantity * level.price;
+            let take_quote = remaining_quote.min(level_quote_capacity);
+            let take_base = take_quote / level.price;
+
+            filled_base += take_base;
+            filled_quote += take_quote;
+            worst_price = level.price;
+            remaining_quote -= take_quote;
+        }
+
+        Self::result(filled_base, filled_quote, worst_price, remaining_quote <= Decimal::ZERO)
+    }
+
+    /// Top of the bid side, if the book has any bids.
+    pub fn best_bid(&self) -> Option<&Order> {
+        self.bids.first()
+    }
+
+    /// Top of the ask side, if the book has any asks.
+    pub fn best_ask(&self) -> Option<&Order> {
+        self.asks.first()
+    }
+
+    /// Volume-weighted average price to execute a market order of `size` base units on
+    /// `side`, or `None` if the book doesn't have enough depth to fill it. Thin wrapper
+    /// over `simulate_market_fill` for callers that only want the executable price, not
+    /// the full fill breakdown.
+    pub fn vwap_for_size(&self, side: FillSide, size: Decimal) -> Option<Decimal> {
+        let result = self.simulate_market_fill(side, size);
+        if result.fully_filled {
+            Some(result.avg_price)
+        } else {
+            None
+        }
+    }
+
+    fn result(filled_base: Decimal, filled_quote: Decimal, worst_price: Decimal, fully_filled: bool) -> FillResult {
+        let avg_price = if filled_base > Decimal::ZERO {
+            filled_quote / filled_base
+        } else {
+            Decimal::ZERO
+        };
+
+        FillResult {
+            filled_base,
+            filled_quote,
+            avg_price,
+            worst_price,
+            fully_filled,
+        }
+    }
+}
+
+impl PriceSource for OrderBook {
+    /// Walks the book via `simulate_market_fill` and reports slippage against the best
+    /// quote on `side` (`(avg_price - best)/best`), matching the order-flow convention
+    /// that a buy slipping *up* and a sell slipping *down* are both reported as positive
+    /// slippage.
+    fn execute(&self, amount: Decimal, side: FillSide) -> PriceExecution {
+        let result = self.simulate_market_fill(side, amount);
+        let best = match side {
+            FillSide::Buy => self.best_ask(),
+            FillSide::Sell => self.best_bid(),
+        };
+
+        let slippage_pct = match best {
+            Some(level) if level.price > Decimal::ZERO && result.filled_base > Decimal::ZERO => {
+                ((result.avg_price - level.price) / level.price * Decimal::from(100)).abs()
+            }
+            _ => Decimal::ZERO,
+        };
+
+        PriceExecution {
+            avg_price: result.avg_price,
+            filled: result.filled_base,
+            slippage_pct,
+            fully_filled: result.fully_filled,
+        }
+    }
+}
+
+#[cfg(test)]
+mod orderbook_tests {
+    use super::*;
+
+    fn level(price: &str, qty: &str) -> Order {
+        Order {
+            price: Decimal::from_str_exact(price).unwrap(),
+            quantity: Decimal::from_str_exact(qty).unwrap(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn book() -> OrderBook {
+        OrderBook {
+            exchange: "Test".to_string(),
+            pair: TokenPair {
+                base: Token { address: String::new(), symbol: "ETH".to_string(), decimals: 18, chain: Chain::Ethereum },
+                quote: Token { address: String::new(), symbol: "USDC".to_string(), decimals: 6, chain: Chain::Ethereum },
+            },
+            bids: vec![level("99", "1"), level("98", "2")],
+            asks: vec![level("100", "1"), level("101", "2")],
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn fills_across_multiple_levels() {
+        let result = book().simulate_market_fill(FillSide::Buy, Decimal::from_str_exact("2").unwrap());
+        assert!(result.fully_filled);
+        assert_eq!(result.filled_quote, Decimal::from_str_exact("201").unwrap());
+        assert_eq!(result.worst_price, Decimal::from_str_exact("101").unwrap());
+    }
+
+    #[test]
+    fn reports_insufficient_depth() {
+        let result = book().simulate_market_fill(FillSide::Buy, Decimal::from_str_exact("10").unwrap());
+        assert!(!result.fully_filled);
+        assert_eq!(result.filled_base, Decimal::from_str_exact("3").unwrap());
+    }
+
+    #[test]
+    fn fillable_for_quote_matches_inverse() {
+        let result = book().fillable_for_quote(FillSide::Sell, Decimal::from_str_exact("99").unwrap());
+        assert!(result.fully_filled);
+        assert_eq!(result.filled_base, Decimal::from_str_exact("1").unwrap());
+    }
+
+    #[test]
+    fn best_bid_and_ask_are_top_of_book() {
+        let book = book();
+        assert_eq!(book.best_bid().unwrap().price, Decimal::from_str_exact("99").unwrap());
+        assert_eq!(book.best_ask().unwrap().price, Decimal::from_str_exact("100").unwrap());
+    }
+
+    #[test]
+    fn vwap_for_size_none_when_depth_insufficient() {
+        let result = book().vwap_for_size(FillSide::Buy, Decimal::from_str_exact("10").unwrap());
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn vwap_for_size_matches_simulated_fill() {
+        let size = Decimal::from_str_exact("2").unwrap();
+        let expected = book().simulate_market_fill(FillSide::Buy, size).avg_price;
+        assert_eq!(book().vwap_for_size(FillSide::Buy, size), Some(expected));
+    }
+
+    #[test]
+    fn price_source_execute_reports_slippage_against_best_quote() {
+        let execution = book().execute(Decimal::from_str_exact("2").unwrap(), FillSide::Buy);
+        assert!(execution.fully_filled);
+        assert_eq!(execution.avg_price, Decimal::from_str_exact("100.5").unwrap());
+        // Best ask is 100, blended fill price is 100.5 => 0.5% slippage.
+        assert_eq!(execution.slippage_pct, Decimal::from_str_exact("0.5").unwrap());
+    }
+}
+
 // Liquidity pool
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LiquidityPool {
@@ -88,6 +399,17 @@ pub struct LiquidityPool {
     pub dex: String,
     pub chain: Chain,
     pub last_update: DateTime<Utc>,
+    /// Which `SwapCurve` prices this pool (constant-product, StableSwap, etc.), tagged
+    /// from the DEX name in `DexManager::get_pool_info`.
+    pub curve: crate::swap_curve::CurveType,
+    /// Rate-provider contract for a rebasing/LSD token held by this pool (e.g. Lido's
+    /// wstETH), if either side is one — see `DexManager::rate_adjusted_reserves`. `None`
+    /// for ordinary pools, which is every pool that isn't pairing an LSD.
+    pub rate_provider: Option<String>,
+    /// Fixed-point scale the provider's rate is expressed in (1e18 for every provider
+    /// `DexManager` currently recognizes), so `rate / rate_scale` is a plain multiplier
+    /// on the rate-bearing side's reserve. `Decimal::ONE` when `rate_provider` is `None`.
+    pub rate_scale: Decimal,
 }
 
 // Price data
@@ -109,6 +431,9 @@ pub struct GasPrice {
     pub fast: Decimal,
     pub standard: Decimal,
     pub slow: Decimal,
+    pub base_fee: Decimal,
+    pub priority_fee: Decimal,
+    pub max_fee: Decimal,
     pub timestamp: DateTime<Utc>,
 }
 
@@ -130,6 +455,10 @@ pub struct ArbitrageOpportunity {
     pub opportunity_type: String,
     pub path: Vec<TradePath>,
     pub initial_amount: Decimal,
+    /// `initial_amount` in raw on-chain units (wei-equivalent), for wire payloads and
+    /// tooling that expects exact integers rather than lossy decimals.
+    #[serde(with = "hex_or_decimal")]
+    pub amount_raw: U256,
     pub final_amount: Decimal,
     pub gross_profit: Decimal,
     pub flash_loan_provider: String,
@@ -140,6 +469,19 @@ pub struct ArbitrageOpportunity {
     pub roi_percentage: Decimal,
     pub confidence_score: f64,
     pub timestamp: DateTime<Utc>,
+    /// `final_amount / initial_amount`, when sized against real pool depth rather than a
+    /// flat quoted spot price. `None` when the opportunity fell back to the quoted-price
+    /// approximation (no pool could be resolved for one of the venues).
+    pub avg_execution_price: Option<Decimal>,
+    /// How far `avg_execution_price` fell below the pools' combined spot price, as a
+    /// percentage — the slippage a depth-aware sizing pass adds over the naive
+    /// spot-price assumption. `None` alongside `avg_execution_price`.
+    pub slippage_pct: Option<Decimal>,
+    /// Hex hash of the still-pending transaction this opportunity depends on, for a
+    /// `Backrun`-type opportunity produced by `MempoolMonitor` from a simulated post-trade
+    /// reserve shift — executing it only makes sense if that transaction actually lands.
+    /// `None` for every opportunity found against already-confirmed state.
+    pub pending_tx_hash: Option<String>,
 }
 
 // Trade path component
@@ -158,5 +500,56 @@ pub struct SharedState {
     pub prices: Arc<DashMap<String, PriceData>>,
     pub pools: Arc<DashMap<String, LiquidityPool>>,
     pub gas_prices: Arc<DashMap<Chain, GasPrice>>,
+    /// Cached `(rate, fetched_at)` per rate-provider contract address, keyed so pools
+    /// sharing a provider (e.g. two wstETH/WETH pools on different DEXs) reuse one RPC
+    /// call — see `DexManager::rate_adjusted_reserves`. Short TTL, checked at read time
+    /// rather than refreshed on a background interval like `gas_prices`, since a stale
+    /// LSD rate produces a phantom arbitrage signal almost immediately.
+    pub rebase_rates: Arc<DashMap<String, (Decimal, DateTime<Utc>)>>,
     pub opportunities: Arc<RwLock<Vec<ArbitrageOpportunity>>>,
+    /// Rolling inter-venue price volatility per `token_pair`, used to widen the spread
+    /// `ArbitrageEngine` requires before it'll trust a cross-DEX discrepancy — a 0.5%
+    /// spread means something different on a pair whose quotes normally agree to the
+    /// penny than on one that swings 2% between venues on its own.
+    pub price_volatility: Arc<DashMap<String, VolatilityTracker>>,
+}
+
+/// Rolling mean/variance of a `token_pair`'s observed price, updated incrementally via
+/// Welford's algorithm so tracking cost stays O(1) per observation instead of keeping a
+/// growing sample buffer around.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VolatilityTracker {
+    count: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl VolatilityTracker {
+    /// Folds one more observed price into the running mean/variance.
+    pub fn observe(&mut self, price: f64) {
+        self.count += 1;
+        let delta = price - self.mean;
+        self.mean += delta / self.count as f64;
+        let delta2 = price - self.mean;
+        self.m2 += delta * delta2;
+    }
+
+    /// Sample standard deviation of prices observed so far, or `0.0` with fewer than two
+    /// observations (there's no spread to measure yet).
+    pub fn std_dev(&self) -> f64 {
+        if self.count < 2 {
+            return 0.0;
+        }
+        (self.m2 / (self.count - 1) as f64).sqrt()
+    }
+
+    /// Standard deviation as a fraction of the running mean, i.e. the coefficient of
+    /// variation — what `ArbitrageEngine` actually widens its spread threshold by, since
+    /// an absolute stddev means nothing without the price scale it was measured against.
+    pub fn relative_std_dev(&self) -> f64 {
+        if self.mean.abs() < f64::EPSILON {
+            return 0.0;
+        }
+        self.std_dev() / self.mean.abs()
+    }
 }
\ No newline at end of file
@@ -25,6 +25,22 @@ pub struct ArbitrageSignal {
     pub confidence: f64,
 }
 
+/// Describes a REST handshake that must run before a feed's WebSocket can be opened at
+/// all, e.g. KuCoin's `/api/v1/bullet-public` bootstrap: POST for a connection token,
+/// then connect to the returned endpoint with that token attached. `endpoint_pointer` and
+/// `token_pointer` are JSON pointers (RFC 6901) into the REST response body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestBootstrap {
+    pub method: String,
+    pub url: String,
+    pub body: serde_json::Value,
+    pub endpoint_pointer: String,
+    pub token_pointer: String,
+    /// JSON pointer to the server-provided ping interval (milliseconds), if the bootstrap
+    /// response carries one. When absent, the feed is never pinged.
+    pub ping_interval_pointer: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebSocketFeed {
     pub name: String,
@@ -32,6 +48,10 @@ pub struct WebSocketFeed {
     pub subscription: serde_json::Value,
     pub chain: Option<String>,
     pub feed_type: FeedType,
+    pub bootstrap: Option<RestBootstrap>,
+    /// Currency this feed's prices are quoted in, e.g. `"KRW"` for Upbit. Defaults to
+    /// `"USD"`, which needs no conversion before entering the shared price index.
+    pub quote_currency: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,8 +83,24 @@ impl WebSocketFeed {
             subscription,
             chain: None,
             feed_type,
+            bootstrap: None,
+            quote_currency: "USD".to_string(),
         }
     }
+
+    /// Attaches a REST bootstrap handshake that must succeed before this feed's
+    /// WebSocket URL is final (see `RestBootstrap`).
+    pub fn with_bootstrap(mut self, bootstrap: RestBootstrap) -> Self {
+        self.bootstrap = Some(bootstrap);
+        self
+    }
+
+    /// Tags this feed as quoting prices in `currency` instead of USD, e.g. `"KRW"` for a
+    /// regional exchange like Upbit.
+    pub fn with_quote_currency(mut self, currency: &str) -> Self {
+        self.quote_currency = currency.to_string();
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
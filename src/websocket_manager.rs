@@ -1,21 +1,36 @@
 use std::sync::Arc;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::{connect_async, tungstenite::Message, WebSocketStream, MaybeTlsStream};
 use futures_util::{StreamExt, SinkExt};
+use futures_util::stream::{FuturesUnordered, SplitSink, SplitStream};
+use tokio_stream::StreamMap;
+use tokio::net::TcpStream;
 use serde_json::{json, Value};
 use anyhow::Result;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 use std::str::FromStr;
+use std::time::{Duration, Instant};
 use chrono::Utc;
 use colored::*;
 use parking_lot::RwLock;
 use std::collections::HashMap;
-use crate::types::{SharedState, ArbitrageSignal, WebSocketFeed};
+use crate::types::{SharedState, ArbitrageSignal, WebSocketFeed, RestBootstrap};
+use crate::price_aggregator::{AggregatorConfig, PriceAggregator};
+use crate::fiat_rates::{FiatRates, RestFiatRateProvider};
+use crate::exchange_parsers::{build_registry, parser_for, ExchangeParser};
+use crate::symbol_normalization::normalize_pair;
+
+/// Concrete stream type every feed connects with; `StreamMap` requires one stream type
+/// across all keys, which this satisfies since every feed is a plain `wss://` endpoint.
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
 
 pub struct WebSocketManager {
     state: Arc<SharedState>,
     feeds: Vec<WebSocketFeed>,
     performance_stats: Arc<RwLock<HashMap<String, FeedStats>>>,
+    price_aggregator: Arc<PriceAggregator>,
+    fiat_rates: Arc<FiatRates>,
+    parsers: Arc<Vec<(&'static str, Box<dyn ExchangeParser>)>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -34,11 +49,22 @@ impl WebSocketManager {
         
         println!("{}", "🚀 Initializing WebSocket Manager with 100+ feeds...".bright_green().bold());
         println!("{}", format!("📡 Total feeds configured: {}", feeds.len()).cyan());
-        
+
+        let price_aggregator = Arc::new(PriceAggregator::new(AggregatorConfig::default(), feeds.len()));
+
+        let fiat_rates = FiatRates::new(
+            Box::new(RestFiatRateProvider::new("https://api.exchangerate.host/latest?base=USD")),
+            chrono::Duration::minutes(10),
+        );
+        fiat_rates.start_polling(std::time::Duration::from_secs(60));
+
         Ok(Self {
             state,
             feeds,
             performance_stats: Arc::new(RwLock::new(HashMap::new())),
+            price_aggregator,
+            fiat_rates,
+            parsers: Arc::new(build_registry()),
         })
     }
     
@@ -82,7 +108,14 @@ impl WebSocketManager {
             WebSocketFeed::new("KuCoin", "wss://ws-api-spot.kucoin.com", json!({
                 "type": "subscribe",
                 "topic": "/market/ticker:ETH-USDT,BTC-USDT,MATIC-USDT,SOL-USDT"
-            })),
+            })).with_bootstrap(RestBootstrap {
+                method: "POST".to_string(),
+                url: "https://api.kucoin.com/api/v1/bullet-public".to_string(),
+                body: json!({}),
+                endpoint_pointer: "/data/instanceServers/0/endpoint".to_string(),
+                token_pointer: "/data/token".to_string(),
+                ping_interval_pointer: Some("/data/instanceServers/0/pingInterval".to_string()),
+            }),
             WebSocketFeed::new("Huobi", "wss://api.huobi.pro/ws", json!({
                 "sub": "market.ethusdt.ticker"
             })),
@@ -304,16 +337,16 @@ impl WebSocketManager {
                 "ticket": "test",
                 "type": "ticker",
                 "codes": ["KRW-BTC", "KRW-ETH"]
-            }])),
+            }])).with_quote_currency("KRW"),
             WebSocketFeed::new("Bithumb", "wss://pubwss.bithumb.com/pub/ws", json!({
                 "type": "ticker",
                 "symbols": ["BTC_KRW", "ETH_KRW"]
-            })),
+            })).with_quote_currency("KRW"),
             WebSocketFeed::new("Bitso", "wss://ws.bitso.com", json!({
                 "action": "subscribe",
                 "book": "eth_mxn",
                 "type": "trades"
-            })),
+            })).with_quote_currency("MXN"),
             
             // More Layer 2s and sidechains
             WebSocketFeed::new("zkSync", "wss://api.zksync.io/ws", json!({
@@ -424,12 +457,12 @@ impl WebSocketManager {
                 "event": "subscribe",
                 "channel": "ticker",
                 "symbol": "ETHINR"
-            })),
+            })).with_quote_currency("INR"),
             WebSocketFeed::new("Mercado", "wss://ws.mercadobitcoin.net/ws", json!({
                 "type": "subscribe",
                 "channel": "ticker",
                 "symbol": "BTC-BRL"
-            })),
+            })).with_quote_currency("BRL"),
             
             // Institutional platforms
             WebSocketFeed::new("FalconX", "wss://api.falconx.io/ws", json!({
@@ -447,152 +480,321 @@ impl WebSocketManager {
         ]
     }
     
+    /// Starts a single multiplexed driver task instead of one task per feed. Every live
+    /// feed's read half lives in one `StreamMap` keyed by feed index, so the driver polls
+    /// them all fairly and yields `(feed_index, message)` tuples from one place; write
+    /// halves live in a parallel map for sending subscriptions and scheduled pings. A feed
+    /// that closes or errors is dropped from both maps and a reconnect future for just
+    /// that index is requeued, rather than an entire task dying and respawning.
     pub async fn start_all_connections(&self) {
         println!("{}", "🔌 Starting WebSocket connections...".yellow());
-        
-        for feed in &self.feeds {
-            let feed_clone = feed.clone();
-            let state_clone = self.state.clone();
-            let stats_clone = self.performance_stats.clone();
-            
-            tokio::spawn(async move {
-                loop {
-                    if let Err(e) = Self::connect_and_monitor(
-                        feed_clone.clone(),
-                        state_clone.clone(),
-                        stats_clone.clone()
-                    ).await {
-                        eprintln!("{} {} {}: {}", 
-                            "❌".red(),
-                            "WebSocket error for".red(),
-                            feed_clone.name.red().bold(),
-                            e
-                        );
-                        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-                    }
-                }
-            });
-        }
-        
+
+        let feeds = self.feeds.clone();
+        let state = self.state.clone();
+        let stats = self.performance_stats.clone();
+        let aggregator = self.price_aggregator.clone();
+        let fiat_rates = self.fiat_rates.clone();
+        let parsers = self.parsers.clone();
+
+        tokio::spawn(async move {
+            Self::run_driver(feeds, state, stats, aggregator, fiat_rates, parsers).await;
+        });
+
         // Start performance monitor
         let stats_clone = self.performance_stats.clone();
         tokio::spawn(async move {
             Self::monitor_performance(stats_clone).await;
         });
     }
-    
-    async fn connect_and_monitor(
+
+    /// Connects (or reconnects) feed `idx`, running its REST bootstrap if it has one and
+    /// sending its subscription message once open. `delay` lets reconnects back off
+    /// without blocking the driver loop (the sleep happens inside this future, which sits
+    /// in `pending_connects` until it resolves).
+    async fn connect_feed(
+        idx: usize,
         feed: WebSocketFeed,
+        delay: Duration,
+    ) -> (usize, Result<(SplitSink<WsStream, Message>, SplitStream<WsStream>, Option<u64>)>) {
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+
+        let result: Result<_> = async {
+            let (connect_url, ping_interval_ms) = Self::resolve_connection_url(&feed).await?;
+            let url = url::Url::parse(&connect_url)?;
+            let (ws_stream, _) = connect_async(url).await?;
+            let (mut write, read) = ws_stream.split();
+
+            if !feed.subscription.is_null() {
+                write.send(Message::Text(feed.subscription.to_string())).await?;
+            }
+
+            Ok((write, read, ping_interval_ms))
+        }.await;
+
+        (idx, result)
+    }
+
+    async fn run_driver(
+        feeds: Vec<WebSocketFeed>,
         state: Arc<SharedState>,
-        stats: Arc<RwLock<HashMap<String, FeedStats>>>
-    ) -> Result<()> {
-        let url = url::Url::parse(&feed.url)?;
-        let (ws_stream, _) = connect_async(url).await?;
-        let (mut write, mut read) = ws_stream.split();
-        
-        // Send subscription message
-        if !feed.subscription.is_null() {
-            write.send(Message::Text(feed.subscription.to_string())).await?;
+        stats: Arc<RwLock<HashMap<String, FeedStats>>>,
+        aggregator: Arc<PriceAggregator>,
+        fiat_rates: Arc<FiatRates>,
+        parsers: Arc<Vec<(&'static str, Box<dyn ExchangeParser>)>>,
+    ) {
+        let mut reads: StreamMap<usize, SplitStream<WsStream>> = StreamMap::new();
+        let mut writes: HashMap<usize, SplitSink<WsStream, Message>> = HashMap::new();
+        let mut next_ping: HashMap<usize, Instant> = HashMap::new();
+        let mut ping_interval_ms: HashMap<usize, u64> = HashMap::new();
+        let mut pending_connects = FuturesUnordered::new();
+
+        for (idx, feed) in feeds.iter().enumerate() {
+            pending_connects.push(Self::connect_feed(idx, feed.clone(), Duration::ZERO));
         }
-        
+
+        // Coarse enough to not busy-loop, fine enough that per-feed ping intervals (on
+        // the order of seconds) still fire close to on time.
+        let mut ping_scan = tokio::time::interval(Duration::from_millis(250));
         let start_time = Utc::now();
-        
-        while let Some(message) = read.next().await {
-            let recv_time = Utc::now();
-            
-            match message {
-                Ok(Message::Text(text)) => {
-                    if let Ok(data) = serde_json::from_str::<Value>(&text) {
-                        let latency = (recv_time - start_time).num_milliseconds() as f64;
-                        
-                        // Process for arbitrage
-                        if let Some(signal) = Self::extract_arbitrage_signal(&feed.name, &data, &state).await {
-                            // Update stats
-                            let mut stats_guard = stats.write();
-                            let feed_stat = stats_guard.entry(feed.name.clone()).or_default();
-                            feed_stat.messages_received += 1;
-                            feed_stat.avg_latency_ms = (feed_stat.avg_latency_ms + latency) / 2.0;
-                            
-                            if signal.profit > Decimal::ZERO {
-                                feed_stat.opportunities_found += 1;
-                                feed_stat.profit_generated += signal.profit;
-                                
-                                // Log opportunity to terminal
-                                Self::log_opportunity(&feed.name, &signal);
-                                
-                                // Store in shared state
-                                state.signals.insert(signal.id.clone(), signal);
+
+        loop {
+            tokio::select! {
+                Some((idx, result)) = pending_connects.next() => {
+                    match result {
+                        Ok((write, read, ms)) => {
+                            writes.insert(idx, write);
+                            reads.insert(idx, read);
+                            if let Some(ms) = ms {
+                                ping_interval_ms.insert(idx, ms);
+                                next_ping.insert(idx, Instant::now() + Duration::from_millis(ms));
                             }
                         }
+                        Err(e) => {
+                            eprintln!("{} {} {}: {}",
+                                "❌".red(),
+                                "WebSocket error for".red(),
+                                feeds[idx].name.red().bold(),
+                                e
+                            );
+                            pending_connects.push(Self::connect_feed(idx, feeds[idx].clone(), Duration::from_secs(5)));
+                        }
                     }
                 }
-                Ok(Message::Binary(bin)) => {
-                    if let Ok(text) = String::from_utf8(bin) {
-                        if let Ok(data) = serde_json::from_str::<Value>(&text) {
-                            Self::extract_arbitrage_signal(&feed.name, &data, &state).await;
+                Some((idx, message)) = reads.next() => {
+                    let feed_name = &feeds[idx].name;
+                    let quote_currency = &feeds[idx].quote_currency;
+                    let recv_time = Utc::now();
+
+                    match message {
+                        Ok(Message::Text(text)) => {
+                            if let Ok(data) = serde_json::from_str::<Value>(&text) {
+                                let latency = (recv_time - start_time).num_milliseconds() as f64;
+                                let signals = Self::extract_arbitrage_signals(feed_name, quote_currency, &data, &state, &aggregator, &fiat_rates, &parsers).await;
+
+                                if !signals.is_empty() {
+                                    let mut stats_guard = stats.write();
+                                    let feed_stat = stats_guard.entry(feed_name.clone()).or_default();
+                                    feed_stat.messages_received += 1;
+                                    feed_stat.avg_latency_ms = (feed_stat.avg_latency_ms + latency) / 2.0;
+
+                                    for signal in signals {
+                                        if signal.profit > Decimal::ZERO {
+                                            feed_stat.opportunities_found += 1;
+                                            feed_stat.profit_generated += signal.profit;
+
+                                            Self::log_opportunity(feed_name, &signal);
+                                            state.signals.insert(signal.id.clone(), signal);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        Ok(Message::Binary(bin)) => {
+                            if let Ok(text) = String::from_utf8(bin) {
+                                if let Ok(data) = serde_json::from_str::<Value>(&text) {
+                                    Self::extract_arbitrage_signals(feed_name, quote_currency, &data, &state, &aggregator, &fiat_rates, &parsers).await;
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("{} {} {}: {}",
+                                "❌".red(),
+                                "WebSocket error for".red(),
+                                feed_name.red().bold(),
+                                e
+                            );
+                            reads.remove(&idx);
+                            writes.remove(&idx);
+                            next_ping.remove(&idx);
+                            ping_interval_ms.remove(&idx);
+                            pending_connects.push(Self::connect_feed(idx, feeds[idx].clone(), Duration::from_secs(5)));
                         }
+                        _ => {}
                     }
                 }
-                Err(e) => {
-                    return Err(anyhow::anyhow!("WebSocket error: {}", e));
+                _ = ping_scan.tick() => {
+                    let now = Instant::now();
+                    let due: Vec<usize> = next_ping.iter()
+                        .filter(|(_, due)| now >= **due)
+                        .map(|(idx, _)| *idx)
+                        .collect();
+
+                    for idx in due {
+                        if let Some(write) = writes.get_mut(&idx) {
+                            let ping = Message::Text(json!({
+                                "id": Utc::now().timestamp_millis().to_string(),
+                                "type": "ping"
+                            }).to_string());
+                            let _ = write.send(ping).await;
+                        }
+                        if let Some(ms) = ping_interval_ms.get(&idx) {
+                            next_ping.insert(idx, now + Duration::from_millis(*ms));
+                        }
+                    }
                 }
-                _ => {}
             }
         }
-        
-        Ok(())
     }
-    
-    async fn extract_arbitrage_signal(
+
+    /// Runs `feed.bootstrap`'s REST handshake, if any, and returns the final URL to open
+    /// the WebSocket against plus the ping interval (ms) the server asked for. Feeds with
+    /// no bootstrap connect to `feed.url` directly and are never pinged.
+    async fn resolve_connection_url(feed: &WebSocketFeed) -> Result<(String, Option<u64>)> {
+        let Some(bootstrap) = &feed.bootstrap else {
+            return Ok((feed.url.clone(), None));
+        };
+
+        let method = reqwest::Method::from_bytes(bootstrap.method.as_bytes())?;
+        let response: Value = reqwest::Client::new()
+            .request(method, &bootstrap.url)
+            .json(&bootstrap.body)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        let endpoint = response
+            .pointer(&bootstrap.endpoint_pointer)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!(
+                "Bootstrap response for {} missing endpoint at {}", feed.name, bootstrap.endpoint_pointer
+            ))?;
+        let token = response
+            .pointer(&bootstrap.token_pointer)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!(
+                "Bootstrap response for {} missing token at {}", feed.name, bootstrap.token_pointer
+            ))?;
+        let ping_interval_ms = bootstrap.ping_interval_pointer
+            .as_ref()
+            .and_then(|pointer| response.pointer(pointer))
+            .and_then(|v| v.as_u64());
+
+        let connect_id = hex::encode(blake3::hash(format!("{}{}", feed.name, Utc::now()).as_bytes()).as_bytes());
+        let connect_url = format!("{}?token={}&connectId={}", endpoint, token, connect_id);
+
+        Ok((connect_url, ping_interval_ms))
+    }
+
+    /// Runs `source`'s registered parser over one raw message and turns every
+    /// `PriceUpdate` it yields into a signal, if that pair clears the consensus checks.
+    /// Array-ticker feeds (Binance's `!ticker@arr`, OKX/Bitget's batched `data[]`) report
+    /// many pairs per message, so this returns all of them rather than the first match.
+    async fn extract_arbitrage_signals(
         source: &str,
+        quote_currency: &str,
         data: &Value,
-        state: &Arc<SharedState>
-    ) -> Option<ArbitrageSignal> {
-        // Extract price data based on source format
-        let price_info = match source {
-            s if s.starts_with("Binance") => Self::parse_binance_data(data),
-            s if s.starts_with("Coinbase") => Self::parse_coinbase_data(data),
-            s if s.starts_with("Kraken") => Self::parse_kraken_data(data),
-            s if s.starts_with("Uniswap") => Self::parse_uniswap_data(data),
-            s if s.starts_with("1inch") => Self::parse_1inch_data(data),
-            _ => None,
+        state: &Arc<SharedState>,
+        aggregator: &Arc<PriceAggregator>,
+        fiat_rates: &Arc<FiatRates>,
+        parsers: &Arc<Vec<(&'static str, Box<dyn ExchangeParser>)>>,
+    ) -> Vec<ArbitrageSignal> {
+        let Some(updates) = parser_for(parsers, source).parse(data) else {
+            return Vec::new();
         };
-        
-        if let Some((token_pair, price, volume)) = price_info {
-            // Check for arbitrage opportunities
-            let existing_prices = state.price_index.read();
-            
-            for (other_source, other_price) in existing_prices.iter() {
-                if other_source != source && other_source.contains(&token_pair) {
-                    let price_diff = (price - *other_price).abs();
-                    let spread_pct = (price_diff / price) * Decimal::from(100);
-                    
-                    if spread_pct > Decimal::from_str("0.5").unwrap() {
-                        // Calculate with gas and fees
-                        let signal = Self::calculate_arbitrage_profit(
-                            source,
-                            other_source,
-                            &token_pair,
-                            price,
-                            *other_price,
-                            volume,
-                            state
-                        ).await;
-                        
-                        return signal;
-                    }
-                }
+
+        let mut signals = Vec::new();
+        for update in updates {
+            if let Some(signal) = Self::extract_arbitrage_signal(
+                source,
+                quote_currency,
+                update.pair,
+                update.price,
+                update.volume,
+                state,
+                aggregator,
+                fiat_rates,
+            ).await {
+                signals.push(signal);
             }
-            
-            // Update price index
-            drop(existing_prices);
-            let mut prices = state.price_index.write();
-            prices.insert(format!("{}:{}", source, token_pair), price);
         }
-        
-        None
+        signals
     }
-    
+
+    async fn extract_arbitrage_signal(
+        source: &str,
+        quote_currency: &str,
+        token_pair: String,
+        raw_price: Decimal,
+        volume: Decimal,
+        state: &Arc<SharedState>,
+        aggregator: &Arc<PriceAggregator>,
+        fiat_rates: &Arc<FiatRates>,
+    ) -> Option<ArbitrageSignal> {
+        // Venues spell the same pair wildly differently (Bitfinex `tETHUSD`, OKX
+        // `ETH-USDT`, Binance `ethusdt`, Upbit `KRW-BTC`, ...); canonicalize before this
+        // pair is ever compared against another source's, so the comparison below is an
+        // exact match on `base/quote` rather than a substring test that both false-matches
+        // (`ETH` inside `WETH`) and misses pairs written differently.
+        let canonical = normalize_pair(source, &token_pair)?;
+        let token_pair = canonical.as_key();
+
+        // Refuse to participate rather than diff a regional-fiat price against USD
+        // feeds raw: with no (or a stale) conversion rate, this source's number simply
+        // isn't comparable to anyone else's right now.
+        let price = fiat_rates.to_usd(raw_price, quote_currency)?;
+
+        // Key the shared price index on the canonical pair, not a free-form source string.
+        let mut prices = state.price_index.write();
+        prices.insert(format!("{}:{}", source, token_pair), price);
+        drop(prices);
+
+        aggregator.record(&token_pair, source, price);
+        let consensus = aggregator.consensus(&token_pair)?;
+
+        // This source's own quote must itself have survived deviation filtering — a
+        // price that was already thrown out as an outlier can't be the basis for a
+        // signal, it's exactly the kind of phantom spread this consensus exists to stop.
+        if !consensus.surviving_sources.contains_key(source) {
+            return None;
+        }
+
+        let spread_pct = ((price - consensus.fair_price) / consensus.fair_price).abs() * Decimal::from(100);
+        if spread_pct <= Decimal::from_str("0.5").unwrap() {
+            return None;
+        }
+
+        // Compare this source against the consensus itself, not an arbitrary peer: buy
+        // on whichever side is cheaper, sell into the fair price.
+        let (buy_exchange, sell_exchange, buy_price, sell_price) = if price < consensus.fair_price {
+            (source, "Consensus", price, consensus.fair_price)
+        } else {
+            ("Consensus", source, consensus.fair_price, price)
+        };
+
+        Self::calculate_arbitrage_profit(
+            buy_exchange,
+            sell_exchange,
+            &token_pair,
+            buy_price,
+            sell_price,
+            volume,
+            state
+        ).await
+    }
+
     async fn calculate_arbitrage_profit(
         buy_exchange: &str,
         sell_exchange: &str,
@@ -716,95 +918,4 @@ impl WebSocketManager {
         }
     }
     
-    // Parser functions for different exchanges
-    fn parse_binance_data(data: &Value) -> Option<(String, Decimal, Decimal)> {
-        if let Some(arr) = data.as_array() {
-            for item in arr {
-                if let (Some(symbol), Some(price), Some(volume)) = (
-                    item["s"].as_str(),
-                    item["c"].as_str(),
-                    item["v"].as_str(),
-                ) {
-                    if let (Ok(p), Ok(v)) = (Decimal::from_str(price), Decimal::from_str(volume)) {
-                        return Some((symbol.to_string(), p, v));
-                    }
-                }
-            }
-        }
-        None
-    }
-    
-    fn parse_coinbase_data(data: &Value) -> Option<(String, Decimal, Decimal)> {
-        if data["type"] == "ticker" {
-            if let (Some(product), Some(price), Some(volume)) = (
-                data["product_id"].as_str(),
-                data["price"].as_str(),
-                data["volume_24h"].as_str(),
-            ) {
-                if let (Ok(p), Ok(v)) = (Decimal::from_str(price), Decimal::from_str(volume)) {
-                    return Some((product.to_string(), p, v));
-                }
-            }
-        }
-        None
-    }
-    
-    fn parse_kraken_data(data: &Value) -> Option<(String, Decimal, Decimal)> {
-        if let Some(arr) = data.as_array() {
-            if arr.len() >= 4 {
-                if let (Some(pair), Some(ticker)) = (arr[3].as_str(), arr[1].as_object()) {
-                    if let (Some(ask), Some(bid), Some(vol)) = (
-                        ticker["a"].as_array().and_then(|a| a[0].as_str()),
-                        ticker["b"].as_array().and_then(|b| b[0].as_str()),
-                        ticker["v"].as_array().and_then(|v| v[1].as_str()),
-                    ) {
-                        if let (Ok(a), Ok(b), Ok(v)) = (
-                            Decimal::from_str(ask),
-                            Decimal::from_str(bid),
-                            Decimal::from_str(vol)
-                        ) {
-                            let price = (a + b) / Decimal::from(2);
-                            return Some((pair.to_string(), price, v));
-                        }
-                    }
-                }
-            }
-        }
-        None
-    }
-    
-    fn parse_uniswap_data(data: &Value) -> Option<(String, Decimal, Decimal)> {
-        if let Some(pool) = data["pool"].as_object() {
-            if let (Some(token0), Some(token1), Some(price), Some(volume)) = (
-                pool["token0"]["symbol"].as_str(),
-                pool["token1"]["symbol"].as_str(),
-                pool["token0Price"].as_str(),
-                pool["volumeUSD"].as_str(),
-            ) {
-                if let (Ok(p), Ok(v)) = (Decimal::from_str(price), Decimal::from_str(volume)) {
-                    let pair = format!("{}/{}", token0, token1);
-                    return Some((pair, p, v));
-                }
-            }
-        }
-        None
-    }
-    
-    fn parse_1inch_data(data: &Value) -> Option<(String, Decimal, Decimal)> {
-        if let Some(quote) = data["quote"].as_object() {
-            if let (Some(from), Some(to), Some(from_amt), Some(to_amt)) = (
-                quote["fromToken"]["symbol"].as_str(),
-                quote["toToken"]["symbol"].as_str(),
-                quote["fromTokenAmount"].as_str(),
-                quote["toTokenAmount"].as_str(),
-            ) {
-                if let (Ok(f), Ok(t)) = (Decimal::from_str(from_amt), Decimal::from_str(to_amt)) {
-                    let price = t / f;
-                    let pair = format!("{}/{}", from, to);
-                    return Some((pair, price, f));
-                }
-            }
-        }
-        None
-    }
 }
\ No newline at end of file